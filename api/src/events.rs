@@ -1,7 +1,9 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::accept_type::AcceptType;
 use crate::accounts::Account;
@@ -17,9 +19,22 @@ use aptos_api_types::{
     Address, AptosErrorCode, EventKey, IdentifierWrapper, LedgerInfo, MoveStructTag, U64,
 };
 use aptos_api_types::{AsConverter, VersionedEvent};
+use aptos_logger::warn;
+use futures::stream::{self, BoxStream, StreamExt};
 use poem_openapi::param::Query;
+use poem_openapi::payload::EventStream;
 use poem_openapi::{param::Path, OpenApi};
 
+/// Maximum number of events read from the DB at once by an event stream.
+const EVENT_STREAM_PAGE_SIZE: u16 = 100;
+
+/// Interval at which a comment is sent on idle event streams, so that they
+/// aren't closed by proxies.
+const EVENT_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+type EventStreamResultWith404 =
+    Result<EventStream<BoxStream<'static, VersionedEvent>>, BasicErrorWith404>;
+
 pub struct EventsApi {
     pub context: Arc<Context>,
 }
@@ -90,6 +105,73 @@ impl EventsApi {
             .into();
         self.list(account.latest_ledger_info, accept_type, page, key)
     }
+
+    /// Stream events by event key
+    ///
+    /// This endpoint streams the events identified by the event key as they
+    /// are committed, as Server-Sent Events carrying one JSON encoded event each.
+    ///
+    /// Events are streamed starting from the sequence number `start`, or from
+    /// the next event to be committed if it is not given. To resume a stream,
+    /// reconnect with `start` set to the sequence number following the last
+    /// received event.
+    #[oai(
+        path = "/events/:event_key/stream",
+        method = "get",
+        operation_id = "stream_events_by_event_key",
+        tag = "ApiTags::Events"
+    )]
+    async fn stream_events_by_event_key(
+        &self,
+        event_key: Path<EventKey>,
+        start: Query<Option<U64>>,
+    ) -> EventStreamResultWith404 {
+        fail_point_poem("endpoint_stream_events_by_event_key")?;
+        self.context
+            .check_api_output_enabled("Stream events by event key", &AcceptType::Json)?;
+
+        // Ensure that account exists
+        let account = Account::new(
+            self.context.clone(),
+            event_key.0 .0.get_creator_address().into(),
+            None,
+        )?;
+        account.account_state()?;
+        self.stream(
+            account.latest_ledger_info,
+            start.0.map(|v| v.0),
+            event_key.0,
+        )
+    }
+
+    /// Stream events by event handle
+    ///
+    /// This API extracts event key from the account resource identified
+    /// by the `event_handle_struct` and `field_name`, then streams the events
+    /// identified by the event key as they are committed, like
+    /// `/events/{event_key}/stream`.
+    #[oai(
+        path = "/accounts/:address/events/:event_handle/:field_name/stream",
+        method = "get",
+        operation_id = "stream_events_by_event_handle",
+        tag = "ApiTags::Events"
+    )]
+    async fn stream_events_by_event_handle(
+        &self,
+        address: Path<Address>,
+        event_handle: Path<MoveStructTag>,
+        field_name: Path<IdentifierWrapper>,
+        start: Query<Option<U64>>,
+    ) -> EventStreamResultWith404 {
+        fail_point_poem("endpoint_stream_events_by_event_handle")?;
+        self.context
+            .check_api_output_enabled("Stream events by event handle", &AcceptType::Json)?;
+        let account = Account::new(self.context.clone(), address.0, None)?;
+        let key = account
+            .find_event_key(event_handle.0, field_name.0.into())?
+            .into();
+        self.stream(account.latest_ledger_info, start.0.map(|v| v.0), key)
+    }
 }
 
 impl EventsApi {
@@ -140,4 +222,97 @@ impl EventsApi {
             }
         }
     }
+
+    fn stream(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        start: Option<u64>,
+        event_key: EventKey,
+    ) -> EventStreamResultWith404 {
+        let event_key = event_key.into();
+        // Without a start, only the events committed from now on are streamed.
+        let next_sequence_number = match start {
+            Some(start) => start,
+            None => self
+                .context
+                .get_events(&event_key, None, 1, latest_ledger_info.version())
+                .context(format!("Failed to find events by key {}", event_key))
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &latest_ledger_info,
+                    )
+                })?
+                .last()
+                .map_or(0, |event| event.event.sequence_number() + 1),
+        };
+
+        let tail = EventTail {
+            context: self.context.clone(),
+            event_key,
+            next_sequence_number,
+            poll_interval: Duration::from_millis(
+                self.context.node_config.api.event_stream_poll_interval_ms,
+            ),
+        };
+        Ok(EventStream::new(tail.into_stream()).keep_alive(EVENT_STREAM_KEEP_ALIVE))
+    }
+}
+
+/// Follows the events of an event key by polling the DB for the events
+/// committed after the last one it returned.
+struct EventTail {
+    context: Arc<Context>,
+    event_key: aptos_types::event::EventKey,
+    next_sequence_number: u64,
+    poll_interval: Duration,
+}
+
+impl EventTail {
+    fn next_events(&mut self) -> anyhow::Result<Vec<VersionedEvent>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info_wrapped()?;
+        let events = self.context.get_events(
+            &self.event_key,
+            Some(self.next_sequence_number),
+            EVENT_STREAM_PAGE_SIZE,
+            latest_ledger_info.version(),
+        )?;
+        if let Some(event) = events.last() {
+            self.next_sequence_number = event.event.sequence_number() + 1;
+        }
+        self.context
+            .move_resolver()?
+            .as_converter(self.context.db.clone())
+            .try_into_versioned_events(&events)
+    }
+
+    /// The stream ends if reading or converting events fails, e.g. because
+    /// they were pruned.
+    fn into_stream(self) -> BoxStream<'static, VersionedEvent> {
+        stream::unfold(
+            (self, VecDeque::new()),
+            |(mut tail, mut pending)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Some((event, (tail, pending)));
+                    }
+                    match tail.next_events() {
+                        Ok(events) if events.is_empty() => {
+                            tokio::time::sleep(tail.poll_interval).await
+                        }
+                        Ok(events) => pending.extend(events),
+                        Err(err) => {
+                            warn!(
+                                "Failed to stream events by key {}: {:?}",
+                                tail.event_key, err
+                            );
+                            return None;
+                        }
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
 }
@@ -24,6 +24,7 @@ mod state;
 #[cfg(test)]
 pub mod tests;
 mod transactions;
+mod view_function;
 
 #[derive(Tags)]
 pub enum ApiTags {
@@ -43,6 +44,9 @@ pub enum ApiTags {
 
     /// Access to transactions
     Transactions,
+
+    /// View functions
+    View,
 }
 
 // Note: Many of these exports are just for the test-context crate, which is
@@ -9,7 +9,7 @@ use crate::set_failpoints;
 use crate::{
     accounts::AccountsApi, basic::BasicApi, check_size::PostSizeLimit, context::Context,
     error_converter::convert_error, events::EventsApi, index::IndexApi, state::StateApi,
-    transactions::TransactionsApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::NodeConfig;
@@ -66,6 +66,7 @@ pub fn get_api_service(
         IndexApi,
         StateApi,
         TransactionsApi,
+        ViewFunctionApi,
     ),
     (),
 > {
@@ -89,7 +90,10 @@ pub fn get_api_service(
         StateApi {
             context: context.clone(),
         },
-        TransactionsApi { context },
+        TransactionsApi {
+            context: context.clone(),
+        },
+        ViewFunctionApi { context },
     );
 
     let version = VERSION.to_string();
@@ -14,7 +14,7 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo, MoveModuleBytecode,
-    MoveResource, MoveStructTag, MoveValue, TableItemRequest, U64,
+    MoveResource, MoveStructTag, MoveValue, RawTableItemRequest, TableItemRequest, U64,
 };
 use aptos_state_view::StateView;
 use aptos_types::{
@@ -28,6 +28,7 @@ use poem_openapi::{
     payload::Json,
     OpenApi,
 };
+use serde_json::json;
 use std::{convert::TryInto, sync::Arc};
 use storage_interface::state_view::DbStateView;
 
@@ -130,6 +131,38 @@ impl StateApi {
             ledger_version.0,
         )
     }
+
+    /// Get raw table item
+    ///
+    /// Get a table item at a specific ledger version from the table identified by {table_handle}
+    /// in the path and the BCS serialized "key" (RawTableItemRequest) provided in the request body.
+    ///
+    /// Unlike get_table_item, no key or value types are needed. The value is returned as raw
+    /// BCS bytes, so this endpoint only supports BCS output. It is meant for tooling that needs
+    /// to read arbitrary state, e.g. to replay transactions locally.
+    #[oai(
+        path = "/tables/:table_handle/raw_item",
+        method = "post",
+        operation_id = "get_raw_table_item",
+        tag = "ApiTags::Tables"
+    )]
+    async fn get_raw_table_item(
+        &self,
+        accept_type: AcceptType,
+        table_handle: Path<Address>,
+        table_item_request: Json<RawTableItemRequest>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_raw_table_item")?;
+        self.context
+            .check_api_output_enabled("Get raw table item", &accept_type)?;
+        self.raw_table_item(
+            &accept_type,
+            table_handle.0,
+            table_item_request.0,
+            ledger_version.0,
+        )
+    }
 }
 
 impl StateApi {
@@ -336,4 +369,43 @@ impl StateApi {
             }
         }
     }
+
+    pub fn raw_table_item(
+        &self,
+        accept_type: &AcceptType,
+        table_handle: Address,
+        table_item_request: RawTableItemRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        if let AcceptType::Json = accept_type {
+            return Err(BasicErrorWith404::bad_request_with_code_no_info(
+                "Get raw table item only supports BCS output, use get table item for JSON",
+                AptosErrorCode::InvalidInput,
+            ));
+        }
+
+        let (ledger_info, ledger_version, state_view) =
+            self.preprocess_request(ledger_version.map(|inner| inner.0))?;
+
+        let key = table_item_request.key;
+        let state_key = StateKey::table_item(TableHandle(table_handle.into()), key.0.clone());
+        let bytes = state_view
+            .get_state_value(&state_key)
+            .context(format!(
+                "Failed when trying to retrieve table item from the DB with key: {}",
+                key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .ok_or_else(|| {
+                table_item_not_found(table_handle, &json!(key), ledger_version, &ledger_info)
+            })?;
+
+        BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+    }
 }
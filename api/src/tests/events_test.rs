@@ -2,8 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::new_test_context;
-use aptos_api_test_context::current_function_name;
+use aptos_api_test_context::{current_function_name, ApiSpecificConfig, TestContext};
+use aptos_api_types::EventKey;
+use aptos_sdk::rest_client::Client;
+use aptos_types::account_config::CORE_CODE_ADDRESS;
+use futures::StreamExt;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use std::str::FromStr;
 
 static EVENT_KEY: &str =
     "0x0500000000000000000000000000000000000000000000000000000000000000000000000a550c18";
@@ -131,3 +136,66 @@ async fn test_get_events_by_struct_type_has_generic_type_parameter() {
     let resp = context.expect_status_code(404).get(path.as_str()).await;
     context.check_golden_output(resp);
 }
+
+fn rest_client(context: &TestContext) -> Client {
+    let ApiSpecificConfig::V1(address) = context.api_specific_config;
+    Client::new(format!("http://{}", address).parse().unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_from_start() {
+    let context = new_test_context(current_function_name!());
+    let expected = context
+        .get(format!("/events/{}?start=0", EVENT_KEY).as_str())
+        .await;
+    let num_events = expected.as_array().unwrap().len();
+
+    let event_key = EventKey::from_str(EVENT_KEY).unwrap();
+    let events: Vec<_> = rest_client(&context)
+        .stream_events_by_event_key(event_key.into(), Some(0))
+        .await
+        .unwrap()
+        .take(num_events)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(serde_json::to_value(&events).unwrap(), expected);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_by_account_event_handle() {
+    let mut context = new_test_context(current_function_name!());
+    let mut stream = Box::pin(
+        rest_client(&context)
+            .stream_account_events(
+                CORE_CODE_ADDRESS,
+                "0x1::block::BlockResource",
+                "new_block_events",
+                None,
+            )
+            .await
+            .unwrap(),
+    );
+
+    // Only the events committed once the stream started are streamed.
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+    let event = stream.next().await.unwrap().unwrap();
+
+    let resp = context
+        .get("/accounts/0x1/events/0x1::block::BlockResource/new_block_events")
+        .await;
+    let latest_event = resp.as_array().unwrap().last().unwrap();
+    assert_eq!(&serde_json::to_value(&event).unwrap(), latest_event);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_by_invalid_account_event_handle() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(404)
+        .get("/accounts/0x1/events/0x1::reconfiguration::Configuration/not_found/stream")
+        .await;
+    assert_eq!(resp["error_code"], "struct_field_not_found");
+}
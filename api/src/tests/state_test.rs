@@ -3,6 +3,7 @@
 
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::mime_types::BCS;
 use aptos_sdk::types::LocalAccount;
 use move_deps::{move_core_types::account_address::AccountAddress, move_package::BuildConfig};
 use serde::Serialize;
@@ -202,6 +203,65 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_raw_table_item() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let mut account = ctx.gen_account();
+    let acc = &mut account;
+    let txn = ctx.create_user_account(acc);
+    ctx.commit_block(&vec![txn.clone()]).await;
+    make_test_tables(ctx, acc).await;
+
+    let tt = ctx
+        .api_get_account_resource(
+            acc,
+            &acc.address().to_hex_literal(),
+            "TableTestData",
+            "TestTables",
+        )
+        .await["data"]
+        .to_owned();
+    let handle: AccountAddress = tt["u64_table"]["handle"].as_str().unwrap().parse().unwrap();
+    let key = bcs::to_bytes(&1u64).unwrap();
+
+    let resp = ctx
+        .reply(
+            warp::test::request()
+                .method("POST")
+                .path(&ctx.prepend_path(&get_raw_table_item(handle)))
+                .header("Accept", BCS)
+                .json(&json!({ "key": format!("0x{}", hex::encode(&key)) })),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(bcs::from_bytes::<u64>(resp.body()).unwrap(), 1);
+
+    let missing_key = bcs::to_bytes(&2u64).unwrap();
+    let resp = ctx
+        .reply(
+            warp::test::request()
+                .method("POST")
+                .path(&ctx.prepend_path(&get_raw_table_item(handle)))
+                .header("Accept", BCS)
+                .json(&json!({
+                    "key": format!("0x{}", hex::encode(&missing_key))
+                })),
+        )
+        .await;
+    assert_eq!(resp.status(), 404);
+
+    // The raw value can't be rendered as JSON
+    let resp = ctx
+        .expect_status_code(400)
+        .post(
+            &get_raw_table_item(handle),
+            json!({ "key": format!("0x{}", hex::encode(&key)) }),
+        )
+        .await;
+    assert_eq!(resp["error_code"], "invalid_input");
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
@@ -221,6 +281,10 @@ fn get_table_item(handle: AccountAddress) -> String {
     format!("/tables/{}/item", handle)
 }
 
+fn get_raw_table_item(handle: AccountAddress) -> String {
+    format!("/tables/{}/raw_item", handle)
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     let module = build_test_module(account.address()).await;
 
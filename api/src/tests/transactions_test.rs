@@ -232,6 +232,60 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_bcs_format_transactions_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let account1 = context.gen_account();
+    let account2 = context.gen_account();
+    let txn1 = context.create_user_account(&account1);
+    let txn2 = context.create_user_account(&account2);
+
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions/batch", bcs::to_bytes(&vec![txn1]).unwrap())
+        .await;
+    assert_json(resp, json!({ "transaction_failures": [] }));
+
+    // txn2 reuses the sequence number of txn1, so only it is rejected.
+    let mut root_account = context.root_account();
+    *root_account.sequence_number_mut() = 1;
+    let txn3 = context.create_user_account_by(&mut root_account, &account2);
+    let resp = context
+        .expect_status_code(206)
+        .post_bcs_txn(
+            "/transactions/batch",
+            bcs::to_bytes(&vec![txn2, txn3]).unwrap(),
+        )
+        .await;
+    let failures = resp["transaction_failures"].as_array().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0]["transaction_index"], json!(0));
+    assert_eq!(
+        failures[0]["error"]["error_code"],
+        json!("invalid_transaction_update")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transactions_batch_too_large() {
+    let mut context = new_test_context(current_function_name!());
+    let max_batch_size = context
+        .context
+        .node_config
+        .api
+        .max_submit_transaction_batch_size;
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let resp = context
+        .expect_status_code(400)
+        .post_bcs_txn(
+            "/transactions/batch",
+            bcs::to_bytes(&vec![txn; max_batch_size + 1]).unwrap(),
+        )
+        .await;
+    assert_eq!(resp["error_code"], json!("invalid_input"));
+}
+
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_agent_signed_transaction() {
@@ -291,6 +345,83 @@ async fn test_multi_agent_signed_transaction() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fee_payer_signed_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let mut sender = context.gen_account();
+    let fee_payer = context.gen_account();
+    let account = context.gen_account();
+    let mut root_account = context.root_account();
+    let factory = context.transaction_factory();
+    let txns = vec![
+        context.create_user_account_by(&mut root_account, &sender),
+        context.create_user_account_by(&mut root_account, &fee_payer),
+        root_account.sign_with_transaction_builder(factory.mint(sender.address(), 1_000)),
+        root_account.sign_with_transaction_builder(factory.mint(fee_payer.address(), 1_000_000)),
+    ];
+    context.commit_block(&txns).await;
+    let sender_balance = coin_balance(&context, &sender).await;
+    let fee_payer_balance = coin_balance(&context, &fee_payer).await;
+
+    let txn = sender.sign_fee_payer_with_transaction_builder(
+        vec![],
+        &fee_payer,
+        factory
+            .create_user_account(account.public_key())
+            .gas_unit_price(1)
+            .expiration_timestamp_secs(u64::MAX),
+    );
+
+    let body = bcs::to_bytes(&txn).unwrap();
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let (sender_signer, fee_payer_signer) = match txn.authenticator() {
+        TransactionAuthenticator::FeePayer {
+            sender,
+            fee_payer_signer,
+            ..
+        } => (sender, fee_payer_signer),
+        _ => panic!(
+            "expecting TransactionAuthenticator::FeePayer, but got: {:?}",
+            txn.authenticator()
+        ),
+    };
+    assert_json(
+        resp["signature"].clone(),
+        json!({
+            "type": "fee_payer_signature",
+            "sender": {
+                "type": "ed25519_signature",
+                "public_key": format!("0x{}", hex::encode(sender_signer.public_key_bytes())),
+                "signature": format!("0x{}", hex::encode(sender_signer.signature_bytes())),
+            },
+            "secondary_signer_addresses": [],
+            "secondary_signers": [],
+            "fee_payer_address": fee_payer.address().to_hex_literal(),
+            "fee_payer_signer": {
+                "type": "ed25519_signature",
+                "public_key": format!("0x{}", hex::encode(fee_payer_signer.public_key_bytes())),
+                "signature": format!("0x{}", hex::encode(fee_payer_signer.signature_bytes())),
+            },
+        }),
+    );
+
+    // the sender's sequence number is bumped, but the fee payer pays for gas
+    context.commit_block(&vec![txn]).await;
+    let resp = context
+        .get(&format!("/accounts/{}", sender.address().to_hex_literal()))
+        .await;
+    assert_eq!(resp["sequence_number"], "1");
+    context
+        .get(&format!("/accounts/{}", account.address().to_hex_literal()))
+        .await;
+    assert_eq!(coin_balance(&context, &sender).await, sender_balance);
+    assert!(coin_balance(&context, &fee_payer).await < fee_payer_balance);
+}
+
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_ed25519_signed_transaction() {
@@ -460,6 +591,31 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_pending_transactions() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let txns = context.get("/transactions/pending").await;
+    assert_eq!(1, txns.as_array().unwrap().len());
+    assert_json(txns[0].clone(), pending_txn);
+
+    context.commit_mempool_txns(1).await;
+    let txns = context.get("/transactions/pending").await;
+    assert!(txns.as_array().unwrap().is_empty());
+
+    context
+        .expect_status_code(400)
+        .get("/transactions/pending?limit=0")
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_message_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
@@ -1041,3 +1197,19 @@ fn gen_string(len: u64) -> String {
 fn build_path(path: &str) -> String {
     format!("/v1/transactions{}", path)
 }
+
+async fn coin_balance(context: &TestContext, account: &LocalAccount) -> u64 {
+    let coin_store = context
+        .api_get_account_resource(
+            account,
+            "0x1",
+            "coin",
+            "CoinStore<0x1::aptos_coin::AptosCoin>",
+        )
+        .await;
+    coin_store["data"]["coin"]["value"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::current_function_name;
+use aptos_types::vm_status::StatusCode;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simple_view() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::account::get_sequence_number",
+                "type_arguments": [],
+                "arguments": ["0xa550c18"],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!(["0"]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_with_type_arguments() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::coin::is_account_registered",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": ["0xa550c18"],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!([true]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_at_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let version = context.get_latest_ledger_info().version();
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&[txn]).await;
+
+    let request = json!({
+        "function": "0x1::account::exists_at",
+        "type_arguments": [],
+        "arguments": [account.address().to_hex_literal()],
+    });
+    let resp = context
+        .post(
+            &format!("/view?ledger_version={}", version),
+            request.clone(),
+        )
+        .await;
+    assert_eq!(resp, json!([false]));
+    let resp = context.post("/view", request).await;
+    assert_eq!(resp, json!([true]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_non_public_function() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::account::create_account_unchecked",
+                "type_arguments": [],
+                "arguments": ["0xa550c18"],
+            }),
+        )
+        .await;
+    assert_eq!(resp["error_code"], "invalid_input");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_function_with_signer() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::signer::address_of",
+                "type_arguments": [],
+                "arguments": [],
+            }),
+        )
+        .await;
+    assert_eq!(resp["error_code"], "vm_error");
+    assert_eq!(
+        resp["vm_error_code"],
+        StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE as u64
+    );
+}
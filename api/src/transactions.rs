@@ -21,9 +21,10 @@ use crate::ApiTags;
 use crate::{generate_error_response, generate_success_response};
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    Address, AptosErrorCode, AsConverter, EncodeSubmissionRequest, GasEstimation, HashValue,
-    HexEncodedBytes, LedgerInfo, PendingTransaction, SubmitTransactionRequest, Transaction,
-    TransactionData, TransactionOnChainData, UserTransaction, U64,
+    Address, AptosError, AptosErrorCode, AsConverter, EncodeSubmissionRequest, GasEstimation,
+    HashValue, HexEncodedBytes, LedgerInfo, PendingTransaction, SubmitTransactionRequest,
+    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
+    TransactionsBatchSubmissionResult, UserTransaction, U64,
 };
 use aptos_crypto::signing_message;
 use aptos_types::mempool_status::MempoolStatusCode;
@@ -37,6 +38,11 @@ use poem_openapi::payload::Json;
 use poem_openapi::{ApiRequest, OpenApi};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
+generate_success_response!(
+    SubmitTransactionsBatchResponse,
+    (202, Accepted),
+    (206, AcceptedPartial)
+);
 generate_error_response!(
     SubmitTransactionError,
     (400, BadRequest),
@@ -50,6 +56,9 @@ generate_error_response!(
 type SubmitTransactionResult<T> =
     poem::Result<SubmitTransactionResponse<T>, SubmitTransactionError>;
 
+type SubmitTransactionsBatchResult<T> =
+    poem::Result<SubmitTransactionsBatchResponse<T>, SubmitTransactionError>;
+
 type SimulateTransactionResult<T> = poem::Result<BasicResponse<T>, SubmitTransactionError>;
 
 // TODO: Consider making both content types accept either
@@ -70,6 +79,17 @@ pub enum SubmitTransactionPost {
     Bcs(Bcs),
 }
 
+// The batch counterpart of SubmitTransactionPost.
+#[derive(ApiRequest, Debug)]
+pub enum SubmitTransactionsBatchPost {
+    #[oai(content_type = "application/json")]
+    Json(Json<Vec<SubmitTransactionRequest>>),
+
+    // The BCS encoded request is a Vec<SignedTransaction>.
+    #[oai(content_type = "application/x.aptos.signed_transaction+bcs")]
+    Bcs(Bcs),
+}
+
 pub struct TransactionsApi {
     pub context: Arc<Context>,
 }
@@ -132,6 +152,30 @@ impl TransactionsApi {
             .await
     }
 
+    /// Get pending transactions
+    ///
+    /// List transactions currently sitting in this node's mempool (pending,
+    /// not yet committed), in the order they entered the mempool. Both
+    /// transactions ready for the next block and transactions waiting on an
+    /// earlier sequence number are included.
+    #[oai(
+        path = "/transactions/pending",
+        method = "get",
+        operation_id = "get_pending_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_pending_transactions(
+        &self,
+        accept_type: AcceptType,
+        limit: Query<Option<u16>>,
+    ) -> BasicResult<Vec<PendingTransaction>> {
+        fail_point_poem("endpoint_get_pending_transactions")?;
+        self.context
+            .check_api_output_enabled("Get pending transactions", &accept_type)?;
+        let page = Page::new(None, limit.0);
+        self.list_pending(&accept_type, page).await
+    }
+
     /// Get transaction by version
     ///
     /// todo
@@ -217,6 +261,61 @@ impl TransactionsApi {
             .await
     }
 
+    /// Submit batch transactions
+    ///
+    /// This allows you to submit multiple transactions in a single request.
+    /// The response has an array of failed transactions, identified by their
+    /// index in the request, and the error they failed with. Transactions are
+    /// handed to mempool one by one in the order they were given, so a batch
+    /// may contain consecutive sequence numbers of the same account.
+    ///
+    /// If every transaction is accepted, the response has status code 202.
+    /// If only some of them are, it has status code 206.
+    ///
+    /// To submit the batch as JSON, you must submit an array of
+    /// SubmitTransactionRequest, see /transactions for how to build them.
+    ///
+    /// To submit the batch as BCS, you must submit a Vec<SignedTransaction>
+    /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
+    #[oai(
+        path = "/transactions/batch",
+        method = "post",
+        operation_id = "submit_batch_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn submit_transactions_batch(
+        &self,
+        accept_type: AcceptType,
+        data: SubmitTransactionsBatchPost,
+    ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
+        fail_point_poem("endpoint_submit_batch_transactions")?;
+        self.context
+            .check_api_output_enabled("Submit batch transactions", &accept_type)?;
+        if !self.context.node_config.api.transaction_submission_enabled {
+            return Err(api_disabled("Submit batch transactions"));
+        }
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let signed_transactions_batch = self.get_signed_transactions_batch(&ledger_info, data)?;
+        let max_batch_size = self
+            .context
+            .node_config
+            .api
+            .max_submit_transaction_batch_size;
+        if signed_transactions_batch.len() > max_batch_size {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                format!(
+                    "Submitted too many transactions: {}, while limit is {}",
+                    signed_transactions_batch.len(),
+                    max_batch_size,
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        self.create_batch(&accept_type, ledger_info, signed_transactions_batch)
+            .await
+    }
+
     /// Simulate transaction
     ///
     /// Simulate submitting a transaction. To use this, you must:
@@ -363,6 +462,50 @@ impl TransactionsApi {
         }
     }
 
+    async fn list_pending(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+    ) -> BasicResult<Vec<PendingTransaction>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let limit = page.limit(&latest_ledger_info)?;
+        let data = self
+            .context
+            .get_pending_transactions(limit)
+            .await
+            .context("Failed to read pending transactions from mempool")
+            .map_err(|err| {
+                BasicError::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let resolver = self.context.move_resolver_poem(&latest_ledger_info)?;
+                let converter = resolver.as_converter(self.context.db.clone());
+                let txns = data
+                    .into_iter()
+                    .map(|txn| converter.try_into_pending_transaction_poem(txn))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to convert pending transactions to PendingTransaction")
+                    .map_err(|err| {
+                        BasicError::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &latest_ledger_info,
+                        )
+                    })?;
+                BasicResponse::try_from_json((txns, &latest_ledger_info, BasicResponseStatus::Ok))
+            }
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
+            }
+        }
+    }
+
     async fn get_transaction_by_hash_inner(
         &self,
         accept_type: &AcceptType,
@@ -564,6 +707,50 @@ impl TransactionsApi {
         }
     }
 
+    fn get_signed_transactions_batch(
+        &self,
+        ledger_info: &LedgerInfo,
+        data: SubmitTransactionsBatchPost,
+    ) -> Result<Vec<SignedTransaction>, SubmitTransactionError> {
+        match data {
+            SubmitTransactionsBatchPost::Bcs(data) => {
+                let signed_transactions = bcs::from_bytes(&data.0)
+                    .context("Failed to deserialize input into Vec<SignedTransaction>")
+                    .map_err(|err| {
+                        SubmitTransactionError::bad_request_with_code(
+                            err,
+                            AptosErrorCode::InvalidInput,
+                            ledger_info,
+                        )
+                    })?;
+                Ok(signed_transactions)
+            }
+            SubmitTransactionsBatchPost::Json(data) => {
+                let resolver = self.context.move_resolver_poem(ledger_info)?;
+                let converter = resolver.as_converter(self.context.db.clone());
+                data.0
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, txn)| {
+                        converter
+                            .try_into_signed_transaction_poem(txn, self.context.chain_id())
+                            .context(format!(
+                                "Failed to create SignedTransaction from SubmitTransactionRequest at index {}",
+                                index
+                            ))
+                            .map_err(|err| {
+                                SubmitTransactionError::bad_request_with_code(
+                                    err,
+                                    AptosErrorCode::InvalidInput,
+                                    ledger_info,
+                                )
+                            })
+                    })
+                    .collect()
+            }
+        }
+    }
+
     async fn create(
         &self,
         accept_type: &AcceptType,
@@ -657,6 +844,90 @@ impl TransactionsApi {
         }
     }
 
+    async fn create_batch(
+        &self,
+        accept_type: &AcceptType,
+        ledger_info: LedgerInfo,
+        txns: Vec<SignedTransaction>,
+    ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
+        // Submit in order, so that a batch can carry several transactions of
+        // the same account.
+        let mut txn_failures = Vec::new();
+        for (transaction_index, txn) in txns.into_iter().enumerate() {
+            if let Err(error) = self.create_internal(txn).await {
+                txn_failures.push(TransactionsBatchSingleSubmissionFailure {
+                    error,
+                    transaction_index,
+                });
+            }
+        }
+
+        let status = if txn_failures.is_empty() {
+            SubmitTransactionsBatchResponseStatus::Accepted
+        } else {
+            SubmitTransactionsBatchResponseStatus::AcceptedPartial
+        };
+        let result = TransactionsBatchSubmissionResult {
+            transaction_failures: txn_failures,
+        };
+        match accept_type {
+            AcceptType::Json => {
+                SubmitTransactionsBatchResponse::try_from_json((result, &ledger_info, status))
+            }
+            AcceptType::Bcs => {
+                SubmitTransactionsBatchResponse::try_from_bcs((result, &ledger_info, status))
+            }
+        }
+    }
+
+    /// Submits a single transaction of a batch to mempool, returning the
+    /// error it was rejected with, if any.
+    async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+        let (mempool_status, vm_status_opt) = self
+            .context
+            .submit_transaction(txn)
+            .await
+            .context("Mempool failed to initially evaluate submitted transaction")
+            .map_err(|err| AptosError::new_with_error_code(err, AptosErrorCode::InternalError))?;
+        match mempool_status.code {
+            MempoolStatusCode::Accepted => Ok(()),
+            MempoolStatusCode::MempoolIsFull | MempoolStatusCode::TooManyTransactions => {
+                Err(AptosError::new_with_error_code(
+                    &mempool_status.message,
+                    AptosErrorCode::MempoolIsFull,
+                ))
+            }
+            MempoolStatusCode::VmError => Err(match vm_status_opt {
+                Some(status) => AptosError::new_with_vm_status(
+                    format!(
+                        "Invalid transaction: Type: {:?} Code: {:?}",
+                        status.status_type(),
+                        status
+                    ),
+                    AptosErrorCode::VmError,
+                    status,
+                ),
+                None => AptosError::new_with_vm_status(
+                    "Invalid transaction: unknown",
+                    AptosErrorCode::VmError,
+                    StatusCode::UNKNOWN_STATUS,
+                ),
+            }),
+            MempoolStatusCode::InvalidSeqNumber => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::SequenceNumberTooOld,
+            )),
+            MempoolStatusCode::InvalidUpdate => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::InvalidTransactionUpdate,
+            )),
+            MempoolStatusCode::UnknownStatus => Err(AptosError::new_with_error_code(
+                format!("Transaction was rejected with status {}", mempool_status),
+                AptosErrorCode::InternalError,
+            )),
+        }
+    }
+
     // TODO: This returns a Vec<Transaction>, but is it possible for a single
     // transaction request to result in multiple executed transactions?
     // TODO: This function leverages a lot of types from aptos_types, use the
@@ -764,8 +1035,19 @@ impl TransactionsApi {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?;
 
-        let raw_message = match request.secondary_signers {
-            Some(secondary_signer_addresses) => {
+        let raw_message = match (request.secondary_signers, request.fee_payer_address) {
+            (secondary_signer_addresses, Some(fee_payer_address)) => {
+                signing_message(&RawTransactionWithData::new_fee_payer(
+                    raw_txn,
+                    secondary_signer_addresses
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|v| v.into())
+                        .collect(),
+                    fee_payer_address.into(),
+                ))
+            }
+            (Some(secondary_signer_addresses), None) => {
                 signing_message(&RawTransactionWithData::new_multi_agent(
                     raw_txn,
                     secondary_signer_addresses
@@ -774,7 +1056,7 @@ impl TransactionsApi {
                         .collect(),
                 ))
             }
-            None => raw_txn.signing_message(),
+            (None, None) => raw_txn.signing_message(),
         };
 
         BasicResponse::try_from_json((
@@ -0,0 +1,170 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::accept_type::AcceptType;
+use crate::bcs_payload::Bcs;
+use crate::context::Context;
+use crate::failpoint::fail_point_poem;
+use crate::response::{
+    api_disabled, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+    BasicResultWith404, InternalError,
+};
+use crate::ApiTags;
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{AptosErrorCode, AsConverter, MoveValue, ViewRequest, U64};
+use aptos_types::transaction::EntryFunction;
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use poem_openapi::param::Query;
+use poem_openapi::payload::Json;
+use poem_openapi::{ApiRequest, OpenApi};
+
+// We need a custom type here because we use different types for each of the
+// content types possible for the POST data.
+#[derive(ApiRequest, Debug)]
+pub enum ViewFunctionRequest {
+    #[oai(content_type = "application/json")]
+    Json(Json<ViewRequest>),
+
+    // The BCS encoded request is an EntryFunction, see
+    // types/src/transaction/script.rs.
+    #[oai(content_type = "application/x.aptos.view_function+bcs")]
+    Bcs(Bcs),
+}
+
+pub struct ViewFunctionApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl ViewFunctionApi {
+    /// Execute view function of a module
+    ///
+    /// Execute the Move function with the given parameters and return its execution result.
+    ///
+    /// The function must be public and must not take any signers. Its effects are never
+    /// committed, so it can be used to read derived data from on-chain state, e.g. balances
+    /// or token metadata, without submitting a transaction. Execution is bounded by the
+    /// node's configured maximum view gas.
+    ///
+    /// If the ledger version is not specified in the request, the latest ledger version
+    /// is used.
+    #[oai(
+        path = "/view",
+        method = "post",
+        operation_id = "view",
+        tag = "ApiTags::View"
+    )]
+    async fn view_function(
+        &self,
+        accept_type: AcceptType,
+        request: ViewFunctionRequest,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<MoveValue>> {
+        fail_point_poem("endpoint_view_function")?;
+        self.context
+            .check_api_output_enabled("View function", &accept_type)?;
+        if !self.context.node_config.api.view_function_enabled {
+            return Err(api_disabled("View function"));
+        }
+        self.view(&accept_type, request, ledger_version.0.map(|inner| inner.0))
+    }
+}
+
+impl ViewFunctionApi {
+    fn view(
+        &self,
+        accept_type: &AcceptType,
+        request: ViewFunctionRequest,
+        ledger_version: Option<u64>,
+    ) -> BasicResultWith404<Vec<MoveValue>> {
+        let (ledger_info, requested_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(ledger_version)?;
+        let state_view = self
+            .context
+            .state_view_at_version(requested_version)
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+
+        let function: EntryFunction = match request {
+            ViewFunctionRequest::Json(data) => converter
+                .try_into_view_function(data.0)
+                .context("Failed to convert ViewRequest into a view function")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &ledger_info,
+                    )
+                })?,
+            ViewFunctionRequest::Bcs(data) => bcs::from_bytes(&data.0)
+                .context("Failed to deserialize input into EntryFunction")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &ledger_info,
+                    )
+                })?,
+        };
+
+        let return_vals = AptosVM::execute_view_function(
+            &state_view,
+            function.module().clone(),
+            function.function().to_owned(),
+            function.ty_args().to_vec(),
+            function.args().to_vec(),
+            self.context.node_config.api.max_view_gas,
+        )
+        .map_err(|status| {
+            BasicErrorWith404::bad_request_with_vm_status(
+                format!("Failed to execute view function: {:?}", status),
+                AptosErrorCode::VmError,
+                status.status_code(),
+                &ledger_info,
+            )
+        })?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let return_types = converter
+                    .function_return_types(&function)
+                    .context("Failed to determine the return types of the view function")
+                    .map_err(|err| {
+                        BasicErrorWith404::bad_request_with_code(
+                            err,
+                            AptosErrorCode::InvalidInput,
+                            &ledger_info,
+                        )
+                    })?;
+                let move_vals = return_vals
+                    .iter()
+                    .zip(return_types.iter())
+                    .map(|(bytes, typ)| converter.try_into_move_value(typ, bytes))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to deserialize the return values of the view function")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+            }
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((return_vals, &ledger_info, BasicResponseStatus::Ok))
+            }
+        }
+    }
+}
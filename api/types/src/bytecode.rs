@@ -10,7 +10,7 @@ use move_deps::{
             AddressIdentifierIndex, CompiledModule, CompiledScript, FieldDefinition,
             FunctionDefinition, FunctionHandle, FunctionHandleIndex, IdentifierIndex, ModuleHandle,
             ModuleHandleIndex, Signature, SignatureIndex, SignatureToken, StructDefinition,
-            StructFieldInformation, StructHandle, StructHandleIndex,
+            StructFieldInformation, StructHandle, StructHandleIndex, Visibility,
         },
     },
     move_core_types::{account_address::AccountAddress, identifier::IdentStr},
@@ -39,6 +39,8 @@ pub trait Bytecode {
 
     fn find_entry_function(&self, name: &IdentStr) -> Option<MoveFunction>;
 
+    fn find_public_function(&self, name: &IdentStr) -> Option<MoveFunction>;
+
     fn new_move_struct_field(&self, def: &FieldDefinition) -> MoveStructField {
         MoveStructField {
             name: self.identifier_at(def.name).to_owned().into(),
@@ -182,6 +184,17 @@ impl Bytecode for CompiledModule {
             })
             .map(|def| self.new_move_function(def))
     }
+
+    fn find_public_function(&self, name: &IdentStr) -> Option<MoveFunction> {
+        self.function_defs
+            .iter()
+            .filter(|def| def.visibility == Visibility::Public)
+            .find(|def| {
+                let fhandle = ModuleAccess::function_handle_at(self, def.function);
+                ModuleAccess::identifier_at(self, fhandle.name) == name
+            })
+            .map(|def| self.new_move_function(def))
+    }
 }
 
 impl Bytecode for CompiledScript {
@@ -216,4 +229,8 @@ impl Bytecode for CompiledScript {
             None
         }
     }
+
+    fn find_public_function(&self, _name: &IdentStr) -> Option<MoveFunction> {
+        None
+    }
 }
@@ -8,10 +8,10 @@ use crate::{
         WriteResource, WriteTableItem,
     },
     Bytecode, DirectWriteSet, EntryFunctionId, EntryFunctionPayload, Event, HexEncodedBytes,
-    MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveValue,
+    MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveType, MoveValue,
     PendingTransaction, ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction,
     TransactionInfo, TransactionOnChainData, TransactionPayload, UserTransactionRequest,
-    VersionedEvent, WriteSet, WriteSetChange, WriteSetPayload,
+    VersionedEvent, ViewRequest, WriteSet, WriteSetChange, WriteSetPayload,
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -534,6 +534,61 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    pub fn try_into_view_function(&self, request: ViewRequest) -> Result<EntryFunction> {
+        let ViewRequest {
+            function,
+            type_arguments,
+            arguments,
+        } = request;
+
+        let module = function.module.clone();
+        let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_public_function(function.name.0.as_ident_str())
+            .ok_or_else(|| format_err!("could not find public function by {}", function))?;
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for view function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        let args = self
+            .try_into_vm_values(func, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok(EntryFunction::new(
+            module.into(),
+            function.name.into(),
+            type_arguments
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_>>()?,
+            args,
+        ))
+    }
+
+    /// Returns the return types of the public function called by `function`, with its
+    /// generic type params instantiated with the given type arguments.
+    pub fn function_return_types(&self, function: &EntryFunction) -> Result<Vec<TypeTag>> {
+        let code = self.inner.get_module(function.module())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_public_function(function.function())
+            .ok_or_else(|| {
+                format_err!(
+                    "could not find public function by {}::{}",
+                    function.module(),
+                    function.function()
+                )
+            })?;
+        func.return_
+            .into_iter()
+            .map(|typ| instantiate_type_tag(typ, function.ty_args()))
+            .collect()
+    }
+
     pub fn try_into_vm_values(
         &self,
         func: MoveFunction,
@@ -761,3 +816,28 @@ pub fn new_vm_utf8_string(string: &str) -> move_core_types::value::MoveValue {
     let move_string = MoveStruct::Runtime(vec![byte_vector]);
     MoveValue::Struct(move_string)
 }
+
+/// Converts a `MoveType` from a function signature into a `TypeTag`, replacing
+/// generic type params with the given type arguments.
+fn instantiate_type_tag(typ: MoveType, type_args: &[TypeTag]) -> Result<TypeTag> {
+    Ok(match typ {
+        MoveType::GenericTypeParam { index } => type_args
+            .get(index as usize)
+            .cloned()
+            .ok_or_else(|| format_err!("missing type argument for type param {}", index))?,
+        MoveType::Vector { items } => {
+            TypeTag::Vector(Box::new(instantiate_type_tag(*items, type_args)?))
+        }
+        MoveType::Struct(tag) => TypeTag::Struct(StructTag {
+            address: tag.address.into(),
+            module: tag.module.into(),
+            name: tag.name.into(),
+            type_params: tag
+                .generic_type_params
+                .into_iter()
+                .map(|typ| instantiate_type_tag(typ, type_args))
+                .collect::<Result<_>>()?,
+        }),
+        typ => typ.try_into()?,
+    })
+}
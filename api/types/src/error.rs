@@ -3,19 +3,18 @@
 
 use aptos_types::vm_status::StatusCode;
 use poem_openapi::{Enum, Object};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 
 /// This is the generic struct we use for all API errors, it contains a string
 /// message and an Aptos API specific error code.
-#[derive(Debug, Deserialize, Object)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct AptosError {
     /// A message describing the error
     pub message: String,
     /// A code providing more granular error information beyond the HTTP status code
     pub error_code: AptosErrorCode,
     /// A code providing VM error details when submitting transactions to the VM
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub vm_error_code: Option<u64>,
 }
 
@@ -54,7 +53,7 @@ impl AptosError {
 
 /// These codes provide more granular error information beyond just the HTTP
 /// status code of the response.
-#[derive(Copy, Clone, Debug, Deserialize, Enum)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
 #[oai(rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 #[repr(u32)]
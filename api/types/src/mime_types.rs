@@ -4,3 +4,4 @@
 pub const BCS_SIGNED_TRANSACTION: &str = "application/x.aptos.signed_transaction+bcs";
 pub const JSON: &str = "application/json";
 pub const BCS: &str = "application/x-bcs";
+pub const BCS_VIEW_FUNCTION: &str = "application/x.aptos.view_function+bcs";
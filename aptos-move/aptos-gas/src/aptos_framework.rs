@@ -68,8 +68,27 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.ristretto255.scalar_sub, "ristretto255.scalar_sub", 1],
     [.ristretto255.scalar_parse_arg, "ristretto255.scalar_parse_arg", 1],
 
+    [.groth16.bn254.prepare_verifying_key_base, "groth16.bn254.prepare_verifying_key_base", 1],
+    [.groth16.bn254.prepare_verifying_key_per_public_input, "groth16.bn254.prepare_verifying_key_per_public_input", 1],
+    [.groth16.bn254.verify_proof_base, "groth16.bn254.verify_proof_base", 1],
+    [.groth16.bn254.verify_proof_per_public_input, "groth16.bn254.verify_proof_per_public_input", 1],
+    [.groth16.bls12381.prepare_verifying_key_base, "groth16.bls12381.prepare_verifying_key_base", 1],
+    [.groth16.bls12381.prepare_verifying_key_per_public_input, "groth16.bls12381.prepare_verifying_key_per_public_input", 1],
+    [.groth16.bls12381.verify_proof_base, "groth16.bls12381.verify_proof_base", 1],
+    [.groth16.bls12381.verify_proof_per_public_input, "groth16.bls12381.verify_proof_per_public_input", 1],
+
     [.hash.sip_hash.base, "hash.sip_hash.base", 1],
     [.hash.sip_hash.per_byte, "hash.sip_hash.per_byte", 1],
+    [.hash.keccak256.base, "hash.keccak256.base", 1],
+    [.hash.keccak256.per_byte, "hash.keccak256.per_byte", 1],
+    [.hash.sha2_512.base, "hash.sha2_512.base", 1],
+    [.hash.sha2_512.per_byte, "hash.sha2_512.per_byte", 1],
+    [.hash.sha3_512.base, "hash.sha3_512.base", 1],
+    [.hash.sha3_512.per_byte, "hash.sha3_512.per_byte", 1],
+    [.hash.blake2b_256.base, "hash.blake2b_256.base", 1],
+    [.hash.blake2b_256.per_byte, "hash.blake2b_256.per_byte", 1],
+    [.hash.ripemd160.base, "hash.ripemd160.base", 1],
+    [.hash.ripemd160.per_byte, "hash.ripemd160.per_byte", 1],
 
     [.type_info.type_of.base, "type_info.type_of.base", 1],
     [.type_info.type_of.per_byte_in_str, "type_info.type_of.per_abstract_memory_unit", 1],
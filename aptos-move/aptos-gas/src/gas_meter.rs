@@ -474,3 +474,30 @@ impl AptosGasMeter {
         self.charge(cost).map_err(|e| e.finish(Location::Undefined))
     }
 }
+
+/// A gas meter that can be used to execute transactions, on top of executing Move code.
+/// This lets the VM run transactions with wrappers of the official gas meter, such as the
+/// gas profiler.
+pub trait TransactionGasMeter: GasMeter {
+    /// Returns the remaining balance, in external gas units.
+    fn balance(&self) -> Gas;
+
+    /// Returns the remaining balance, in internal gas units.
+    fn balance_internal(&self) -> InternalGas;
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+}
+
+impl TransactionGasMeter for AptosGasMeter {
+    fn balance(&self) -> Gas {
+        AptosGasMeter::balance(self)
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.balance
+    }
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
+        AptosGasMeter::charge_intrinsic_gas_for_transaction(self, txn_size)
+    }
+}
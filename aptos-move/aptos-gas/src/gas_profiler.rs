@@ -0,0 +1,789 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a gas meter wrapper that records where the gas of a transaction is spent,
+//! so the cost of Move code can be profiled.
+//!
+//! The profiler attributes the cost of every instruction, native call and resource load to the
+//! frame of the function being executed, and builds a call graph out of these frames. The
+//! resulting [`TransactionGasLog`] can be rendered as folded stacks (the input format of
+//! flamegraph tools such as `inferno-flamegraph` or `flamegraph.pl`) or as a JSON-serializable
+//! cost breakdown.
+//!
+//! All costs are expressed in internal gas units.
+
+use crate::{
+    algebra::Gas,
+    gas_meter::{AptosGasMeter, TransactionGasMeter},
+};
+use move_binary_format::errors::{PartialVMResult, VMResult};
+use move_core_types::{
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
+    language_storage::ModuleId,
+};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The gas spent in the execution of a single function, including the functions it called.
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    /// The fully qualified name of the function, e.g. `0x1::coin::transfer`.
+    pub name: String,
+    /// The cost of the instructions executed in this frame, per instruction.
+    pub instructions: BTreeMap<String, InternalGas>,
+    /// The cost of the native functions called from this frame, per native function.
+    pub natives: BTreeMap<String, InternalGas>,
+    /// The cost of the resources loaded from storage in this frame.
+    pub loads: InternalGas,
+    /// The frames of the Move functions called from this frame, in call order.
+    pub callees: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            instructions: BTreeMap::new(),
+            natives: BTreeMap::new(),
+            loads: 0.into(),
+            callees: vec![],
+        }
+    }
+
+    /// The gas spent in this frame only, excluding the callees.
+    pub fn self_cost(&self) -> InternalGas {
+        self.instructions
+            .values()
+            .chain(self.natives.values())
+            .fold(self.loads, |total, cost| total + *cost)
+    }
+
+    /// The gas spent in this frame and all of its callees.
+    pub fn total_cost(&self) -> InternalGas {
+        self.callees.iter().fold(self.self_cost(), |total, callee| {
+            total + callee.total_cost()
+        })
+    }
+}
+
+/// What a storage write is to, which determines the operations that are attributed to it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StorageWriteKind {
+    /// A resource of the given type, written by `move_to`, `move_from` and mutable
+    /// `borrow_global` on that type.
+    Resource(String),
+    /// A table item, written by the table natives that add, mutably borrow or remove items.
+    TableItem,
+    /// A module, written by the native that requests code to be published.
+    Module,
+}
+
+impl StorageWriteKind {
+    /// The kind of the writes performed by the native function with the given name, if any.
+    fn of_native(name: &str) -> Option<Self> {
+        match name {
+            "0x1::table::add_box" | "0x1::table::borrow_box_mut" | "0x1::table::remove_box" => {
+                Some(Self::TableItem)
+            }
+            "0x1::code::request_publish" => Some(Self::Module),
+            _ => None,
+        }
+    }
+}
+
+/// A write to storage performed by a transaction.
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageWrite {
+    /// A human readable representation of the state key being written.
+    pub key: String,
+    /// The size of the value written, or `None` if the value is deleted.
+    pub num_bytes: Option<u64>,
+    /// The cost of the operations that performed the write (see [`StorageWriteKind`]). When a
+    /// transaction performs several writes of the same kind, e.g. to the same resource type under
+    /// different addresses, the cost is split evenly among them.
+    ///
+    /// NOTE(Gas): the current gas schedule charges nothing for the write itself, so this cost is
+    /// also part of the cost of the instructions and native functions, and is not added to the
+    /// total.
+    pub cost: u64,
+}
+
+/// The gas profile of a transaction.
+#[derive(Debug, Clone)]
+pub struct TransactionGasLog {
+    pub intrinsic_cost: InternalGas,
+    /// The frame of the script or entry function executed by the transaction.
+    pub call_graph: CallFrame,
+    pub storage_writes: Vec<StorageWrite>,
+}
+
+/// The cost of a function, aggregated over all of its calls.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FunctionCost {
+    pub calls: u64,
+    /// The gas spent in the function itself, excluding its callees.
+    pub self_cost: u64,
+    /// The gas spent in the function and its callees. The cost of recursive calls is counted
+    /// once per call on the stack.
+    pub total_cost: u64,
+}
+
+/// A breakdown of the gas spent by a transaction, meant to be serialized to JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct GasCostBreakdown {
+    pub total_cost: u64,
+    pub intrinsic_cost: u64,
+    pub loads_cost: u64,
+    pub instructions: BTreeMap<String, u64>,
+    pub natives: BTreeMap<String, u64>,
+    pub functions: BTreeMap<String, FunctionCost>,
+    pub storage_writes: Vec<StorageWrite>,
+}
+
+const INTRINSIC_FRAME: &str = "intrinsic";
+const LOAD_RESOURCE_FRAME: &str = "load_resource";
+
+impl TransactionGasLog {
+    pub fn total_cost(&self) -> InternalGas {
+        self.intrinsic_cost + self.call_graph.total_cost()
+    }
+
+    /// Renders the gas profile as folded stacks, one `frame;...;frame cost` line per distinct
+    /// stack. The leaves of the stacks are the instructions, native functions and resource loads
+    /// of the frames. Stacks that cost nothing are omitted.
+    pub fn to_folded_stacks(&self) -> String {
+        fn collect(frame: &CallFrame, path: &str, stacks: &mut BTreeMap<String, u64>) {
+            let path = match path {
+                "" => frame.name.clone(),
+                path => format!("{};{}", path, frame.name),
+            };
+            let leaves = frame
+                .instructions
+                .iter()
+                .chain(frame.natives.iter())
+                .map(|(name, cost)| (name.as_str(), *cost))
+                .chain(std::iter::once((LOAD_RESOURCE_FRAME, frame.loads)));
+            for (name, cost) in leaves {
+                *stacks.entry(format!("{};{}", path, name)).or_default() += u64::from(cost);
+            }
+            for callee in &frame.callees {
+                collect(callee, &path, stacks);
+            }
+        }
+
+        let mut stacks = BTreeMap::new();
+        stacks.insert(INTRINSIC_FRAME.to_string(), u64::from(self.intrinsic_cost));
+        collect(&self.call_graph, "", &mut stacks);
+
+        stacks
+            .into_iter()
+            .filter(|(_, cost)| *cost > 0)
+            .map(|(stack, cost)| format!("{} {}\n", stack, cost))
+            .collect()
+    }
+
+    /// Aggregates the gas profile per instruction, native function and Move function.
+    pub fn to_cost_breakdown(&self) -> GasCostBreakdown {
+        fn collect(frame: &CallFrame, breakdown: &mut GasCostBreakdown) {
+            for (name, cost) in &frame.instructions {
+                *breakdown.instructions.entry(name.clone()).or_default() += u64::from(*cost);
+            }
+            for (name, cost) in &frame.natives {
+                *breakdown.natives.entry(name.clone()).or_default() += u64::from(*cost);
+            }
+            breakdown.loads_cost += u64::from(frame.loads);
+
+            let function = breakdown.functions.entry(frame.name.clone()).or_default();
+            function.calls += 1;
+            function.self_cost += u64::from(frame.self_cost());
+            function.total_cost += u64::from(frame.total_cost());
+
+            for callee in &frame.callees {
+                collect(callee, breakdown);
+            }
+        }
+
+        let mut breakdown = GasCostBreakdown {
+            total_cost: u64::from(self.total_cost()),
+            intrinsic_cost: u64::from(self.intrinsic_cost),
+            loads_cost: 0,
+            instructions: BTreeMap::new(),
+            natives: BTreeMap::new(),
+            functions: BTreeMap::new(),
+            storage_writes: self.storage_writes.clone(),
+        };
+        collect(&self.call_graph, &mut breakdown);
+        breakdown
+    }
+}
+
+/// A gas meter that forwards all charges to the wrapped gas meter, and records the cost of each
+/// of them in the frame of the function being executed.
+pub struct GasProfiler<G = AptosGasMeter> {
+    base: G,
+    intrinsic_cost: InternalGas,
+    /// The frames of the functions being executed. The first frame is the one of the script or
+    /// entry function, and is never popped.
+    frames: Vec<CallFrame>,
+    /// The cost of the operations that write to storage, per kind of write.
+    storage_op_costs: BTreeMap<StorageWriteKind, InternalGas>,
+    storage_writes: Vec<(StorageWriteKind, String, Option<NumBytes>)>,
+}
+
+impl<G: TransactionGasMeter> GasProfiler<G> {
+    /// Creates a profiler wrapping `base`, where `root` is the name of the script or entry
+    /// function executed by the transaction.
+    pub fn new(base: G, root: impl Into<String>) -> Self {
+        Self {
+            base,
+            intrinsic_cost: 0.into(),
+            frames: vec![CallFrame::new(root.into())],
+            storage_op_costs: BTreeMap::new(),
+            storage_writes: vec![],
+        }
+    }
+
+    /// Records a write to storage, which is not charged by the gas meter itself. Its cost is
+    /// derived from the operations of the same kind when the profile is finished.
+    pub fn record_storage_write(
+        &mut self,
+        kind: StorageWriteKind,
+        key: String,
+        num_bytes: Option<NumBytes>,
+    ) {
+        self.storage_writes.push((kind, key, num_bytes));
+    }
+
+    /// Returns the gas profile of the transaction.
+    /// Frames that are still active, e.g. because the execution aborted, are closed.
+    pub fn finish(mut self) -> TransactionGasLog {
+        while self.frames.len() > 1 {
+            self.pop_frame();
+        }
+
+        let mut num_writes = BTreeMap::<_, u64>::new();
+        for (kind, _, _) in &self.storage_writes {
+            *num_writes.entry(kind.clone()).or_default() += 1;
+        }
+        let storage_op_costs = &self.storage_op_costs;
+        let storage_writes = self
+            .storage_writes
+            .drain(..)
+            .map(|(kind, key, num_bytes)| StorageWrite {
+                cost: storage_op_costs
+                    .get(&kind)
+                    .map_or(0, |cost| u64::from(*cost) / num_writes[&kind]),
+                key,
+                num_bytes: num_bytes.map(u64::from),
+            })
+            .collect();
+
+        TransactionGasLog {
+            intrinsic_cost: self.intrinsic_cost,
+            call_graph: self.frames.pop().expect("the root frame is never popped"),
+            storage_writes,
+        }
+    }
+
+    /// Runs `charge` against the wrapped gas meter, and returns its result along with the amount
+    /// of gas it charged.
+    fn delegate<T>(&mut self, charge: impl FnOnce(&mut G) -> T) -> (T, InternalGas) {
+        let balance = self.base.balance_internal();
+        let res = charge(&mut self.base);
+        let cost = balance
+            .checked_sub(self.base.balance_internal())
+            .unwrap_or_else(|| 0.into());
+        (res, cost)
+    }
+
+    fn current_frame(&mut self) -> &mut CallFrame {
+        self.frames
+            .last_mut()
+            .expect("the root frame is never popped")
+    }
+
+    fn record_instr(&mut self, instr: impl ToString, cost: InternalGas) {
+        *self
+            .current_frame()
+            .instructions
+            .entry(instr.to_string())
+            .or_insert_with(|| 0.into()) += cost;
+    }
+
+    fn record_storage_op(&mut self, kind: StorageWriteKind, cost: InternalGas) {
+        *self
+            .storage_op_costs
+            .entry(kind)
+            .or_insert_with(|| 0.into()) += cost;
+    }
+
+    fn pop_frame(&mut self) {
+        if self.frames.len() > 1 {
+            let frame = self.frames.pop().expect("there are at least two frames");
+            self.current_frame().callees.push(frame);
+        }
+    }
+
+    fn charge_instr(
+        &mut self,
+        instr: impl ToString,
+        charge: impl FnOnce(&mut G) -> PartialVMResult<()>,
+    ) -> PartialVMResult<()> {
+        let (res, cost) = self.delegate(charge);
+        self.record_instr(instr, cost);
+        res
+    }
+
+    /// Like `charge_instr`, but also attributes the cost to the storage writes of the given kind.
+    fn charge_storage_write_instr(
+        &mut self,
+        instr: impl ToString,
+        kind: StorageWriteKind,
+        charge: impl FnOnce(&mut G) -> PartialVMResult<()>,
+    ) -> PartialVMResult<()> {
+        let (res, cost) = self.delegate(charge);
+        self.record_instr(instr, cost);
+        self.record_storage_op(kind, cost);
+        res
+    }
+}
+
+impl<G: TransactionGasMeter> GasMeter for GasProfiler<G> {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        let res = self.charge_instr(format!("{:?}", instr), |base| {
+            base.charge_simple_instr(instr)
+        });
+        if let SimpleInstruction::Ret = instr {
+            self.pop_frame();
+        }
+        res
+    }
+
+    fn charge_native_function(&mut self, amount: InternalGas) -> PartialVMResult<()> {
+        let (res, cost) = self.delegate(|base| base.charge_native_function(amount));
+
+        // Calls to native functions are charged like calls to Move functions, right before the
+        // native function itself. The frame pushed for the call is hence the one of the native
+        // function, and nothing has been charged in it yet.
+        let is_root = self.frames.len() == 1;
+        let frame = self.current_frame();
+        let is_native_frame = !is_root
+            && frame.instructions.is_empty()
+            && frame.natives.is_empty()
+            && frame.callees.is_empty();
+        if is_native_frame {
+            let native = self.frames.pop().expect("there are at least two frames");
+            if let Some(kind) = StorageWriteKind::of_native(&native.name) {
+                self.record_storage_op(kind, cost);
+            }
+            *self
+                .current_frame()
+                .natives
+                .entry(native.name)
+                .or_insert_with(|| 0.into()) += cost;
+        } else {
+            self.record_instr("native", cost);
+        }
+        res
+    }
+
+    fn charge_load_resource(&mut self, loaded: Option<NumBytes>) -> PartialVMResult<()> {
+        let (res, cost) = self.delegate(|base| base.charge_load_resource(loaded));
+        self.current_frame().loads += cost;
+        res
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let res = self.charge_instr("Call", |base| base.charge_call(module_id, func_name, args));
+        self.frames.push(CallFrame::new(format!(
+            "{}::{}",
+            module_id.short_str_lossless(),
+            func_name
+        )));
+        res
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let res = self.charge_instr("CallGeneric", |base| {
+            base.charge_call_generic(module_id, func_name, ty_args, args)
+        });
+        self.frames.push(CallFrame::new(format!(
+            "{}::{}",
+            module_id.short_str_lossless(),
+            func_name
+        )));
+        res
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.charge_instr("LdConst", |base| base.charge_ld_const(size))
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("CopyLoc", |base| base.charge_copy_loc(val))
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("MoveLoc", |base| base.charge_move_loc(val))
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("StLoc", |base| base.charge_store_loc(val))
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let instr = if is_generic { "PackGeneric" } else { "Pack" };
+        self.charge_instr(instr, |base| base.charge_pack(is_generic, args))
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let instr = if is_generic {
+            "UnpackGeneric"
+        } else {
+            "Unpack"
+        };
+        self.charge_instr(instr, |base| base.charge_unpack(is_generic, args))
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("ReadRef", |base| base.charge_read_ref(val))
+    }
+
+    fn charge_write_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("WriteRef", |base| base.charge_write_ref(val))
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("Eq", |base| base.charge_eq(lhs, rhs))
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.charge_instr("Neq", |base| base.charge_neq(lhs, rhs))
+    }
+
+    fn charge_borrow_global(
+        &mut self,
+        is_mut: bool,
+        is_generic: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let instr = match (is_mut, is_generic) {
+            (false, false) => "ImmBorrowGlobal",
+            (false, true) => "ImmBorrowGlobalGeneric",
+            (true, false) => "MutBorrowGlobal",
+            (true, true) => "MutBorrowGlobalGeneric",
+        };
+        if is_mut {
+            let kind = StorageWriteKind::Resource(ty.to_type_tag().to_string());
+            self.charge_storage_write_instr(instr, kind, |base| {
+                base.charge_borrow_global(is_mut, is_generic, ty, is_success)
+            })
+        } else {
+            self.charge_instr(instr, |base| {
+                base.charge_borrow_global(is_mut, is_generic, ty, is_success)
+            })
+        }
+    }
+
+    fn charge_exists(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        exists: bool,
+    ) -> PartialVMResult<()> {
+        let instr = if is_generic {
+            "ExistsGeneric"
+        } else {
+            "Exists"
+        };
+        self.charge_instr(instr, |base| base.charge_exists(is_generic, ty, exists))
+    }
+
+    fn charge_move_from(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let instr = if is_generic {
+            "MoveFromGeneric"
+        } else {
+            "MoveFrom"
+        };
+        let kind = StorageWriteKind::Resource(ty.to_type_tag().to_string());
+        self.charge_storage_write_instr(instr, kind, |base| {
+            base.charge_move_from(is_generic, ty, val)
+        })
+    }
+
+    fn charge_move_to(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: impl ValueView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let instr = if is_generic {
+            "MoveToGeneric"
+        } else {
+            "MoveTo"
+        };
+        let kind = StorageWriteKind::Resource(ty.to_type_tag().to_string());
+        self.charge_storage_write_instr(instr, kind, |base| {
+            base.charge_move_to(is_generic, ty, val, is_success)
+        })
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge_instr("VecPack", |base| base.charge_vec_pack(ty, args))
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.charge_instr("VecUnpack", |base| {
+            base.charge_vec_unpack(ty, expect_num_elements)
+        })
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.charge_instr("VecLen", |base| base.charge_vec_len(ty))
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let instr = if is_mut {
+            "VecMutBorrow"
+        } else {
+            "VecImmBorrow"
+        };
+        self.charge_instr(instr, |base| base.charge_vec_borrow(is_mut, ty, is_success))
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.charge_instr("VecPushBack", |base| base.charge_vec_push_back(ty, val))
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge_instr("VecPopBack", |base| base.charge_vec_pop_back(ty, val))
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.charge_instr("VecSwap", |base| base.charge_vec_swap(ty))
+    }
+}
+
+impl<G: TransactionGasMeter> TransactionGasMeter for GasProfiler<G> {
+    fn balance(&self) -> Gas {
+        self.base.balance()
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
+        let (res, cost) = self.delegate(|base| base.charge_intrinsic_gas_for_transaction(txn_size));
+        self.intrinsic_cost += cost;
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gas_meter::AptosGasParameters, InitialGasSchedule};
+    use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+    use move_vm_types::views::ValueVisitor;
+
+    struct U64Value(u64);
+
+    impl ValueView for U64Value {
+        fn visit(&self, visitor: &mut impl ValueVisitor) {
+            visitor.visit_u64(0, self.0)
+        }
+    }
+
+    fn profiler() -> GasProfiler {
+        let base = AptosGasMeter::new(AptosGasParameters::initial(), 1_000_000);
+        GasProfiler::new(base, "0x1::m::main")
+    }
+
+    fn call(profiler: &mut GasProfiler, func_name: &str) {
+        call_in(profiler, "m", func_name)
+    }
+
+    fn call_in(profiler: &mut GasProfiler, module_name: &str, func_name: &str) {
+        let module_id = ModuleId::new(AccountAddress::ONE, Identifier::new(module_name).unwrap());
+        profiler
+            .charge_call(&module_id, func_name, std::iter::once(U64Value(1)))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_call_graph() {
+        let mut profiler = profiler();
+        let initial_balance = profiler.balance_internal();
+        profiler
+            .charge_intrinsic_gas_for_transaction(NumBytes::new(100))
+            .unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::LdU64)
+            .unwrap();
+        call(&mut profiler, "f");
+        profiler
+            .charge_simple_instr(SimpleInstruction::Add)
+            .unwrap();
+        call(&mut profiler, "native");
+        profiler.charge_native_function(10.into()).unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        call(&mut profiler, "f");
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        let balance = profiler.balance_internal();
+        let log = profiler.finish();
+
+        let root = &log.call_graph;
+        assert_eq!(root.name, "0x1::m::main");
+        assert_eq!(
+            root.instructions.keys().collect::<Vec<_>>(),
+            vec!["Call", "LdU64", "Ret"]
+        );
+        assert_eq!(root.callees.len(), 2);
+
+        let f = &root.callees[0];
+        assert_eq!(f.name, "0x1::m::f");
+        assert!(f.callees.is_empty());
+        assert_eq!(u64::from(f.natives["0x1::m::native"]), 10);
+        assert!(root.callees[1].natives.is_empty());
+
+        assert!(u64::from(log.intrinsic_cost) > 0);
+        assert_eq!(log.total_cost() + balance, initial_balance);
+    }
+
+    #[test]
+    fn test_folded_stacks_and_breakdown() {
+        let mut profiler = profiler();
+        call(&mut profiler, "f");
+        profiler
+            .charge_simple_instr(SimpleInstruction::Add)
+            .unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        call(&mut profiler, "f");
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        profiler.record_storage_write(
+            StorageWriteKind::Resource("0x1::m::R".to_string()),
+            "0x1::m::R".to_string(),
+            Some(NumBytes::new(8)),
+        );
+        let log = profiler.finish();
+
+        let stacks = log.to_folded_stacks();
+        let lines: Vec<_> = stacks.lines().collect();
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("0x1::m::main;Call ")));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("0x1::m::main;0x1::m::f;Add ")));
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("0x1::m::main;0x1::m::f;Ret "))
+                .count(),
+            1
+        );
+
+        let breakdown = log.to_cost_breakdown();
+        assert_eq!(breakdown.functions["0x1::m::f"].calls, 2);
+        assert_eq!(breakdown.total_cost, u64::from(log.total_cost()));
+        assert_eq!(
+            breakdown.total_cost,
+            stacks
+                .lines()
+                .map(|line| line.rsplit(' ').next().unwrap().parse::<u64>().unwrap())
+                .sum::<u64>()
+        );
+        assert_eq!(breakdown.storage_writes[0].num_bytes, Some(8));
+        assert_eq!(breakdown.storage_writes[0].cost, 0);
+    }
+
+    #[test]
+    fn test_storage_write_costs() {
+        let mut profiler = profiler();
+        call_in(&mut profiler, "table", "add_box");
+        profiler.charge_native_function(10.into()).unwrap();
+        call_in(&mut profiler, "table", "remove_box");
+        profiler.charge_native_function(20.into()).unwrap();
+        call_in(&mut profiler, "table", "borrow_box");
+        profiler.charge_native_function(40.into()).unwrap();
+        for key in ["a", "b"] {
+            profiler.record_storage_write(
+                StorageWriteKind::TableItem,
+                key.to_string(),
+                Some(NumBytes::new(8)),
+            );
+        }
+        profiler.record_storage_write(StorageWriteKind::Module, "m".to_string(), None);
+        let log = profiler.finish();
+
+        let costs: Vec<_> = log
+            .storage_writes
+            .iter()
+            .map(|write| (write.key.as_str(), write.cost))
+            .collect();
+        assert_eq!(costs, vec![("a", 15), ("b", 15), ("m", 0)]);
+    }
+}
@@ -4,7 +4,7 @@
 //! This crate is the core of the gas metering system of the Aptos blockchain.
 //!
 //! More specifically, it
-//!   - Is home to the gas meter implementation
+//!   - Is home to the gas meter implementation, and a gas profiler wrapping it
 //!   - Defines the gas parameters and formulae for instructions
 //!   - Defines the gas parameters for transactions
 //!   - Sets the initial values for all gas parameters, including the instruction, transaction
@@ -27,6 +27,7 @@ mod params;
 mod algebra;
 mod aptos_framework;
 mod gas_meter;
+mod gas_profiler;
 mod instr;
 mod misc;
 mod move_stdlib;
@@ -36,7 +37,11 @@ mod transaction;
 pub use algebra::*;
 pub use gas_meter::{
     AptosGasMeter, AptosGasParameters, FromOnChainGasSchedule, InitialGasSchedule,
-    NativeGasParameters, ToOnChainGasSchedule,
+    NativeGasParameters, ToOnChainGasSchedule, TransactionGasMeter,
+};
+pub use gas_profiler::{
+    CallFrame, FunctionCost, GasCostBreakdown, GasProfiler, StorageWrite, StorageWriteKind,
+    TransactionGasLog,
 };
 pub use instr::InstructionGasParameters;
 pub use misc::{AbstractValueSizeGasParameters, MiscGasParameters};
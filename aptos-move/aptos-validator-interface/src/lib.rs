@@ -1,9 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod rest_interface;
 mod storage_interface;
 
-pub use crate::storage_interface::DBDebuggerInterface;
+pub use crate::{rest_interface::RestDebuggerInterface, storage_interface::DBDebuggerInterface};
 
 use anyhow::{anyhow, Result};
 use aptos_state_view::state_storage_usage::StateStorageUsage;
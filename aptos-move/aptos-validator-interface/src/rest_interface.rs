@@ -0,0 +1,281 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosValidatorInterface;
+use anyhow::{anyhow, Result};
+use aptos_infallible::Mutex;
+use aptos_rest_client::{error::RestError, Client};
+use aptos_types::{
+    access_path::{AccessPath, Path},
+    account_address::AccountAddress,
+    account_state::AccountState,
+    contract_event::EventWithVersion,
+    event::EventKey,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, Version},
+};
+use move_deps::move_core_types::language_storage::{ModuleId, ResourceKey};
+use std::{collections::HashMap, convert::TryFrom, future::Future};
+use tokio::runtime::Runtime;
+
+/// Maximum number of transactions requested from the REST API at once.
+const MAX_TRANSACTIONS_PER_REQUEST: u64 = 100;
+
+/// An `AptosValidatorInterface` backed by the REST API of a fullnode.
+///
+/// State values are fetched lazily, at the requested version, and cached so that replaying a
+/// transaction only reads the state it actually touches, once.
+pub struct RestDebuggerInterface {
+    client: Client,
+    runtime: Runtime,
+    state_cache: Mutex<HashMap<(StateKey, Version), Option<StateValue>>>,
+}
+
+impl RestDebuggerInterface {
+    pub fn new(client: Client) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .thread_name("rest-debugger")
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            client,
+            runtime,
+            state_cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The validator interface is synchronous, so requests are driven on our own runtime. This
+    /// must not be called from within an async context.
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    fn fetch_state_value(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        let bytes = match state_key {
+            StateKey::AccessPath(access_path) => match access_path.get_path() {
+                Path::Code(module_id) => self
+                    .block_on(self.client.get_account_module_at_version_bcs(
+                        *module_id.address(),
+                        module_id.name().as_str(),
+                        version,
+                    ))
+                    .map(|response| response.into_inner().to_vec()),
+                Path::Resource(struct_tag) => self
+                    .block_on(self.client.get_account_resource_at_version_bytes(
+                        access_path.address,
+                        &struct_tag.to_string(),
+                        version,
+                    ))
+                    .map(|response| response.into_inner()),
+            },
+            StateKey::TableItem { handle, key } => self
+                .block_on(self.client.get_raw_table_item(handle.0, key, version))
+                .map(|response| response.into_inner()),
+            StateKey::Raw(_) => return Err(anyhow!("Raw state keys can't be fetched over REST")),
+        };
+        match bytes {
+            Ok(bytes) => Ok(Some(StateValue::from(bytes))),
+            Err(err) if is_not_found(&err) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+impl AptosValidatorInterface for RestDebuggerInterface {
+    fn get_account_state_by_version(
+        &self,
+        account: AccountAddress,
+        version: Version,
+    ) -> Result<Option<AccountState>> {
+        let resources = match self.block_on(
+            self.client
+                .get_account_resources_at_version_bcs(account, version),
+        ) {
+            Ok(response) => response.into_inner(),
+            Err(err) if is_not_found(&err) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let modules = self
+            .block_on(
+                self.client
+                    .get_account_modules_at_version_bcs(account, version),
+            )?
+            .into_inner();
+
+        let mut key_value_map = HashMap::new();
+        for (struct_tag, bytes) in resources {
+            let access_path =
+                AccessPath::resource_access_path(ResourceKey::new(account, struct_tag));
+            key_value_map.insert(StateKey::AccessPath(access_path), StateValue::from(bytes));
+        }
+        for (module_id, bytes) in modules {
+            let access_path = AccessPath::code_access_path(ModuleId::from(module_id));
+            key_value_map.insert(StateKey::AccessPath(access_path), StateValue::from(bytes));
+        }
+        AccountState::from_access_paths_and_values(account, &key_value_map)
+    }
+
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        let cache_key = (state_key.clone(), version);
+        if let Some(state_value) = self.state_cache.lock().get(&cache_key) {
+            return Ok(state_value.clone());
+        }
+        let state_value = self.fetch_state_value(state_key, version)?;
+        self.state_cache
+            .lock()
+            .insert(cache_key, state_value.clone());
+        Ok(state_value)
+    }
+
+    fn get_events(
+        &self,
+        key: &EventKey,
+        start_seq: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        let limit = u16::try_from(limit).unwrap_or(u16::MAX);
+        let events = match self.block_on(self.client.get_events_bcs_by_event_key(
+            *key,
+            Some(start_seq),
+            Some(limit),
+        )) {
+            Ok(response) => response.into_inner(),
+            Err(err) if is_not_found(&err) => vec![],
+            Err(err) => return Err(err.into()),
+        };
+        // The events endpoint always reads from the latest ledger version.
+        Ok(events
+            .into_iter()
+            .filter(|event| event.transaction_version <= ledger_version)
+            .collect())
+    }
+
+    fn get_committed_transactions(&self, start: Version, limit: u64) -> Result<Vec<Transaction>> {
+        let mut transactions = vec![];
+        let end = start.saturating_add(limit);
+        let mut cursor = start;
+        while cursor < end {
+            let page_size = std::cmp::min(end - cursor, MAX_TRANSACTIONS_PER_REQUEST) as u16;
+            let page = self
+                .block_on(
+                    self.client
+                        .get_transactions_bcs(Some(cursor), Some(page_size)),
+                )?
+                .into_inner();
+            if page.is_empty() {
+                break;
+            }
+            cursor += page.len() as u64;
+            transactions.extend(page.into_iter().map(|txn| txn.transaction));
+        }
+        Ok(transactions)
+    }
+
+    fn get_latest_version(&self) -> Result<Version> {
+        Ok(self
+            .block_on(self.client.get_ledger_information())?
+            .into_inner()
+            .version)
+    }
+
+    fn get_version_by_account_sequence(
+        &self,
+        account: AccountAddress,
+        seq: u64,
+    ) -> Result<Option<Version>> {
+        match self.block_on(
+            self.client
+                .get_account_transactions_bcs(account, Some(seq), Some(1)),
+        ) {
+            Ok(response) => Ok(response.into_inner().first().map(|txn| txn.version)),
+            Err(err) if is_not_found(&err) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+fn is_not_found(err: &RestError) -> bool {
+    matches!(err, RestError::Api(response) if response.status_code.as_u16() == 404)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_api_types::{
+        X_APTOS_BLOCK_HEIGHT, X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_OLDEST_VERSION,
+        X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION, X_APTOS_OLDEST_BLOCK_HEIGHT,
+    };
+    use aptos_types::{account_config::AccountResource, state_store::table::TableHandle};
+    use httpmock::{Method::POST, MockServer, Then};
+    use move_deps::move_core_types::move_resource::MoveStructType;
+    use serde_json::json;
+
+    fn with_state_headers(then: Then) -> Then {
+        then.header(X_APTOS_CHAIN_ID, "4")
+            .header(X_APTOS_LEDGER_VERSION, "10")
+            .header(X_APTOS_LEDGER_TIMESTAMP, "0")
+            .header(X_APTOS_EPOCH, "1")
+            .header(X_APTOS_LEDGER_OLDEST_VERSION, "0")
+            .header(X_APTOS_BLOCK_HEIGHT, "5")
+            .header(X_APTOS_OLDEST_BLOCK_HEIGHT, "0")
+    }
+
+    fn debugger(server: &MockServer) -> RestDebuggerInterface {
+        RestDebuggerInterface::new(Client::new(server.base_url().parse().unwrap())).unwrap()
+    }
+
+    #[test]
+    fn test_get_resource_is_cached() {
+        let server = MockServer::start();
+        let account = AccountAddress::random();
+        let struct_tag = AccountResource::struct_tag();
+        let mock = server.mock(|when, then| {
+            when.path(format!("/v1/accounts/{}/resource/{}", account, struct_tag))
+                .query_param("ledger_version", "7");
+            with_state_headers(then.status(200)).body(vec![1, 2, 3]);
+        });
+
+        let debugger = debugger(&server);
+        let state_key = StateKey::AccessPath(AccessPath::resource_access_path(ResourceKey::new(
+            account, struct_tag,
+        )));
+        for _ in 0..2 {
+            let state_value = debugger.get_state_value_by_version(&state_key, 7).unwrap();
+            assert_eq!(state_value, Some(StateValue::from(vec![1, 2, 3])));
+        }
+        mock.assert_hits(1);
+    }
+
+    #[test]
+    fn test_get_missing_table_item() {
+        let server = MockServer::start();
+        let handle = TableHandle(AccountAddress::random());
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path(format!("/v1/tables/{}/raw_item", handle.0))
+                .query_param("ledger_version", "7")
+                .json_body(json!({ "key": "0x0102" }));
+            then.status(404).json_body(json!({
+                "message": "Table Item not found",
+                "error_code": "table_item_not_found",
+            }));
+        });
+
+        let debugger = debugger(&server);
+        let state_key = StateKey::table_item(handle, vec![1, 2]);
+        assert_eq!(
+            debugger.get_state_value_by_version(&state_key, 7).unwrap(),
+            None
+        );
+        mock.assert();
+    }
+}
@@ -129,9 +129,11 @@ pub(crate) fn execute_block_impl<A: VMAdapter, S: StateView>(
     adapter: &A,
     transactions: Vec<Transaction>,
     data_cache: &mut StateViewCache<S>,
+    maybe_block_gas_limit: Option<u64>,
 ) -> Result<Vec<(VMStatus, TransactionOutput)>, VMStatus> {
     let mut result = vec![];
     let mut should_restart = false;
+    let mut accumulated_gas: u64 = 0;
 
     info!(
         AdapterLogSchema::new(data_cache.id(), 0),
@@ -156,7 +158,10 @@ pub(crate) fn execute_block_impl<A: VMAdapter, S: StateView>(
             let txn_output =
                 TransactionOutput::new(WriteSet::default(), vec![], 0, TransactionStatus::Retry);
             result.push((VMStatus::Error(StatusCode::UNKNOWN_STATUS), txn_output));
-            debug!(log_context, "Retry after reconfiguration");
+            debug!(
+                log_context,
+                "Retry after reconfiguration or reaching the block gas limit"
+            );
             continue;
         };
         let (vm_status, output_ext, sender) = adapter.execute_single_transaction(
@@ -190,6 +195,17 @@ pub(crate) fn execute_block_impl<A: VMAdapter, S: StateView>(
             should_restart = true;
         }
 
+        // The transactions following the one at which the block gas limit is reached are
+        // retried, the same way as in the parallel executor.
+        accumulated_gas = accumulated_gas.saturating_add(output.gas_used());
+        if maybe_block_gas_limit.map_or(false, |limit| accumulated_gas >= limit) {
+            info!(
+                AdapterLogSchema::new(data_cache.id(), idx),
+                "Block gas limit reached: {} gas used", accumulated_gas,
+            );
+            should_restart = true;
+        }
+
         // `result` is initially empty, a single element is pushed per loop iteration and
         // the number of iterations is bound to the max size of `signature_verified_block`
         assume!(result.len() < usize::max_value());
@@ -25,23 +25,28 @@ use aptos_aggregator::{
     transaction::{ChangeSetExt, TransactionOutputExt},
 };
 use aptos_crypto::HashValue;
-use aptos_gas::AptosGasMeter;
+use aptos_gas::{
+    AptosGasMeter, AptosGasParameters, Gas, GasProfiler, NumBytes, StorageWriteKind,
+    TransactionGasLog, TransactionGasMeter,
+};
 use aptos_logger::prelude::*;
 use aptos_module_verifier::module_init::verify_module_init_function;
 use aptos_state_view::StateView;
 use aptos_types::account_config::new_block_event_key;
 use aptos_types::vm_status::AbortLocation;
 use aptos_types::{
+    access_path::Path,
     account_config,
     block_metadata::BlockMetadata,
     on_chain_config::{new_epoch_event_key, GasSchedule, Version},
+    state_store::state_key::StateKey,
     transaction::{
         ChangeSet, ExecutionStatus, ModuleBundle, SignatureCheckedTransaction, SignedTransaction,
         Transaction, TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
         WriteSetPayload,
     },
     vm_status::{StatusCode, VMStatus},
-    write_set::WriteSet,
+    write_set::{WriteOp, WriteSet},
 };
 use fail::fail_point;
 use framework::natives::code::PublishRequest;
@@ -49,12 +54,14 @@ use move_deps::{
     move_binary_format::{
         access::ModuleAccess,
         errors::{verification_error, Location, PartialVMError, VMResult},
+        file_format::Visibility,
         CompiledModule, IndexKind,
     },
     move_core_types::{
         account_address::AccountAddress,
         ident_str,
-        language_storage::ModuleId,
+        identifier::Identifier,
+        language_storage::{ModuleId, TypeTag},
         transaction_argument::convert_txn_args,
         value::{serialize_values, MoveValue},
     },
@@ -141,12 +148,17 @@ impl AptosVM {
         self.0.load_module(module_id, state)
     }
 
+    /// Invalidate the code cache of its internal MoveVM, which is flushed before the next session.
+    pub fn mark_loader_cache_as_invalid(&self) {
+        self.0.mark_loader_cache_as_invalid()
+    }
+
     /// Generates a transaction output for a transaction that encountered errors during the
     /// execution process. This is public for now only for tests.
-    pub fn failed_transaction_cleanup<S: MoveResolverExt>(
+    pub fn failed_transaction_cleanup<S: MoveResolverExt, G: TransactionGasMeter>(
         &self,
         error_code: VMStatus,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
         txn_data: &TransactionMetadata,
         storage: &S,
         log_context: &AdapterLogSchema,
@@ -161,10 +173,10 @@ impl AptosVM {
         .1
     }
 
-    fn failed_transaction_cleanup_and_keep_vm_status<S: MoveResolverExt>(
+    fn failed_transaction_cleanup_and_keep_vm_status<S: MoveResolverExt, G: TransactionGasMeter>(
         &self,
         error_code: VMStatus,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
         txn_data: &TransactionMetadata,
         storage: &S,
         log_context: &AdapterLogSchema,
@@ -215,11 +227,11 @@ impl AptosVM {
         }
     }
 
-    fn success_transaction_cleanup<S: MoveResolverExt + StateView>(
+    fn success_transaction_cleanup<S: MoveResolverExt + StateView, G: TransactionGasMeter>(
         &self,
         storage: &S,
         user_txn_change_set_ext: ChangeSetExt,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(VMStatus, TransactionOutputExt), VMStatus> {
@@ -280,11 +292,11 @@ impl AptosVM {
         ))
     }
 
-    fn execute_script_or_entry_function<S: MoveResolverExt + StateView>(
+    fn execute_script_or_entry_function<S: MoveResolverExt + StateView, G: TransactionGasMeter>(
         &self,
         storage: &S,
         mut session: SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
         txn_data: &TransactionMetadata,
         payload: &TransactionPayload,
         log_context: &AdapterLogSchema,
@@ -395,10 +407,10 @@ impl AptosVM {
     }
 
     /// Execute all module initializers.
-    fn execute_module_initialization<S: MoveResolverExt>(
+    fn execute_module_initialization<S: MoveResolverExt, G: TransactionGasMeter>(
         &self,
         session: &mut SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
         modules: &[CompiledModule],
         senders: &[AccountAddress],
     ) -> VMResult<()> {
@@ -449,11 +461,11 @@ impl AptosVM {
     /// Execute a module bundle load request.
     /// TODO: this is going to be deprecated and removed in favor of code publishing via
     /// NativeCodeContext
-    fn execute_modules<S: MoveResolverExt + StateView>(
+    fn execute_modules<S: MoveResolverExt + StateView, G: TransactionGasMeter>(
         &self,
         storage: &S,
         mut session: SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
         txn_data: &TransactionMetadata,
         modules: &ModuleBundle,
         log_context: &AdapterLogSchema,
@@ -469,6 +481,9 @@ impl AptosVM {
             .map_err(|e| e.into_vm_status())?;
 
         Self::verify_module_bundle(&mut session, modules)?;
+        // The modules loaded from the bundle (e.g. to run the initializers) must not be used
+        // from the code cache by other transactions, as the publishing may fail or be discarded.
+        self.mark_loader_cache_as_invalid();
         session
             .publish_module_bundle(modules.clone().into_inner(), txn_data.sender(), gas_meter)
             .map_err(|e| e.into_vm_status())?;
@@ -488,10 +503,10 @@ impl AptosVM {
     }
 
     /// Resolve a pending code publish request registered via the NativeCodeContext.
-    fn resolve_pending_code_publish<S: MoveResolverExt>(
+    fn resolve_pending_code_publish<S: MoveResolverExt, G: TransactionGasMeter>(
         &self,
         session: &mut SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut G,
     ) -> VMResult<()> {
         if let Some(PublishRequest {
             destination,
@@ -509,6 +524,10 @@ impl AptosVM {
             // Validate the module bundle
             self.validate_publish_request(&modules, expected_modules)?;
 
+            // The modules loaded from the bundle (e.g. to run the initializers) must not be used
+            // from the code cache by other transactions, as the publishing may fail or be discarded.
+            self.mark_loader_cache_as_invalid();
+
             // Publish the bundle
             if check_compat {
                 session.publish_module_bundle(bundle.into_inner(), destination, gas_meter)?
@@ -794,7 +813,13 @@ impl AptosVM {
         let mut state_view_cache = StateViewCache::new(state_view);
         let count = transactions.len();
         let vm = AptosVM::new(&state_view_cache);
-        let res = adapter_common::execute_block_impl(&vm, transactions, &mut state_view_cache)?;
+        let maybe_block_gas_limit = vm.0.get_block_gas_limit();
+        let res = adapter_common::execute_block_impl(
+            &vm,
+            transactions,
+            &mut state_view_cache,
+            maybe_block_gas_limit,
+        )?;
         // Record the histogram count for transactions per block.
         BLOCK_TRANSACTION_COUNT.observe(count as f64);
         Ok(res)
@@ -807,7 +832,134 @@ impl AptosVM {
         let vm = AptosVM::new(state_view);
         let simulation_vm = AptosSimulationVM(vm);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        simulation_vm.simulate_signed_transaction(&state_view.as_move_resolver(), txn, &log_context)
+        let (vm_status, output, _gas_meter) = simulation_vm.simulate_signed_transaction(
+            &state_view.as_move_resolver(),
+            txn,
+            &log_context,
+            true,
+            AptosGasMeter::new,
+        );
+        (vm_status, output)
+    }
+
+    /// Same as `simulate_signed_transaction`, but also profiles the gas used by the transaction.
+    /// The gas profile is `None` if the transaction is discarded before being executed.
+    pub fn simulate_signed_transaction_with_gas_profiling(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutputExt, Option<TransactionGasLog>) {
+        Self::execute_signed_transaction_with_gas_profiling(txn, state_view, true)
+    }
+
+    /// Re-executes an already committed transaction, e.g. to replay it, and profiles the gas it
+    /// uses. Unlike a simulated transaction, it carries a valid signature, which isn't checked.
+    pub fn replay_signed_transaction_with_gas_profiling(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutputExt, Option<TransactionGasLog>) {
+        Self::execute_signed_transaction_with_gas_profiling(txn, state_view, false)
+    }
+
+    fn execute_signed_transaction_with_gas_profiling(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+        is_simulation: bool,
+    ) -> (VMStatus, TransactionOutputExt, Option<TransactionGasLog>) {
+        let vm = AptosVM::new(state_view);
+        let simulation_vm = AptosSimulationVM(vm);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let root = match txn.payload() {
+            TransactionPayload::Script(_) => "script".to_string(),
+            TransactionPayload::EntryFunction(entry_function) => format!(
+                "{}::{}",
+                entry_function.module().short_str_lossless(),
+                entry_function.function()
+            ),
+            TransactionPayload::ModuleBundle(_) => "module_bundle".to_string(),
+        };
+        let (vm_status, output, gas_profiler) = simulation_vm.simulate_signed_transaction(
+            &state_view.as_move_resolver(),
+            txn,
+            &log_context,
+            is_simulation,
+            |gas_params, balance| GasProfiler::new(AptosGasMeter::new(gas_params, balance), root),
+        );
+
+        let gas_log = gas_profiler.map(|mut gas_profiler| {
+            for (key, op) in output.txn_output().write_set() {
+                let num_bytes = match op {
+                    WriteOp::Creation(blob) | WriteOp::Modification(blob) => {
+                        Some(NumBytes::new(blob.len() as u64))
+                    }
+                    WriteOp::Deletion => None,
+                };
+                let kind = match key {
+                    StateKey::AccessPath(access_path) => match access_path.get_path() {
+                        Path::Resource(struct_tag) => {
+                            StorageWriteKind::Resource(struct_tag.to_string())
+                        }
+                        Path::Code(_) => StorageWriteKind::Module,
+                    },
+                    StateKey::TableItem { .. } => StorageWriteKind::TableItem,
+                    // Raw keys are only written by tests, never by transactions.
+                    StateKey::Raw(_) => continue,
+                };
+                gas_profiler.record_storage_write(kind, format!("{:?}", key), num_bytes);
+            }
+            gas_profiler.finish()
+        });
+        (vm_status, output, gas_log)
+    }
+
+    /// Executes a public Move function against `state_view` without committing any of its
+    /// effects, and returns the BCS encoded return values.
+    ///
+    /// The function must not take any signer arguments and must return at least one value.
+    /// Execution is metered and aborts once `gas_budget` is exhausted.
+    pub fn execute_view_function(
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> Result<Vec<Vec<u8>>, VMStatus> {
+        let vm = AptosVM::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let gas_params = vm.0.get_gas_parameters(&log_context)?.clone();
+        let mut gas_meter = AptosGasMeter::new(gas_params, gas_budget);
+
+        let resolver = state_view.as_move_resolver();
+        let module = vm
+            .load_module(&module_id, &resolver)
+            .map_err(|e| e.into_vm_status())?;
+        if !module.function_defs().iter().any(|func_def| {
+            func_def.visibility == Visibility::Public
+                && module.identifier_at(module.function_handle_at(func_def.function).name)
+                    == func_name.as_ident_str()
+        }) {
+            return Err(VMStatus::Error(StatusCode::FUNCTION_RESOLUTION_FAILURE));
+        }
+
+        let mut session = vm.new_session(&resolver, SessionId::void());
+        let function = session.load_function(&module_id, &func_name, &type_args)?;
+        let arguments = transaction_arg_validation::validate_view_function_args(
+            &session, arguments, &function,
+        )?;
+        let return_values = session
+            .execute_function_bypass_visibility(
+                &module_id,
+                &func_name,
+                type_args,
+                arguments,
+                &mut gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?
+            .return_values;
+        Ok(return_values
+            .into_iter()
+            .map(|(bytes, _layout)| bytes)
+            .collect())
     }
 
     fn run_prologue_with_payload<S: MoveResolverExt>(
@@ -854,13 +1006,11 @@ impl VMExecutor for AptosVM {
 
         let concurrency_level = Self::get_concurrency_level();
         if concurrency_level > 1 {
-            let (result, err) = crate::parallel_executor::ParallelAptosVM::execute_block(
+            crate::parallel_executor::ParallelAptosVM::execute_block(
                 transactions,
                 state_view,
                 concurrency_level,
-            )?;
-            debug!("Parallel execution error {:?}", err);
-            Ok(result)
+            )
         } else {
             let output = Self::execute_block_and_keep_vm_status(transactions, state_view)?;
             Ok(output
@@ -1018,18 +1168,28 @@ impl AptosSimulationVM {
     }
 
     /*
-    Executes a SignedTransaction without performing signature verification
+    Executes a SignedTransaction without performing signature verification, metered by the gas
+    meter built by `new_gas_meter`. The gas meter is returned unless the transaction is discarded
+    before being executed. Simulated transactions must not carry a valid signature, only already
+    committed transactions that are replayed may.
      */
-    fn simulate_signed_transaction<S: MoveResolverExt + StateView>(
+    fn simulate_signed_transaction<S: MoveResolverExt + StateView, G: TransactionGasMeter>(
         &self,
         storage: &S,
         txn: &SignedTransaction,
         log_context: &AdapterLogSchema,
-    ) -> (VMStatus, TransactionOutputExt) {
+        is_simulation: bool,
+        new_gas_meter: impl FnOnce(AptosGasParameters, Gas) -> G,
+    ) -> (VMStatus, TransactionOutputExt, Option<G>) {
+        let discard = |err| {
+            let (vm_status, output) = discard_error_vm_status(err);
+            (vm_status, output, None)
+        };
+
         // simulation transactions should not carry valid signatures, otherwise malicious fullnodes
         // may execute them without user's explicit permission.
-        if txn.signature_is_valid() {
-            return discard_error_vm_status(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
+        if is_simulation && txn.signature_is_valid() {
+            return discard(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
         }
 
         // Revalidate the transaction.
@@ -1038,14 +1198,14 @@ impl AptosSimulationVM {
         if let Err(err) =
             self.validate_simulated_transaction::<S>(&mut session, txn, &txn_data, log_context)
         {
-            return discard_error_vm_status(err);
+            return discard(err);
         };
 
         let gas_params = match self.0 .0.get_gas_parameters(log_context) {
-            Err(err) => return discard_error_vm_status(err),
+            Err(err) => return discard(err),
             Ok(s) => s,
         };
-        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
+        let mut gas_meter = new_gas_meter(gas_params.clone(), txn_data.max_gas_amount());
 
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
@@ -1065,7 +1225,7 @@ impl AptosSimulationVM {
             }
         };
 
-        match result {
+        let (vm_status, output) = match result {
             Ok(output) => output,
             Err(err) => {
                 let txn_status = TransactionStatus::from(err.clone());
@@ -1082,6 +1242,7 @@ impl AptosSimulationVM {
                     (vm_status, output)
                 }
             }
-        }
+        };
+        (vm_status, output, Some(gas_meter))
     }
 }
@@ -8,6 +8,7 @@ use crate::{
     errors::{convert_epilogue_error, convert_prologue_error, expect_only_successful_execution},
     logging::AdapterLogSchema,
     move_vm_ext::{MoveResolverExt, MoveVmExt, SessionExt, SessionId},
+    system_module_names::{FEE_PAYER_SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_GAS_PAYER_NAME},
     transaction_metadata::TransactionMetadata,
 };
 use aptos_aggregator::transaction::TransactionOutputExt;
@@ -20,7 +21,9 @@ use aptos_state_view::StateView;
 use aptos_types::transaction::AbortInfo;
 use aptos_types::{
     account_config::{ChainSpecificAccountInfo, APTOS_CHAIN_INFO, CORE_CODE_ADDRESS},
-    on_chain_config::{GasSchedule, OnChainConfig, Version, APTOS_VERSION_3},
+    on_chain_config::{
+        BlockGasLimit, GasSchedule, OnChainConfig, Version, APTOS_VERSION_3, APTOS_VERSION_5,
+    },
     transaction::{ExecutionStatus, TransactionOutput, TransactionStatus},
     vm_status::{StatusCode, VMStatus},
 };
@@ -30,6 +33,7 @@ use framework::{RuntimeModuleMetadata, APTOS_METADATA_KEY};
 use move_deps::{
     move_binary_format::{errors::VMResult, CompiledModule},
     move_core_types::{
+        identifier::IdentStr,
         language_storage::ModuleId,
         move_resource::MoveStructType,
         resolver::ResourceResolver,
@@ -46,6 +50,7 @@ pub struct AptosVMImpl {
     move_vm: Arc<MoveVmExt>,
     gas_params: Option<AptosGasParameters>,
     version: Option<Version>,
+    block_gas_limit: Option<u64>,
     chain_account_info: Option<ChainSpecificAccountInfo>,
     metadata_cache: DashMap<ModuleId, Option<RuntimeModuleMetadata>>,
 }
@@ -77,10 +82,13 @@ impl AptosVMImpl {
             move_vm: Arc::new(inner),
             gas_params,
             version: None,
+            block_gas_limit: None,
             chain_account_info: None,
             metadata_cache: Default::default(),
         };
         vm.version = Version::fetch_config(&storage);
+        vm.block_gas_limit =
+            BlockGasLimit::fetch_config(&storage).and_then(|block_gas_limit| block_gas_limit.limit);
         vm.chain_account_info = Self::get_chain_specific_account_info(&StorageAdapter::new(state));
         vm
     }
@@ -98,6 +106,7 @@ impl AptosVMImpl {
             move_vm: Arc::new(inner),
             gas_params: Some(gas_params),
             version: Some(version),
+            block_gas_limit: None,
             chain_account_info: None,
             metadata_cache: Default::default(),
         }
@@ -146,6 +155,11 @@ impl AptosVMImpl {
         })
     }
 
+    /// The limit on the total gas used by the transactions of a block, if any.
+    pub fn get_block_gas_limit(&self) -> Option<u64> {
+        self.block_gas_limit
+    }
+
     pub fn check_gas(
         &self,
         txn_data: &TransactionMetadata,
@@ -230,8 +244,9 @@ impl AptosVMImpl {
         Ok(())
     }
 
-    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function
-    /// or `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function stored in the `ACCOUNT_MODULE` on chain.
+    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function,
+    /// `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function or `FEE_PAYER_SCRIPT_PROLOGUE_NAME` function
+    /// stored in the `ACCOUNT_MODULE` on chain.
     pub(crate) fn run_script_prologue<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
@@ -251,20 +266,47 @@ impl AptosVMImpl {
             .iter()
             .map(|auth_key| MoveValue::vector_u8(auth_key.to_vec()))
             .collect();
-        let args = if self.get_version()? >= APTOS_VERSION_3 && txn_data.is_multi_agent() {
-            vec![
+        if (txn_data.is_fee_payer() || txn_data.has_secp256k1_ecdsa_signer())
+            && self.get_version()? < APTOS_VERSION_5
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
+        let (prologue_function_name, args) = if let (Some(fee_payer), Some(fee_payer_auth_key)) = (
+            txn_data.fee_payer(),
+            txn_data.fee_payer_authentication_key.as_ref(),
+        ) {
+            let args = vec![
                 MoveValue::Signer(txn_data.sender),
                 MoveValue::U64(txn_sequence_number),
                 MoveValue::vector_u8(txn_authentication_key),
                 MoveValue::vector_address(txn_data.secondary_signers()),
                 MoveValue::Vector(secondary_auth_keys),
+                MoveValue::Address(fee_payer),
+                MoveValue::vector_u8(fee_payer_auth_key.to_vec()),
                 MoveValue::U64(txn_gas_price.into()),
                 MoveValue::U64(txn_max_gas_units.into()),
                 MoveValue::U64(txn_expiration_timestamp_secs),
                 MoveValue::U8(chain_id.id()),
-            ]
+            ];
+            (FEE_PAYER_SCRIPT_PROLOGUE_NAME, args)
+        } else if self.get_version()? >= APTOS_VERSION_3 && txn_data.is_multi_agent() {
+            let args = vec![
+                MoveValue::Signer(txn_data.sender),
+                MoveValue::U64(txn_sequence_number),
+                MoveValue::vector_u8(txn_authentication_key),
+                MoveValue::vector_address(txn_data.secondary_signers()),
+                MoveValue::Vector(secondary_auth_keys),
+                MoveValue::U64(txn_gas_price.into()),
+                MoveValue::U64(txn_max_gas_units.into()),
+                MoveValue::U64(txn_expiration_timestamp_secs),
+                MoveValue::U8(chain_id.id()),
+            ];
+            (
+                chain_specific_info.multi_agent_prologue_name.as_ident_str(),
+                args,
+            )
         } else {
-            vec![
+            let args = vec![
                 MoveValue::Signer(txn_data.sender),
                 MoveValue::U64(txn_sequence_number),
                 MoveValue::vector_u8(txn_authentication_key),
@@ -273,14 +315,12 @@ impl AptosVMImpl {
                 MoveValue::U64(txn_expiration_timestamp_secs),
                 MoveValue::U8(chain_id.id()),
                 MoveValue::vector_u8(txn_data.script_hash.clone()),
-            ]
+            ];
+            (
+                chain_specific_info.script_prologue_name.as_ident_str(),
+                args,
+            )
         };
-        let prologue_function_name =
-            if self.get_version()? >= APTOS_VERSION_3 && txn_data.is_multi_agent() {
-                &chain_specific_info.multi_agent_prologue_name
-            } else {
-                &chain_specific_info.script_prologue_name
-            };
         session
             .execute_function_bypass_visibility(
                 &chain_specific_info.module_id(),
@@ -303,6 +343,13 @@ impl AptosVMImpl {
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
+        // Module publishing always charges gas to the sender.
+        if txn_data.is_fee_payer() {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
+        if txn_data.has_secp256k1_ecdsa_signer() && self.get_version()? < APTOS_VERSION_5 {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
         let chain_specific_info = self.chain_info();
 
         let txn_sequence_number = txn_data.sequence_number();
@@ -350,22 +397,14 @@ impl AptosVMImpl {
         });
 
         let chain_specific_info = self.chain_info();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
+        let (epilogue_function_name, args) = self.epilogue_args(gas_remaining, txn_data);
         session
             .execute_function_bypass_visibility(
                 &chain_specific_info.module_id(),
-                &chain_specific_info.user_epilogue_name,
+                epilogue_function_name,
                 // TODO: Deprecate this once we remove gas currency on the Move side.
                 vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
@@ -383,35 +422,58 @@ impl AptosVMImpl {
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
         let chain_specific_info = self.chain_info();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
+        let (epilogue_function_name, args) = self.epilogue_args(gas_remaining, txn_data);
         session
             .execute_function_bypass_visibility(
                 &chain_specific_info.module_id(),
-                &chain_specific_info.user_epilogue_name,
+                epilogue_function_name,
                 // TODO: Deprecate this once we remove gas currency on the Move side.
                 vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
             .map_err(expect_no_verification_errors)
             .or_else(|e| {
-                expect_only_successful_execution(
-                    e,
-                    chain_specific_info.user_epilogue_name.as_str(),
-                    log_context,
-                )
+                expect_only_successful_execution(e, epilogue_function_name.as_str(), log_context)
             })
     }
 
+    /// Returns the epilogue function to call and its arguments. Fee payer transactions charge
+    /// gas to the fee payer via `USER_EPILOGUE_GAS_PAYER_NAME`, all others to the sender.
+    fn epilogue_args(
+        &self,
+        gas_remaining: Gas,
+        txn_data: &TransactionMetadata,
+    ) -> (&IdentStr, Vec<MoveValue>) {
+        let txn_sequence_number = txn_data.sequence_number();
+        let txn_gas_price = txn_data.gas_unit_price();
+        let txn_max_gas_units = txn_data.max_gas_amount();
+        match txn_data.fee_payer() {
+            Some(fee_payer) => (
+                USER_EPILOGUE_GAS_PAYER_NAME,
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::Address(fee_payer),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+            None => (
+                self.chain_info().user_epilogue_name.as_ident_str(),
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+        }
+    }
+
     pub(crate) fn extract_abort_info(
         &self,
         module: &ModuleId,
@@ -452,6 +514,10 @@ impl AptosVMImpl {
     ) -> VMResult<Arc<CompiledModule>> {
         self.move_vm.load_module(module_id, remote)
     }
+
+    pub fn mark_loader_cache_as_invalid(&self) {
+        self.move_vm.mark_loader_cache_as_invalid()
+    }
 }
 
 /// Internal APIs for the VM, primarily used for testing.
@@ -7,6 +7,7 @@ mod vm_wrapper;
 use crate::{
     adapter_common::{preprocess_transaction, PreprocessedTransaction},
     aptos_vm::AptosVM,
+    data_cache::StorageAdapter,
     parallel_executor::vm_wrapper::AptosVMWrapper,
 };
 use aptos_aggregator::{delta_change_set::DeltaOp, transaction::TransactionOutputExt};
@@ -18,6 +19,7 @@ use aptos_parallel_executor::{
 };
 use aptos_state_view::StateView;
 use aptos_types::{
+    on_chain_config::{BlockGasLimit, OnChainConfig},
     state_store::state_key::StateKey,
     transaction::{Transaction, TransactionOutput, TransactionStatus},
     write_set::{WriteOp, WriteSet, WriteSetMut},
@@ -68,6 +70,10 @@ impl PTransactionOutput for AptosTransactionOutput {
             .collect()
     }
 
+    fn gas_used(&self) -> u64 {
+        self.0.txn_output().gas_used()
+    }
+
     /// Execution output for transactions that comes after SkipRest signal.
     fn skip_output() -> Self {
         Self(TransactionOutputExt::from(TransactionOutput::new(
@@ -86,7 +92,7 @@ impl ParallelAptosVM {
         transactions: Vec<Transaction>,
         state_view: &S,
         concurrency_level: usize,
-    ) -> Result<(Vec<TransactionOutput>, Option<Error<VMStatus>>), VMStatus> {
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
         // Verify the signatures of all the transactions in parallel.
         // This is time consuming so don't wait and do the checking
         // sequentially while executing the transactions.
@@ -95,12 +101,16 @@ impl ParallelAptosVM {
             .map(|txn| preprocess_transaction::<AptosVM>(txn.clone()))
             .collect();
 
+        let maybe_block_gas_limit = BlockGasLimit::fetch_config(&StorageAdapter::new(state_view))
+            .and_then(|block_gas_limit| block_gas_limit.limit);
+
         match ParallelTransactionExecutor::<PreprocessedTransaction, AptosVMWrapper<S>>::new(
             concurrency_level,
+            maybe_block_gas_limit,
         )
         .execute_transactions_parallel(state_view, signature_verified_block)
         {
-            Ok((results, delta_resolver)) => {
+            Ok((results, committed_len, delta_resolver)) => {
                 // TODO: with more deltas, collect keys in parallel (in parallel executor).
                 let mut aggregator_keys: HashMap<StateKey, anyhow::Result<ResolvedData>> =
                     HashMap::new();
@@ -114,29 +124,19 @@ impl ParallelAptosVM {
                     }
                 }
 
-                let materialized_deltas =
-                    delta_resolver.resolve(aggregator_keys.into_iter().collect(), results.len());
-                Ok((
-                    results
-                        .into_iter()
-                        .zip(materialized_deltas.into_iter())
-                        .map(|(res, delta_writes)| {
-                            let output_ext = AptosTransactionOutput::into(res);
-                            output_ext.output_with_delta_writes(WriteSetMut::new(delta_writes))
-                        })
-                        .collect(),
-                    None,
-                ))
-            }
-            Err(err @ Error::ModulePathReadWrite) => {
-                let output = AptosVM::execute_block_and_keep_vm_status(transactions, state_view)?;
-                Ok((
-                    output
-                        .into_iter()
-                        .map(|(_vm_status, txn_output)| txn_output)
-                        .collect(),
-                    Some(err),
-                ))
+                let materialized_deltas = delta_resolver.resolve(
+                    aggregator_keys.into_iter().collect(),
+                    committed_len,
+                    results.len(),
+                );
+                Ok(results
+                    .into_iter()
+                    .zip(materialized_deltas.into_iter())
+                    .map(|(res, delta_writes)| {
+                        let output_ext = AptosTransactionOutput::into(res);
+                        output_ext.output_with_delta_writes(WriteSetMut::new(delta_writes))
+                    })
+                    .collect())
             }
             Err(Error::InvariantViolation) => Err(VMStatus::Error(
                 StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
@@ -4,7 +4,6 @@
 use crate::{
     adapter_common::{PreprocessedTransaction, VMAdapter},
     aptos_vm::AptosVM,
-    data_cache::StorageAdapter,
     logging::AdapterLogSchema,
     parallel_executor::{storage_wrapper::VersionedView, AptosTransactionOutput},
 };
@@ -20,10 +19,12 @@ use move_deps::move_core_types::{
     language_storage::{ModuleId, CORE_CODE_ADDRESS},
     vm_status::VMStatus,
 };
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub(crate) struct AptosVMWrapper<'a, S> {
     vm: AptosVM,
     base_view: &'a S,
+    warmed_up: AtomicBool,
 }
 
 impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
@@ -35,22 +36,10 @@ impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
     fn init(argument: &'a S) -> Self {
         let vm = AptosVM::new(argument);
 
-        // Loading `0x1::account` and its transitive dependency into the code cache.
-        //
-        // This should give us a warm VM to avoid the overhead of VM cold start.
-        // Result of this load could be omitted as this is a best effort approach and won't hurt if that fails.
-        //
-        // Loading up `0x1::account` should be sufficient as this is the most common module
-        // used for prologue, epilogue and transfer functionality.
-
-        let _ = vm.load_module(
-            &ModuleId::new(CORE_CODE_ADDRESS, ident_str!("account").to_owned()),
-            &StorageAdapter::new(argument),
-        );
-
         Self {
             vm,
             base_view: argument,
+            warmed_up: AtomicBool::new(false),
         }
     }
 
@@ -62,6 +51,22 @@ impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
         let log_context = AdapterLogSchema::new(self.base_view.id(), view.txn_idx());
         let versioned_view = VersionedView::new_view(self.base_view, view);
 
+        // Loading `0x1::account` and its transitive dependency into the code cache.
+        //
+        // This should give us a warm VM to avoid the overhead of VM cold start.
+        // Result of this load could be omitted as this is a best effort approach and won't hurt if that fails.
+        //
+        // Loading up `0x1::account` should be sufficient as this is the most common module
+        // used for prologue, epilogue and transfer functionality. The modules are loaded through
+        // the versioned view of the first transaction, so that the versions of the cached modules
+        // are tracked by the parallel executor.
+        if !self.warmed_up.swap(true, Ordering::Relaxed) {
+            let _ = self.vm.load_module(
+                &ModuleId::new(CORE_CODE_ADDRESS, ident_str!("account").to_owned()),
+                &versioned_view,
+            );
+        }
+
         match self
             .vm
             .execute_single_transaction(txn, &versioned_view, &log_context)
@@ -89,4 +94,8 @@ impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
             Err(err) => ExecutionStatus::Abort(err),
         }
     }
+
+    fn flush_module_cache(&self) {
+        self.vm.mark_loader_cache_as_invalid();
+    }
 }
@@ -86,6 +86,33 @@ pub(crate) fn validate_combine_signer_and_txn_args<S: MoveResolverExt>(
     Ok(combined_args)
 }
 
+/// Validate args for view function
+/// validation includes:
+/// 1. return signature is not empty
+/// 2. there are no signer params
+/// 3. number of args is same as the number of params and all arg types are allowed
+pub(crate) fn validate_view_function_args<S: MoveResolverExt>(
+    session: &SessionExt<S>,
+    args: Vec<Vec<u8>>,
+    func: &LoadedFunctionInstantiation,
+) -> Result<Vec<Vec<u8>>, VMStatus> {
+    // view function should return something
+    if func.return_.is_empty() {
+        return Err(VMStatus::Error(StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE));
+    }
+    // signers cannot be forged, so view functions cannot take them, and the remaining
+    // params must be valid transaction args
+    for ty in func.parameters.iter() {
+        if !is_valid_txn_arg(session, ty) {
+            return Err(VMStatus::Error(StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE));
+        }
+    }
+    if args.len() != func.parameters.len() {
+        return Err(VMStatus::Error(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH));
+    }
+    Ok(args)
+}
+
 fn is_valid_txn_arg<S: MoveResolverExt>(session: &SessionExt<S>, typ: &Type) -> bool {
     use move_deps::move_vm_types::loaded_data::runtime_types::Type::*;
     match typ {
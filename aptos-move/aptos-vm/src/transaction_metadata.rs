@@ -3,7 +3,7 @@
 
 use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey};
 use aptos_gas::{FeePerGasUnit, Gas, NumBytes};
-use aptos_types::transaction::authenticator::AuthenticationKey;
+use aptos_types::transaction::authenticator::{AccountAuthenticator, AuthenticationKey};
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
@@ -16,6 +16,9 @@ pub struct TransactionMetadata {
     pub authentication_key: Vec<u8>,
     pub secondary_signers: Vec<AccountAddress>,
     pub secondary_authentication_keys: Vec<Vec<u8>>,
+    pub fee_payer: Option<AccountAddress>,
+    pub fee_payer_authentication_key: Option<Vec<u8>>,
+    pub has_secp256k1_ecdsa_signer: bool,
     pub sequence_number: u64,
     pub max_gas_amount: Gas,
     pub gas_unit_price: FeePerGasUnit,
@@ -37,6 +40,15 @@ impl TransactionMetadata {
                 .iter()
                 .map(|account_auth| account_auth.authentication_key().to_vec())
                 .collect(),
+            fee_payer: txn.authenticator().fee_payer_address(),
+            fee_payer_authentication_key: txn
+                .authenticator()
+                .fee_payer_signer()
+                .map(|signer| signer.authentication_key().to_vec()),
+            has_secp256k1_ecdsa_signer: std::iter::once(txn.authenticator().sender())
+                .chain(txn.authenticator().secondary_signers())
+                .chain(txn.authenticator().fee_payer_signer())
+                .any(|signer| matches!(signer, AccountAuthenticator::Secp256k1Ecdsa { .. })),
             sequence_number: txn.sequence_number(),
             max_gas_amount: txn.max_gas_amount().into(),
             gas_unit_price: txn.gas_unit_price().into(),
@@ -67,6 +79,10 @@ impl TransactionMetadata {
         self.secondary_signers.to_owned()
     }
 
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.fee_payer.to_owned()
+    }
+
     pub fn authentication_key(&self) -> &[u8] {
         &self.authentication_key
     }
@@ -90,6 +106,14 @@ impl TransactionMetadata {
     pub fn is_multi_agent(&self) -> bool {
         !self.secondary_signers.is_empty()
     }
+
+    pub fn is_fee_payer(&self) -> bool {
+        self.fee_payer.is_some()
+    }
+
+    pub fn has_secp256k1_ecdsa_signer(&self) -> bool {
+        self.has_secp256k1_ecdsa_signer
+    }
 }
 
 impl Default for TransactionMetadata {
@@ -102,6 +126,9 @@ impl Default for TransactionMetadata {
             authentication_key: AuthenticationKey::ed25519(&public_key).to_vec(),
             secondary_signers: vec![],
             secondary_authentication_keys: vec![],
+            fee_payer: None,
+            fee_payer_authentication_key: None,
+            has_secp256k1_ecdsa_signer: false,
             sequence_number: 0,
             max_gas_amount: 100_000_000.into(),
             gas_unit_price: 0.into(),
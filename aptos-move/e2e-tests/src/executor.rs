@@ -346,10 +346,7 @@ impl FakeExecutor {
         &self,
         txn_block: Vec<Transaction>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
-        let (result, _) =
-            ParallelAptosVM::execute_block(txn_block, &self.data_store, num_cpus::get())?;
-
-        Ok(result)
+        ParallelAptosVM::execute_block(txn_block, &self.data_store, num_cpus::get())
     }
 
     pub fn execute_transaction_block(
@@ -0,0 +1,68 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    on_chain_config::{access_path_for_config, BlockGasLimit, OnChainConfig},
+    state_store::state_key::StateKey,
+    transaction::{ExecutionStatus, Transaction, TransactionStatus},
+    write_set::{WriteOp, WriteSetMut},
+};
+use aptos_vm::{parallel_executor::ParallelAptosVM, AptosVM};
+use language_e2e_tests::{common_transactions::peer_to_peer_txn, executor::FakeExecutor};
+
+fn set_block_gas_limit(executor: &mut FakeExecutor, limit: Option<u64>) {
+    let write_set = WriteSetMut::new(vec![(
+        StateKey::AccessPath(access_path_for_config(BlockGasLimit::CONFIG_ID)),
+        WriteOp::Modification(bcs::to_bytes(&BlockGasLimit { limit }).unwrap()),
+    )])
+    .freeze()
+    .unwrap();
+    executor.apply_write_set(&write_set);
+}
+
+#[test]
+fn sequential_and_parallel_execution_match_with_block_gas_limit() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let receiver = executor.create_raw_account_data(100_000, 10);
+    executor.add_account_data(&receiver);
+
+    // All the transfers go to the same receiver, so the parallel executor has to resolve
+    // conflicts between them.
+    let block: Vec<Transaction> = (0..10)
+        .map(|_| {
+            let sender = executor.create_raw_account_data(1_000_000, 10);
+            executor.add_account_data(&sender);
+            Transaction::UserTransaction(peer_to_peer_txn(
+                sender.account(),
+                receiver.account(),
+                10,
+                1_000,
+            ))
+        })
+        .collect();
+
+    let outputs = AptosVM::execute_block(block.clone(), executor.get_state_view()).unwrap();
+    assert!(outputs
+        .iter()
+        .all(|output| output.status() == &TransactionStatus::Keep(ExecutionStatus::Success)));
+    let gas_used = outputs[0].gas_used();
+
+    // The limit is reached by the third transaction, so the rest of the block is retried.
+    set_block_gas_limit(&mut executor, Some(gas_used * 3 - 1));
+    let sequential_outputs =
+        AptosVM::execute_block(block.clone(), executor.get_state_view()).unwrap();
+    let parallel_outputs =
+        ParallelAptosVM::execute_block(block, executor.get_state_view(), 4).unwrap();
+    assert_eq!(sequential_outputs, parallel_outputs);
+
+    for (idx, output) in sequential_outputs.iter().enumerate() {
+        if idx < 3 {
+            assert_eq!(
+                output.status(),
+                &TransactionStatus::Keep(ExecutionStatus::Success)
+            );
+        } else {
+            assert_eq!(output.status(), &TransactionStatus::Retry);
+        }
+    }
+}
@@ -12,6 +12,7 @@
 //! Set env REGENERATE_GOLDENFILES to update the golden files when running tests..
 
 mod account_universe;
+mod block_gas_limit;
 mod create_account;
 mod data_store;
 mod execution_strategies;
@@ -23,5 +24,6 @@ mod module_publishing;
 mod on_chain_configs;
 mod peer_to_peer;
 mod scripts;
+mod secp256k1_ecdsa;
 mod transaction_fuzzer;
 mod verify_txn;
@@ -0,0 +1,51 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::secp256k1_ecdsa::Secp256k1EcdsaPublicKey;
+use aptos_keygen::KeyGen;
+use aptos_types::transaction::{
+    authenticator::AuthenticationKey, ExecutionStatus, TransactionStatus,
+};
+use cached_packages::aptos_stdlib;
+use language_e2e_tests::{
+    account::Account, common_transactions::create_account_txn, executor::FakeExecutor,
+};
+
+#[test]
+fn secp256k1_ecdsa_signed_transaction() {
+    let mut executor = FakeExecutor::from_genesis_file();
+
+    // The on-chain authentication key of a new account is its address, so deriving the address
+    // from the secp256k1 authentication key lets the account sign with that key right away.
+    let private_key = KeyGen::from_os_rng().generate_secp256k1_ecdsa_private_key();
+    let public_key = Secp256k1EcdsaPublicKey::from(&private_key);
+    let sender = Account::new_genesis_account(
+        AuthenticationKey::secp256k1_ecdsa(&public_key).derived_address(),
+    );
+
+    let root = Account::new_aptos_root();
+    executor.execute_and_apply(create_account_txn(&root, &sender, 0));
+
+    let new_account = executor.create_raw_account();
+    let txn = sender
+        .transaction()
+        .payload(aptos_stdlib::account_create_account(*new_account.address()))
+        .sequence_number(0)
+        .raw()
+        .sign_secp256k1_ecdsa(&private_key, public_key)
+        .unwrap()
+        .into_inner();
+
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+
+    let updated_sender = executor
+        .read_account_resource(&sender)
+        .expect("sender must exist");
+    assert_eq!(1, updated_sender.sequence_number());
+    assert!(executor.read_account_resource(&new_account).is_some());
+}
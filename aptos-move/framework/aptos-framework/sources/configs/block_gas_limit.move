@@ -0,0 +1,38 @@
+/// Maintains the optional limit on the total gas used by the transactions of a block. Once the
+/// gas used by the transactions executed in a block reaches the limit, the rest of the
+/// transactions of the block are not committed and are retried in a later block.
+module aptos_framework::block_gas_limit {
+    use std::option::{Self, Option};
+
+    use aptos_framework::system_addresses;
+
+    struct BlockGasLimit has key {
+        limit: Option<u64>,
+    }
+
+    /// This can be called by on-chain governance to set (or unset, with `option::none()`) the
+    /// block gas limit. The new limit applies from the next block onwards.
+    public fun set_block_gas_limit(aptos_framework: &signer, limit: Option<u64>) acquires BlockGasLimit {
+        system_addresses::assert_aptos_framework(aptos_framework);
+
+        if (exists<BlockGasLimit>(@aptos_framework)) {
+            borrow_global_mut<BlockGasLimit>(@aptos_framework).limit = limit;
+        } else {
+            move_to(aptos_framework, BlockGasLimit { limit });
+        }
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    public entry fun test_set_block_gas_limit(aptos_framework: signer) acquires BlockGasLimit {
+        set_block_gas_limit(&aptos_framework, option::some(1000));
+        assert!(borrow_global<BlockGasLimit>(@aptos_framework).limit == option::some(1000), 0);
+        set_block_gas_limit(&aptos_framework, option::none());
+        assert!(option::is_none(&borrow_global<BlockGasLimit>(@aptos_framework).limit), 1);
+    }
+
+    #[test(random_account = @0x123)]
+    #[expected_failure(abort_code = 327683)]
+    public entry fun test_set_block_gas_limit_unauthorized_should_fail(random_account: signer) acquires BlockGasLimit {
+        set_block_gas_limit(&random_account, option::some(1000));
+    }
+}
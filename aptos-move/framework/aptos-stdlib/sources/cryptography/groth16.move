@@ -0,0 +1,309 @@
+/// This module implements the verification of [Groth16](https://eprint.iacr.org/2016/260.pdf) zk-SNARK proofs, over
+/// the BN254 and BLS12-381 pairing-friendly curves.
+///
+/// Verifying keys, proofs and public inputs are given in the (compressed) serialization format of the
+/// [arkworks](https://github.com/arkworks-rs) libraries:
+///  - a verifying key is `alpha_g1 || beta_g2 || gamma_g2 || delta_g2 || n + 1 || gamma_abc_g1[0] || ... || gamma_abc_g1[n]`,
+///    where `n + 1` is an 8-byte little-endian integer and `n` is the number of public inputs,
+///  - a proof is `a_g1 || b_g2 || c_g1`,
+///  - public inputs are the concatenation of the 32-byte little-endian representations of the scalars.
+module aptos_std::groth16 {
+    use std::error;
+    use std::option::{Self, Option};
+
+    /// The curve is not supported.
+    const E_UNSUPPORTED_CURVE: u64 = 1;   // This code must be the same, if ever returned from the native Rust implementation.
+
+    /// The public inputs are not a sequence of serialized scalars.
+    const E_WRONG_PUBLIC_INPUTS_SIZE: u64 = 2;
+
+    /// The number of public inputs does not match the prepared verifying key.
+    const E_WRONG_NUM_PUBLIC_INPUTS: u64 = 3;   // This code must be the same, if ever returned from the native Rust implementation.
+
+    /// The size of a serialized public input (i.e., scalar), in bytes.
+    const PUBLIC_INPUT_NUM_BYTES: u64 = 32;
+
+    // The identifiers of the supported curves, which must be the same as in the native Rust implementation.
+    const BN254: u8 = 0;
+    const BLS12_381: u8 = 1;
+
+    /// A pairing-friendly curve, over which proofs are verified.
+    struct Curve has copy, drop, store {
+        id: u8
+    }
+
+    /// A verifying key prepared for the verification of proofs, i.e. with the pairing of `alpha_g1` and `beta_g2`
+    /// precomputed, and with `gamma_g2` and `delta_g2` negated. Preparing a verifying key is costly, hence it is meant to
+    /// be prepared once and stored.
+    struct PreparedVerifyingKey has copy, drop, store {
+        curve: Curve,
+        vk_gamma_abc_g1_bytes: vector<u8>,
+        alpha_g1_beta_g2_bytes: vector<u8>,
+        gamma_g2_neg_bytes: vector<u8>,
+        delta_g2_neg_bytes: vector<u8>,
+    }
+
+    /// The public inputs of a proof.
+    struct PublicProofInputs has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// A purported proof, which can be verified via `verify_proof`.
+    struct Proof has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// The BN254 curve (also known as BN128 or alt_bn128), e.g. as used by Ethereum and circom.
+    public fun bn254(): Curve {
+        Curve { id: BN254 }
+    }
+
+    /// The BLS12-381 curve.
+    public fun bls12381(): Curve {
+        Curve { id: BLS12_381 }
+    }
+
+    /// Prepares the given serialized verifying key. Returns `None` if it is not a valid verifying key over `curve`.
+    public fun prepare_verifying_key(curve: Curve, verifying_key: vector<u8>): Option<PreparedVerifyingKey> {
+        let (success, vk_gamma_abc_g1_bytes, alpha_g1_beta_g2_bytes, gamma_g2_neg_bytes, delta_g2_neg_bytes) =
+            prepare_verifying_key_internal(curve.id, verifying_key);
+        if (success) {
+            option::some(PreparedVerifyingKey {
+                curve,
+                vk_gamma_abc_g1_bytes,
+                alpha_g1_beta_g2_bytes,
+                gamma_g2_neg_bytes,
+                delta_g2_neg_bytes,
+            })
+        } else {
+            option::none<PreparedVerifyingKey>()
+        }
+    }
+
+    /// Constructs a prepared verifying key from its parts, e.g. as returned by `prepared_verifying_key_to_parts`. The parts
+    /// are only deserialized (and validated) when verifying proofs.
+    public fun new_prepared_verifying_key_from_parts(
+        curve: Curve,
+        vk_gamma_abc_g1_bytes: vector<u8>,
+        alpha_g1_beta_g2_bytes: vector<u8>,
+        gamma_g2_neg_bytes: vector<u8>,
+        delta_g2_neg_bytes: vector<u8>,
+    ): PreparedVerifyingKey {
+        PreparedVerifyingKey {
+            curve,
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_bytes,
+            delta_g2_neg_bytes,
+        }
+    }
+
+    /// Returns the parts of a prepared verifying key: `(vk_gamma_abc_g1_bytes, alpha_g1_beta_g2_bytes,
+    /// gamma_g2_neg_bytes, delta_g2_neg_bytes)`.
+    public fun prepared_verifying_key_to_parts(pvk: &PreparedVerifyingKey): (vector<u8>, vector<u8>, vector<u8>, vector<u8>) {
+        (pvk.vk_gamma_abc_g1_bytes, pvk.alpha_g1_beta_g2_bytes, pvk.gamma_g2_neg_bytes, pvk.delta_g2_neg_bytes)
+    }
+
+    /// Constructs the public inputs of a proof from the concatenation of the serialized scalars.
+    public fun new_public_proof_inputs_from_bytes(bytes: vector<u8>): PublicProofInputs {
+        assert!(
+            std::vector::length(&bytes) % PUBLIC_INPUT_NUM_BYTES == 0,
+            error::invalid_argument(E_WRONG_PUBLIC_INPUTS_SIZE)
+        );
+        PublicProofInputs { bytes }
+    }
+
+    /// Constructs a purported proof from its serialization.
+    public fun new_proof_from_bytes(bytes: vector<u8>): Proof {
+        Proof { bytes }
+    }
+
+    /// Returns `true` iff `proof` is a valid proof for `public_inputs` under the prepared verifying key `pvk`. Returns
+    /// `false` if the key, the public inputs or the proof are malformed. Aborts if the number of public inputs does not
+    /// match the key.
+    public fun verify_proof(pvk: &PreparedVerifyingKey, public_inputs: &PublicProofInputs, proof: &Proof): bool {
+        verify_proof_internal(
+            pvk.curve.id,
+            pvk.vk_gamma_abc_g1_bytes,
+            pvk.alpha_g1_beta_g2_bytes,
+            pvk.gamma_g2_neg_bytes,
+            pvk.delta_g2_neg_bytes,
+            public_inputs.bytes,
+            proof.bytes,
+        )
+    }
+
+    //
+    // Native functions
+    //
+
+    native fun prepare_verifying_key_internal(
+        curve: u8,
+        verifying_key: vector<u8>,
+    ): (bool, vector<u8>, vector<u8>, vector<u8>, vector<u8>);
+
+    native fun verify_proof_internal(
+        curve: u8,
+        vk_gamma_abc_g1_bytes: vector<u8>,
+        alpha_g1_beta_g2_bytes: vector<u8>,
+        gamma_g2_neg_bytes: vector<u8>,
+        delta_g2_neg_bytes: vector<u8>,
+        public_inputs: vector<u8>,
+        proof: vector<u8>,
+    ): bool;
+
+    //
+    // Tests
+    //
+
+    // A (trivially forgeable) BN254 verifying key without public inputs, all points of which are generators, and for
+    // which (a = g1, b = g2, c = -g1) is a valid proof.
+    #[test_only]
+    const BN254_VK_NO_PUBLIC_INPUTS: vector<u8> = x"0100000000000000000000000000000000000000000000000000000000000000edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e1901000000000000000100000000000000000000000000000000000000000000000000000000000000";
+
+    // The same verifying key with a public input, for which the same proof is valid iff the input is 0.
+    #[test_only]
+    const BN254_VK_ONE_PUBLIC_INPUT: vector<u8> = x"0100000000000000000000000000000000000000000000000000000000000000edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19020000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000";
+
+    // The proof (a = g1, b = g2, c = -g1).
+    #[test_only]
+    const BN254_PROOF: vector<u8> = x"0100000000000000000000000000000000000000000000000000000000000000edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e190100000000000000000000000000000000000000000000000000000000000080";
+
+    // The proof (a = g1, b = g2, c = g1).
+    #[test_only]
+    const BN254_INVALID_PROOF: vector<u8> = x"0100000000000000000000000000000000000000000000000000000000000000edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e190100000000000000000000000000000000000000000000000000000000000000";
+
+    // A verifying key and a proof generated with arkworks, for the circuit proving the knowledge of `x` such that
+    // `x^3 + x + 5 == y`, with the witness `x = 3` and the public input `y = 35`, over BN254.
+    #[test_only]
+    const BN254_CUBIC_VK: vector<u8> = x"e57314eb0d2d4acef7a0b56306a4ac1dc99b9a1dc15a34dc549a052171bd981b1e6533af92c383be56b39f0520f0c3f5713f404cc505f4887a88224fbf49562da2f948129e080d367595fcc2f8a6beee2c088f4e77fdfdb9edaaeb4b407d381617e42ded924236cbb7a82ba74b9ae3198aeef633e290a9931ed396a6e6109d1abb8615f6e3dfff68ed2be32ce4035b12e85792795c2f87bc0d2999cf9d2a81224795e7b72c7be59554ce1157ac2db49d2757e81bae97505a0d81ccbe88beb12f3e2e2c16d42cc821a62663a6cd59dcfa621b9aa7472df54a6f13500c1dea611e02000000000000000d4a76c89fadc396ed5bad9613a991bc1f11361f5849692eb518e93f39731e14b19e8f09aaa8757d8e08477bd02b484ee4a61d583c20dfda4e6555543e5b2f93";
+
+    #[test_only]
+    const BN254_CUBIC_PROOF: vector<u8> = x"45b53b62c6d47a24b633a23bb1c8f7a77fb1d262276e51b53a0e4ecb6079708b57b10575072a007a43deb544f4f91bbcdbe70ce0ac716473146d20ff92a856181f94165d88a77193a56ac611c879af3c3ee5263bbf97e3498d206ed15b4905ac3d4bbf077f9ca5c5ff96075555688a2b686883ad2d635b15fcaf852f0880d085";
+
+    // The same circuit, witness and public input, over BLS12-381.
+    #[test_only]
+    const BLS12_381_CUBIC_VK: vector<u8> = x"23012f3a5f19144344c9a58eb0655e49857e67e128b1f9dcf0e7c08c476bad9e694484f3735d27013ece15d1d2084c09134d2598662837a933e4c5b3620d3bbbda2271928457f9925b0d94e8b87b1d98e8b0b44a6a9b6f4ee30d429aa606c519019939e308551ac87126c47d520336320e8e65010cb52b09f4340dc2cf1d8c18303eac912b396bfd1d2da869f249fc8eb335df0e90cadf9bae2ff474e26e317b2277570bf284fe4313e72496949acea32e2a82236a5e4c8ad0ead4858a222308e147f323e2205f6cfb8b88265098bd350f234a28726ac389e112fb207b7e307cc6712c2b17dafe2aeac01af6a309298ade5ab8f3a7289fd187009333a34b1ece492d94d41baf56f4ee6b87103cfcf0a04fcbbf6ac2ca59d6a45a9a3ad2b4ff046bb0860fb8faf04e70de7ef2099e675ae949a88a966f4df187a38994d26615443f628fe98c8a533acfff0b7398c070870200000000000000af2c899d597b6819166797b6f99f57097dfcb396a3a19326991c504360341181b9e7613259b2ea09f47ae1f4e1f196008c55534bbcae439b78fa45afbeccaf89b7b1eacc7a15848eaca8380aec33546ff2721d6514106e81fadba31fe9ea870d";
+
+    #[test_only]
+    const BLS12_381_CUBIC_PROOF: vector<u8> = x"827a29513fab94b8389ebb4d02fab92390b528bdd6e59efd2695dc5ebd1941fd200ffbda9303ace2ee2923062af9d98990cbba8432ce9c22d729eb63edd5bdb21b50c1746fd2cad2e615112f0b6ea84efe620ddaf04d13244d953e8c7146ea0a641f26f9d4afdf04ad43ac43c4200db692b1de20ccf1876a7682c9327323d20dd590f456f6f50e52ffc3f8b21964c41209ac69db0cc13a92230d95bc21e6a6addb340e33df7fee6e2813a5586bc8f34066243942cda67359435c3675afbc7816";
+
+    // The public input `y = 35`, and the (wrong) public input `y = 36`.
+    #[test_only]
+    const CUBIC_PUBLIC_INPUT: vector<u8> = x"2300000000000000000000000000000000000000000000000000000000000000";
+
+    #[test_only]
+    const CUBIC_WRONG_PUBLIC_INPUT: vector<u8> = x"2400000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fun test_verify_proof_bn254_known_answer() {
+        let pvk = option::extract(&mut prepare_verifying_key(bn254(), BN254_CUBIC_VK));
+        let proof = new_proof_from_bytes(BN254_CUBIC_PROOF);
+        assert!(verify_proof(&pvk, &new_public_proof_inputs_from_bytes(CUBIC_PUBLIC_INPUT), &proof), 1);
+        assert!(!verify_proof(&pvk, &new_public_proof_inputs_from_bytes(CUBIC_WRONG_PUBLIC_INPUT), &proof), 2);
+        // A proof for another key is not valid.
+        let other_proof = new_proof_from_bytes(BN254_PROOF);
+        assert!(!verify_proof(&pvk, &new_public_proof_inputs_from_bytes(CUBIC_PUBLIC_INPUT), &other_proof), 3);
+    }
+
+    #[test]
+    fun test_verify_proof_bls12381_known_answer() {
+        let pvk = option::extract(&mut prepare_verifying_key(bls12381(), BLS12_381_CUBIC_VK));
+        let proof = new_proof_from_bytes(BLS12_381_CUBIC_PROOF);
+        assert!(verify_proof(&pvk, &new_public_proof_inputs_from_bytes(CUBIC_PUBLIC_INPUT), &proof), 1);
+        assert!(!verify_proof(&pvk, &new_public_proof_inputs_from_bytes(CUBIC_WRONG_PUBLIC_INPUT), &proof), 2);
+        // A BN254 proof is not a BLS12-381 one.
+        let bn254_proof = new_proof_from_bytes(BN254_CUBIC_PROOF);
+        assert!(!verify_proof(&pvk, &new_public_proof_inputs_from_bytes(CUBIC_PUBLIC_INPUT), &bn254_proof), 3);
+    }
+
+    #[test]
+    fun test_verify_proof_bn254() {
+        let pvk = option::extract(&mut prepare_verifying_key(bn254(), BN254_VK_NO_PUBLIC_INPUTS));
+        let no_public_inputs = new_public_proof_inputs_from_bytes(x"");
+        assert!(verify_proof(&pvk, &no_public_inputs, &new_proof_from_bytes(BN254_PROOF)), 1);
+        assert!(!verify_proof(&pvk, &no_public_inputs, &new_proof_from_bytes(BN254_INVALID_PROOF)), 2);
+    }
+
+    #[test]
+    fun test_verify_proof_bn254_public_inputs() {
+        let pvk = option::extract(&mut prepare_verifying_key(bn254(), BN254_VK_ONE_PUBLIC_INPUT));
+        let proof = new_proof_from_bytes(BN254_PROOF);
+        let zero = new_public_proof_inputs_from_bytes(x"0000000000000000000000000000000000000000000000000000000000000000");
+        let one = new_public_proof_inputs_from_bytes(x"0100000000000000000000000000000000000000000000000000000000000000");
+        assert!(verify_proof(&pvk, &zero, &proof), 1);
+        assert!(!verify_proof(&pvk, &one, &proof), 2);
+
+        // A prepared verifying key can be stored as its parts.
+        let (vk_gamma_abc_g1_bytes, alpha_g1_beta_g2_bytes, gamma_g2_neg_bytes, delta_g2_neg_bytes) =
+            prepared_verifying_key_to_parts(&pvk);
+        let pvk = new_prepared_verifying_key_from_parts(
+            bn254(),
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_bytes,
+            delta_g2_neg_bytes,
+        );
+        assert!(verify_proof(&pvk, &zero, &proof), 3);
+
+        // Scalars must be canonical, i.e. smaller than the order of the groups.
+        let order = new_public_proof_inputs_from_bytes(x"010000f093f5e1439170b97948e833285d588181b64550b829a031e1724e6430");
+        assert!(!verify_proof(&pvk, &order, &proof), 4);
+    }
+
+    #[test]
+    fun test_malformed_inputs() {
+        assert!(option::is_none(&prepare_verifying_key(bn254(), x"")), 1);
+        // A BN254 verifying key is not a BLS12-381 one.
+        assert!(option::is_none(&prepare_verifying_key(bls12381(), BN254_VK_NO_PUBLIC_INPUTS)), 2);
+
+        let pvk = option::extract(&mut prepare_verifying_key(bn254(), BN254_VK_NO_PUBLIC_INPUTS));
+        let no_public_inputs = new_public_proof_inputs_from_bytes(x"");
+        assert!(!verify_proof(&pvk, &no_public_inputs, &new_proof_from_bytes(x"")), 3);
+
+        // The parts of a prepared verifying key are only validated when verifying proofs.
+        let (_, alpha_g1_beta_g2_bytes, gamma_g2_neg_bytes, delta_g2_neg_bytes) = prepared_verifying_key_to_parts(&pvk);
+        let malformed_pvk = new_prepared_verifying_key_from_parts(
+            bn254(),
+            x"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_bytes,
+            delta_g2_neg_bytes,
+        );
+        assert!(!verify_proof(&malformed_pvk, &no_public_inputs, &new_proof_from_bytes(BN254_PROOF)), 4);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10002)]
+    fun test_wrong_public_inputs_size() {
+        new_public_proof_inputs_from_bytes(x"01");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun test_wrong_num_public_inputs() {
+        let pvk = option::extract(&mut prepare_verifying_key(bn254(), BN254_VK_NO_PUBLIC_INPUTS));
+        let zero = new_public_proof_inputs_from_bytes(x"0000000000000000000000000000000000000000000000000000000000000000");
+        verify_proof(&pvk, &zero, &new_proof_from_bytes(BN254_PROOF));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun test_oversized_prepared_verifying_key() {
+        // The key commits to more public inputs than given, so it is rejected before any of its points is deserialized.
+        let pvk = option::extract(&mut prepare_verifying_key(bn254(), BN254_VK_ONE_PUBLIC_INPUT));
+        let (vk_gamma_abc_g1_bytes, alpha_g1_beta_g2_bytes, gamma_g2_neg_bytes, delta_g2_neg_bytes) =
+            prepared_verifying_key_to_parts(&pvk);
+        let extra_points = vk_gamma_abc_g1_bytes;
+        std::vector::append(&mut vk_gamma_abc_g1_bytes, extra_points);
+        let pvk = new_prepared_verifying_key_from_parts(
+            bn254(),
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_bytes,
+            delta_g2_neg_bytes,
+        );
+        let zero = new_public_proof_inputs_from_bytes(x"0000000000000000000000000000000000000000000000000000000000000000");
+        verify_proof(&pvk, &zero, &new_proof_from_bytes(BN254_PROOF));
+    }
+}
@@ -1,6 +1,11 @@
-/// Non-cryptographic hashes
+/// Hashes which are not available in `std::hash`:
+/// - Non-cryptographic: SipHash
+/// - Cryptographic: Keccak-256 (as used by Ethereum, NOT the same as SHA3-256), SHA2-512, SHA3-512, BLAKE2b-256 and
+///   RIPEMD-160
 module aptos_std::aptos_hash {
     use std::bcs;
+    #[test_only]
+    use std::vector;
 
     native public fun sip_hash(bytes: vector<u8>): u64;
 
@@ -9,4 +14,124 @@ module aptos_std::aptos_hash {
 
         sip_hash(bytes)
     }
+
+    native public fun keccak256(bytes: vector<u8>): vector<u8>;
+
+    native public fun sha2_512(bytes: vector<u8>): vector<u8>;
+
+    native public fun sha3_512(bytes: vector<u8>): vector<u8>;
+
+    native public fun blake2b_256(bytes: vector<u8>): vector<u8>;
+
+    /// WARNING: RIPEMD-160 only provides 80-bit security against collisions.
+    native public fun ripemd160(bytes: vector<u8>): vector<u8>;
+
+    //
+    // Tests
+    //
+
+    #[test]
+    fun keccak256_test() {
+        let inputs = vector[
+            b"",
+            b"testing",
+            b"The quick brown fox jumps over the lazy dog",
+        ];
+        let outputs = vector[
+            x"c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            x"5f16f4c7f149ac4f9510d9cf8cf384038ad348b3bcdc01915f95de12df9d1b02",
+            x"4d741b6f1eb29cb2a9b9911c82f56fa8d73b04959d3d9d222895df6c0b28aa15",
+        ];
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            assert!(keccak256(input) == hash_expected, i);
+            i = i + 1;
+        };
+    }
+
+    #[test]
+    fun sha2_512_test() {
+        let inputs = vector[
+            b"",
+            b"testing",
+            b"The quick brown fox jumps over the lazy dog",
+        ];
+        let outputs = vector[
+            x"cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+            x"521b9ccefbcd14d179e7a1bb877752870a6d620938b28a66a107eac6e6805b9d0989f45b5730508041aa5e710847d439ea74cd312c9355f1f2dae08d40e41d50",
+            x"07e547d9586f6a73f73fbac0435ed76951218fb7d0c8d788a309d785436bbb642e93a252a954f23912547d1e8a3b5ed6e1bfd7097821233fa0538f3db854fee6",
+        ];
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            assert!(sha2_512(input) == hash_expected, i);
+            i = i + 1;
+        };
+    }
+
+    #[test]
+    fun sha3_512_test() {
+        let inputs = vector[
+            b"",
+            b"testing",
+            b"The quick brown fox jumps over the lazy dog",
+        ];
+        let outputs = vector[
+            x"a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+            x"881c7d6ba98678bcd96e253086c4048c3ea15306d0d13ff48341c6285ee71102a47b6f16e20e4d65c0c3d677be689dfda6d326695609cbadfafa1800e9eb7fc1",
+            x"01dedd5de4ef14642445ba5f5b97c15e47b9ad931326e4b0727cd94cefc44fff23f07bf543139939b49128caf436dc1bdee54fcb24023a08d9403f9b4bf0d450",
+        ];
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            assert!(sha3_512(input) == hash_expected, i);
+            i = i + 1;
+        };
+    }
+
+    #[test]
+    fun blake2b_256_test() {
+        let inputs = vector[
+            b"",
+            b"testing",
+            b"The quick brown fox jumps over the lazy dog",
+        ];
+        let outputs = vector[
+            x"0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+            x"99397ff32ae348b8b6536d5c213f343d7e9fdeaa10e8a23a9f90ab21a1658565",
+            x"01718cec35cd3d796dd00020e0bfecb473ad23457d063b75eff29c0ffa2e58a9",
+        ];
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            assert!(blake2b_256(input) == hash_expected, i);
+            i = i + 1;
+        };
+    }
+
+    #[test]
+    fun ripemd160_test() {
+        let inputs = vector[
+            b"",
+            b"testing",
+            b"The quick brown fox jumps over the lazy dog",
+        ];
+        let outputs = vector[
+            x"9c1185a5c5e9fc54612808977ee8f548b2258d31",
+            x"b89ba156b40bed29a5965684b7d244c49a3a769b",
+            x"37f332f68db77bd9d7edd4969571ad671cf9dd3b",
+        ];
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            assert!(ripemd160(input) == hash_expected, i);
+            i = i + 1;
+        };
+    }
 }
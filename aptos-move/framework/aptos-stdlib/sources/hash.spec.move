@@ -8,4 +8,44 @@ spec aptos_std::aptos_hash {
         // TODO: temporary mockup.
         pragma opaque;
     }
+
+    spec keccak256 {
+        pragma opaque;
+        aborts_if false;
+        ensures [abstract] result == spec_keccak256(bytes);
+    }
+
+    spec fun spec_keccak256(bytes: vector<u8>): vector<u8>;
+
+    spec sha2_512 {
+        pragma opaque;
+        aborts_if false;
+        ensures [abstract] result == spec_sha2_512(bytes);
+    }
+
+    spec fun spec_sha2_512(bytes: vector<u8>): vector<u8>;
+
+    spec sha3_512 {
+        pragma opaque;
+        aborts_if false;
+        ensures [abstract] result == spec_sha3_512(bytes);
+    }
+
+    spec fun spec_sha3_512(bytes: vector<u8>): vector<u8>;
+
+    spec blake2b_256 {
+        pragma opaque;
+        aborts_if false;
+        ensures [abstract] result == spec_blake2b_256(bytes);
+    }
+
+    spec fun spec_blake2b_256(bytes: vector<u8>): vector<u8>;
+
+    spec ripemd160 {
+        pragma opaque;
+        aborts_if false;
+        ensures [abstract] result == spec_ripemd160(bytes);
+    }
+
+    spec fun spec_ripemd160(bytes: vector<u8>): vector<u8>;
 }
@@ -0,0 +1,307 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::util::make_native_from_func;
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_groth16::{PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use move_deps::{
+    move_binary_format::errors::PartialVMResult,
+    move_core_types::gas_algebra::{InternalGas, InternalGasPerArg, NumArgs},
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// Abort codes (0x01 == INVALID_ARGUMENT)
+/// NOTE: These must match the codes in the Move implementation
+pub mod abort_codes {
+    /// The curve is not supported
+    pub const E_UNSUPPORTED_CURVE: u64 = 0x01_0001;
+    /// The number of public inputs does not match the prepared verifying key
+    pub const E_WRONG_NUM_PUBLIC_INPUTS: u64 = 0x01_0003;
+}
+
+/// The identifiers of the supported curves.
+/// NOTE: These must match the ones in the Move implementation
+const BN254: u8 = 0;
+const BLS12_381: u8 = 1;
+
+/// The size of a serialized scalar (i.e., public input) in bytes, for all supported curves.
+const SCALAR_NUM_BYTES: usize = 32;
+
+/// Splits `bytes` into the serialized elements of size `element_num_bytes` and deserializes them.
+/// Returns `None` if `bytes` is not a sequence of valid elements.
+fn deserialize_all<T: CanonicalDeserialize>(
+    bytes: &[u8],
+    element_num_bytes: usize,
+) -> Option<Vec<T>> {
+    if bytes.len() % element_num_bytes != 0 {
+        return None;
+    }
+    bytes
+        .chunks(element_num_bytes)
+        .map(|chunk| T::deserialize(chunk).ok())
+        .collect()
+}
+
+fn serialize<T: CanonicalSerialize>(element: &T) -> Vec<u8> {
+    let mut bytes = vec![];
+    element
+        .serialize(&mut bytes)
+        .expect("serializing into a vector should not fail");
+    bytes
+}
+
+fn g1_num_bytes<E: PairingEngine>() -> usize {
+    E::G1Affine::prime_subgroup_generator().serialized_size()
+}
+
+fn g2_num_bytes<E: PairingEngine>() -> usize {
+    E::G2Affine::prime_subgroup_generator().serialized_size()
+}
+
+/// The number of public inputs of the serialized verifying key, derived from its size: a
+/// verifying key consists of one G1 point and three G2 points, followed by the (length-prefixed)
+/// vector of the `num_public_inputs + 1` G1 points used to commit to the public inputs.
+fn num_public_inputs_of_verifying_key<E: PairingEngine>(vk_bytes: &[u8]) -> usize {
+    let fixed_num_bytes = g1_num_bytes::<E>() + 3 * g2_num_bytes::<E>() + 8;
+    (vk_bytes.len().saturating_sub(fixed_num_bytes) / g1_num_bytes::<E>()).saturating_sub(1)
+}
+
+/// Prepares the serialized verifying key, and returns the serialized parts of the prepared
+/// verifying key that are needed for verification (see `verify_proof`).
+fn prepare_verifying_key<E: PairingEngine>(vk_bytes: &[u8]) -> Option<[Vec<u8>; 4]> {
+    let vk = VerifyingKey::<E>::deserialize(vk_bytes).ok()?;
+
+    let vk_gamma_abc_g1 = vk.gamma_abc_g1.iter().flat_map(serialize).collect();
+    let alpha_g1_beta_g2 = serialize(&E::pairing(vk.alpha_g1, vk.beta_g2));
+    let gamma_g2_neg = serialize(&-vk.gamma_g2);
+    let delta_g2_neg = serialize(&-vk.delta_g2);
+
+    Some([
+        vk_gamma_abc_g1,
+        alpha_g1_beta_g2,
+        gamma_g2_neg,
+        delta_g2_neg,
+    ])
+}
+
+/// Verifies the serialized proof for the serialized public inputs against the serialized parts
+/// of a prepared verifying key. Returns false if any of them is malformed.
+fn verify_proof<E: PairingEngine>(
+    vk_gamma_abc_g1_bytes: &[u8],
+    alpha_g1_beta_g2_bytes: &[u8],
+    gamma_g2_neg_bytes: &[u8],
+    delta_g2_neg_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    proof_bytes: &[u8],
+) -> bool {
+    let pvk = match (
+        deserialize_all::<E::G1Affine>(vk_gamma_abc_g1_bytes, g1_num_bytes::<E>()),
+        E::Fqk::deserialize(alpha_g1_beta_g2_bytes),
+        E::G2Affine::deserialize(gamma_g2_neg_bytes),
+        E::G2Affine::deserialize(delta_g2_neg_bytes),
+    ) {
+        (Some(gamma_abc_g1), Ok(alpha_g1_beta_g2), Ok(gamma_g2_neg), Ok(delta_g2_neg)) => {
+            PreparedVerifyingKey {
+                vk: VerifyingKey {
+                    gamma_abc_g1,
+                    ..Default::default()
+                },
+                alpha_g1_beta_g2,
+                gamma_g2_neg_pc: gamma_g2_neg.into(),
+                delta_g2_neg_pc: delta_g2_neg.into(),
+            }
+        }
+        _ => return false,
+    };
+
+    let public_inputs = match deserialize_all::<E::Fr>(public_inputs_bytes, SCALAR_NUM_BYTES) {
+        Some(public_inputs) => public_inputs,
+        None => return false,
+    };
+
+    let proof = match Proof::<E>::deserialize(proof_bytes) {
+        Ok(proof) => proof,
+        Err(_) => return false,
+    };
+
+    // Fails if the number of public inputs does not match the verifying key.
+    ark_groth16::verify_proof(&pvk, &proof, &public_inputs).unwrap_or(false)
+}
+
+/***************************************************************************************************
+ * native fun prepare_verifying_key_internal
+ *
+ *   gas cost: base_cost + per_public_input_cost * num_public_inputs
+ *
+ * where the costs are those of the curve, and num_public_inputs is derived from the size of the
+ * verifying key
+ **************************************************************************************************/
+fn native_prepare_verifying_key(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let vk_bytes = pop_arg!(arguments, Vec<u8>);
+    let curve = pop_arg!(arguments, u8);
+
+    let (curve_gas_params, num_public_inputs) = match curve {
+        BN254 => (
+            &gas_params.bn254,
+            num_public_inputs_of_verifying_key::<ark_bn254::Bn254>(&vk_bytes),
+        ),
+        BLS12_381 => (
+            &gas_params.bls12381,
+            num_public_inputs_of_verifying_key::<ark_bls12_381::Bls12_381>(&vk_bytes),
+        ),
+        _ => {
+            return Ok(NativeResult::err(
+                InternalGas::zero(),
+                abort_codes::E_UNSUPPORTED_CURVE,
+            ))
+        }
+    };
+
+    // NOTE(Gas): a pairing, plus the deserialization of O(num_public_inputs) points
+    let cost = curve_gas_params.prepare_verifying_key_base
+        + curve_gas_params.prepare_verifying_key_per_public_input
+            * NumArgs::new(num_public_inputs as u64);
+
+    let parts = match curve {
+        BN254 => prepare_verifying_key::<ark_bn254::Bn254>(&vk_bytes),
+        _ => prepare_verifying_key::<ark_bls12_381::Bls12_381>(&vk_bytes),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        match parts {
+            Some([vk_gamma_abc_g1, alpha_g1_beta_g2, gamma_g2_neg, delta_g2_neg]) => smallvec![
+                Value::bool(true),
+                Value::vector_u8(vk_gamma_abc_g1),
+                Value::vector_u8(alpha_g1_beta_g2),
+                Value::vector_u8(gamma_g2_neg),
+                Value::vector_u8(delta_g2_neg),
+            ],
+            None => smallvec![
+                Value::bool(false),
+                Value::vector_u8(vec![]),
+                Value::vector_u8(vec![]),
+                Value::vector_u8(vec![]),
+                Value::vector_u8(vec![]),
+            ],
+        },
+    ))
+}
+
+/***************************************************************************************************
+ * native fun verify_proof_internal
+ *
+ *   gas cost: base_cost + per_public_input_cost * num_public_inputs
+ *
+ * where the costs are those of the curve. Aborts before deserializing anything if the prepared
+ * verifying key does not commit to exactly num_public_inputs public inputs, so that the work done
+ * is bounded by the cost
+ **************************************************************************************************/
+fn native_verify_proof(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 7);
+
+    let proof_bytes = pop_arg!(arguments, Vec<u8>);
+    let public_inputs_bytes = pop_arg!(arguments, Vec<u8>);
+    let delta_g2_neg_bytes = pop_arg!(arguments, Vec<u8>);
+    let gamma_g2_neg_bytes = pop_arg!(arguments, Vec<u8>);
+    let alpha_g1_beta_g2_bytes = pop_arg!(arguments, Vec<u8>);
+    let vk_gamma_abc_g1_bytes = pop_arg!(arguments, Vec<u8>);
+    let curve = pop_arg!(arguments, u8);
+
+    let (curve_gas_params, g1_num_bytes) = match curve {
+        BN254 => (&gas_params.bn254, g1_num_bytes::<ark_bn254::Bn254>()),
+        BLS12_381 => (
+            &gas_params.bls12381,
+            g1_num_bytes::<ark_bls12_381::Bls12_381>(),
+        ),
+        _ => {
+            return Ok(NativeResult::err(
+                InternalGas::zero(),
+                abort_codes::E_UNSUPPORTED_CURVE,
+            ))
+        }
+    };
+
+    // NOTE(Gas): three pairings and a multi-pairing, plus a multi-scalar multiplication of size
+    // num_public_inputs
+    let num_public_inputs = public_inputs_bytes.len() / SCALAR_NUM_BYTES;
+    let cost = curve_gas_params.verify_proof_base
+        + curve_gas_params.verify_proof_per_public_input * NumArgs::new(num_public_inputs as u64);
+
+    if public_inputs_bytes.len() % SCALAR_NUM_BYTES != 0
+        || vk_gamma_abc_g1_bytes.len() != (num_public_inputs + 1) * g1_num_bytes
+    {
+        return Ok(NativeResult::err(
+            cost,
+            abort_codes::E_WRONG_NUM_PUBLIC_INPUTS,
+        ));
+    }
+
+    let verify = match curve {
+        BN254 => verify_proof::<ark_bn254::Bn254>,
+        _ => verify_proof::<ark_bls12_381::Bls12_381>,
+    };
+    let valid = verify(
+        &vk_gamma_abc_g1_bytes,
+        &alpha_g1_beta_g2_bytes,
+        &gamma_g2_neg_bytes,
+        &delta_g2_neg_bytes,
+        &public_inputs_bytes,
+        &proof_bytes,
+    );
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct CurveGasParameters {
+    pub prepare_verifying_key_base: InternalGas,
+    pub prepare_verifying_key_per_public_input: InternalGasPerArg,
+    pub verify_proof_base: InternalGas,
+    pub verify_proof_per_public_input: InternalGasPerArg,
+}
+
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub bn254: CurveGasParameters,
+    pub bls12381: CurveGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "prepare_verifying_key_internal",
+            make_native_from_func(gas_params.clone(), native_prepare_verifying_key),
+        ),
+        (
+            "verify_proof_internal",
+            make_native_from_func(gas_params, native_verify_proof),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
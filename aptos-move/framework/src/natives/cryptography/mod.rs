@@ -3,6 +3,7 @@
 
 pub mod bls12381;
 pub mod ed25519;
+pub mod groth16;
 pub mod ristretto255;
 pub mod ristretto255_point;
 pub mod ristretto255_scalar;
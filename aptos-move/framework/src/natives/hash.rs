@@ -9,6 +9,7 @@ use move_deps::{
         loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
     },
 };
+use sha2::Digest;
 use smallvec::smallvec;
 use std::{collections::VecDeque, hash::Hasher, sync::Arc};
 
@@ -50,6 +51,178 @@ pub fn make_native_sip_hash(gas_params: SipHashGasParameters) -> NativeFunction
     Arc::new(move |context, ty_args, args| native_sip_hash(&gas_params, context, ty_args, args))
 }
 
+/***************************************************************************************************
+ * native fun keccak256
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Keccak256GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_keccak256(
+    gas_params: &Keccak256GasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let hash = sha3::Keccak256::digest(&bytes).to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+pub fn make_native_keccak256(gas_params: Keccak256GasParameters) -> NativeFunction {
+    Arc::new(move |context, ty_args, args| native_keccak256(&gas_params, context, ty_args, args))
+}
+
+/***************************************************************************************************
+ * native fun sha2_512
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Sha2_512GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_sha2_512(
+    gas_params: &Sha2_512GasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let hash = sha2::Sha512::digest(&bytes).to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+pub fn make_native_sha2_512(gas_params: Sha2_512GasParameters) -> NativeFunction {
+    Arc::new(move |context, ty_args, args| native_sha2_512(&gas_params, context, ty_args, args))
+}
+
+/***************************************************************************************************
+ * native fun sha3_512
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Sha3_512GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_sha3_512(
+    gas_params: &Sha3_512GasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let hash = sha3::Sha3_512::digest(&bytes).to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+pub fn make_native_sha3_512(gas_params: Sha3_512GasParameters) -> NativeFunction {
+    Arc::new(move |context, ty_args, args| native_sha3_512(&gas_params, context, ty_args, args))
+}
+
+/***************************************************************************************************
+ * native fun blake2b_256
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Blake2b256GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_blake2b_256(
+    gas_params: &Blake2b256GasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let hash = blake2_rfc::blake2b::blake2b(32, &[], &bytes)
+        .as_bytes()
+        .to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+pub fn make_native_blake2b_256(gas_params: Blake2b256GasParameters) -> NativeFunction {
+    Arc::new(move |context, ty_args, args| native_blake2b_256(&gas_params, context, ty_args, args))
+}
+
+/***************************************************************************************************
+ * native fun ripemd160
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Ripemd160GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_ripemd160(
+    gas_params: &Ripemd160GasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let hash = ripemd160::Ripemd160::digest(&bytes).to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(hash)]))
+}
+
+pub fn make_native_ripemd160(gas_params: Ripemd160GasParameters) -> NativeFunction {
+    Arc::new(move |context, ty_args, args| native_ripemd160(&gas_params, context, ty_args, args))
+}
+
 /***************************************************************************************************
  * module
  *
@@ -57,10 +230,25 @@ pub fn make_native_sip_hash(gas_params: SipHashGasParameters) -> NativeFunction
 #[derive(Debug, Clone)]
 pub struct GasParameters {
     pub sip_hash: SipHashGasParameters,
+    pub keccak256: Keccak256GasParameters,
+    pub sha2_512: Sha2_512GasParameters,
+    pub sha3_512: Sha3_512GasParameters,
+    pub blake2b_256: Blake2b256GasParameters,
+    pub ripemd160: Ripemd160GasParameters,
 }
 
 pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
-    let natives = [("sip_hash", make_native_sip_hash(gas_params.sip_hash))];
+    let natives = [
+        ("sip_hash", make_native_sip_hash(gas_params.sip_hash)),
+        ("keccak256", make_native_keccak256(gas_params.keccak256)),
+        ("sha2_512", make_native_sha2_512(gas_params.sha2_512)),
+        ("sha3_512", make_native_sha3_512(gas_params.sha3_512)),
+        (
+            "blake2b_256",
+            make_native_blake2b_256(gas_params.blake2b_256),
+        ),
+        ("ripemd160", make_native_ripemd160(gas_params.ripemd160)),
+    ];
 
     crate::natives::helpers::make_module_natives(natives)
 }
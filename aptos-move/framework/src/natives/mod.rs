@@ -37,6 +37,7 @@ pub struct GasParameters {
     pub bls12381: cryptography::bls12381::GasParameters,
     pub secp256k1: cryptography::secp256k1::GasParameters,
     pub ristretto255: cryptography::ristretto255::GasParameters,
+    pub groth16: cryptography::groth16::GasParameters,
     pub hash: hash::GasParameters,
     pub type_info: type_info::GasParameters,
     pub util: util::GasParameters,
@@ -109,11 +110,45 @@ impl GasParameters {
                 point_parse_arg: 0.into(),
                 scalar_parse_arg: 0.into(),
             },
+            groth16: cryptography::groth16::GasParameters {
+                bn254: cryptography::groth16::CurveGasParameters {
+                    prepare_verifying_key_base: 0.into(),
+                    prepare_verifying_key_per_public_input: 0.into(),
+                    verify_proof_base: 0.into(),
+                    verify_proof_per_public_input: 0.into(),
+                },
+                bls12381: cryptography::groth16::CurveGasParameters {
+                    prepare_verifying_key_base: 0.into(),
+                    prepare_verifying_key_per_public_input: 0.into(),
+                    verify_proof_base: 0.into(),
+                    verify_proof_per_public_input: 0.into(),
+                },
+            },
             hash: hash::GasParameters {
                 sip_hash: hash::SipHashGasParameters {
                     base: 0.into(),
                     per_byte: 0.into(),
                 },
+                keccak256: hash::Keccak256GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                sha2_512: hash::Sha2_512GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                sha3_512: hash::Sha3_512GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                blake2b_256: hash::Blake2b256GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                ripemd160: hash::Ripemd160GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
             },
             type_info: type_info::GasParameters {
                 type_of: type_info::TypeOfGasParameters {
@@ -189,6 +224,10 @@ pub fn all_natives(
         "secp256k1",
         cryptography::secp256k1::make_all(gas_params.secp256k1)
     );
+    add_natives_from_module!(
+        "groth16",
+        cryptography::groth16::make_all(gas_params.groth16)
+    );
     add_natives_from_module!("aptos_hash", hash::make_all(gas_params.hash));
     add_natives_from_module!(
         "ristretto255",
@@ -14,7 +14,7 @@ use aptos_types::write_set::TransactionWrite;
 use mvhashmap::{MVHashMap, MVHashMapError, MVHashMapOutput};
 use num_cpus;
 use once_cell::sync::Lazy;
-use std::{hash::Hash, marker::PhantomData, sync::Arc, thread::spawn};
+use std::{collections::HashMap, hash::Hash, marker::PhantomData, sync::Arc, thread::spawn};
 
 static RAYON_EXEC_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
     rayon::ThreadPoolBuilder::new()
@@ -100,6 +100,11 @@ impl<
                         .push(ReadDescriptor::from_unresolved(key.clone(), delta));
                     return ReadResult::Unresolved(delta);
                 }
+                Err(Dependency(_)) if self.scheduler.halted() => {
+                    // The execution of the block is halted, and `self.txn_idx` is skipped as it
+                    // follows the committed prefix, hence its output is discarded anyway.
+                    return ReadResult::None;
+                }
                 Err(Dependency(dep_idx)) => {
                     // `self.txn_idx` estimated to depend on a write from `dep_idx`.
                     match self.scheduler.wait_for_dependency(self.txn_idx, dep_idx) {
@@ -146,10 +151,19 @@ impl<
     }
 }
 
+/// The reads of the modules (code) that an executor may have cached since its module cache was
+/// last flushed, by module access path. Like data, modules are versioned in the multi-version
+/// data-structure, and a cached module may only be used by a transaction that would read the
+/// same version of it.
+type ModuleCacheReads<K> = HashMap<K, ReadDescriptor<K>>;
+
 pub struct ParallelTransactionExecutor<T: Transaction, E: ExecutorTask> {
     // number of active concurrent tasks, corresponding to the maximum number of rayon
     // threads that may be concurrently participating in parallel execution.
     concurrency_level: usize,
+    // if set, the transactions following the first transaction at which the total gas used
+    // by the block reaches the limit are skipped.
+    maybe_block_gas_limit: Option<u64>,
     phantom: PhantomData<(T, E)>,
 }
 
@@ -160,7 +174,7 @@ where
 {
     /// The caller needs to ensure that concurrency_level > 1 (0 is illegal and 1 should
     /// be handled by sequential execution) and that concurrency_level <= num_cpus.
-    pub fn new(concurrency_level: usize) -> Self {
+    pub fn new(concurrency_level: usize, maybe_block_gas_limit: Option<u64>) -> Self {
         assert!(
             concurrency_level > 1 && concurrency_level <= num_cpus::get(),
             "Parallel execution concurrency level {} should be between 2 and number of CPUs",
@@ -168,6 +182,7 @@ where
         );
         Self {
             concurrency_level,
+            maybe_block_gas_limit,
             phantom: PhantomData,
         }
     }
@@ -185,23 +200,43 @@ where
         versioned_data_cache: &MVHashMap<<T as Transaction>::Key, <T as Transaction>::Value>,
         scheduler: &'a Scheduler,
         executor: &E,
+        module_cache_reads: &mut ModuleCacheReads<<T as Transaction>::Key>,
     ) -> SchedulerTask<'a> {
         let (idx_to_execute, incarnation) = version;
         let txn = &signature_verified_block[idx_to_execute];
 
+        // The executor may use the modules it cached while executing other transactions without
+        // reading them through the view. This is only correct if the transaction would read the
+        // same versions of these modules, otherwise the cache must be flushed. The reads of the
+        // cached modules are captured as the reads of the transaction, so that the validation
+        // fails if a lower transaction (re-)publishes any of them.
+        if !module_cache_reads
+            .values()
+            .all(|r| Self::validate_read(versioned_data_cache, r, idx_to_execute))
+        {
+            executor.flush_module_cache();
+            module_cache_reads.clear();
+        }
+
         let state_view = MVHashMapView {
             versioned_map: versioned_data_cache,
             txn_idx: idx_to_execute,
             scheduler,
-            captured_reads: Mutex::new(Vec::new()),
+            captured_reads: Mutex::new(module_cache_reads.values().cloned().collect()),
         };
 
         // VM execution.
         let execute_result = executor.execute_transaction(&state_view, txn);
+        let reads = state_view.take_reads();
+        for r in reads.iter().filter(|r| r.module_path().is_some()) {
+            module_cache_reads.insert(r.path().clone(), r.clone());
+        }
         let mut prev_modified_keys = last_input_output.modified_keys(idx_to_execute);
 
         // For tracking whether the recent execution wrote outside of the previous write/delta set.
         let mut updates_outside = false;
+        // For tracking whether the recent execution published modules.
+        let mut modules_published = false;
         let mut apply_updates = |output: &<E as ExecutorTask>::Output| {
             // First, apply writes.
             let write_version = (idx_to_execute, incarnation);
@@ -209,6 +244,9 @@ where
                 if !prev_modified_keys.remove(&k) {
                     updates_outside = true;
                 }
+                if k.module_path().is_some() {
+                    modules_published = true;
+                }
                 versioned_data_cache.add_write(&k, write_version, v);
             }
 
@@ -247,10 +285,43 @@ where
             versioned_data_cache.delete(&k, idx_to_execute);
         }
 
-        last_input_output.record(idx_to_execute, state_view.take_reads(), result);
+        // The executor may have cached the modules published by the transaction, which are
+        // speculative and not visible to other transactions until committed.
+        if modules_published {
+            executor.flush_module_cache();
+            module_cache_reads.clear();
+        }
+
+        last_input_output.record(idx_to_execute, reads, result);
         scheduler.finish_execution(idx_to_execute, incarnation, updates_outside, guard)
     }
 
+    /// Returns whether a read by the transaction at the given index would still observe the
+    /// version or value described by the read descriptor.
+    fn validate_read(
+        versioned_data_cache: &MVHashMap<<T as Transaction>::Key, <T as Transaction>::Value>,
+        r: &ReadDescriptor<<T as Transaction>::Key>,
+        txn_idx: TxnIndex,
+    ) -> bool {
+        use MVHashMapError::*;
+        use MVHashMapOutput::*;
+
+        match versioned_data_cache.read(r.path(), txn_idx) {
+            Ok(Version(version, _)) => r.validate_version(version),
+            Ok(Resolved(value)) => r.validate_resolved(value),
+            Err(Dependency(_)) => false, // Dependency implies a validation failure.
+            Err(Unresolved(delta)) => r.validate_unresolved(delta),
+            Err(NotFound) => r.validate_storage(),
+            // We successfully validate when read (again) results in a delta application
+            // failure. If the failure is speculative, a later validation will fail due to
+            // a read without this error. However, if the failure is real, passing
+            // validation here allows to avoid infinitely looping and instead panic when
+            // materializing deltas as writes in the final output preparation state. Panic
+            // is also preferrable as it allows testing for this scenario.
+            Err(DeltaApplicationFailure) => r.validate_delta_application_failure(),
+        }
+    }
+
     fn validate<'a>(
         &self,
         version_to_validate: Version,
@@ -263,30 +334,14 @@ where
         versioned_data_cache: &MVHashMap<<T as Transaction>::Key, <T as Transaction>::Value>,
         scheduler: &'a Scheduler,
     ) -> SchedulerTask<'a> {
-        use MVHashMapError::*;
-        use MVHashMapOutput::*;
-
         let (idx_to_validate, incarnation) = version_to_validate;
         let read_set = last_input_output
             .read_set(idx_to_validate)
             .expect("Prior read-set must be recorded");
 
-        let valid = read_set.iter().all(|r| {
-            match versioned_data_cache.read(r.path(), idx_to_validate) {
-                Ok(Version(version, _)) => r.validate_version(version),
-                Ok(Resolved(value)) => r.validate_resolved(value),
-                Err(Dependency(_)) => false, // Dependency implies a validation failure.
-                Err(Unresolved(delta)) => r.validate_unresolved(delta),
-                Err(NotFound) => r.validate_storage(),
-                // We successfully validate when read (again) results in a delta application
-                // failure. If the failure is speculative, a later validation will fail due to
-                // a read without this error. However, if the failure is real, passing
-                // validation here allows to avoid infinitely looping and instead panic when
-                // materializing deltas as writes in the final output preparation state. Panic
-                // is also preferrable as it allows testing for this scenario.
-                Err(DeltaApplicationFailure) => r.validate_delta_application_failure(),
-            }
-        });
+        let valid = read_set
+            .iter()
+            .all(|r| Self::validate_read(versioned_data_cache, r, idx_to_validate));
 
         let aborted = !valid && scheduler.try_abort(idx_to_validate, incarnation);
 
@@ -316,6 +371,7 @@ where
     ) {
         // Make executor for each task. TODO: fast concurrent executor.
         let executor = E::init(*executor_arguments);
+        let mut module_cache_reads = HashMap::new();
 
         let mut scheduler_task = SchedulerTask::NoTask;
         loop {
@@ -335,6 +391,7 @@ where
                     versioned_data_cache,
                     scheduler,
                     &executor,
+                    &mut module_cache_reads,
                 ),
                 SchedulerTask::ExecutionTask(_, Some(condvar), _guard) => {
                     let (lock, cvar) = &*condvar;
@@ -345,7 +402,20 @@ where
 
                     SchedulerTask::NoTask
                 }
-                SchedulerTask::NoTask => scheduler.next_task(),
+                SchedulerTask::NoTask => {
+                    scheduler.try_commit(
+                        self.maybe_block_gas_limit,
+                        |(txn_idx, _)| {
+                            last_input_output
+                                .read_set(txn_idx)
+                                .expect("Prior read-set must be recorded")
+                                .iter()
+                                .all(|r| Self::validate_read(versioned_data_cache, r, txn_idx))
+                        },
+                        |txn_idx| last_input_output.gas_used(txn_idx),
+                    );
+                    scheduler.next_task()
+                }
                 SchedulerTask::Done => {
                     break;
                 }
@@ -360,6 +430,7 @@ where
     ) -> Result<
         (
             Vec<E::Output>,
+            usize,
             OutputDeltaResolver<<T as Transaction>::Key, <T as Transaction>::Value>,
         ),
         E::Error,
@@ -367,7 +438,7 @@ where
         let versioned_data_cache = MVHashMap::new();
 
         if signature_verified_block.is_empty() {
-            return Ok((vec![], OutputDeltaResolver::new(versioned_data_cache)));
+            return Ok((vec![], 0, OutputDeltaResolver::new(versioned_data_cache)));
         }
 
         let num_txns = signature_verified_block.len();
@@ -392,25 +463,34 @@ where
         let num_txns = scheduler.num_txn_to_execute();
         let mut final_results = Vec::with_capacity(num_txns);
 
-        let maybe_err = if last_input_output.module_publishing_may_race() {
-            Some(Error::ModulePathReadWrite)
-        } else {
-            let mut ret = None;
-            for idx in 0..num_txns {
-                match last_input_output.take_output(idx) {
-                    ExecutionStatus::Success(t) => final_results.push(t),
-                    ExecutionStatus::SkipRest(t) => {
-                        final_results.push(t);
-                        break;
-                    }
-                    ExecutionStatus::Abort(err) => {
-                        ret = Some(err);
-                        break;
-                    }
-                };
+        // The outputs are final up to the cut-off of the block (if the execution was halted, all
+        // the transactions up to the cut-off are committed), which is determined the same way
+        // as when committing, so that the outputs do not depend on the execution being halted.
+        let mut maybe_err = None;
+        let mut accumulated_gas: u64 = 0;
+        for idx in 0..num_txns {
+            match last_input_output.take_output(idx) {
+                ExecutionStatus::Success(t) => {
+                    accumulated_gas = accumulated_gas.saturating_add(t.gas_used());
+                    final_results.push(t);
+                }
+                ExecutionStatus::SkipRest(t) => {
+                    final_results.push(t);
+                    break;
+                }
+                ExecutionStatus::Abort(err) => {
+                    maybe_err = Some(err);
+                    break;
+                }
+            };
+
+            if self
+                .maybe_block_gas_limit
+                .map_or(false, |limit| accumulated_gas >= limit)
+            {
+                break;
             }
-            ret
-        };
+        }
 
         spawn(move || {
             // Explicit async drops.
@@ -422,9 +502,13 @@ where
         match maybe_err {
             Some(err) => Err(err),
             None => {
+                // Only the outputs of the committed prefix are final, the MVHashMap may still
+                // contain speculative entries of the transactions past the cut-off.
+                let committed_len = final_results.len();
                 final_results.resize_with(num_txns, E::Output::skip_output);
                 Ok((
                     final_results,
+                    committed_len,
                     OutputDeltaResolver::new(versioned_data_cache),
                 ))
             }
@@ -134,6 +134,18 @@ optimistically creating validation tasks for higher transactions in 2(b),
 and threads that perform these tasks can already detect validation failures
 due to the ESTIMATE markers on memory locations, instead of waiting for a
 subsequent incarnation to finish.
+
+Modules (code) are versioned in the multi-version data-structure like any other
+memory location, and module publishing is handled speculatively like any other
+write. However, the executor of each thread (e.g. the Move-VM and its loader)
+may cache the modules it loaded, and use them in the following executions
+without reading them again. Hence, each thread tracks the versions of the
+modules its executor may have cached. Before an incarnation executes, these
+versions are compared to the ones the incarnation would read: if they are the
+same, they are recorded in the read-set of the incarnation (so that validation
+covers the cached modules), otherwise the cache of the executor is flushed.
+The cache is also flushed after an incarnation publishes modules, as the
+executor may have cached the speculatively published modules.
 **/
 pub mod errors;
 pub mod executor;
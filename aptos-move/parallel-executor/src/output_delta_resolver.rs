@@ -22,10 +22,13 @@ impl<K: Hash + Clone + Eq + Send + 'static, V: TransactionWrite + Send + Sync +
 
     /// Takes Self, vector of all involved aggregator keys (each with at least one
     /// delta to resolve in the output), resolved values from storage for each key,
-    /// and blocksize, and returns a Vec of materialized deltas per transaction index.
+    /// the number of committed transactions and blocksize, and returns a Vec of
+    /// materialized deltas per transaction index. Entries of transactions past the
+    /// committed prefix are speculative (never validated) and are not resolved.
     pub fn resolve(
         self,
         aggregator_keys: Vec<(K, anyhow::Result<ResolvedData>)>,
+        committed_len: usize,
         block_size: usize,
     ) -> Vec<Vec<(K, WriteOp)>> {
         let mut ret: Vec<Vec<(K, WriteOp)>> = (0..block_size).map(|_| Vec::new()).collect();
@@ -45,6 +48,11 @@ impl<K: Hash + Clone + Eq + Send + 'static, V: TransactionWrite + Send + Sync +
                 .entry_map_for_key(&key)
                 .expect("No entries found for the provided key");
             for (idx, entry) in indexed_entries.iter() {
+                if *idx >= committed_len {
+                    // Entries are ordered by transaction index.
+                    break;
+                }
+
                 match &entry.cell {
                     EntryCell::Write(_, data) => {
                         latest_value = data.extract_raw_bytes().map(|bytes| deserialize(&bytes))
@@ -99,7 +99,7 @@ where
             .map(|txn_gen| txn_gen.materialize(&key_universe, (false, false)))
             .collect();
 
-        let expected_output = ExpectedOutput::generate_baseline(&transactions, None);
+        let expected_output = ExpectedOutput::generate_baseline(&transactions, None, None);
 
         Self {
             transactions,
@@ -111,9 +111,9 @@ where
         let output = ParallelTransactionExecutor::<
             Transaction<KeyType<K>, ValueType<V>>,
             Task<KeyType<K>, ValueType<V>>,
-        >::new(num_cpus::get())
+        >::new(num_cpus::get(), None)
         .execute_transactions_parallel((), self.transactions.clone())
-        .map(|(res, _)| res);
+        .map(|(res, _, _)| res);
 
         self.expected_output.assert_output(&output, None);
     }
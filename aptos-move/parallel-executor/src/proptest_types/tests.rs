@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    errors::Error,
     executor::ParallelTransactionExecutor,
     proptest_types::types::{
         ExpectedOutput, KeyType, Task, Transaction, TransactionGen, TransactionGenParams, ValueType,
@@ -27,6 +26,7 @@ fn run_transactions<K, V>(
     skip_rest_transactions: Vec<Index>,
     num_repeat: usize,
     module_access: (bool, bool),
+    maybe_block_gas_limit: Option<u64>,
 ) where
     K: Hash + Clone + Debug + Eq + Send + Sync + PartialOrd + Ord + 'static,
     V: Clone + Eq + Send + Sync + Arbitrary + 'static,
@@ -49,21 +49,65 @@ fn run_transactions<K, V>(
         let output = ParallelTransactionExecutor::<
             Transaction<KeyType<K>, ValueType<V>>,
             Task<KeyType<K>, ValueType<V>>,
-        >::new(num_cpus::get())
+        >::new(num_cpus::get(), maybe_block_gas_limit)
         .execute_transactions_parallel((), transactions.clone())
-        .map(|(res, _)| res);
+        .map(|(res, _, _)| res);
 
-        if module_access.0 && module_access.1 {
-            assert_eq!(output.unwrap_err(), Error::ModulePathReadWrite);
-            continue;
-        }
-
-        let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+        let baseline =
+            ExpectedOutput::generate_baseline(&transactions, None, maybe_block_gas_limit);
 
         baseline.assert_output(&output, None);
     }
 }
 
+fn run_deltas_resolver(
+    universe: &[[u8; 32]],
+    transaction_gen: Vec<TransactionGen<[u8; 32]>>,
+    maybe_block_gas_limit: Option<u64>,
+) {
+    let num_txns = transaction_gen.len();
+
+    // Do not allow deletes as that would panic in resolver.
+    let transactions: Vec<_> = transaction_gen
+        .into_iter()
+        .map(|txn_gen| txn_gen.materialize_with_deltas(universe, 15, false))
+        .collect();
+
+    let output = ParallelTransactionExecutor::<
+        Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
+        Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
+    >::new(num_cpus::get(), maybe_block_gas_limit)
+    .execute_transactions_parallel((), transactions.clone());
+
+    let (output, committed_len, delta_resolver) = output.unwrap();
+    // Should not be possible to overflow or underflow, as each delta is at
+    // most 100 in the tests.
+    let storage_delta_val = 100001;
+    let resolved = delta_resolver.resolve(
+        (15..universe.len())
+            .map(|i| {
+                (
+                    KeyType(universe[i], false),
+                    Ok(Some(serialize(&storage_delta_val))),
+                )
+            })
+            .collect(),
+        committed_len,
+        num_txns,
+    );
+
+    // Deltas of the transactions past the cut-off must not be resolved.
+    assert_eq!(resolved.len(), num_txns);
+    assert!(resolved
+        .iter()
+        .skip(committed_len)
+        .all(|delta_writes| delta_writes.is_empty()));
+
+    let baseline =
+        ExpectedOutput::generate_baseline(&transactions, Some(resolved), maybe_block_gas_limit);
+    baseline.assert_output(&Ok(output), Some(storage_delta_val));
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(32))]
     #[test]
@@ -73,7 +117,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -83,7 +127,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -93,7 +137,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -103,7 +147,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -113,7 +157,36 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 3),
         skip_rest_transactions in vec(any::<Index>(), 3),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+    }
+
+    #[test]
+    fn block_gas_limit(
+        universe in vec(any::<[u8; 32]>(), 100),
+        transaction_gen in vec(any_with::<TransactionGen<[u8;32]>>(TransactionGenParams::new_dynamic()), 3000).no_shrink(),
+        block_gas_limit in 1u64..6000,
+    ) {
+        run_transactions(&universe, transaction_gen, vec![], vec![], 1, (false, false), Some(block_gas_limit));
+    }
+
+    #[test]
+    fn deltas_resolver_block_gas_limit(
+        universe in vec(any::<[u8; 32]>(), 50),
+        transaction_gen in vec(any_with::<TransactionGen<[u8;32]>>(TransactionGenParams::new_dynamic()), 1000).no_shrink(),
+        block_gas_limit in 1u64..2000,
+    ) {
+        run_deltas_resolver(&universe, transaction_gen, Some(block_gas_limit));
+    }
+
+    #[test]
+    fn block_gas_limit_mixed_transactions(
+        universe in vec(any::<[u8; 32]>(), 100),
+        transaction_gen in vec(any::<TransactionGen<[u8;32]>>(), 3000).no_shrink(),
+        abort_transactions in vec(any::<Index>(), 3),
+        skip_rest_transactions in vec(any::<Index>(), 3),
+        block_gas_limit in 1u64..6000,
+    ) {
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(block_gas_limit));
     }
 }
 
@@ -140,6 +213,7 @@ fn dynamic_read_writes() {
         vec![],
         100,
         (false, false),
+        None,
     );
 }
 
@@ -169,11 +243,11 @@ fn deltas_writes_mixed() {
         let output = ParallelTransactionExecutor::<
             Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-        >::new(num_cpus::get())
+        >::new(num_cpus::get(), None)
         .execute_transactions_parallel((), transactions.clone())
-        .map(|(res, _)| res);
+        .map(|(res, _, _)| res);
 
-        let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+        let baseline = ExpectedOutput::generate_baseline(&transactions, None, None);
         baseline.assert_output(&output, None);
     }
 }
@@ -195,37 +269,8 @@ fn deltas_resolver() {
     .expect("creating a new value should succeed")
     .current();
 
-    // Do not allow deletes as that would panic in resolver.
-    let transactions: Vec<_> = transaction_gen
-        .into_iter()
-        .map(|txn_gen| txn_gen.materialize_with_deltas(&universe, 15, false))
-        .collect();
-
     for _ in 0..20 {
-        let output = ParallelTransactionExecutor::<
-            Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-            Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-        >::new(num_cpus::get())
-        .execute_transactions_parallel((), transactions.clone());
-
-        let (output, delta_resolver) = output.unwrap();
-        // Should not be possible to overflow or underflow, as each delta is at
-        // most 100 in the tests.
-        let storage_delta_val = 100001;
-        let resolved = delta_resolver.resolve(
-            (15..50)
-                .map(|i| {
-                    (
-                        KeyType(universe[i], false),
-                        Ok(Some(serialize(&storage_delta_val))),
-                    )
-                })
-                .collect(),
-            num_txns,
-        );
-
-        let baseline = ExpectedOutput::generate_baseline(&transactions, Some(resolved));
-        baseline.assert_output(&Ok(output), Some(storage_delta_val));
+        run_deltas_resolver(&universe, transaction_gen.clone(), None);
     }
 }
 
@@ -253,11 +298,12 @@ fn dynamic_read_writes_contended() {
         vec![],
         100,
         (false, false),
+        None,
     );
 }
 
 #[test]
-fn module_publishing_fallback() {
+fn module_publishing() {
     let mut runner = TestRunner::default();
 
     let universe = vec(any::<[u8; 32]>(), 100)
@@ -279,6 +325,7 @@ fn module_publishing_fallback() {
         vec![],
         2,
         (false, true),
+        None,
     );
     run_transactions(
         &universe,
@@ -287,8 +334,17 @@ fn module_publishing_fallback() {
         vec![],
         2,
         (false, true),
+        None,
+    );
+    run_transactions(
+        &universe,
+        transaction_gen,
+        vec![],
+        vec![],
+        2,
+        (true, true),
+        None,
     );
-    run_transactions(&universe, transaction_gen, vec![], vec![], 2, (true, true));
 }
 
 fn publishing_fixed_params() {
@@ -350,7 +406,7 @@ fn publishing_fixed_params() {
     let output = ParallelTransactionExecutor::<
         Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
         Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-    >::new(num_cpus::get())
+    >::new(num_cpus::get(), None)
     .execute_transactions_parallel((), transactions.clone());
     assert_ok!(output);
 
@@ -385,11 +441,12 @@ fn publishing_fixed_params() {
         let output = ParallelTransactionExecutor::<
             Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-        >::new(num_cpus::get())
+        >::new(num_cpus::get(), None)
         .execute_transactions_parallel((), transactions.clone())
-        .map(|(res, _)| res);
+        .map(|(res, _, _)| res);
 
-        assert_eq!(output.unwrap_err(), Error::ModulePathReadWrite);
+        let baseline = ExpectedOutput::generate_baseline(&transactions, None, None);
+        baseline.assert_output(&output, None);
     }
 }
 
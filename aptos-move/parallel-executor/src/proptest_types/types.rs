@@ -13,6 +13,7 @@ use aptos_aggregator::{
     delta_change_set::{delta_add, delta_sub, DeltaOp},
     transaction::AggregatorValue,
 };
+use aptos_infallible::Mutex;
 use aptos_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
@@ -26,7 +27,6 @@ use std::{
     convert::TryInto,
     fmt::Debug,
     hash::{Hash, Hasher},
-    marker::PhantomData,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -45,9 +45,9 @@ pub struct KeyType<K: Hash + Clone + Debug + PartialOrd + Eq>(
     /// Wrapping the types used for testing to add ModulePath trait implementation (below).
     pub K,
     /// The bool field determines for testing purposes, whether the key will be interpreted
-    /// as a module access path. In this case, if a module path is both read and written
-    /// during parallel execution, Error::ModulePathReadWrite must be returned and the
-    /// block execution must fall back to the sequential execution.
+    /// as a module access path. In this case, the values read are cached by the executor
+    /// (see Task below), and the parallel execution must ensure that a stale cached value
+    /// is never observed.
     pub bool,
 );
 
@@ -286,6 +286,29 @@ impl<V: Into<Vec<u8>> + Arbitrary + Clone + Debug + Eq + Sync + Send> Transactio
         }
     }
 
+    pub fn materialize_with_modules<K: Clone + Hash + Debug + Eq + Ord>(
+        self,
+        universe: &[K],
+        // keys generated with indices below module_threshold will be treated as module
+        // access in both reads and writes, i.e. modules are published and loaded.
+        module_threshold: usize,
+    ) -> Transaction<KeyType<K>, ValueType<V>> {
+        let is_module_access = |i| -> bool { i < module_threshold };
+        let is_delta = |_, _: &V| -> Option<DeltaOp> { None };
+
+        Transaction::Write {
+            incarnation: Arc::new(AtomicUsize::new(0)),
+            writes_and_deltas: Self::writes_and_deltas_from_gen(
+                universe,
+                self.keys_modified,
+                &is_module_access,
+                &is_delta,
+                true,
+            ),
+            reads: Self::reads_from_gen(universe, self.keys_read, &is_module_access),
+        }
+    }
+
     pub fn materialize_disjoint_module_rw<K: Clone + Hash + Debug + Eq + Ord>(
         self,
         universe: &[K],
@@ -331,11 +354,19 @@ where
 // Naive transaction executor implementation.
 ///////////////////////////////////////////////////////////////////////////
 
-pub struct Task<K, V>(PhantomData<(K, V)>);
+/// The executor caches the values read from module access paths, similar to the loader cache
+/// of the Move-VM, and serves them without reading through the view until the cache is flushed.
+/// The values written to module access paths are cached as well, similar to the modules loaded
+/// by the Move-VM when publishing (e.g. to run their initializers).
+pub struct Task<K, V> {
+    module_cache: Mutex<HashMap<K, Option<Arc<V>>>>,
+}
 
 impl<K, V> Task<K, V> {
     pub fn new() -> Self {
-        Self(PhantomData)
+        Self {
+            module_cache: Mutex::new(HashMap::new()),
+        }
     }
 }
 
@@ -373,10 +404,37 @@ where
                 let write_idx = idx % writes_and_deltas.len();
 
                 // Reads
+                let mut module_cache = self.module_cache.lock();
                 let mut reads_result = vec![];
                 for k in reads[read_idx].iter() {
-                    reads_result.push(view.read(k));
+                    if k.module_path().is_none() {
+                        reads_result.push(view.read(k));
+                        continue;
+                    }
+                    let cached = match module_cache.get(k) {
+                        Some(cached) => cached.clone(),
+                        None => {
+                            let loaded = match view.read(k) {
+                                ReadResult::Value(v) => Some(v),
+                                ReadResult::None => None,
+                                _ => unreachable!("Modules are never updated by deltas"),
+                            };
+                            module_cache.insert(k.clone(), loaded.clone());
+                            loaded
+                        }
+                    };
+                    reads_result.push(match cached {
+                        Some(v) => ReadResult::Value(v),
+                        None => ReadResult::None,
+                    });
                 }
+
+                for (k, v) in writes_and_deltas[write_idx].0.iter() {
+                    if k.module_path().is_some() {
+                        module_cache.insert(k.clone(), Some(Arc::new(v.clone())));
+                    }
+                }
+
                 ExecutionStatus::Success(Output(
                     writes_and_deltas[write_idx].0.clone(),
                     writes_and_deltas[write_idx].1.clone(),
@@ -387,6 +445,10 @@ where
             Transaction::Abort => ExecutionStatus::Abort(view.txn_idx()),
         }
     }
+
+    fn flush_module_cache(&self) {
+        self.module_cache.lock().clear();
+    }
 }
 
 #[derive(Debug)]
@@ -407,11 +469,20 @@ where
         self.1.clone()
     }
 
+    fn gas_used(&self) -> u64 {
+        gas_used(&self.0, &self.1)
+    }
+
     fn skip_output() -> Self {
         Self(vec![], vec![], vec![])
     }
 }
 
+/// For testing purposes, the gas used by a transaction is the number of its writes and deltas.
+fn gas_used<K, V>(writes: &[(K, V)], deltas: &[(K, DeltaOp)]) -> u64 {
+    (writes.len() + deltas.len()) as u64
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Sequential Baseline implementation.
 ///////////////////////////////////////////////////////////////////////////
@@ -429,8 +500,10 @@ impl<V: Debug + Clone + PartialEq + Eq + TransactionWrite> ExpectedOutput<V> {
     pub fn generate_baseline<K: Hash + Clone + Eq>(
         txns: &[Transaction<K, V>],
         resolved_deltas: Option<Vec<Vec<(K, WriteOp)>>>,
+        maybe_block_gas_limit: Option<u64>,
     ) -> Self {
         let mut current_world = HashMap::new();
+        let mut accumulated_gas = 0;
         // Delta world stores the latest u128 value of delta aggregator. When empty, the
         // value is derived based on deserializing current_world, or falling back to
         // STORAGE_DELTA_VAL.
@@ -527,7 +600,14 @@ impl<V: Debug + Clone + PartialEq + Eq + TransactionWrite> ExpectedOutput<V> {
                         }
                     }
 
-                    result_vec.push(result)
+                    result_vec.push(result);
+
+                    // The transactions following the one at which the block gas limit is
+                    // reached are skipped.
+                    accumulated_gas += gas_used(write_set, delta_set);
+                    if maybe_block_gas_limit.map_or(false, |limit| accumulated_gas >= limit) {
+                        return Self::SkipRest(idx + 1, result_vec);
+                    }
                 }
                 Transaction::SkipRest => return Self::SkipRest(idx, result_vec),
             }
@@ -81,6 +81,16 @@ pub enum SchedulerTask<'a> {
 ///    ↓                finish_abort
 /// Aborting(i) ---------------------------------------------------------> Ready(i+1)
 ///
+/// 'Executed' status of an incarnation turns into 'Committed' once all the lower transactions are
+/// committed and the incarnation is validated (again), implying that it is final, i.e. it may not
+/// be aborted anymore.
+///
+/// Executed(i)
+///    |
+///    |  try_commit (all lower transactions committed & validated successfully)
+///    ↓
+/// Committed(i)
+///
 #[derive(Debug)]
 enum TransactionStatus {
     ReadyToExecute(Incarnation, Option<DependencyCondvar>),
@@ -88,6 +98,7 @@ enum TransactionStatus {
     Suspended(Incarnation, DependencyCondvar),
     Executed(Incarnation),
     Aborting(Incarnation),
+    Committed(Incarnation),
 }
 
 impl PartialEq for TransactionStatus {
@@ -98,12 +109,20 @@ impl PartialEq for TransactionStatus {
             | (&Executing(ref a), &Executing(ref b))
             | (&Suspended(ref a, _), &Suspended(ref b, _))
             | (&Executed(ref a), &Executed(ref b))
-            | (&Aborting(ref a), &Aborting(ref b)) => a == b,
+            | (&Aborting(ref a), &Aborting(ref b))
+            | (&Committed(ref a), &Committed(ref b)) => a == b,
             _ => false,
         }
     }
 }
 
+/// The committed prefix of the block: all transactions with lower indices than commit_idx are
+/// committed, and accumulated_gas is the total gas used by their final incarnations.
+struct CommitState {
+    commit_idx: TxnIndex,
+    accumulated_gas: u64,
+}
+
 pub struct Scheduler {
     /// Number of txns to execute, immutable.
     num_txns: usize,
@@ -129,6 +148,12 @@ pub struct Scheduler {
     num_active_tasks: AtomicUsize,
     /// Shared marker that is set when a thread detects that all txns can be committed.
     done_marker: AtomicBool,
+    /// Shared marker that is set when the committed prefix of the block reaches the block gas
+    /// limit, in which case the remaining transactions are skipped and no more tasks are created.
+    halted: AtomicBool,
+
+    /// The committed prefix of the block, which is extended by one thread at a time.
+    commit_state: CachePadded<Mutex<CommitState>>,
 
     /// An index i maps to indices of other transactions that depend on transaction i, i.e. they
     /// should be re-executed once transaction i's next incarnation finishes.
@@ -147,6 +172,11 @@ impl Scheduler {
             decrease_cnt: AtomicUsize::new(0),
             num_active_tasks: AtomicUsize::new(0),
             done_marker: AtomicBool::new(false),
+            halted: AtomicBool::new(false),
+            commit_state: CachePadded::new(Mutex::new(CommitState {
+                commit_idx: 0,
+                accumulated_gas: 0,
+            })),
             txn_dependency: (0..num_txns)
                 .map(|_| CachePadded::new(Mutex::new(Vec::new())))
                 .collect(),
@@ -178,6 +208,58 @@ impl Scheduler {
         }
     }
 
+    /// Try to extend the committed prefix of the block with the following transactions, in order.
+    /// The latest incarnation of a transaction is committed when it is executed and `validate`
+    /// succeeds: as all lower transactions are committed, the incarnation then read the final
+    /// values and may not be aborted anymore. `gas_used` returns the gas used by a committed
+    /// transaction, and once the total gas used by the committed prefix reaches the block gas
+    /// limit, the remaining transactions are skipped and the execution of the block is halted.
+    /// Returns immediately if another thread is committing.
+    pub fn try_commit(
+        &self,
+        maybe_block_gas_limit: Option<u64>,
+        validate: impl Fn(Version) -> bool,
+        gas_used: impl Fn(TxnIndex) -> u64,
+    ) {
+        let mut commit_state = match self.commit_state.try_lock() {
+            Some(commit_state) => commit_state,
+            None => return,
+        };
+
+        while commit_state.commit_idx < self.num_txns && !self.halted() {
+            let txn_idx = commit_state.commit_idx;
+            {
+                // Holding the lock on the status, so the incarnation may not be aborted
+                // concurrently with its validation.
+                let mut status = self.txn_status[txn_idx].lock();
+                match *status {
+                    TransactionStatus::Executed(incarnation)
+                        if validate((txn_idx, incarnation)) =>
+                    {
+                        *status = TransactionStatus::Committed(incarnation);
+                    }
+                    _ => return,
+                }
+            }
+
+            commit_state.commit_idx += 1;
+            commit_state.accumulated_gas = commit_state
+                .accumulated_gas
+                .saturating_add(gas_used(txn_idx));
+            if maybe_block_gas_limit.map_or(false, |limit| commit_state.accumulated_gas >= limit)
+                && commit_state.commit_idx < self.num_txns
+            {
+                self.halt();
+            }
+        }
+    }
+
+    /// Checks whether the execution of the block was halted, in which case all transactions
+    /// that are still executed are skipped and their outputs are discarded.
+    pub fn halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
     /// Return the next task for the thread.
     pub fn next_task(&self) -> SchedulerTask {
         loop {
@@ -233,7 +315,10 @@ impl Scheduler {
                 return None;
             }
 
-            self.suspend(txn_idx, dep_condvar.clone());
+            if !self.suspend(txn_idx, dep_condvar.clone()) {
+                // The execution is halted, the dependency will not be resolved.
+                return None;
+            }
 
             // Safe to add dependency here (still holding the lock) - finish_execution of txn
             // dep_txn_idx is guaranteed to acquire the same lock later and clear the dependency.
@@ -279,6 +364,11 @@ impl Scheduler {
             self.decrease_execution_idx(execution_target_idx);
         }
 
+        if self.halted() {
+            // No more tasks once the execution is halted.
+            return SchedulerTask::NoTask;
+        }
+
         // If validation_idx is already lower than txn_idx, all required transactions will be
         // considered for validation, and there is nothing to do.
         if self.validation_idx.load(Ordering::SeqCst) > txn_idx {
@@ -307,6 +397,11 @@ impl Scheduler {
     ) -> SchedulerTask<'a> {
         self.set_aborted_status(txn_idx, incarnation);
 
+        if self.halted() {
+            // No more tasks once the execution is halted.
+            return SchedulerTask::NoTask;
+        }
+
         // Schedule strictly higher txns for validation
         // (txn_idx needs to be re-executed first).
         self.decrease_validation_idx(txn_idx + 1);
@@ -368,19 +463,19 @@ impl Scheduler {
         }
     }
 
-    /// If the status of transaction is Executed(incarnation), returns Some(incarnation),
-    /// otherwise returns None. Useful to determine when a transaction can be validated,
-    /// and to avoid a race in dependency resolution.
+    /// If the status of transaction is Executed(incarnation) or Committed(incarnation), returns
+    /// Some(incarnation), otherwise returns None. Useful to determine when a transaction can be
+    /// validated, and to avoid a race in dependency resolution.
     fn is_executed(&self, txn_idx: TxnIndex) -> Option<Incarnation> {
         if txn_idx >= self.txn_status.len() {
             return None;
         }
 
         let status = self.txn_status[txn_idx].lock();
-        if let TransactionStatus::Executed(incarnation) = *status {
-            Some(incarnation)
-        } else {
-            None
+        match *status {
+            TransactionStatus::Executed(incarnation)
+            | TransactionStatus::Committed(incarnation) => Some(incarnation),
+            _ => None,
         }
     }
 
@@ -446,15 +541,22 @@ impl Scheduler {
     }
 
     /// Put a transaction in a suspended state, with a condition variable that can be
-    /// used to wake it up after the dependency is resolved.
-    fn suspend(&self, txn_idx: TxnIndex, dep_condvar: DependencyCondvar) {
+    /// used to wake it up after the dependency is resolved. Returns false without
+    /// suspending the transaction if the execution is halted.
+    fn suspend(&self, txn_idx: TxnIndex, dep_condvar: DependencyCondvar) -> bool {
         let mut status = self.txn_status[txn_idx].lock();
 
+        // Checked while holding the lock, so that 'halt' wakes the transaction up otherwise.
+        if self.halted() {
+            return false;
+        }
+
         if let TransactionStatus::Executing(incarnation) = *status {
             *status = TransactionStatus::Suspended(incarnation, dep_condvar);
         } else {
             unreachable!();
         }
+        true
     }
 
     /// When a dependency is resolved, mark the transaction as ReadyToExecute with an
@@ -467,6 +569,40 @@ impl Scheduler {
         } else {
             unreachable!();
         }
+
+        // Checked while holding the lock, so that 'halt' wakes the transaction up otherwise.
+        if self.halted() {
+            Self::wake_up(&mut status);
+        }
+    }
+
+    /// Halts the execution of the block: the scheduler is done, i.e. no more tasks are created,
+    /// and the executions waiting on a dependency are woken up to finish, as the dependencies
+    /// may not be resolved anymore.
+    fn halt(&self) {
+        // Set before acquiring the locks on the statuses, see 'suspend' and 'resume'.
+        self.halted.store(true, Ordering::SeqCst);
+        self.done_marker.store(true, Ordering::Release);
+
+        for status in self.txn_status.iter() {
+            Self::wake_up(&mut status.lock());
+        }
+    }
+
+    /// If an execution of the transaction is waiting on a dependency, wakes it up with the
+    /// status set to Executing(incarnation), so that it can finish.
+    fn wake_up(status: &mut TransactionStatus) {
+        let incarnation = match &*status {
+            TransactionStatus::Suspended(incarnation, dep_condvar)
+            | TransactionStatus::ReadyToExecute(incarnation, Some(dep_condvar)) => {
+                let (lock, cvar) = &**dep_condvar;
+                *lock.lock() = true;
+                cvar.notify_one();
+                *incarnation
+            }
+            _ => return,
+        };
+        *status = TransactionStatus::Executing(incarnation);
     }
 
     /// Set status of the transaction to Executed(incarnation).
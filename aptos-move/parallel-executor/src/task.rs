@@ -74,6 +74,12 @@ pub trait ExecutorTask: Sync {
         view: &MVHashMapView<<Self::T as Transaction>::Key, <Self::T as Transaction>::Value>,
         txn: &Self::T,
     ) -> ExecutionStatus<Self::Output, Self::Error>;
+
+    /// Invalidate the modules (code) the executor has cached while executing transactions, as
+    /// they are stale for the next transaction to execute. All modules must be read through the
+    /// view again before being cached, so that the cache is consistent with the versions of the
+    /// modules in the multi-version data-structure.
+    fn flush_module_cache(&self);
 }
 
 /// Trait for execution result of a transaction.
@@ -92,6 +98,9 @@ pub trait TransactionOutput: Send + Sync {
     /// Get the deltas of a transaction from its output.
     fn get_deltas(&self) -> Vec<(<Self::T as Transaction>::Key, DeltaOp)>;
 
+    /// Get the amount of gas used by the transaction, accounted towards the block gas limit.
+    fn gas_used(&self) -> u64;
+
     /// Execution output for transactions that comes after SkipRest signal.
     fn skip_output() -> Self;
 }
@@ -10,14 +10,7 @@ use aptos_aggregator::delta_change_set::DeltaOp;
 use aptos_types::access_path::AccessPath;
 use arc_swap::ArcSwapOption;
 use crossbeam::utils::CachePadded;
-use dashmap::DashSet;
-use std::{
-    collections::HashSet,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-};
+use std::{collections::HashSet, sync::Arc};
 
 type TxnInput<K> = Vec<ReadDescriptor<K>>;
 type TxnOutput<T, E> = ExecutionStatus<T, Error<E>>;
@@ -83,7 +76,7 @@ impl<K: ModulePath> ReadDescriptor<K> {
         }
     }
 
-    fn module_path(&self) -> Option<AccessPath> {
+    pub fn module_path(&self) -> Option<AccessPath> {
         self.access_path.module_path()
     }
 
@@ -122,14 +115,6 @@ pub struct TxnLastInputOutput<K, T, E> {
     inputs: Vec<CachePadded<ArcSwapOption<TxnInput<K>>>>, // txn_idx -> input.
 
     outputs: Vec<CachePadded<ArcSwapOption<TxnOutput<T, E>>>>, // txn_idx -> output.
-
-    // Record all writes and reads to access paths corresponding to modules (code) in any
-    // (speculative) executions. Used to avoid a potential race with module publishing and
-    // Move-VM loader cache - see 'record' function comment for more information.
-    module_writes: DashSet<AccessPath>,
-    module_reads: DashSet<AccessPath>,
-
-    module_read_write_intersection: AtomicBool,
 }
 
 impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K, T, E> {
@@ -141,75 +126,19 @@ impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K,
             outputs: (0..num_txns)
                 .map(|_| CachePadded::new(ArcSwapOption::empty()))
                 .collect(),
-            module_writes: DashSet::new(),
-            module_reads: DashSet::new(),
-            module_read_write_intersection: AtomicBool::new(false),
         }
     }
 
-    fn append_and_check(
-        paths: Vec<AccessPath>,
-        set_to_append: &DashSet<AccessPath>,
-        set_to_check: &DashSet<AccessPath>,
-    ) -> bool {
-        for path in paths {
-            // Standard flags, first show, then look.
-            set_to_append.insert(path.clone());
-
-            if set_to_check.contains(&path) {
-                return true;
-            }
-        }
-        false
-    }
-
-    /// Returns an error if a module path that was read was previously written to, and vice versa.
-    /// Since parallel executor is instantiated per block, any module that is in the Move-VM loader
-    /// cache must previously be read and would be recorded in the 'module_reads' set. Any module
-    /// that is written (published or re-published) goes through transaction output write-set and
-    /// gets recorded in the 'module_writes' set. If these sets have an intersection, it is currently
-    /// possible that Move-VM loader cache loads a module and incorrectly uses it for another
-    /// transaction (e.g. a smaller transaction, or if the speculative execution of the publishing
-    /// transaction later aborts). The intersection is guaranteed to be found because we first
-    /// record the paths then check the other set (flags principle), and in this case we return an
-    /// error that ensures a fallback to a correct sequential execution.
-    /// When the sets do not have an intersection, it is impossible for the race to occur as any
-    /// module in the loader cache may not be published by a transaction in the ongoing block.
     pub fn record(
         &self,
         txn_idx: TxnIndex,
         input: Vec<ReadDescriptor<K>>,
         output: ExecutionStatus<T, Error<E>>,
     ) {
-        let read_modules: Vec<AccessPath> =
-            input.iter().filter_map(|desc| desc.module_path()).collect();
-        let written_modules: Vec<AccessPath> = match &output {
-            ExecutionStatus::Success(output) | ExecutionStatus::SkipRest(output) => output
-                .get_writes()
-                .into_iter()
-                .filter_map(|(k, _)| k.module_path())
-                .collect(),
-            ExecutionStatus::Abort(_) => Vec::new(),
-        };
-
-        if !self.module_read_write_intersection.load(Ordering::Relaxed) {
-            // Check if adding new read & write modules leads to intersections.
-            if Self::append_and_check(read_modules, &self.module_reads, &self.module_writes)
-                || Self::append_and_check(written_modules, &self.module_writes, &self.module_reads)
-            {
-                self.module_read_write_intersection
-                    .store(true, Ordering::Release);
-            }
-        }
-
         self.inputs[txn_idx].store(Some(Arc::new(input)));
         self.outputs[txn_idx].store(Some(Arc::new(output)));
     }
 
-    pub fn module_publishing_may_race(&self) -> bool {
-        self.module_read_write_intersection.load(Ordering::Acquire)
-    }
-
     pub fn read_set(&self, txn_idx: TxnIndex) -> Option<Arc<Vec<ReadDescriptor<K>>>> {
         self.inputs[txn_idx].load_full()
     }
@@ -231,6 +160,17 @@ impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K,
         }
     }
 
+    // Returns the gas used by the transaction according to its recorded output.
+    pub fn gas_used(&self, txn_idx: TxnIndex) -> u64 {
+        match &self.outputs[txn_idx].load_full() {
+            None => 0,
+            Some(txn_output) => match txn_output.as_ref() {
+                ExecutionStatus::Success(t) | ExecutionStatus::SkipRest(t) => t.gas_used(),
+                ExecutionStatus::Abort(_) => 0,
+            },
+        }
+    }
+
     // Must be executed after parallel execution is done, grabs outputs. Will panic if
     // other outstanding references to the recorded outputs exist.
     pub fn take_output(&self, txn_idx: TxnIndex) -> ExecutionStatus<T, Error<E>> {
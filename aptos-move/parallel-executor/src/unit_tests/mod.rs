@@ -3,12 +3,15 @@
 
 use crate::{
     executor::ParallelTransactionExecutor,
-    proptest_types::types::{ExpectedOutput, KeyType, Task, Transaction, ValueType},
+    proptest_types::types::{
+        ExpectedOutput, KeyType, Task, Transaction, TransactionGen, TransactionGenParams, ValueType,
+    },
     scheduler::{Scheduler, SchedulerTask, TaskGuard},
     task::ModulePath,
 };
 use aptos_aggregator::delta_change_set::{delta_add, delta_sub, DeltaOp, DeltaUpdate};
 use aptos_types::write_set::TransactionWrite;
+use proptest::{collection::vec, prelude::*};
 use rand::random;
 use std::{
     fmt::Debug,
@@ -21,11 +24,12 @@ where
     K: PartialOrd + Send + Sync + Clone + Hash + Eq + ModulePath + 'static,
     V: Send + Sync + Debug + Clone + Eq + TransactionWrite + 'static,
 {
-    let output = ParallelTransactionExecutor::<Transaction<K, V>, Task<K, V>>::new(num_cpus::get())
-        .execute_transactions_parallel((), transactions.clone())
-        .map(|(res, _)| res);
+    let output =
+        ParallelTransactionExecutor::<Transaction<K, V>, Task<K, V>>::new(num_cpus::get(), None)
+            .execute_transactions_parallel((), transactions.clone())
+            .map(|(res, _, _)| res);
 
-    let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+    let baseline = ExpectedOutput::generate_baseline(&transactions, None, None);
 
     baseline.assert_output(&output, None);
 }
@@ -271,6 +275,68 @@ fn early_skips() {
     run_and_assert(transactions)
 }
 
+#[test]
+fn module_upgrades() {
+    let mut transactions = vec![];
+    let modules: Vec<_> = (0..10)
+        .map(|_| KeyType(random::<[u8; 32]>(), true))
+        .collect();
+
+    for _ in 0..NUM_BLOCKS {
+        // Transactions loading all modules, which are then cached by the executors.
+        for _ in 0..TXN_PER_BLOCK {
+            transactions.push(Transaction::Write {
+                incarnation: Arc::new(AtomicUsize::new(0)),
+                reads: vec![modules.clone()],
+                writes_and_deltas: vec![(vec![], vec![])],
+            })
+        }
+        // One transaction upgrading a module, which must not be loaded from the caches
+        // by the following transactions anymore.
+        transactions.push(Transaction::Write {
+            incarnation: Arc::new(AtomicUsize::new(0)),
+            reads: vec![vec![]],
+            writes_and_deltas: vec![(
+                vec![(
+                    modules[random::<usize>() % modules.len()],
+                    random_value(false),
+                )],
+                vec![],
+            )],
+        })
+    }
+    run_and_assert(transactions)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+    #[test]
+    fn module_publishing_and_loading(
+        universe in vec(any::<[u8; 32]>(), 50),
+        transaction_gen in vec(any_with::<TransactionGen<[u8; 32]>>(TransactionGenParams::new_dynamic()), 2000).no_shrink(),
+        module_threshold in 1usize..25,
+    ) {
+        let transactions = transaction_gen
+            .into_iter()
+            .map(|txn_gen| txn_gen.materialize_with_modules(&universe, module_threshold))
+            .collect();
+        run_and_assert(transactions);
+    }
+
+    #[test]
+    fn module_publishing_contended(
+        universe in vec(any::<[u8; 32]>(), 5),
+        transaction_gen in vec(any_with::<TransactionGen<[u8; 32]>>(TransactionGenParams::new_dynamic()), 500).no_shrink(),
+    ) {
+        // All accesses are to a few module paths, i.e. modules are continuously re-published.
+        let transactions = transaction_gen
+            .into_iter()
+            .map(|txn_gen| txn_gen.materialize_with_modules(&universe, universe.len()))
+            .collect();
+        run_and_assert(transactions);
+    }
+}
+
 #[test]
 fn scheduler_tasks() {
     let s = Scheduler::new(6);
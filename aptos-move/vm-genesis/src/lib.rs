@@ -248,6 +248,9 @@ fn initialize(
     let gas_schedule_blob = bcs::to_bytes(&genesis_gas_params.to_on_chain_gas_schedule())
         .expect("Failure serializing genesis gas schedule");
 
+    consensus_config
+        .validate()
+        .expect("Invalid genesis consensus config");
     let consensus_config_bytes =
         bcs::to_bytes(&consensus_config).expect("Failure serializing genesis consensus config");
 
@@ -29,8 +29,8 @@ use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
 use backup_service::start_backup_service;
 use clap::Parser;
-use consensus::consensus_provider::start_consensus;
-use consensus_notifications::ConsensusNotificationListener;
+use consensus::consensus_provider::{start_consensus, start_consensus_observer};
+use consensus_notifications::{ConsensusNotificationListener, ConsensusObserverStatus};
 use data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
@@ -43,6 +43,12 @@ use hex::FromHex;
 use mempool_notifications::MempoolNotificationSender;
 use network::application::storage::PeerMetadataStorage;
 use network_builder::builder::NetworkBuilder;
+use peer_monitoring_service_client::{
+    PeerMonitoringServiceClient, PeerMonitoringServiceMultiSender,
+};
+use peer_monitoring_service_server::{
+    network::PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer,
+};
 use rand::{rngs::StdRng, SeedableRng};
 use state_sync_driver::{
     driver_factory::{DriverFactory, StateSyncRuntimes},
@@ -154,8 +160,10 @@ pub struct AptosHandle {
     _api: Runtime,
     _backup: Runtime,
     _consensus_runtime: Option<Runtime>,
+    _consensus_observer_runtime: Option<Runtime>,
     _mempool: Runtime,
     _network_runtimes: Vec<Runtime>,
+    _peer_monitoring_service_runtime: Runtime,
     _fh_stream: Option<Runtime>,
     _state_sync_runtimes: StateSyncRuntimes,
     _telemetry_runtime: Option<Runtime>,
@@ -344,6 +352,7 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
     peer_metadata_storage: Arc<PeerMetadataStorage>,
     mempool_notifier: M,
     consensus_listener: ConsensusNotificationListener,
+    consensus_observer_status: ConsensusObserverStatus,
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
@@ -384,6 +393,7 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
         mempool_notifier,
         metadata_storage,
         consensus_listener,
+        consensus_observer_status,
         event_subscription_service,
         aptos_data_client,
         streaming_service_client,
@@ -481,14 +491,84 @@ fn setup_state_sync_storage_service(
     Ok(storage_service_runtime)
 }
 
+fn setup_peer_monitoring_service(
+    node_config: &NodeConfig,
+    server_network_handles: Vec<(NetworkId, PeerMonitoringServiceNetworkEvents)>,
+    client_network_handles: HashMap<
+        NetworkId,
+        peer_monitoring_service_client::PeerMonitoringServiceNetworkSender,
+    >,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+) -> anyhow::Result<Runtime> {
+    let peer_monitoring_service_runtime = Builder::new_multi_thread()
+        .thread_name("peer-monitoring-service")
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to start peer monitoring service runtime: {}", err))?;
+
+    // Start a peer monitoring server for each network
+    let config = node_config.peer_monitoring_service.clone();
+    for (network_id, events) in server_network_handles {
+        let service = PeerMonitoringServiceServer::new(
+            config.clone(),
+            peer_monitoring_service_runtime.handle().clone(),
+            network_id,
+            events,
+            peer_metadata_storage.clone(),
+        );
+        peer_monitoring_service_runtime.spawn(service.start());
+    }
+
+    // Start the peer monitor that periodically monitors all connected peers
+    if config.enable_peer_monitoring_client {
+        let client = PeerMonitoringServiceClient::new(
+            PeerMonitoringServiceMultiSender::new(client_network_handles),
+            peer_metadata_storage,
+        );
+        peer_monitoring_service_runtime.spawn(peer_monitoring_service_client::start_peer_monitor(
+            config,
+            client,
+            TimeService::real(),
+        ));
+    }
+
+    Ok(peer_monitoring_service_runtime)
+}
+
 pub fn setup_environment(
     node_config: NodeConfig,
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
 ) -> anyhow::Result<AptosHandle> {
+    // Gather all network configs into a single vector.
+    let mut network_configs: Vec<&NetworkConfig> = node_config.full_node_networks.iter().collect();
+    if let Some(network_config) = node_config.validator_network.as_ref() {
+        network_configs.push(network_config);
+    }
+
+    // Collect the network ids and create the peer metadata storage shared across all networks
+    let mut network_ids = HashSet::new();
+    network_configs.iter().for_each(|config| {
+        let network_id = config.network_id;
+        // Guarantee there is only one of this network
+        if network_ids.contains(&network_id) {
+            panic!(
+                "Duplicate NetworkId: '{}'.  Can't start node with duplicate networks",
+                network_id
+            );
+        }
+        network_ids.insert(network_id);
+    });
+    let network_ids: Vec<_> = network_ids.into_iter().collect();
+    let peer_metadata_storage = PeerMetadataStorage::new(&network_ids);
+
     // Start the node inspection service
     let node_config_clone = node_config.clone();
+    let peer_metadata_storage_clone = peer_metadata_storage.clone();
     thread::spawn(move || {
-        inspection_service::inspection_service::start_inspection_service(node_config_clone)
+        inspection_service::inspection_service::start_inspection_service(
+            node_config_clone,
+            peer_metadata_storage_clone,
+        )
     });
 
     // Open the database
@@ -532,8 +612,12 @@ pub fn setup_environment(
     let mut network_runtimes = vec![];
     let mut mempool_network_handles = vec![];
     let mut consensus_network_handles = None;
+    let mut consensus_publisher_network_sender = None;
+    let mut consensus_observer_network_events = None;
     let mut storage_service_server_network_handles = vec![];
     let mut storage_service_client_network_handles = HashMap::new();
+    let mut peer_monitoring_service_server_network_handles = vec![];
+    let mut peer_monitoring_service_client_network_handles = HashMap::new();
 
     // Create an event subscription service so that components can be notified of events and reconfigs
     let mut event_subscription_service = EventSubscriptionService::new(
@@ -550,28 +634,15 @@ pub fn setup_environment(
         None
     };
 
-    // Gather all network configs into a single vector.
-    let mut network_configs: Vec<&NetworkConfig> = node_config.full_node_networks.iter().collect();
-    if let Some(network_config) = node_config.validator_network.as_ref() {
-        network_configs.push(network_config);
-    }
+    // Create a consensus observer subscription for reconfiguration events (if the observer is enabled).
+    let consensus_observer_reconfig_subscription =
+        if node_config.consensus_observer.observer_enabled {
+            Some(event_subscription_service.subscribe_to_reconfigurations()?)
+        } else {
+            None
+        };
 
     // Instantiate every network and collect the requisite endpoints for state_sync, mempool, and consensus.
-    let mut network_ids = HashSet::new();
-    network_configs.iter().for_each(|config| {
-        let network_id = config.network_id;
-        // Guarantee there is only one of this network
-        if network_ids.contains(&network_id) {
-            panic!(
-                "Duplicate NetworkId: '{}'.  Can't start node with duplicate networks",
-                network_id
-            );
-        }
-        network_ids.insert(network_id);
-    });
-    let network_ids: Vec<_> = network_ids.into_iter().collect();
-
-    let peer_metadata_storage = PeerMetadataStorage::new(&network_ids);
     for network_config in network_configs.into_iter() {
         debug!("Creating runtime for {}", network_config.network_id);
         let mut runtime_builder = Builder::new_multi_thread();
@@ -618,6 +689,19 @@ pub fn setup_environment(
             network_builder.add_client(&storage_service_client::network_endpoint_config());
         storage_service_client_network_handles.insert(network_id, storage_service_sender);
 
+        // Register the peer monitoring service (both client and server) with Network
+        let peer_monitoring_service_events = network_builder.add_service(
+            &peer_monitoring_service_server::network::network_endpoint_config(
+                node_config.peer_monitoring_service.clone(),
+            ),
+        );
+        peer_monitoring_service_server_network_handles
+            .push((network_id, peer_monitoring_service_events));
+        let peer_monitoring_service_sender =
+            network_builder.add_client(&peer_monitoring_service_client::network_endpoint_config());
+        peer_monitoring_service_client_network_handles
+            .insert(network_id, peer_monitoring_service_sender);
+
         // Create the endpoints to connect the Network to mempool.
         let (mempool_sender, mempool_events) = network_builder.add_p2p_service(
             &aptos_mempool::network::network_endpoint_config(MEMPOOL_NETWORK_CHANNEL_BUFFER_SIZE),
@@ -638,6 +722,26 @@ pub fn setup_environment(
             );
         }
 
+        // Register the consensus publisher (on validators) or the consensus observer
+        // (on validator fullnodes) with the VFN network. The validator pushes blocks to
+        // every VFN that supports the observer protocol.
+        if network_id == NetworkId::Vfn {
+            let consensus_observer_config = node_config.consensus_observer;
+            if node_config.base.role.is_validator() && consensus_observer_config.publisher_enabled {
+                consensus_publisher_network_sender = Some(network_builder.add_client(
+                    &consensus::consensus_observer::network::publisher_network_endpoint_config(),
+                ));
+            } else if !node_config.base.role.is_validator()
+                && consensus_observer_config.observer_enabled
+            {
+                consensus_observer_network_events = Some(network_builder.add_service(
+                    &consensus::consensus_observer::network::observer_network_endpoint_config(
+                        consensus_observer_config,
+                    ),
+                ));
+            }
+        }
+
         let network_context = network_builder.network_context();
         network_builder.build(runtime.handle().clone());
         network_builder.start();
@@ -648,6 +752,14 @@ pub fn setup_environment(
     // TODO set up on-chain discovery network based on UpstreamConfig.fallback_network
     // and pass network handles to mempool/state sync
 
+    // Start the peer monitoring service
+    let peer_monitoring_service_runtime = setup_peer_monitoring_service(
+        &node_config,
+        peer_monitoring_service_server_network_handles,
+        peer_monitoring_service_client_network_handles,
+        peer_metadata_storage.clone(),
+    )?;
+
     // For state sync to send notifications to mempool and receive notifications from consensus.
     let (mempool_notifier, mempool_listener) =
         mempool_notifications::new_mempool_notifier_listener_pair();
@@ -659,6 +771,9 @@ pub fn setup_environment(
                 .commit_notification_timeout_ms,
         );
 
+    // For the consensus observer (if enabled) to take over execution from state sync.
+    let consensus_observer_status = ConsensusObserverStatus::new();
+
     // Create the state sync runtimes
     let state_sync_runtimes = create_state_sync_runtimes(
         &node_config,
@@ -667,6 +782,7 @@ pub fn setup_environment(
         peer_metadata_storage.clone(),
         mempool_notifier,
         consensus_listener,
+        consensus_observer_status.clone(),
         genesis_waypoint,
         event_subscription_service,
         db_rw.clone(),
@@ -686,6 +802,7 @@ pub fn setup_environment(
     };
 
     let mut consensus_runtime = None;
+    let mut consensus_observer_runtime = None;
     let (consensus_to_mempool_sender, consensus_to_mempool_receiver) =
         mpsc::channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
 
@@ -702,10 +819,6 @@ pub fn setup_environment(
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
-    assert!(
-        !node_config.consensus.use_quorum_store,
-        "QuorumStore is not yet implemented"
-    );
     assert_ne!(
         node_config.consensus.use_quorum_store,
         node_config.mempool.shared_mempool_validator_broadcast,
@@ -736,8 +849,30 @@ pub fn setup_environment(
             consensus_reconfig_subscription
                 .expect("Consensus requires a reconfiguration subscription!"),
             peer_metadata_storage,
+            consensus_publisher_network_sender,
         ));
         debug!("Consensus started in {} ms", instant.elapsed().as_millis());
+    } else if let Some(consensus_observer_network_events) = consensus_observer_network_events {
+        // The consensus observer executes the blocks on top of the synced state
+        debug!("Wait until state sync is initialized");
+        state_sync_runtimes.block_until_initialized();
+        debug!("State sync initialization complete.");
+
+        // Initialize and start the consensus observer.
+        instant = Instant::now();
+        consensus_observer_runtime = Some(start_consensus_observer(
+            &node_config,
+            consensus_observer_network_events,
+            Arc::new(consensus_notifier),
+            consensus_observer_status,
+            db_rw,
+            consensus_observer_reconfig_subscription
+                .expect("The consensus observer requires a reconfiguration subscription!"),
+        ));
+        debug!(
+            "Consensus observer started in {} ms",
+            instant.elapsed().as_millis()
+        );
     }
 
     let build_info = build_information!();
@@ -753,8 +888,10 @@ pub fn setup_environment(
         _api: api_runtime,
         _backup: backup_service,
         _consensus_runtime: consensus_runtime,
+        _consensus_observer_runtime: consensus_observer_runtime,
         _mempool: mempool,
         _network_runtimes: network_runtimes,
+        _peer_monitoring_service_runtime: peer_monitoring_service_runtime,
         _fh_stream: sf_runtime,
         _state_sync_runtimes: state_sync_runtimes,
         _telemetry_runtime: telemetry_runtime,
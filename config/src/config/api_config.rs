@@ -30,11 +30,22 @@ pub struct ApiConfig {
     pub transaction_submission_enabled: bool,
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    #[serde(default = "default_max_submit_transaction_batch_size")]
+    pub max_submit_transaction_batch_size: usize,
+    #[serde(default = "default_enabled")]
+    pub view_function_enabled: bool,
+    #[serde(default = "default_max_view_gas")]
+    pub max_view_gas: u64,
+    #[serde(default = "default_event_stream_poll_interval_ms")]
+    pub event_stream_poll_interval_ms: u64,
 }
 
 pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
 pub const DEFAULT_PORT: u16 = 8080;
 pub const DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT: u64 = 8 * 1024 * 1024; // 8 MB
+pub const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000;
+pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 100;
+pub const DEFAULT_EVENT_STREAM_POLL_INTERVAL_MS: u64 = 500;
 
 fn default_enabled() -> bool {
     true
@@ -44,6 +55,18 @@ fn default_disabled() -> bool {
     false
 }
 
+fn default_max_view_gas() -> u64 {
+    DEFAULT_MAX_VIEW_GAS
+}
+
+fn default_max_submit_transaction_batch_size() -> usize {
+    DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE
+}
+
+fn default_event_stream_poll_interval_ms() -> u64 {
+    DEFAULT_EVENT_STREAM_POLL_INTERVAL_MS
+}
+
 impl Default for ApiConfig {
     fn default() -> ApiConfig {
         ApiConfig {
@@ -60,6 +83,10 @@ impl Default for ApiConfig {
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            max_submit_transaction_batch_size: default_max_submit_transaction_batch_size(),
+            view_function_enabled: default_enabled(),
+            max_view_gas: default_max_view_gas(),
+            event_stream_poll_interval_ms: default_event_stream_poll_interval_ms(),
         }
     }
 }
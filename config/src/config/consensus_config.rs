@@ -28,6 +28,14 @@ pub struct ConsensusConfig {
     // the period = (poll_count - 1) * 30ms
     pub quorum_store_poll_count: u64,
     pub intra_consensus_channel_buffer_size: usize,
+    // How often the quorum store pulls a new batch from mempool (in milliseconds)
+    pub quorum_store_batch_interval_ms: u64,
+    pub quorum_store_max_batch_txns: u64,
+    pub quorum_store_max_batch_bytes: u64,
+    // How long a batch stays available to be proposed and executed after it is created (in milliseconds)
+    pub quorum_store_batch_expiry_ms: u64,
+    // Timeout for fetching a missing batch from one of its signers (in milliseconds)
+    pub quorum_store_batch_request_timeout_ms: u64,
 }
 
 impl Default for ConsensusConfig {
@@ -47,6 +55,11 @@ impl Default for ConsensusConfig {
             quorum_store_pull_timeout_ms: 1000,
             quorum_store_poll_count: 20,
             intra_consensus_channel_buffer_size: 10,
+            quorum_store_batch_interval_ms: 50,
+            quorum_store_max_batch_txns: 500,
+            quorum_store_max_batch_bytes: 1024 * 1024, // 1MB
+            quorum_store_batch_expiry_ms: 20_000,
+            quorum_store_batch_request_timeout_ms: 1000,
         }
     }
 }
@@ -57,6 +70,28 @@ impl ConsensusConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusObserverConfig {
+    pub publisher_enabled: bool, // Whether validators push ordered blocks and commits to subscribed VFNs
+    pub observer_enabled: bool,  // Whether VFNs subscribe to consensus updates from their validator
+    pub max_network_channel_size: u64, // Max num of pending network messages
+    pub max_pending_blocks: u64, // Max num of executed blocks pending commit before falling back to state sync
+    pub observer_fallback_timeout_ms: u64, // Max time (ms) without commits before state sync resumes
+}
+
+impl Default for ConsensusObserverConfig {
+    fn default() -> Self {
+        Self {
+            publisher_enabled: false,
+            observer_enabled: false,
+            max_network_channel_size: 1000,
+            max_pending_blocks: 100,
+            observer_fallback_timeout_ms: 10_000,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
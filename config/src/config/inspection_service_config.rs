@@ -10,6 +10,7 @@ pub struct InspectionServiceConfig {
     pub address: String,
     pub port: u16,
     pub expose_configuration: bool,
+    pub expose_peer_information: bool,
     pub expose_system_information: bool,
 }
 
@@ -19,6 +20,7 @@ impl Default for InspectionServiceConfig {
             address: "0.0.0.0".to_string(),
             port: 9101,
             expose_configuration: false,
+            expose_peer_information: false,
             expose_system_information: true,
         }
     }
@@ -22,6 +22,9 @@ pub struct MempoolConfig {
     pub system_transaction_timeout_secs: u64,
     pub system_transaction_gc_interval_ms: u64,
     pub shared_mempool_validator_broadcast: bool,
+    // minimum gas price increase, in percent, for a transaction to replace the one in mempool
+    // with the same sender and sequence number
+    pub replacement_gas_price_bump_percentage: u64,
 }
 
 impl Default for MempoolConfig {
@@ -41,6 +44,7 @@ impl Default for MempoolConfig {
             system_transaction_timeout_secs: 600,
             system_transaction_gc_interval_ms: 60_000,
             shared_mempool_validator_broadcast: true,
+            replacement_gas_price_bump_percentage: 10,
         }
     }
 }
@@ -27,6 +27,7 @@ use std::{
     string::ToString,
     time::Duration,
 };
+use url::Url;
 
 // TODO: We could possibly move these constants somewhere else, but since they are defaults for the
 //   configurations of the system, we'll leave it here for now.
@@ -212,6 +213,7 @@ impl NetworkConfig {
                 .ok_or_else(|| Error::InvariantViolation("No local IP".to_string()))?;
         }
 
+        self.verify_discovery_methods()?;
         self.prepare_identity();
         Ok(())
     }
@@ -292,6 +294,20 @@ impl NetworkConfig {
         )
     }
 
+    // Verifies that REST discovery only fetches peer sets over https, as the
+    // fetched peers are dialed as seeds
+    pub fn verify_discovery_methods(&self) -> Result<(), Error> {
+        for method in std::iter::once(&self.discovery_method).chain(self.discovery_methods.iter()) {
+            if let DiscoveryMethod::Rest(url, _) = method {
+                crate::config::invariant(
+                    url.scheme() == "https",
+                    format!("REST discovery URL must use https: '{}'", url),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     // Verifies both the `seed_addrs` and `seeds` before they're merged
     pub fn verify_seeds(&self) -> Result<(), Error> {
         for (peer_id, addrs) in self.seed_addrs.iter() {
@@ -318,15 +334,23 @@ impl NetworkConfig {
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct PeerMonitoringServiceConfig {
-    pub max_concurrent_requests: u64, // Max num of concurrent server tasks
-    pub max_network_channel_size: u64, // Max num of pending network messages
+    pub enable_peer_monitoring_client: bool, // Whether or not to run the client-side peer monitor
+    pub max_concurrent_requests: u64,        // Max num of concurrent server tasks
+    pub max_network_channel_size: u64,       // Max num of pending network messages
+    pub max_num_known_peers: u64,            // Max num of known peers sent in a response
+    pub peer_monitor_interval_ms: u64, // Interval (in milliseconds) between peer monitoring rounds
+    pub peer_monitor_request_timeout_ms: u64, // Timeout (in milliseconds) for monitoring requests
 }
 
 impl Default for PeerMonitoringServiceConfig {
     fn default() -> Self {
         Self {
+            enable_peer_monitoring_client: true,
             max_concurrent_requests: 1000,
             max_network_channel_size: 1000,
+            max_num_known_peers: 100,
+            peer_monitor_interval_ms: 5000,
+            peer_monitor_request_timeout_ms: 5000,
         }
     }
 }
@@ -336,6 +360,8 @@ impl Default for PeerMonitoringServiceConfig {
 pub enum DiscoveryMethod {
     Onchain,
     File(PathBuf, Duration),
+    // Periodically fetches a `PeerSet` from the given https URL
+    Rest(Url, Duration),
     None,
 }
 
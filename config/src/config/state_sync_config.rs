@@ -164,6 +164,7 @@ impl Default for DataStreamingServiceConfig {
 #[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct AptosDataClientConfig {
+    pub latency_weighted_peer_selection: bool, // Whether to weight peer selection by request latency
     pub max_num_in_flight_priority_polls: u64, // Max num of in-flight polls for priority peers
     pub max_num_in_flight_regular_polls: u64,  // Max num of in-flight polls for regular peers
     pub response_timeout_ms: u64, // Timeout (in milliseconds) when waiting for a response
@@ -174,6 +175,7 @@ pub struct AptosDataClientConfig {
 impl Default for AptosDataClientConfig {
     fn default() -> Self {
         Self {
+            latency_weighted_peer_selection: true,
             max_num_in_flight_priority_polls: 10,
             max_num_in_flight_regular_polls: 10,
             response_timeout_ms: 5000,
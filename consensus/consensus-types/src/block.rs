@@ -15,7 +15,7 @@ use aptos_types::{
     block_metadata::BlockMetadata,
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
-    transaction::{Transaction, Version},
+    transaction::{SignedTransaction, Transaction, Version},
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
@@ -243,6 +243,11 @@ impl Block {
                     .as_ref()
                     .ok_or_else(|| format_err!("Missing signature in Proposal"))?;
                 validator.verify(*author, &self.block_data, signature)?;
+                if let Some(Payload::InQuorumStore(proofs)) = self.payload() {
+                    for proof in proofs {
+                        proof.verify(validator)?;
+                    }
+                }
                 self.quorum_cert().verify(validator)
             }
         }
@@ -270,6 +275,20 @@ impl Block {
                 "Reconfiguration suffix should not carry payload"
             );
         }
+        if let Some(Payload::InQuorumStore(proofs)) = self.payload() {
+            for proof in proofs {
+                ensure!(
+                    proof.epoch() == self.epoch(),
+                    "{} is not from the block's epoch",
+                    proof
+                );
+                ensure!(
+                    proof.expiration_usecs() > self.timestamp_usecs(),
+                    "{} expired before the block's timestamp",
+                    proof
+                );
+            }
+        }
         if let Some(failed_authors) = self.block_data().failed_authors() {
             // when validating for being well formed,
             // allow for missing failed authors,
@@ -329,17 +348,16 @@ impl Block {
         Ok(())
     }
 
-    pub fn transactions_to_execute(&self, validators: &[AccountAddress]) -> Vec<Transaction> {
+    /// The transactions to execute for this block, given the user transactions of its payload.
+    pub fn transactions_to_execute(
+        &self,
+        validators: &[AccountAddress],
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Transaction> {
         once(Transaction::BlockMetadata(
             self.new_block_metadata(validators),
         ))
-        .chain(
-            self.payload()
-                .unwrap_or(&Payload::empty())
-                .clone()
-                .into_iter()
-                .map(Transaction::UserTransaction),
-        )
+        .chain(txns.into_iter().map(Transaction::UserTransaction))
         .chain(once(Transaction::StateCheckpoint(self.id)))
         .collect()
     }
@@ -1,9 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::proof_of_store::ProofOfStore;
+use aptos_crypto::HashValue;
 use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
 use serde::{Deserialize, Serialize};
-use std::{fmt, fmt::Write};
+use std::{collections::HashSet, fmt, fmt::Write};
 
 /// The round of a block is a consensus-internal counter, which starts with 0 and increases
 /// monotonically. It is used for the protocol safety and liveness (please see the detailed
@@ -36,30 +38,21 @@ impl Payload {
         Payload::DirectMempool(Vec::new())
     }
 
+    /// The number of transactions in the payload. For a quorum store payload these are the
+    /// transactions of the batches its proofs refer to.
     pub fn len(&self) -> usize {
         match self {
             Payload::DirectMempool(txns) => txns.len(),
-            Payload::InQuorumStore(_poavs) => todo!(),
+            Payload::InQuorumStore(proofs) => {
+                proofs.iter().map(|proof| proof.num_txns() as usize).sum()
+            }
         }
     }
 
     pub fn is_empty(&self) -> bool {
         match self {
             Payload::DirectMempool(txns) => txns.is_empty(),
-            Payload::InQuorumStore(_poavs) => todo!(),
-        }
-    }
-}
-
-// TODO: What I really want is an iterator that isn't necessarily a vector (e.g., read lazily from RocksDB). This doesn't seem like the way.
-impl IntoIterator for Payload {
-    type Item = SignedTransaction;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        match self {
-            Payload::DirectMempool(txns) => txns.into_iter(),
-            Payload::InQuorumStore(_poavs) => todo!(),
+            Payload::InQuorumStore(proofs) => proofs.is_empty(),
         }
     }
 }
@@ -70,7 +63,9 @@ impl fmt::Display for Payload {
             Payload::DirectMempool(txns) => {
                 write!(f, "InMemory txns: {}", txns.len())
             }
-            Payload::InQuorumStore(_poavs) => todo!(),
+            Payload::InQuorumStore(proofs) => {
+                write!(f, "InQuorumStore proofs: {}", proofs.len())
+            }
         }
     }
 }
@@ -79,31 +74,38 @@ impl fmt::Display for Payload {
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub enum PayloadFilter {
     DirectMempool(Vec<TransactionSummary>),
-    InQuorumStore(Vec<ProofOfStore>),
+    // Digests of the batches whose proofs are already in pending blocks.
+    InQuorumStore(HashSet<HashValue>),
 }
 
 impl From<&Vec<&Payload>> for PayloadFilter {
     fn from(exclude_payloads: &Vec<&Payload>) -> Self {
-        if exclude_payloads.is_empty() {
-            return PayloadFilter::DirectMempool(vec![]);
+        // Reconfiguration suffix blocks always carry an empty direct mempool payload, so a
+        // quorum store filter is built as soon as any of the payloads comes from quorum store.
+        if exclude_payloads
+            .iter()
+            .any(|payload| matches!(payload, Payload::InQuorumStore(_)))
+        {
+            let mut exclude_digests = HashSet::new();
+            for payload in exclude_payloads {
+                if let Payload::InQuorumStore(proofs) = payload {
+                    exclude_digests.extend(proofs.iter().map(|proof| *proof.digest()));
+                }
+            }
+            return PayloadFilter::InQuorumStore(exclude_digests);
         }
-        match exclude_payloads.first().unwrap() {
-            Payload::DirectMempool(_) => {
-                let mut exclude_txns = vec![];
-                for payload in exclude_payloads {
-                    if let Payload::DirectMempool(txns) = payload {
-                        for txn in txns {
-                            exclude_txns.push(TransactionSummary {
-                                sender: txn.sender(),
-                                sequence_number: txn.sequence_number(),
-                            });
-                        }
-                    }
+        let mut exclude_txns = vec![];
+        for payload in exclude_payloads {
+            if let Payload::DirectMempool(txns) = payload {
+                for txn in txns {
+                    exclude_txns.push(TransactionSummary {
+                        sender: txn.sender(),
+                        sequence_number: txn.sequence_number(),
+                    });
                 }
-                PayloadFilter::DirectMempool(exclude_txns)
             }
-            Payload::InQuorumStore(_) => todo!(),
         }
+        PayloadFilter::DirectMempool(exclude_txns)
     }
 }
 
@@ -117,13 +119,13 @@ impl fmt::Display for PayloadFilter {
                 }
                 write!(f, "{}", txns_str)
             }
-            PayloadFilter::InQuorumStore(_poavs) => todo!(),
+            PayloadFilter::InQuorumStore(excluded_digests) => {
+                let mut digests_str = "".to_string();
+                for digest in excluded_digests.iter() {
+                    write!(digests_str, "{} ", digest)?;
+                }
+                write!(f, "{}", digests_str)
+            }
         }
     }
 }
-
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct ProofOfStore {
-    // TODO: This is currently just a placeholder for a real ProofOfStore implementation.
-    placeholder: u64,
-}
@@ -0,0 +1,171 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::Author;
+use anyhow::{ensure, Context};
+use aptos_crypto::{bls12381, HashValue};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_types::{
+    aggregate_signature::AggregateSignature, validator_signer::ValidatorSigner,
+    validator_verifier::ValidatorVerifier,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The information about a batch of transactions that validators sign to attest that they have
+/// persisted the batch and will serve it until it expires.
+#[derive(
+    Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash, CryptoHasher, BCSCryptoHash,
+)]
+pub struct SignedDigestInfo {
+    pub epoch: u64,
+    pub digest: HashValue,
+    // Timestamp in microseconds after which the batch may be garbage collected.
+    pub expiration_usecs: u64,
+    pub num_txns: u64,
+    pub num_bytes: u64,
+}
+
+impl SignedDigestInfo {
+    pub fn new(
+        epoch: u64,
+        digest: HashValue,
+        expiration_usecs: u64,
+        num_txns: u64,
+        num_bytes: u64,
+    ) -> Self {
+        Self {
+            epoch,
+            digest,
+            expiration_usecs,
+            num_txns,
+            num_bytes,
+        }
+    }
+}
+
+impl Display for SignedDigestInfo {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[digest: {}, epoch: {}, expiration: {}, txns: {}, bytes: {}]",
+            self.digest, self.epoch, self.expiration_usecs, self.num_txns, self.num_bytes
+        )
+    }
+}
+
+/// A single validator's signature on a `SignedDigestInfo`, sent back to the author of the batch.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SignedDigest {
+    signer: Author,
+    info: SignedDigestInfo,
+    signature: bls12381::Signature,
+}
+
+impl SignedDigest {
+    pub fn new(info: SignedDigestInfo, validator_signer: &ValidatorSigner) -> Self {
+        let signature = validator_signer.sign(&info);
+        Self {
+            signer: validator_signer.author(),
+            info,
+            signature,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.info.epoch
+    }
+
+    pub fn signer(&self) -> Author {
+        self.signer
+    }
+
+    pub fn info(&self) -> &SignedDigestInfo {
+        &self.info
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.info.digest
+    }
+
+    pub fn signature(&self) -> &bls12381::Signature {
+        &self.signature
+    }
+
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        validator
+            .verify(self.signer, &self.info, &self.signature)
+            .context("Failed to verify SignedDigest")
+    }
+}
+
+/// A proof that a quorum of validators persisted a batch: proposals carry these instead of the
+/// transactions themselves.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProofOfStore {
+    info: SignedDigestInfo,
+    multi_signature: AggregateSignature,
+}
+
+impl ProofOfStore {
+    pub fn new(info: SignedDigestInfo, multi_signature: AggregateSignature) -> Self {
+        Self {
+            info,
+            multi_signature,
+        }
+    }
+
+    pub fn info(&self) -> &SignedDigestInfo {
+        &self.info
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.info.epoch
+    }
+
+    pub fn digest(&self) -> &HashValue {
+        &self.info.digest
+    }
+
+    pub fn expiration_usecs(&self) -> u64 {
+        self.info.expiration_usecs
+    }
+
+    pub fn num_txns(&self) -> u64 {
+        self.info.num_txns
+    }
+
+    pub fn num_bytes(&self) -> u64 {
+        self.info.num_bytes
+    }
+
+    pub fn multi_signature(&self) -> &AggregateSignature {
+        &self.multi_signature
+    }
+
+    /// The validators that signed the proof, i.e. the ones that can serve the batch.
+    pub fn signers(&self, validator: &ValidatorVerifier) -> Vec<Author> {
+        self.multi_signature.get_voter_addresses(
+            &validator
+                .get_ordered_account_addresses_iter()
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            self.multi_signature.get_num_voters() > 0,
+            "ProofOfStore {} has no signatures",
+            self.info
+        );
+        validator
+            .verify_multi_signatures(&self.info, &self.multi_signature)
+            .context("Failed to verify ProofOfStore")
+    }
+}
+
+impl Display for ProofOfStore {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "ProofOfStore {}", self.info)
+    }
+}
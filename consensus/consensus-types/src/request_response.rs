@@ -3,6 +3,7 @@
 
 use crate::common::{Payload, PayloadFilter, Round};
 use anyhow::Result;
+use aptos_crypto::HashValue;
 use futures::channel::oneshot;
 use std::{fmt, fmt::Formatter};
 
@@ -25,6 +26,10 @@ pub enum ConsensusRequest {
         u64,
         // round
         Round,
+        // timestamp of the last committed block (in microseconds)
+        u64,
+        // digests of the batches committed by the blocks
+        Vec<HashValue>,
         // callback to respond to
         oneshot::Sender<Result<ConsensusResponse>>,
     ),
@@ -40,8 +45,15 @@ impl fmt::Display for ConsensusRequest {
                     max_txns, max_bytes, excluded
                 )
             }
-            ConsensusRequest::CleanRequest(epoch, round, _) => {
-                write!(f, "CleanRequest [epoch: {}, round: {}]", epoch, round)
+            ConsensusRequest::CleanRequest(epoch, round, timestamp_usecs, digests, _) => {
+                write!(
+                    f,
+                    "CleanRequest [epoch: {}, round: {}, timestamp: {}, batches: {}]",
+                    epoch,
+                    round,
+                    timestamp_usecs,
+                    digests.len()
+                )
             }
         }
     }
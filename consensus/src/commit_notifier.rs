@@ -4,6 +4,7 @@
 use crate::error::QuorumStoreError;
 use crate::monitor;
 use anyhow::{format_err, Result};
+use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
 use consensus_types::{common::Round, request_response::ConsensusRequest};
 use futures::channel::{mpsc, mpsc::Sender, oneshot};
@@ -13,8 +14,15 @@ use tokio::time::timeout;
 /// Notification of execution committed logical time for QuorumStore to clean.
 #[async_trait::async_trait]
 pub trait CommitNotifier: Send + Sync {
-    /// Notification of committed logical time
-    async fn notify_commit(&self, epoch: u64, round: Round) -> Result<(), QuorumStoreError>;
+    /// Notification of committed logical time, along with the timestamp of the last committed
+    /// block and the digests of the quorum store batches the blocks committed.
+    async fn notify_commit(
+        &self,
+        epoch: u64,
+        round: Round,
+        timestamp_usecs: u64,
+        committed_digests: Vec<HashValue>,
+    ) -> Result<(), QuorumStoreError>;
 
     fn new_epoch(&self, quorum_store_commit_sender: mpsc::Sender<ConsensusRequest>);
 }
@@ -39,9 +47,21 @@ impl QuorumStoreCommitNotifier {
 
 #[async_trait::async_trait]
 impl CommitNotifier for QuorumStoreCommitNotifier {
-    async fn notify_commit(&self, epoch: u64, round: Round) -> Result<(), QuorumStoreError> {
+    async fn notify_commit(
+        &self,
+        epoch: u64,
+        round: Round,
+        timestamp_usecs: u64,
+        committed_digests: Vec<HashValue>,
+    ) -> Result<(), QuorumStoreError> {
         let (callback, callback_rcv) = oneshot::channel();
-        let req = ConsensusRequest::CleanRequest(epoch, round, callback);
+        let req = ConsensusRequest::CleanRequest(
+            epoch,
+            round,
+            timestamp_usecs,
+            committed_digests,
+            callback,
+        );
 
         self.quorum_store_commit_sender
             .lock()
@@ -0,0 +1,8 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod network;
+pub(crate) mod observer;
+#[cfg(test)]
+mod observer_test;
+pub(crate) mod publisher;
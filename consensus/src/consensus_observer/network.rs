@@ -0,0 +1,72 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Interface between the consensus publisher (on validators), the consensus
+//! observer (on validator fullnodes) and the network layer.
+
+use crate::counters;
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use channel::{aptos_channel, message_queues::QueueStyle};
+use consensus_types::block::Block;
+use network::{
+    protocols::network::{AppConfig, NetworkEvents, NetworkSender},
+    ProtocolId,
+};
+use serde::{Deserialize, Serialize};
+
+/// Network type for the consensus observer
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ConsensusObserverMessage {
+    /// A chain of blocks ordered by consensus, along with the proof of their order.
+    OrderedBlock(OrderedBlock),
+    /// A ledger info carrying the execution result of the committed block and the signatures of
+    /// no fewer than 2f + 1 validators. All ordered blocks up to the committed block can then be
+    /// committed by the observer.
+    CommitDecision(LedgerInfoWithSignatures),
+}
+
+impl ConsensusObserverMessage {
+    /// Returns a summary label for the message
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ConsensusObserverMessage::OrderedBlock(_) => "ordered_block",
+            ConsensusObserverMessage::CommitDecision(_) => "commit_decision",
+        }
+    }
+}
+
+/// A chain of ordered blocks that is ready to be (speculatively) executed
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderedBlock {
+    /// The ordered blocks, from the oldest to the newest. Each block extends the previous one.
+    pub blocks: Vec<Block>,
+    /// The ledger info that certifies the order of the last block (and all its ancestors).
+    pub ordered_proof: LedgerInfoWithSignatures,
+}
+
+/// The interface from the consensus publisher to the network layer
+pub type ConsensusObserverNetworkSender = NetworkSender<ConsensusObserverMessage>;
+
+/// The interface from the network layer to the consensus observer
+pub type ConsensusObserverNetworkEvents = NetworkEvents<ConsensusObserverMessage>;
+
+/// Supported protocols in preferred order (from highest priority to lowest).
+pub const DIRECT_SEND: &[ProtocolId] = &[ProtocolId::ConsensusObserverDirectSend];
+
+/// Configuration for the network endpoints of the consensus publisher. Validators only
+/// push messages to subscribed fullnodes, so the publisher is a client-only application.
+pub fn publisher_network_endpoint_config() -> AppConfig {
+    AppConfig::client(DIRECT_SEND.iter().copied())
+}
+
+/// Configuration for the network endpoints of the consensus observer. Registering the
+/// service advertises the protocol to the upstream validator, which subscribes the node.
+pub fn observer_network_endpoint_config(config: ConsensusObserverConfig) -> AppConfig {
+    AppConfig::service(
+        DIRECT_SEND.iter().copied(),
+        aptos_channel::Config::new(config.max_network_channel_size as usize)
+            .queue_style(QueueStyle::FIFO)
+            .counters(&counters::PENDING_CONSENSUS_OBSERVER_NETWORK_EVENTS),
+    )
+}
@@ -0,0 +1,638 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::network::{
+        ConsensusObserverMessage, ConsensusObserverNetworkEvents, OrderedBlock,
+    },
+    counters,
+};
+use anyhow::{anyhow, ensure};
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_address::AccountAddress,
+    epoch_state::EpochState,
+    ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::{OnChainConfigPayload, ValidatorSet},
+    transaction::SignedTransaction,
+    PeerId,
+};
+use consensus_notifications::{ConsensusNotificationSender, ConsensusObserverStatus};
+use consensus_types::{
+    block::Block,
+    common::{Payload, Round},
+    executed_block::ExecutedBlock,
+};
+use event_notifications::ReconfigNotificationListener;
+use executor_types::BlockExecutorTrait;
+use futures::{FutureExt, StreamExt};
+use network::protocols::network::Event;
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The interval at which the observer checks that it's still committing blocks
+const PROGRESS_CHECK_INTERVAL_MS: u64 = 1000;
+
+/// An ordered block that was executed speculatively and is waiting for its commit decision
+struct ExecutedPendingBlock {
+    executed_block: ExecutedBlock,
+    transactions: Vec<SignedTransaction>,
+}
+
+/// The consensus observer runs on validator fullnodes. It receives the ordered
+/// blocks and commit decisions pushed by the upstream validator, executes the
+/// ordered blocks speculatively and commits them once the commit decision (i.e.,
+/// the signatures on the execution result) arrives. This avoids waiting for state
+/// sync to fetch the committed data.
+///
+/// The observer and state sync never execute at the same time. The observer
+/// starts inactive and takes over from state sync by syncing to a commit decision
+/// that its buffered blocks extend. If it doesn't commit a block for too long
+/// (e.g., the publisher stopped sending commit decisions), it becomes inactive
+/// and state sync resumes syncing.
+///
+/// If a gap is detected (e.g., a message was dropped, the execution result doesn't
+/// match the commit decision or the block payload isn't available locally) the
+/// observer falls back to state sync, using the next commit decision as the target.
+pub struct ConsensusObserver {
+    config: ConsensusObserverConfig,
+    executor: Arc<dyn BlockExecutorTrait>,
+    state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
+    // Whether the observer (and not state sync) is executing (shared with state sync)
+    observer_status: ConsensusObserverStatus,
+    reconfig_events: ReconfigNotificationListener,
+
+    // The state of the current epoch (used to verify all messages)
+    epoch_state: Option<EpochState>,
+    // The validators of the current epoch (used to build the block metadata transactions)
+    validators: Vec<AccountAddress>,
+
+    // The id and (epoch, round) of the last ordered block. New ordered blocks must extend it.
+    last_ordered_block_id: HashValue,
+    last_ordered_round: (u64, Round),
+    // The (epoch, round) of the last committed (or synced) block
+    last_committed_round: (u64, Round),
+
+    // The ordered blocks that have been executed, but not yet committed
+    executed_blocks: VecDeque<ExecutedPendingBlock>,
+    // The ordered blocks received while waiting to sync (they are executed after the sync)
+    buffered_blocks: VecDeque<Block>,
+    // Whether a gap was detected and the observer must sync to the next commit decision
+    sync_required: bool,
+
+    // The time at which the observer last committed (or synced to) a block
+    last_commit_time: Instant,
+}
+
+impl ConsensusObserver {
+    pub fn new(
+        config: ConsensusObserverConfig,
+        executor: Arc<dyn BlockExecutorTrait>,
+        state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
+        observer_status: ConsensusObserverStatus,
+        reconfig_events: ReconfigNotificationListener,
+    ) -> Self {
+        Self {
+            config,
+            executor,
+            state_sync_notifier,
+            observer_status,
+            reconfig_events,
+            epoch_state: None,
+            validators: vec![],
+            last_ordered_block_id: HashValue::zero(),
+            last_ordered_round: (0, 0),
+            last_committed_round: (0, 0),
+            executed_blocks: VecDeque::new(),
+            buffered_blocks: VecDeque::new(),
+            sync_required: false,
+            last_commit_time: Instant::now(),
+        }
+    }
+
+    /// Starts the consensus observer loop that processes all messages pushed by the publisher
+    pub async fn start(mut self, mut network_events: ConsensusObserverNetworkEvents) {
+        info!("Starting the consensus observer!");
+        self.initialize().await;
+
+        let mut progress_check_interval =
+            tokio::time::interval(Duration::from_millis(PROGRESS_CHECK_INTERVAL_MS));
+        loop {
+            tokio::select! {
+                event = network_events.next() => match event {
+                    Some(Event::Message(peer_id, message)) => {
+                        self.process_message(peer_id, message).await;
+                    }
+                    Some(_) => {}
+                    None => break,
+                },
+                _ = progress_check_interval.tick() => {
+                    self.check_progress();
+                }
+            }
+        }
+        warn!("The consensus observer network stream has terminated!");
+        self.stop_executing();
+    }
+
+    /// Waits for the initial epoch and resets the speculative execution state
+    pub(crate) async fn initialize(&mut self) {
+        self.await_reconfig_notification().await;
+        self.reset_execution_state();
+    }
+
+    /// Processes a single message pushed by the publisher
+    pub(crate) async fn process_message(
+        &mut self,
+        peer_id: PeerId,
+        message: ConsensusObserverMessage,
+    ) {
+        counters::CONSENSUS_OBSERVER_RECEIVED_MESSAGES
+            .with_label_values(&[message.get_label()])
+            .inc();
+
+        // While the observer is inactive, state sync might cross epoch boundaries
+        if !self.observer_status.is_active() {
+            self.process_pending_reconfig_notifications();
+        }
+
+        let message_label = message.get_label();
+        let result = match message {
+            ConsensusObserverMessage::OrderedBlock(ordered_block) => {
+                self.process_ordered_block(ordered_block).await
+            }
+            ConsensusObserverMessage::CommitDecision(commit_proof) => {
+                self.process_commit_decision(commit_proof).await
+            }
+        };
+        if let Err(error) = result {
+            warn!(
+                remote_peer = peer_id,
+                error = ?error,
+                "Failed to process the consensus observer message: {}", message_label
+            );
+        }
+    }
+
+    /// Verifies the ordered blocks and executes them speculatively (or buffers
+    /// them if the observer is waiting to sync).
+    async fn process_ordered_block(&mut self, ordered_block: OrderedBlock) -> anyhow::Result<()> {
+        let OrderedBlock {
+            blocks,
+            ordered_proof,
+        } = ordered_block;
+
+        // Messages from other epochs can't be verified. If the publisher is
+        // already in a future epoch, we'll have to sync to catch up.
+        let epoch = self.epoch();
+        let ordered_epoch = ordered_proof.ledger_info().epoch();
+        if ordered_epoch != epoch {
+            if ordered_epoch > epoch {
+                self.require_sync("future_epoch");
+            }
+            return Err(anyhow!(
+                "Ordered block for epoch {} received in epoch {}",
+                ordered_epoch,
+                epoch
+            ));
+        }
+
+        // Ignore blocks that were already ordered
+        let ordered_round = (ordered_epoch, ordered_proof.commit_info().round());
+        if ordered_round <= self.last_ordered_round {
+            return Ok(());
+        }
+
+        // Verify the blocks form a chain that is certified by the ordered proof
+        self.verify_ordered_blocks(&blocks, &ordered_proof)?;
+
+        // If some ordered blocks were missed, they can only be recovered by
+        // state sync. Keep the new blocks, as they might extend the sync target.
+        if blocks[0].parent_id() != self.last_ordered_block_id {
+            self.buffered_blocks.clear();
+            self.require_sync("missing_ordered_blocks");
+        }
+        self.last_ordered_block_id = blocks[blocks.len() - 1].id();
+        self.last_ordered_round = ordered_round;
+
+        // Execute the blocks (or buffer them until the sync completes)
+        if self.sync_required || !self.observer_status.is_active() {
+            self.buffered_blocks.extend(blocks);
+            while self.buffered_blocks.len() as u64 > self.config.max_pending_blocks {
+                self.buffered_blocks.pop_front();
+            }
+            Ok(())
+        } else {
+            self.execute_blocks(blocks).await
+        }
+    }
+
+    /// Verifies that the given blocks form a chain and that the last block is
+    /// certified by the ordered proof (signed by the validators of this epoch).
+    fn verify_ordered_blocks(
+        &self,
+        blocks: &[Block],
+        ordered_proof: &LedgerInfoWithSignatures,
+    ) -> anyhow::Result<()> {
+        let last_block = blocks
+            .last()
+            .ok_or_else(|| anyhow!("The ordered block message has no blocks!"))?;
+        ensure!(
+            last_block.id() == ordered_proof.commit_info().id(),
+            "The last ordered block {} doesn't match the ordered proof {}",
+            last_block.id(),
+            ordered_proof.commit_info().id()
+        );
+        for window in blocks.windows(2) {
+            ensure!(
+                window[1].parent_id() == window[0].id(),
+                "The ordered block {} doesn't extend block {}",
+                window[1].id(),
+                window[0].id()
+            );
+        }
+        ordered_proof.verify_signatures(&self.epoch_state()?.verifier)?;
+        Ok(())
+    }
+
+    /// Executes the given blocks (in order) on top of the last executed block
+    async fn execute_blocks(
+        &mut self,
+        blocks: impl IntoIterator<Item = Block>,
+    ) -> anyhow::Result<()> {
+        for block in blocks {
+            if self.executed_blocks.len() as u64 >= self.config.max_pending_blocks {
+                self.require_sync("too_many_pending_blocks");
+                return Err(anyhow!("Too many executed blocks are pending commit!"));
+            }
+
+            // The observer has no access to the quorum store, so it can only
+            // execute blocks that carry their transactions.
+            let transactions = match block.payload() {
+                None => vec![],
+                Some(Payload::DirectMempool(transactions)) => transactions.clone(),
+                Some(Payload::InQuorumStore(_)) => {
+                    self.require_sync("quorum_store_payload");
+                    return Ok(());
+                }
+            };
+
+            let executor = self.executor.clone();
+            let block_id = block.id();
+            let parent_block_id = block.parent_id();
+            let transactions_to_execute =
+                block.transactions_to_execute(&self.validators, transactions.clone());
+            let compute_result = tokio::task::spawn_blocking(move || {
+                executor.execute_block((block_id, transactions_to_execute), parent_block_id)
+            })
+            .await
+            .expect("spawn_blocking failed");
+            match compute_result {
+                Ok(compute_result) => {
+                    self.executed_blocks.push_back(ExecutedPendingBlock {
+                        executed_block: ExecutedBlock::new(block, compute_result),
+                        transactions,
+                    });
+                }
+                Err(error) => {
+                    self.require_sync("execution_error");
+                    return Err(error.into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Commits all executed blocks up to the committed block, or syncs to the
+    /// commit decision if the committed block wasn't executed locally.
+    async fn process_commit_decision(
+        &mut self,
+        commit_proof: LedgerInfoWithSignatures,
+    ) -> anyhow::Result<()> {
+        let epoch = self.epoch();
+        let commit_epoch = commit_proof.ledger_info().epoch();
+        let commit_round = (commit_epoch, commit_proof.commit_info().round());
+
+        // Ignore commit decisions for blocks that were already committed
+        if commit_epoch < epoch || commit_round <= self.last_committed_round {
+            return Ok(());
+        }
+
+        // While state sync is executing, wait for a commit decision to take over from.
+        // Commit decisions from future epochs are skipped until state sync reaches them.
+        if !self.observer_status.is_active() {
+            if commit_epoch > epoch {
+                return Ok(());
+            }
+            commit_proof.verify_signatures(&self.epoch_state()?.verifier)?;
+            return self.take_over_execution(commit_proof).await;
+        }
+
+        // We've missed an epoch change. The signatures can't be verified using
+        // the current validator set, but state sync verifies all synced data.
+        if commit_epoch > epoch {
+            return self.sync_to_commit(commit_proof, "future_epoch").await;
+        }
+
+        commit_proof.verify_signatures(&self.epoch_state()?.verifier)?;
+        if self.sync_required {
+            return self.sync_to_commit(commit_proof, "sync_required").await;
+        }
+
+        // Find the committed block and verify its execution result matches
+        let committed_block_id = commit_proof.commit_info().id();
+        let committed_index = match self
+            .executed_blocks
+            .iter()
+            .position(|block| block.executed_block.id() == committed_block_id)
+        {
+            Some(committed_index) => committed_index,
+            None => {
+                return self
+                    .sync_to_commit(commit_proof, "missing_committed_block")
+                    .await
+            }
+        };
+        let executed_block_info = self.executed_blocks[committed_index]
+            .executed_block
+            .block_info();
+        if executed_block_info != *commit_proof.commit_info() {
+            warn!(
+                "The execution result {:?} doesn't match the commit decision {:?}!",
+                executed_block_info,
+                commit_proof.commit_info()
+            );
+            return self
+                .sync_to_commit(commit_proof, "execution_mismatch")
+                .await;
+        }
+
+        self.commit_blocks(committed_index, commit_proof).await
+    }
+
+    /// Commits the executed blocks up to (and including) the given index
+    async fn commit_blocks(
+        &mut self,
+        committed_index: usize,
+        commit_proof: LedgerInfoWithSignatures,
+    ) -> anyhow::Result<()> {
+        let mut block_ids = vec![];
+        let mut transactions = vec![];
+        let mut reconfig_events = vec![];
+        for pending_block in self.executed_blocks.drain(..=committed_index) {
+            let executed_block = pending_block.executed_block;
+            block_ids.push(executed_block.id());
+            transactions.extend(
+                executed_block.transactions_to_commit(&self.validators, pending_block.transactions),
+            );
+            reconfig_events.extend(executed_block.reconfig_event());
+        }
+
+        let executor = self.executor.clone();
+        let proof = commit_proof.clone();
+        let commit_result = tokio::task::spawn_blocking(move || {
+            executor.commit_blocks_ext(block_ids, proof, false /* save_state_snapshots */)
+        })
+        .await
+        .expect("spawn_blocking failed");
+        if let Err(error) = commit_result {
+            warn!(error = ?error, "Failed to commit the executed blocks!");
+            return self.sync_to_commit(commit_proof, "commit_error").await;
+        }
+        self.last_committed_round = (
+            commit_proof.ledger_info().epoch(),
+            commit_proof.commit_info().round(),
+        );
+        self.last_commit_time = Instant::now();
+        counters::CONSENSUS_OBSERVER_LAST_COMMITTED_ROUND
+            .set(commit_proof.commit_info().round() as i64);
+
+        // Notify state sync of the new commit (to notify mempool and event subscribers)
+        if let Err(error) = self
+            .state_sync_notifier
+            .notify_new_commit(transactions, reconfig_events)
+            .await
+        {
+            error!(error = ?error, "Failed to notify state sync of the new commit!");
+        }
+
+        // If the epoch ended, the remaining blocks (i.e., the reconfiguration
+        // suffix) are dropped and the observer waits for the new epoch to start.
+        if commit_proof.ledger_info().ends_epoch() {
+            self.await_epoch(commit_proof.ledger_info().next_block_epoch())
+                .await;
+            self.reset_execution_state();
+        }
+        Ok(())
+    }
+
+    /// Takes over execution from state sync by syncing to the given commit
+    /// decision. This is only done if one of the buffered blocks extends the
+    /// committed block, so that the observer can execute it after the sync.
+    async fn take_over_execution(
+        &mut self,
+        commit_proof: LedgerInfoWithSignatures,
+    ) -> anyhow::Result<()> {
+        let committed_block_id = commit_proof.commit_info().id();
+        if !self
+            .buffered_blocks
+            .iter()
+            .any(|block| block.parent_id() == committed_block_id)
+        {
+            return Ok(());
+        }
+
+        // State sync stops syncing before it handles the sync request, and
+        // doesn't resume while the observer is active.
+        info!("Taking over execution from state sync at: {}", commit_proof);
+        self.observer_status.set_active(true);
+        let sync_result = self.sync_to_target(commit_proof).await;
+        if sync_result.is_err() {
+            self.stop_executing();
+        }
+        sync_result
+    }
+
+    /// Falls back to state sync to reach the given commit decision
+    async fn sync_to_commit(
+        &mut self,
+        commit_proof: LedgerInfoWithSignatures,
+        reason: &'static str,
+    ) -> anyhow::Result<()> {
+        info!(
+            "Falling back to state sync ({}). Syncing to: {}",
+            reason, commit_proof
+        );
+        counters::CONSENSUS_OBSERVER_STATE_SYNC_FALLBACKS
+            .with_label_values(&[reason])
+            .inc();
+        self.sync_to_target(commit_proof).await
+    }
+
+    /// Syncs to the given commit decision using state sync. Afterwards, any
+    /// buffered blocks that extend the synced block are executed.
+    async fn sync_to_target(
+        &mut self,
+        commit_proof: LedgerInfoWithSignatures,
+    ) -> anyhow::Result<()> {
+        // Free the in-memory state of the executor before the sync, and reload
+        // the latest committed state afterwards (as done by the state computer).
+        self.executed_blocks.clear();
+        self.executor.finish();
+        let sync_result = self
+            .state_sync_notifier
+            .sync_to_target(commit_proof.clone())
+            .await;
+        self.executor.reset()?;
+        if let Err(error) = sync_result {
+            self.require_sync("sync_error");
+            return Err(anyhow!(
+                "Failed to sync to the commit decision: {:?}",
+                error
+            ));
+        }
+        self.last_committed_round = (
+            commit_proof.ledger_info().epoch(),
+            commit_proof.commit_info().round(),
+        );
+        self.last_commit_time = Instant::now();
+
+        // If the sync crossed an epoch boundary, wait for the new epoch to start
+        let synced_epoch = commit_proof.ledger_info().next_block_epoch();
+        if synced_epoch > self.epoch() {
+            self.await_epoch(synced_epoch).await;
+        }
+
+        // Drop the buffered blocks that were committed by the sync
+        let last_committed_round = self.last_committed_round;
+        let epoch = self.epoch();
+        self.buffered_blocks.retain(|block| {
+            block.epoch() == epoch && (block.epoch(), block.round()) > last_committed_round
+        });
+
+        // Execute the buffered blocks if they extend the synced block. Otherwise,
+        // wait for the next commit decision to sync again.
+        let committed_block_id = self.executor.committed_block_id();
+        match self.buffered_blocks.front() {
+            None => {
+                self.sync_required = false;
+                self.last_ordered_block_id = committed_block_id;
+                self.last_ordered_round = self.last_ordered_round.max(last_committed_round);
+                Ok(())
+            }
+            Some(block) if block.parent_id() == committed_block_id => {
+                self.sync_required = false;
+                let buffered_blocks: Vec<_> = self.buffered_blocks.drain(..).collect();
+                self.execute_blocks(buffered_blocks).await
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Hands execution back to state sync if the observer hasn't committed (or
+    /// synced to) a block within the fallback timeout. Receiving ordered blocks
+    /// isn't enough, as state sync must not stall while nothing is committed.
+    pub(crate) fn check_progress(&mut self) {
+        let fallback_timeout = Duration::from_millis(self.config.observer_fallback_timeout_ms);
+        if self.observer_status.is_active() && self.last_commit_time.elapsed() > fallback_timeout {
+            info!(
+                "No blocks committed for {:?}! Handing execution back to state sync.",
+                fallback_timeout
+            );
+            counters::CONSENSUS_OBSERVER_STATE_SYNC_FALLBACKS
+                .with_label_values(&["commit_timeout"])
+                .inc();
+            self.stop_executing();
+        }
+    }
+
+    /// Drops the speculative execution state and marks the observer as inactive,
+    /// so that state sync resumes syncing.
+    fn stop_executing(&mut self) {
+        self.executed_blocks.clear();
+        self.buffered_blocks.clear();
+        self.executor.finish();
+        self.observer_status.set_active(false);
+    }
+
+    /// Marks that a gap was detected and the observer must sync to the next commit decision
+    fn require_sync(&mut self, reason: &'static str) {
+        if !self.sync_required {
+            info!("Consensus observer requires state sync: {}", reason);
+            self.sync_required = true;
+        }
+    }
+
+    /// Resets the speculative execution state to the latest committed block in storage
+    fn reset_execution_state(&mut self) {
+        self.executed_blocks.clear();
+        self.buffered_blocks.clear();
+        self.sync_required = false;
+        if let Err(error) = self.executor.reset() {
+            error!(error = ?error, "Failed to reset the block executor!");
+        }
+        self.last_ordered_block_id = self.executor.committed_block_id();
+    }
+
+    /// Waits until the given epoch (or a later one) has started
+    async fn await_epoch(&mut self, epoch: u64) {
+        while self.epoch() < epoch {
+            self.await_reconfig_notification().await;
+        }
+    }
+
+    /// Waits for the next reconfiguration notification and starts the new epoch
+    async fn await_reconfig_notification(&mut self) {
+        let reconfig_notification = self
+            .reconfig_events
+            .next()
+            .await
+            .expect("Reconfig sender dropped, unable to start new epoch");
+        self.start_new_epoch(reconfig_notification.on_chain_configs);
+    }
+
+    /// Starts the new epoch if a reconfiguration notification is already pending
+    fn process_pending_reconfig_notifications(&mut self) {
+        if let Some(Some(reconfig_notification)) = self.reconfig_events.next().now_or_never() {
+            self.start_new_epoch(reconfig_notification.on_chain_configs);
+        }
+    }
+
+    /// Updates the epoch state using the given on-chain configs
+    fn start_new_epoch(&mut self, payload: OnChainConfigPayload) {
+        let validator_set: ValidatorSet = payload
+            .get()
+            .expect("failed to get ValidatorSet from payload");
+        let epoch_state = EpochState {
+            epoch: payload.epoch(),
+            verifier: (&validator_set).into(),
+        };
+        info!("Consensus observer starting epoch: {}", epoch_state.epoch);
+
+        self.validators = epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .collect();
+        self.epoch_state = Some(epoch_state);
+    }
+
+    /// Returns the state of the current epoch
+    fn epoch_state(&self) -> anyhow::Result<&EpochState> {
+        self.epoch_state
+            .as_ref()
+            .ok_or_else(|| anyhow!("The consensus observer hasn't started an epoch!"))
+    }
+
+    /// Returns the current epoch (or 0 if no epoch has started)
+    fn epoch(&self) -> u64 {
+        self.epoch_state
+            .as_ref()
+            .map(|epoch_state| epoch_state.epoch)
+            .unwrap_or(0)
+    }
+}
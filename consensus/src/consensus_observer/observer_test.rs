@@ -0,0 +1,455 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::consensus_observer::{
+    network::{ConsensusObserverMessage, OrderedBlock},
+    observer::ConsensusObserver,
+};
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::{generate_ledger_info_with_sig, LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::{OnChainConfig, OnChainConfigPayload, ValidatorSet},
+    transaction::{ExecutionStatus, Transaction, TransactionStatus},
+    validator_signer::ValidatorSigner,
+    validator_verifier::random_validator_verifier,
+    PeerId,
+};
+use channel::{aptos_channel, message_queues::QueueStyle};
+use consensus_notifications::{
+    ConsensusNotificationSender, ConsensusObserverStatus, Error as NotificationError,
+};
+use consensus_types::{
+    block::{
+        block_test_utils::{certificate_for_genesis, placeholder_certificate_for_block},
+        Block,
+    },
+    common::Payload,
+};
+use event_notifications::{ReconfigNotification, ReconfigNotificationListener};
+use executor_types::{BlockExecutorTrait, Error as ExecutionError, StateComputeResult};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+#[tokio::test]
+async fn test_ordered_blocks_are_executed_and_committed() {
+    let (signers, mut observer, executor, state_sync, _) = create_observer().await;
+    let blocks = create_block_chain(&signers, 3);
+
+    // Send the ordered blocks and verify they're executed (but not committed)
+    send_ordered_blocks(&mut observer, &signers, &blocks).await;
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks));
+    assert!(executor.committed_blocks().is_empty());
+
+    // Send the ordered blocks again and verify they're not re-executed
+    send_ordered_blocks(&mut observer, &signers, &blocks).await;
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks));
+
+    // Commit the first two blocks and verify state sync is notified
+    send_commit_decision(&mut observer, &signers, &blocks[1]).await;
+    assert_eq!(executor.committed_blocks(), block_ids(&blocks[..2]));
+    assert_eq!(state_sync.committed_transactions(), 4); // Each block has 2 transactions
+
+    // Commit the last block
+    send_commit_decision(&mut observer, &signers, &blocks[2]).await;
+    assert_eq!(executor.committed_blocks(), block_ids(&blocks));
+    assert_eq!(state_sync.committed_transactions(), 6);
+    assert!(state_sync.sync_targets().is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_ordered_blocks_are_ignored() {
+    let (signers, mut observer, executor, _, _) = create_observer().await;
+    let blocks = create_block_chain(&signers, 3);
+
+    // Send ordered blocks with a proof signed by unknown validators
+    let unknown_signers: Vec<_> = (0..4)
+        .map(|index| ValidatorSigner::random([100 + index; 32]))
+        .collect();
+    send_ordered_blocks(&mut observer, &unknown_signers, &blocks).await;
+    assert!(executor.executed_blocks().is_empty());
+
+    // Send ordered blocks that don't form a chain
+    let unchained_blocks = vec![blocks[0].clone(), blocks[2].clone()];
+    send_ordered_blocks(&mut observer, &signers, &unchained_blocks).await;
+    assert!(executor.executed_blocks().is_empty());
+
+    // Send the valid ordered blocks and verify they're executed
+    send_ordered_blocks(&mut observer, &signers, &blocks).await;
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks));
+}
+
+#[tokio::test]
+async fn test_missing_ordered_blocks_fall_back_to_state_sync() {
+    let (signers, mut observer, executor, state_sync, _) = create_observer().await;
+    let blocks = create_block_chain(&signers, 5);
+
+    // Skip the first two ordered blocks and verify the rest aren't executed
+    send_ordered_blocks(&mut observer, &signers, &blocks[2..4]).await;
+    send_ordered_blocks(&mut observer, &signers, &blocks[4..]).await;
+    assert!(executor.executed_blocks().is_empty());
+
+    // Send the commit decision for the fourth block and verify the observer syncs to it
+    let commit_proof = send_commit_decision(&mut observer, &signers, &blocks[3]).await;
+    assert_eq!(state_sync.sync_targets(), vec![commit_proof]);
+    assert!(executor.committed_blocks().is_empty());
+
+    // Verify the buffered block that extends the synced block is executed
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks[4..]));
+
+    // Commit the last block and verify it's committed by the observer
+    send_commit_decision(&mut observer, &signers, &blocks[4]).await;
+    assert_eq!(executor.committed_blocks(), block_ids(&blocks[4..]));
+    assert_eq!(state_sync.sync_targets().len(), 1);
+}
+
+#[tokio::test]
+async fn test_execution_mismatch_falls_back_to_state_sync() {
+    let (signers, mut observer, executor, state_sync, _) = create_observer().await;
+    let blocks = create_block_chain(&signers, 2);
+
+    // Send the ordered blocks and verify they're executed
+    send_ordered_blocks(&mut observer, &signers, &blocks).await;
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks));
+
+    // Send a commit decision with a different execution result
+    let block_info = blocks[1].gen_block_info(HashValue::random(), 0, None);
+    let commit_proof =
+        generate_ledger_info_with_sig(&signers, LedgerInfo::new(block_info, HashValue::zero()));
+    observer
+        .process_message(
+            PeerId::random(),
+            ConsensusObserverMessage::CommitDecision(commit_proof.clone()),
+        )
+        .await;
+
+    // Verify the observer didn't commit the blocks, but synced to the commit decision
+    assert!(executor.committed_blocks().is_empty());
+    assert_eq!(state_sync.sync_targets(), vec![commit_proof]);
+}
+
+#[tokio::test]
+async fn test_inactive_observer_takes_over_from_state_sync() {
+    let (signers, mut observer, executor, state_sync, observer_status) = create_observer().await;
+    let blocks = create_block_chain(&signers, 3);
+
+    // Mark the observer as inactive (i.e., state sync is executing)
+    observer_status.set_active(false);
+
+    // Send the ordered blocks and verify they're not executed
+    send_ordered_blocks(&mut observer, &signers, &blocks[..2]).await;
+    send_ordered_blocks(&mut observer, &signers, &blocks[2..]).await;
+    assert!(executor.executed_blocks().is_empty());
+
+    // Send the commit decision for the first block and verify the observer takes over
+    let commit_proof = send_commit_decision(&mut observer, &signers, &blocks[0]).await;
+    assert_eq!(state_sync.sync_targets(), vec![commit_proof]);
+    assert!(observer_status.is_active());
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks[1..]));
+
+    // Commit the last block and verify it's committed by the observer
+    send_commit_decision(&mut observer, &signers, &blocks[2]).await;
+    assert_eq!(executor.committed_blocks(), block_ids(&blocks[1..]));
+    assert_eq!(state_sync.sync_targets().len(), 1);
+}
+
+#[tokio::test]
+async fn test_inactive_observer_waits_for_buffered_blocks() {
+    let (signers, mut observer, executor, state_sync, observer_status) = create_observer().await;
+    let blocks = create_block_chain(&signers, 3);
+    observer_status.set_active(false);
+
+    // Send a commit decision for a block that no buffered block extends
+    send_ordered_blocks(&mut observer, &signers, &blocks[..1]).await;
+    send_commit_decision(&mut observer, &signers, &blocks[0]).await;
+
+    // Verify the observer didn't take over (state sync keeps executing)
+    assert!(state_sync.sync_targets().is_empty());
+    assert!(!observer_status.is_active());
+    assert!(executor.executed_blocks().is_empty());
+}
+
+#[tokio::test]
+async fn test_observer_without_commits_falls_back_to_state_sync() {
+    let config = ConsensusObserverConfig {
+        observer_fallback_timeout_ms: 500,
+        ..ConsensusObserverConfig::default()
+    };
+    let (signers, mut observer, executor, state_sync, observer_status) =
+        create_observer_with_config(config).await;
+    let blocks = create_block_chain(&signers, 4);
+
+    // Send the ordered blocks and verify they're executed
+    send_ordered_blocks(&mut observer, &signers, &blocks[..2]).await;
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks[..2]));
+
+    // Verify the observer stays active while it's within the timeout
+    observer.check_progress();
+    assert!(observer_status.is_active());
+
+    // Keep ordering blocks without commits and verify the observer becomes inactive
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    send_ordered_blocks(&mut observer, &signers, &blocks[2..3]).await;
+    observer.check_progress();
+    assert!(!observer_status.is_active());
+
+    // Verify new ordered blocks are no longer executed
+    send_ordered_blocks(&mut observer, &signers, &blocks[3..]).await;
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks[..3]));
+
+    // Verify the observer only takes over again by syncing to a commit decision
+    let commit_proof = send_commit_decision(&mut observer, &signers, &blocks[2]).await;
+    assert_eq!(state_sync.sync_targets(), vec![commit_proof]);
+    assert!(executor.committed_blocks().is_empty());
+    assert!(observer_status.is_active());
+    assert_eq!(executor.executed_blocks(), block_ids(&blocks));
+}
+
+/// Creates a consensus observer (started in epoch 1) along with its mock dependencies
+async fn create_observer() -> (
+    Vec<ValidatorSigner>,
+    ConsensusObserver,
+    Arc<MockBlockExecutor>,
+    Arc<MockStateSyncNotifier>,
+    ConsensusObserverStatus,
+) {
+    create_observer_with_config(ConsensusObserverConfig::default()).await
+}
+
+/// Creates a consensus observer using the given config. The observer is
+/// marked as active, i.e., it has already taken over from state sync.
+async fn create_observer_with_config(
+    config: ConsensusObserverConfig,
+) -> (
+    Vec<ValidatorSigner>,
+    ConsensusObserver,
+    Arc<MockBlockExecutor>,
+    Arc<MockStateSyncNotifier>,
+    ConsensusObserverStatus,
+) {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+    let executor = Arc::new(MockBlockExecutor::new(
+        certificate_for_genesis().certified_block().id(),
+    ));
+    let state_sync = Arc::new(MockStateSyncNotifier::new(executor.clone()));
+
+    // Create the reconfig listener and push the initial epoch
+    let (reconfig_sender, reconfig_events) = aptos_channel::new(QueueStyle::LIFO, 1, None);
+    let reconfig_listener = ReconfigNotificationListener {
+        notification_receiver: reconfig_events,
+    };
+    let mut configs = HashMap::new();
+    configs.insert(
+        ValidatorSet::CONFIG_ID,
+        bcs::to_bytes(&ValidatorSet::from(&verifier)).unwrap(),
+    );
+    reconfig_sender
+        .push(
+            (),
+            ReconfigNotification {
+                version: 1,
+                on_chain_configs: OnChainConfigPayload::new(1, Arc::new(configs)),
+            },
+        )
+        .unwrap();
+
+    let observer_status = ConsensusObserverStatus::new();
+    let mut observer = ConsensusObserver::new(
+        config,
+        executor.clone(),
+        state_sync.clone(),
+        observer_status.clone(),
+        reconfig_listener,
+    );
+    observer.initialize().await;
+    observer_status.set_active(true);
+
+    (signers, observer, executor, state_sync, observer_status)
+}
+
+/// Creates a chain of blocks (extending genesis) with empty payloads
+fn create_block_chain(signers: &[ValidatorSigner], num_blocks: u64) -> Vec<Block> {
+    let mut blocks: Vec<Block> = vec![];
+    for round in 1..=num_blocks {
+        let quorum_cert = match blocks.last() {
+            Some(parent) => placeholder_certificate_for_block(
+                signers,
+                parent.id(),
+                parent.round(),
+                parent.parent_id(),
+                parent.round() - 1,
+            ),
+            None => certificate_for_genesis(),
+        };
+        blocks.push(Block::new_proposal(
+            Payload::empty(),
+            round,
+            round,
+            quorum_cert,
+            &signers[0],
+            vec![],
+        ));
+    }
+    blocks
+}
+
+/// Sends the given blocks (ordered by a proof for the last block) to the observer
+async fn send_ordered_blocks(
+    observer: &mut ConsensusObserver,
+    signers: &[ValidatorSigner],
+    blocks: &[Block],
+) {
+    let block_info = blocks
+        .last()
+        .unwrap()
+        .gen_block_info(HashValue::zero(), 0, None);
+    let ordered_proof =
+        generate_ledger_info_with_sig(signers, LedgerInfo::new(block_info, HashValue::zero()));
+    let ordered_block = OrderedBlock {
+        blocks: blocks.to_vec(),
+        ordered_proof,
+    };
+    observer
+        .process_message(
+            PeerId::random(),
+            ConsensusObserverMessage::OrderedBlock(ordered_block),
+        )
+        .await;
+}
+
+/// Sends the commit decision for the given block (matching the mock execution result)
+async fn send_commit_decision(
+    observer: &mut ConsensusObserver,
+    signers: &[ValidatorSigner],
+    block: &Block,
+) -> LedgerInfoWithSignatures {
+    let block_info = block.gen_block_info(block.id(), 0, None);
+    let commit_proof =
+        generate_ledger_info_with_sig(signers, LedgerInfo::new(block_info, HashValue::zero()));
+    observer
+        .process_message(
+            PeerId::random(),
+            ConsensusObserverMessage::CommitDecision(commit_proof.clone()),
+        )
+        .await;
+    commit_proof
+}
+
+fn block_ids(blocks: &[Block]) -> Vec<HashValue> {
+    blocks.iter().map(|block| block.id()).collect()
+}
+
+/// A mock block executor that uses the block id as the execution result
+struct MockBlockExecutor {
+    committed_block_id: Mutex<HashValue>,
+    executed_blocks: Mutex<Vec<HashValue>>,
+    committed_blocks: Mutex<Vec<HashValue>>,
+}
+
+impl MockBlockExecutor {
+    fn new(committed_block_id: HashValue) -> Self {
+        Self {
+            committed_block_id: Mutex::new(committed_block_id),
+            executed_blocks: Mutex::new(vec![]),
+            committed_blocks: Mutex::new(vec![]),
+        }
+    }
+
+    fn executed_blocks(&self) -> Vec<HashValue> {
+        self.executed_blocks.lock().clone()
+    }
+
+    fn committed_blocks(&self) -> Vec<HashValue> {
+        self.committed_blocks.lock().clone()
+    }
+}
+
+impl BlockExecutorTrait for MockBlockExecutor {
+    fn committed_block_id(&self) -> HashValue {
+        *self.committed_block_id.lock()
+    }
+
+    fn reset(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn execute_block(
+        &self,
+        block: (HashValue, Vec<Transaction>),
+        _parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, ExecutionError> {
+        let (block_id, transactions) = block;
+        self.executed_blocks.lock().push(block_id);
+        Ok(StateComputeResult::new(
+            block_id,
+            vec![],
+            0,
+            vec![],
+            0,
+            None,
+            vec![TransactionStatus::Keep(ExecutionStatus::Success); transactions.len()],
+            vec![],
+            vec![],
+        ))
+    }
+
+    fn commit_blocks_ext(
+        &self,
+        block_ids: Vec<HashValue>,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+        _save_state_snapshots: bool,
+    ) -> Result<(), ExecutionError> {
+        self.committed_blocks.lock().extend(block_ids);
+        *self.committed_block_id.lock() = ledger_info_with_sigs.commit_info().id();
+        Ok(())
+    }
+
+    fn finish(&self) {}
+}
+
+/// A mock state sync notifier that "syncs" by updating the committed block of the executor
+struct MockStateSyncNotifier {
+    executor: Arc<MockBlockExecutor>,
+    committed_transactions: Mutex<usize>,
+    sync_targets: Mutex<Vec<LedgerInfoWithSignatures>>,
+}
+
+impl MockStateSyncNotifier {
+    fn new(executor: Arc<MockBlockExecutor>) -> Self {
+        Self {
+            executor,
+            committed_transactions: Mutex::new(0),
+            sync_targets: Mutex::new(vec![]),
+        }
+    }
+
+    fn committed_transactions(&self) -> usize {
+        *self.committed_transactions.lock()
+    }
+
+    fn sync_targets(&self) -> Vec<LedgerInfoWithSignatures> {
+        self.sync_targets.lock().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl ConsensusNotificationSender for MockStateSyncNotifier {
+    async fn notify_new_commit(
+        &self,
+        transactions: Vec<Transaction>,
+        _reconfiguration_events: Vec<ContractEvent>,
+    ) -> Result<(), NotificationError> {
+        *self.committed_transactions.lock() += transactions.len();
+        Ok(())
+    }
+
+    async fn sync_to_target(
+        &self,
+        target: LedgerInfoWithSignatures,
+    ) -> Result<(), NotificationError> {
+        *self.executor.committed_block_id.lock() = target.commit_info().id();
+        self.sync_targets.lock().push(target);
+        Ok(())
+    }
+}
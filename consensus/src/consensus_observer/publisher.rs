@@ -0,0 +1,115 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::network::{
+        ConsensusObserverMessage, ConsensusObserverNetworkSender, OrderedBlock,
+    },
+    counters,
+};
+use aptos_config::network_id::NetworkId;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, PeerId};
+use consensus_types::{common::Round, executed_block::ExecutedBlock};
+use network::{application::storage::PeerMetadataStorage, ProtocolId};
+use std::{sync::Arc, time::Duration};
+
+/// The frequency (in seconds) at which to log publishing errors
+const ERROR_LOG_FREQ_SECS: u64 = 5;
+
+/// Pushes ordered blocks and commit decisions to the consensus observers of
+/// subscribed validator fullnodes. A VFN subscribes by supporting the consensus
+/// observer protocol on its connection to the validator.
+pub struct ConsensusPublisher {
+    network_sender: ConsensusObserverNetworkSender,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+    // The (epoch, round) of the last ordered block that was published
+    last_published_round: Mutex<(u64, Round)>,
+}
+
+impl ConsensusPublisher {
+    pub fn new(
+        network_sender: ConsensusObserverNetworkSender,
+        peer_metadata_storage: Arc<PeerMetadataStorage>,
+    ) -> Self {
+        Self {
+            network_sender,
+            peer_metadata_storage,
+            last_published_round: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Publishes the given ordered blocks to all subscribers. Blocks are only
+    /// published once: with decoupled execution they are published when ordered,
+    /// and skipped when the same blocks are later committed.
+    pub fn publish_ordered_blocks(
+        &self,
+        blocks: &[Arc<ExecutedBlock>],
+        ordered_proof: &LedgerInfoWithSignatures,
+    ) {
+        if blocks.is_empty() {
+            return;
+        }
+
+        let ordered_round = (
+            ordered_proof.ledger_info().epoch(),
+            ordered_proof.commit_info().round(),
+        );
+        {
+            let mut last_published_round = self.last_published_round.lock();
+            if ordered_round <= *last_published_round {
+                return;
+            }
+            *last_published_round = ordered_round;
+        }
+
+        let ordered_block = OrderedBlock {
+            blocks: blocks.iter().map(|block| block.block().clone()).collect(),
+            ordered_proof: ordered_proof.clone(),
+        };
+        self.publish(ConsensusObserverMessage::OrderedBlock(ordered_block));
+    }
+
+    /// Publishes the given commit decision to all subscribers
+    pub fn publish_commit_decision(&self, commit_proof: &LedgerInfoWithSignatures) {
+        self.publish(ConsensusObserverMessage::CommitDecision(
+            commit_proof.clone(),
+        ));
+    }
+
+    /// Returns the currently subscribed peers, i.e., the connected VFNs that
+    /// support the consensus observer protocol.
+    pub fn get_subscribers(&self) -> Vec<PeerId> {
+        self.peer_metadata_storage
+            .read_filtered(NetworkId::Vfn, |(_, peer_info)| {
+                peer_info.is_connected()
+                    && peer_info.supports_protocol(ProtocolId::ConsensusObserverDirectSend)
+            })
+            .into_keys()
+            .map(|peer| peer.peer_id())
+            .collect()
+    }
+
+    /// Sends the message to all subscribers (if there are any)
+    fn publish(&self, message: ConsensusObserverMessage) {
+        let subscribers = self.get_subscribers();
+        if subscribers.is_empty() {
+            return;
+        }
+
+        counters::CONSENSUS_OBSERVER_PUBLISHED_MESSAGES
+            .with_label_values(&[message.get_label()])
+            .inc();
+        if let Err(error) = self.network_sender.send_to_many(
+            subscribers.into_iter(),
+            ProtocolId::ConsensusObserverDirectSend,
+            message,
+        ) {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(ERROR_LOG_FREQ_SECS)),
+                warn!(error = ?error, "Failed to publish the consensus observer message")
+            );
+        }
+    }
+}
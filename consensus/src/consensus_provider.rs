@@ -3,6 +3,11 @@
 
 use crate::{
     commit_notifier::QuorumStoreCommitNotifier,
+    consensus_observer::{
+        network::{ConsensusObserverNetworkEvents, ConsensusObserverNetworkSender},
+        observer::ConsensusObserver,
+        publisher::ConsensusPublisher,
+    },
     counters,
     epoch_manager::EpochManager,
     network::NetworkTask,
@@ -16,7 +21,7 @@ use aptos_config::config::NodeConfig;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_vm::AptosVM;
-use consensus_notifications::ConsensusNotificationSender;
+use consensus_notifications::{ConsensusNotificationSender, ConsensusObserverStatus};
 use event_notifications::ReconfigNotificationListener;
 use executor::block_executor::BlockExecutor;
 use futures::channel::mpsc;
@@ -35,6 +40,7 @@ pub fn start_consensus(
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
+    consensus_observer_network_sender: Option<ConsensusObserverNetworkSender>,
 ) -> Runtime {
     let runtime = runtime::Builder::new_multi_thread()
         .thread_name("consensus")
@@ -50,12 +56,20 @@ pub fn start_consensus(
         node_config.consensus.quorum_store_pull_timeout_ms,
     ));
 
+    let consensus_publisher = consensus_observer_network_sender.map(|network_sender| {
+        Arc::new(ConsensusPublisher::new(
+            network_sender,
+            peer_metadata_storage.clone(),
+        ))
+    });
+
     let state_computer = Arc::new(ExecutionProxy::new(
         Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
         txn_notifier,
         state_sync_notifier,
         commit_notifier.clone(),
         runtime.handle(),
+        consensus_publisher.clone(),
     ));
 
     let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
@@ -75,6 +89,7 @@ pub fn start_consensus(
         storage,
         reconfig_events,
         commit_notifier,
+        consensus_publisher,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
@@ -85,3 +100,31 @@ pub fn start_consensus(
     debug!("Consensus started.");
     runtime
 }
+
+/// Helper function to start the consensus observer (on validator fullnodes) and return the runtime
+pub fn start_consensus_observer(
+    node_config: &NodeConfig,
+    network_events: ConsensusObserverNetworkEvents,
+    state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
+    observer_status: ConsensusObserverStatus,
+    aptos_db: DbReaderWriter,
+    reconfig_events: ReconfigNotificationListener,
+) -> Runtime {
+    let runtime = runtime::Builder::new_multi_thread()
+        .thread_name("consensus-observer")
+        .enable_all()
+        .build()
+        .expect("Failed to create Tokio runtime!");
+
+    let consensus_observer = ConsensusObserver::new(
+        node_config.consensus_observer,
+        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        state_sync_notifier,
+        observer_status,
+        reconfig_events,
+    );
+    runtime.spawn(consensus_observer.start(network_events));
+
+    debug!("Consensus observer started.");
+    runtime
+}
@@ -3,7 +3,7 @@
 
 use super::*;
 use aptos_temppath::TempPath;
-use consensus_types::block::block_test_utils::certificate_for_genesis;
+use consensus_types::block::block_test_utils::{certificate_for_genesis, random_transactions};
 
 #[test]
 fn test_put_get() {
@@ -69,3 +69,29 @@ fn test_delete_block_and_qc() {
     assert_eq!(db.get_blocks().unwrap().len(), 0);
     assert_eq!(db.get_quorum_certificates().unwrap().len(), 0);
 }
+
+#[test]
+fn test_put_get_delete_batches() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    let author = aptos_types::account_address::AccountAddress::random();
+    let batches: Vec<_> = (0..3)
+        .map(|i| Batch::new(1, author, i, random_transactions(2)))
+        .collect();
+    for batch in &batches {
+        db.save_batch(batch).unwrap();
+    }
+
+    let persisted = db.get_all_batches().unwrap();
+    assert_eq!(persisted.len(), 3);
+    for batch in &batches {
+        assert_eq!(persisted.get(&batch.digest()), Some(batch));
+    }
+
+    db.delete_batches(vec![batches[0].digest(), batches[1].digest()])
+        .unwrap();
+    let persisted = db.get_all_batches().unwrap();
+    assert_eq!(persisted.len(), 1);
+    assert!(persisted.contains_key(&batches[2].digest()));
+}
@@ -0,0 +1,15 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_types::account_address::AccountAddress;
+use consensus_types::block::block_test_utils::random_transactions;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+#[test]
+fn test_encode_decode() {
+    let batch = Batch::new(1, AccountAddress::random(), 100, random_transactions(2));
+    assert_encode_decode::<BatchSchema>(&batch.digest(), &batch);
+}
+
+test_no_panic_decoding!(BatchSchema);
@@ -4,6 +4,7 @@
 use crate::{
     block_storage::BlockStore,
     commit_notifier::CommitNotifier,
+    consensus_observer::publisher::ConsensusPublisher,
     counters,
     error::{error_kind, DbError},
     experimental::{
@@ -15,7 +16,7 @@ use crate::{
         cached_proposer_election::CachedProposerElection,
         leader_reputation::{
             extract_epoch_to_proposers, AptosDBBackend, LeaderReputation,
-            ProposerAndVoterHeuristic, ReputationHeuristic,
+            ProposerAndVoterHeuristic, ReputationHeuristic, StakeAndLatencyHeuristic,
         },
         proposal_generator::ProposalGenerator,
         proposer_election::ProposerElection,
@@ -26,11 +27,17 @@ use crate::{
     logging::{LogEvent, LogSchema},
     metrics_safety_rules::MetricsSafetyRules,
     monitor,
-    network::{IncomingBlockRetrievalRequest, NetworkReceivers, NetworkSender},
+    network::{
+        IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, NetworkReceivers,
+        NetworkSender,
+    },
     network_interface::{ConsensusMsg, ConsensusNetworkSender},
     payload_manager::QuorumStoreClient,
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
-    quorum_store::direct_mempool_quorum_store::DirectMempoolQuorumStore,
+    quorum_store::{
+        batch_store::BatchStore, direct_mempool_quorum_store::DirectMempoolQuorumStore,
+        networked_quorum_store::NetworkedQuorumStore,
+    },
     round_manager::{RoundManager, UnverifiedEvent, VerifiedEvent},
     state_replication::StateComputer,
     util::time_service::TimeService,
@@ -48,6 +55,7 @@ use aptos_types::{
         LeaderReputationType, OnChainConfigPayload, OnChainConsensusConfig, ProposerElectionType,
         ValidatorSet,
     },
+    validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
 use channel::{aptos_channel, message_queues::QueueStyle};
@@ -67,7 +75,7 @@ use futures::{
 };
 use itertools::Itertools;
 use network::protocols::network::{ApplicationNetworkSender, Event};
-use safety_rules::SafetyRulesManager;
+use safety_rules::{PersistentSafetyStorage, SafetyRulesManager};
 use std::{
     cmp::Ordering,
     collections::HashMap,
@@ -111,8 +119,12 @@ pub struct EpochManager {
     commit_state_computer: Arc<dyn StateComputer>,
     storage: Arc<dyn PersistentLivenessStorage>,
     safety_rules_manager: SafetyRulesManager,
+    // storage of the consensus key used to sign batches, only set when the quorum store is enabled
+    quorum_store_key_storage: Option<PersistentSafetyStorage>,
     reconfig_events: ReconfigNotificationListener,
     commit_notifier: Arc<dyn CommitNotifier>,
+    // publishes ordered blocks to subscribed consensus observers (if enabled)
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
     // channels to buffer manager
     buffer_manager_msg_tx: Option<aptos_channel::Sender<AccountAddress, VerifiedEvent>>,
     buffer_manager_reset_tx: Option<UnboundedSender<ResetRequest>>,
@@ -120,8 +132,11 @@ pub struct EpochManager {
     round_manager_tx: Option<
         aptos_channel::Sender<(Author, Discriminant<VerifiedEvent>), (Author, VerifiedEvent)>,
     >,
+    // channel to quorum store
+    quorum_store_msg_tx: Option<aptos_channel::Sender<AccountAddress, VerifiedEvent>>,
     epoch_state: Option<EpochState>,
     block_store: Option<Arc<BlockStore>>,
+    batch_store: Option<Arc<BatchStore>>,
 }
 
 impl EpochManager {
@@ -136,11 +151,17 @@ impl EpochManager {
         storage: Arc<dyn PersistentLivenessStorage>,
         reconfig_events: ReconfigNotificationListener,
         commit_notifier: Arc<dyn CommitNotifier>,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
         let sr_config = &node_config.consensus.safety_rules;
         let safety_rules_manager = SafetyRulesManager::new(sr_config);
+        let quorum_store_key_storage = if config.use_quorum_store {
+            Some(safety_rules::storage(sr_config))
+        } else {
+            None
+        };
         Self {
             author,
             config,
@@ -152,13 +173,17 @@ impl EpochManager {
             commit_state_computer,
             storage,
             safety_rules_manager,
+            quorum_store_key_storage,
             reconfig_events,
             commit_notifier,
+            consensus_publisher,
             buffer_manager_msg_tx: None,
             buffer_manager_reset_tx: None,
             round_manager_tx: None,
+            quorum_store_msg_tx: None,
             epoch_state: None,
             block_store: None,
+            batch_store: None,
         }
     }
 
@@ -193,6 +218,16 @@ impl EpochManager {
         epoch_state: &EpochState,
         onchain_config: &OnChainConsensusConfig,
     ) -> Box<dyn ProposerElection + Send + Sync> {
+        // Invalid configs are rejected when the on-chain config is read, but never
+        // start an epoch with one: all validators would fail in the same way.
+        if let Err(error) = onchain_config.validate() {
+            error!(
+                "Invalid on-chain consensus config, using the default proposer election: {}",
+                error
+            );
+            return self.create_proposer_election(epoch_state, &OnChainConsensusConfig::default());
+        }
+
         let proposers = epoch_state
             .verifier
             .get_ordered_account_addresses_iter()
@@ -235,6 +270,27 @@ impl EpochManager {
                             proposer_and_voter_config.use_history_from_previous_epoch_max_count,
                         )
                     }
+                    LeaderReputationType::StakeAndLatency(stake_and_latency_config) => {
+                        let window_size = proposers.len()
+                            * stake_and_latency_config.window_num_validators_multiplier;
+                        let heuristic: Box<dyn ReputationHeuristic> =
+                            Box::new(StakeAndLatencyHeuristic::new(
+                                stake_and_latency_config.max_weight,
+                                stake_and_latency_config.min_weight,
+                                stake_and_latency_config.no_history_weight,
+                                stake_and_latency_config.decay_percent,
+                                stake_and_latency_config.min_latency_multiplier_percent,
+                                stake_and_latency_config.max_latency_multiplier_percent,
+                                window_size,
+                            ));
+                        // Stake is always part of the reputation for this type
+                        (
+                            heuristic,
+                            window_size,
+                            true,
+                            stake_and_latency_config.use_history_from_previous_epoch_max_count,
+                        )
+                    }
                 };
 
                 let seek_len = onchain_config.leader_reputation_exclude_round() as usize
@@ -406,9 +462,98 @@ impl EpochManager {
         Ok(())
     }
 
+    /// Spawns the quorum store of the epoch and returns the batch store it persists batches to,
+    /// if the quorum store is enabled.
+    ///
+    /// Only validators of the epoch can sign batches, so other nodes just keep the batch store to
+    /// fetch the batches of committed blocks, and pull transactions from mempool directly.
     fn spawn_quorum_store(
         &mut self,
         consensus_to_quorum_store_receiver: Receiver<ConsensusRequest>,
+        epoch_state: &EpochState,
+        network_sender: NetworkSender,
+    ) -> Option<Arc<BatchStore>> {
+        let validator_signer = match &self.quorum_store_key_storage {
+            Some(key_storage) => {
+                Self::quorum_store_signer(self.author, key_storage, epoch_state).map(Arc::new)
+            }
+            None => {
+                self.spawn_direct_mempool_quorum_store(consensus_to_quorum_store_receiver);
+                return None;
+            }
+        };
+
+        let batch_store = Arc::new(BatchStore::new(
+            epoch_state.epoch,
+            self.author,
+            self.storage.quorum_store_db(),
+            network_sender.clone(),
+            epoch_state.verifier.clone(),
+            self.config.quorum_store_batch_request_timeout_ms,
+        ));
+        let validator_signer = match validator_signer {
+            Ok(validator_signer) => validator_signer,
+            Err(error) => {
+                warn!(
+                    epoch = epoch_state.epoch,
+                    error = ?error,
+                    "Not running the QuorumStore of the epoch, pulling from mempool directly",
+                );
+                self.spawn_direct_mempool_quorum_store(consensus_to_quorum_store_receiver);
+                return Some(batch_store);
+            }
+        };
+
+        let (quorum_store_msg_tx, quorum_store_msg_rx) =
+            aptos_channel::new::<AccountAddress, VerifiedEvent>(
+                QueueStyle::FIFO,
+                self.config.channel_size,
+                Some(&counters::QUORUM_STORE_MSGS),
+            );
+        self.quorum_store_msg_tx = Some(quorum_store_msg_tx);
+
+        let quorum_store = NetworkedQuorumStore::new(
+            epoch_state.epoch,
+            self.author,
+            self.config.clone(),
+            consensus_to_quorum_store_receiver,
+            self.quorum_store_to_mempool_sender.clone(),
+            quorum_store_msg_rx,
+            network_sender,
+            batch_store.clone(),
+            validator_signer,
+            epoch_state.verifier.clone(),
+        );
+        tokio::spawn(quorum_store.start());
+        Some(batch_store)
+    }
+
+    /// Returns the signer of the QuorumStore, which requires the node to be a validator of the
+    /// epoch with access to its consensus key.
+    fn quorum_store_signer(
+        author: Author,
+        key_storage: &PersistentSafetyStorage,
+        epoch_state: &EpochState,
+    ) -> anyhow::Result<ValidatorSigner> {
+        let public_key = epoch_state
+            .verifier
+            .get_public_key(&author)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} is not a validator of epoch {}",
+                    author,
+                    epoch_state.epoch
+                )
+            })?;
+        let consensus_key = key_storage
+            .consensus_key_for_version(public_key)
+            .context("Unable to retrieve the consensus key for the QuorumStore")?;
+        Ok(ValidatorSigner::new(author, consensus_key))
+    }
+
+    fn spawn_direct_mempool_quorum_store(
+        &self,
+        consensus_to_quorum_store_receiver: Receiver<ConsensusRequest>,
     ) {
         let quorum_store = DirectMempoolQuorumStore::new(
             consensus_to_quorum_store_receiver,
@@ -462,7 +607,12 @@ impl EpochManager {
         tokio::spawn(persisting_phase.start());
         tokio::spawn(buffer_manager.start());
 
-        OrderingStateComputer::new(block_tx, self.commit_state_computer.clone(), reset_tx)
+        OrderingStateComputer::new(
+            block_tx,
+            self.commit_state_computer.clone(),
+            reset_tx,
+            self.consensus_publisher.clone(),
+        )
     }
 
     async fn shutdown_current_processor(&mut self) {
@@ -477,6 +627,11 @@ impl EpochManager {
         }
         self.round_manager_tx = None;
 
+        // Stop forwarding messages to the previous quorum store, which stops once consensus
+        // releases its channel.
+        self.quorum_store_msg_tx = None;
+        self.batch_store = None;
+
         // Shutdown the previous buffer manager, to release the SafetyRule client
         self.buffer_manager_msg_tx = None;
         if let Some(mut tx) = self.buffer_manager_reset_tx.take() {
@@ -539,16 +694,23 @@ impl EpochManager {
 
         let (consensus_to_quorum_store_sender, consensus_to_quorum_store_receiver) =
             mpsc::channel(self.config.intra_consensus_channel_buffer_size);
-        self.spawn_quorum_store(consensus_to_quorum_store_receiver);
-        let payload_manager = QuorumStoreClient::new(
+        let batch_store = self.spawn_quorum_store(
+            consensus_to_quorum_store_receiver,
+            &epoch_state,
+            network_sender.clone(),
+        );
+        let payload_manager = Arc::new(QuorumStoreClient::new(
             consensus_to_quorum_store_sender.clone(),
             self.config.quorum_store_poll_count,
             self.config.quorum_store_pull_timeout_ms,
-        );
+            batch_store.clone(),
+        ));
+        self.batch_store = batch_store;
         self.commit_notifier
             .new_epoch(consensus_to_quorum_store_sender);
 
-        self.commit_state_computer.new_epoch(&epoch_state);
+        self.commit_state_computer
+            .new_epoch(&epoch_state, payload_manager.clone());
         let state_computer = if onchain_config.decoupled_execution() {
             Arc::new(self.spawn_decoupled_execution(
                 safety_rules_container.clone(),
@@ -574,7 +736,7 @@ impl EpochManager {
         let proposal_generator = ProposalGenerator::new(
             self.author,
             block_store.clone(),
-            Arc::new(payload_manager),
+            payload_manager,
             self.time_service.clone(),
             self.config.max_block_txns,
             self.config.max_block_bytes,
@@ -673,7 +835,10 @@ impl EpochManager {
             | ConsensusMsg::SyncInfo(_)
             | ConsensusMsg::VoteMsg(_)
             | ConsensusMsg::CommitVoteMsg(_)
-            | ConsensusMsg::CommitDecisionMsg(_) => {
+            | ConsensusMsg::CommitDecisionMsg(_)
+            | ConsensusMsg::BatchMsg(_)
+            | ConsensusMsg::SignedDigestMsg(_)
+            | ConsensusMsg::ProofOfStoreMsg(_) => {
                 let event: UnverifiedEvent = msg.into();
                 if event.epoch() == self.epoch() {
                     return Ok(Some(event));
@@ -733,6 +898,15 @@ impl EpochManager {
                     bail!("Commit Phase not started but received Commit Message (CommitVote/CommitDecision)");
                 }
             }
+            quorum_store_event @ (VerifiedEvent::Batch(_)
+            | VerifiedEvent::SignedDigest(_)
+            | VerifiedEvent::ProofOfStore(_)) => {
+                if let Some(sender) = &mut self.quorum_store_msg_tx {
+                    sender.push(peer_id, quorum_store_event)?;
+                } else {
+                    bail!("QuorumStore not enabled but received QuorumStore Message (Batch/SignedDigest/ProofOfStore)");
+                }
+            }
             round_manager_event => {
                 self.forward_to_round_manager(peer_id, round_manager_event);
             }
@@ -761,6 +935,17 @@ impl EpochManager {
         }
     }
 
+    async fn process_batch_retrieval(
+        &self,
+        request: IncomingBatchRetrievalRequest,
+    ) -> anyhow::Result<()> {
+        if let Some(batch_store) = &self.batch_store {
+            batch_store.process_batch_request(request).await
+        } else {
+            Err(anyhow::anyhow!("QuorumStore not started"))
+        }
+    }
+
     fn process_local_timeout(&mut self, round: u64) {
         self.forward_to_round_manager(self.author, VerifiedEvent::LocalTimeout(round));
     }
@@ -794,6 +979,16 @@ impl EpochManager {
                         error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
                     }
                 }
+                Some((peer, msg)) = network_receivers.quorum_store_messages.next() => {
+                    if let Err(e) = self.process_message(peer, msg).await {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                }
+                Some(request) = network_receivers.batch_retrieval.next() => {
+                    if let Err(e) = self.process_batch_retrieval(request).await {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                }
                 Some(round) = round_timeout_sender_rx.next() => {
                     self.process_local_timeout(round);
                 }
@@ -38,6 +38,8 @@ mod twins;
 mod txn_notifier;
 mod util;
 
+/// Consensus observer for validator fullnodes
+pub mod consensus_observer;
 /// AptosBFT implementation
 pub mod consensus_provider;
 /// Required by the telemetry service
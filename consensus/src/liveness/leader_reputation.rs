@@ -443,6 +443,185 @@ impl ReputationHeuristic for ProposerAndVoterHeuristic {
     }
 }
 
+/// Scale of the decay weight of the newest block in the window. Older blocks get
+/// geometrically smaller weights, computed with integer arithmetic only.
+const DECAY_WEIGHT_SCALE: u128 = 1_000_000_000;
+
+/// Decayed proposal statistics of a single validator within the window.
+#[derive(Default)]
+struct DecayedProposerStats {
+    successes: u128,
+    failures: u128,
+    latency_sum: u128,
+    latency_weight: u128,
+}
+
+impl DecayedProposerStats {
+    fn average_latency(&self) -> Option<u128> {
+        if self.latency_weight > 0 {
+            Some(self.latency_sum / self.latency_weight)
+        } else {
+            None
+        }
+    }
+}
+
+/// Heuristic that combines a decaying history of proposal successes and round latencies
+/// to define node reputation, used (together with voting power) for leader selection.
+///
+/// Logic is:
+///  * blocks in the window are ordered from newest to oldest by (epoch, round), and each one
+///    counts decay_percent less than the block after it.
+///  * the proposer of each block gets a success, and each failed proposer a failure.
+///  * round latency is the difference between the timestamp of the next block and the block
+///    timestamp, and is only attributed when the next block is from the next round and from a
+///    different proposer. A proposer can't lower its latency by choosing its timestamp: an
+///    earlier timestamp increases it, and voters wait for a later timestamp before voting.
+///  * base weight is max_weight scaled by the decayed success rate (at least min_weight), or
+///    no_history_weight if node had no proposal rounds within the window.
+///  * base weight is multiplied by the ratio of the median latency across candidates to the
+///    node's own latency, bounded by [min_latency_multiplier_percent, max_latency_multiplier_percent].
+///
+/// Decay makes the heuristic react to recent changes in node performance, while older history
+/// still prevents a single failure from excluding an otherwise healthy node.
+/// Only integer arithmetic is used, and history is ordered independently of the backend,
+/// so all nodes compute the same weights from the same committed NewBlockEvent history.
+pub struct StakeAndLatencyHeuristic {
+    max_weight: u64,
+    min_weight: u64,
+    no_history_weight: u64,
+    decay_percent: u32,
+    min_latency_multiplier_percent: u64,
+    max_latency_multiplier_percent: u64,
+    window_size: usize,
+}
+
+impl StakeAndLatencyHeuristic {
+    pub fn new(
+        max_weight: u64,
+        min_weight: u64,
+        no_history_weight: u64,
+        decay_percent: u32,
+        min_latency_multiplier_percent: u64,
+        max_latency_multiplier_percent: u64,
+        window_size: usize,
+    ) -> Self {
+        Self {
+            max_weight,
+            min_weight,
+            no_history_weight,
+            decay_percent,
+            min_latency_multiplier_percent,
+            max_latency_multiplier_percent,
+            window_size,
+        }
+    }
+
+    fn aggregate_stats(
+        &self,
+        epoch_to_candidates: &HashMap<u64, Vec<Author>>,
+        history: &[NewBlockEvent],
+    ) -> HashMap<Author, DecayedProposerStats> {
+        let mut events: Vec<&NewBlockEvent> = history
+            .iter()
+            .filter(|meta| epoch_to_candidates.contains_key(&meta.epoch()))
+            .collect();
+        events.sort_by_key(|meta| std::cmp::Reverse((meta.epoch(), meta.round())));
+        events.dedup_by_key(|meta| (meta.epoch(), meta.round()));
+        events.truncate(self.window_size);
+
+        let mut stats: HashMap<Author, DecayedProposerStats> = HashMap::new();
+        let mut decay_weight = DECAY_WEIGHT_SCALE;
+        for (i, meta) in events.iter().enumerate() {
+            let proposer_stats = stats.entry(meta.proposer()).or_default();
+            proposer_stats.successes += decay_weight;
+            if let Some(next) = i.checked_sub(1).map(|next_index| events[next_index]) {
+                if next.epoch() == meta.epoch()
+                    && next.round() == meta.round() + 1
+                    && next.proposer() != meta.proposer()
+                {
+                    let latency = next.proposed_time().saturating_sub(meta.proposed_time());
+                    proposer_stats.latency_sum += latency as u128 * decay_weight;
+                    proposer_stats.latency_weight += decay_weight;
+                }
+            }
+
+            match NewBlockEventAggregation::indices_to_validators(
+                &epoch_to_candidates[&meta.epoch()],
+                meta.failed_proposer_indices(),
+            ) {
+                Ok(failed_proposers) => {
+                    for &failed_proposer in failed_proposers {
+                        stats.entry(failed_proposer).or_default().failures += decay_weight;
+                    }
+                }
+                Err(msg) => {
+                    error!(
+                        "Failed proposer conversion from indices failed at epoch {}, round {}: {}",
+                        meta.epoch(),
+                        meta.round(),
+                        msg
+                    )
+                }
+            }
+
+            decay_weight = decay_weight * 100u128.saturating_sub(self.decay_percent as u128) / 100;
+        }
+        stats
+    }
+
+    fn latency_multiplier_percent(&self, latency: Option<u128>, median_latency: u128) -> u128 {
+        match latency {
+            Some(0) => self.max_latency_multiplier_percent as u128,
+            Some(latency) => (100 * median_latency / latency)
+                .max(self.min_latency_multiplier_percent as u128)
+                .min(self.max_latency_multiplier_percent as u128),
+            None => 100,
+        }
+    }
+}
+
+impl ReputationHeuristic for StakeAndLatencyHeuristic {
+    fn get_weights(
+        &self,
+        epoch: u64,
+        epoch_to_candidates: &HashMap<u64, Vec<Author>>,
+        history: &[NewBlockEvent],
+    ) -> Vec<u64> {
+        assert!(epoch_to_candidates.contains_key(&epoch));
+
+        let stats = self.aggregate_stats(epoch_to_candidates, history);
+        let candidates = &epoch_to_candidates[&epoch];
+
+        let mut latencies: Vec<u128> = candidates
+            .iter()
+            .filter_map(|author| stats.get(author).and_then(|s| s.average_latency()))
+            .collect();
+        latencies.sort_unstable();
+        let median_latency = latencies
+            .get(latencies.len().saturating_sub(1) / 2)
+            .copied()
+            .unwrap_or(0);
+
+        candidates
+            .iter()
+            .map(|author| match stats.get(author) {
+                Some(cur_stats) if cur_stats.successes + cur_stats.failures > 0 => {
+                    let base_weight = self.max_weight as u128 * cur_stats.successes
+                        / (cur_stats.successes + cur_stats.failures);
+                    let multiplier = self
+                        .latency_multiplier_percent(cur_stats.average_latency(), median_latency);
+                    std::cmp::max(
+                        self.min_weight,
+                        u64::try_from(base_weight * multiplier / 100).unwrap_or(u64::MAX),
+                    )
+                }
+                _ => self.no_history_weight,
+            })
+            .collect()
+    }
+}
+
 /// Committed history based proposer election implementation that could help bias towards
 /// successful leaders to help improve performance.
 pub struct LeaderReputation {
@@ -490,7 +669,7 @@ impl ProposerElection for LeaderReputation {
         weights
             .iter_mut()
             .enumerate()
-            .for_each(|(i, w)| *w *= self.voting_powers[i]);
+            .for_each(|(i, w)| *w = w.saturating_mul(self.voting_powers[i]));
 
         let state = [self.epoch.to_le_bytes(), round.to_le_bytes()]
             .concat()
@@ -3,6 +3,7 @@
 
 use super::leader_reputation::{
     extract_epoch_to_proposers_impl, AptosDBBackend, ProposerAndVoterHeuristic,
+    StakeAndLatencyHeuristic,
 };
 use crate::liveness::{
     leader_reputation::{
@@ -87,6 +88,25 @@ impl TestBlockBuilder {
             self.round * 3600,
         )
     }
+
+    fn create_block_with_timestamp(
+        &mut self,
+        proposer: Author,
+        failed_proposers: Vec<u64>,
+        timestamp: u64,
+    ) -> NewBlockEvent {
+        self.round += 1 + failed_proposers.len() as u64;
+        NewBlockEvent::new(
+            AccountAddress::random(),
+            self.epoch,
+            self.round,
+            self.round,
+            BitVec::from(vec![true; 4]).into(),
+            proposer,
+            failed_proposers,
+            timestamp,
+        )
+    }
 }
 
 /// #### NewBlockEventAggregation tests ####
@@ -348,6 +368,289 @@ fn test_proposer_and_voter_heuristic() {
     );
 }
 
+#[test]
+fn test_stake_and_latency_heuristic() {
+    let validators: Vec<Author> = (0..4).map(|_| Author::random()).collect();
+    let epoch_to_validators = HashMap::from([(0u64, validators.clone())]);
+    // No decay, so that weights only depend on success rate and latency
+    let heuristic = StakeAndLatencyHeuristic::new(1000, 1, 100, 0, 50, 200, 100);
+
+    let mut block_builder = TestBlockBuilder::new();
+    let mut history = vec![
+        block_builder.create_block_with_timestamp(validators[0], vec![], 1000),
+        block_builder.create_block_with_timestamp(validators[1], vec![], 2000),
+        block_builder.create_block_with_timestamp(validators[0], vec![], 3000),
+        // latency until a block after a failed round isn't attributed to the proposer
+        block_builder.create_block_with_timestamp(validators[1], vec![], 4000),
+        block_builder.create_block_with_timestamp(validators[2], vec![3], 8000),
+        block_builder.create_block_with_timestamp(validators[0], vec![], 12000),
+    ];
+
+    // validators[2] is 4 times slower than the median, bounded by 50%,
+    // and validators[3] only has failures
+    let expected_weights = vec![1000, 1000, 500, 1];
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &history),
+        expected_weights
+    );
+
+    // Weights don't depend on the order in which history is provided
+    history.reverse();
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &history),
+        expected_weights
+    );
+
+    // Window is limited to the most recent blocks, by (epoch, round)
+    let heuristic = StakeAndLatencyHeuristic::new(1000, 1, 100, 0, 50, 200, 2);
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &history),
+        vec![1000, 100, 1000, 1]
+    );
+}
+
+#[test]
+fn test_stake_and_latency_heuristic_made_up_timestamp() {
+    let validators: Vec<Author> = (0..4).map(|_| Author::random()).collect();
+    let epoch_to_validators = HashMap::from([(0u64, validators.clone())]);
+    let heuristic = StakeAndLatencyHeuristic::new(1000, 1, 100, 0, 50, 200, 100);
+
+    // validators[2] is slow: the next block is only proposed 4000 after its block
+    let create_history = |slow_proposer_timestamps: [u64; 2]| {
+        let mut block_builder = TestBlockBuilder::new();
+        vec![
+            block_builder.create_block_with_timestamp(validators[0], vec![], 1000),
+            block_builder.create_block_with_timestamp(validators[1], vec![], 2000),
+            block_builder.create_block_with_timestamp(
+                validators[2],
+                vec![],
+                slow_proposer_timestamps[0],
+            ),
+            block_builder.create_block_with_timestamp(validators[0], vec![], 7000),
+            block_builder.create_block_with_timestamp(validators[1], vec![], 8000),
+            block_builder.create_block_with_timestamp(
+                validators[2],
+                vec![],
+                slow_proposer_timestamps[1],
+            ),
+            block_builder.create_block_with_timestamp(validators[0], vec![], 13000),
+        ]
+    };
+    let honest_history = create_history([3000, 9000]);
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &honest_history),
+        vec![1000, 1000, 500, 100]
+    );
+
+    // validators[2] makes up timestamps right after the previous block to appear fast,
+    // but that only increases the latency until the next block
+    let made_up_history = create_history([2001, 8001]);
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &made_up_history)[2],
+        500
+    );
+}
+
+#[test]
+fn test_stake_and_latency_heuristic_decay() {
+    let validators: Vec<Author> = (0..4).map(|_| Author::random()).collect();
+    let epoch_to_validators = HashMap::from([(0u64, validators.clone())]);
+    // Each block counts half as much as the block after it
+    let heuristic = StakeAndLatencyHeuristic::new(1000, 1, 100, 50, 50, 200, 100);
+
+    let mut block_builder = TestBlockBuilder::new();
+    let history = vec![
+        block_builder.create_block(validators[0], vec![true; 4], vec![1]),
+        block_builder.create_block(validators[1], vec![true; 4], vec![0]),
+        block_builder.create_block(validators[0], vec![true; 4], vec![]),
+    ];
+
+    // validators[0]: successes 1 + 1/4, failures 1/2
+    // validators[1]: successes 1/2, failures 1/4
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &history),
+        vec![714, 666, 100, 100]
+    );
+}
+
+#[test]
+fn test_stake_and_latency_heuristic_large_weights() {
+    let validators: Vec<Author> = (0..4).map(|_| Author::random()).collect();
+    let epoch_to_validators = HashMap::from([(0u64, validators.clone())]);
+    let max_weight = u64::MAX / 2 + 1;
+    let heuristic = StakeAndLatencyHeuristic::new(max_weight, 1, 100, 0, 50, 200, 100);
+
+    // validators[0] is twice as fast as the median
+    let mut block_builder = TestBlockBuilder::new();
+    let history = vec![
+        block_builder.create_block_with_timestamp(validators[0], vec![], 1000),
+        block_builder.create_block_with_timestamp(validators[1], vec![], 1500),
+        block_builder.create_block_with_timestamp(validators[2], vec![], 2500),
+        block_builder.create_block_with_timestamp(validators[0], vec![], 3500),
+    ];
+
+    // Weights above u64::MAX saturate instead of being truncated
+    assert_eq!(
+        heuristic.get_weights(0, &epoch_to_validators, &history),
+        vec![u64::MAX, max_weight, max_weight, 100]
+    );
+
+    // Multiplying the weights by the voting power doesn't overflow either
+    let leader_reputation = LeaderReputation::new(
+        0,
+        epoch_to_validators,
+        vec![u64::MAX; validators.len()],
+        Box::new(MockHistory::new(100, history)),
+        Box::new(heuristic),
+        0,
+    );
+    assert!(validators.contains(&leader_reputation.get_valid_proposer(1)));
+}
+
+struct SimulatedValidator {
+    voting_power: u64,
+    fails_to_propose: bool,
+    round_latency: u64,
+}
+
+/// Runs leader election with StakeAndLatencyHeuristic for the given number of rounds,
+/// feeding back the simulated outcome of each round into the history.
+/// Returns how many times each validator was selected.
+fn simulate_stake_and_latency_election(
+    validators: &[SimulatedValidator],
+    num_rounds: u64,
+) -> Vec<u64> {
+    let epoch = 1;
+    let round_timeout = 10_000;
+    let window_size = validators.len() * 10;
+    let proposers: Vec<Author> = (0..validators.len())
+        .map(|_| Author::random())
+        .sorted()
+        .collect();
+    let voting_powers: Vec<u64> = validators.iter().map(|v| v.voting_power).collect();
+
+    let mut history: Vec<NewBlockEvent> = vec![];
+    let mut failed_proposers = vec![];
+    let mut timestamp = 0;
+    let mut selected = vec![0; validators.len()];
+    for round in 1..=num_rounds {
+        let leader_reputation = LeaderReputation::new(
+            epoch,
+            HashMap::from([(epoch, proposers.clone())]),
+            voting_powers.clone(),
+            Box::new(MockHistory::new(window_size, history.clone())),
+            Box::new(StakeAndLatencyHeuristic::new(
+                1000,
+                1,
+                100,
+                2,
+                10,
+                200,
+                window_size,
+            )),
+            0,
+        );
+        let leader = leader_reputation.get_valid_proposer(round);
+        let index = proposers.iter().position(|p| *p == leader).unwrap();
+        selected[index] += 1;
+
+        let validator = &validators[index];
+        if validator.fails_to_propose {
+            timestamp += round_timeout;
+            failed_proposers.push(index as u64);
+        } else {
+            history.push(NewBlockEvent::new(
+                AccountAddress::random(),
+                epoch,
+                round,
+                round,
+                BitVec::from(vec![true; validators.len()]).into(),
+                leader,
+                std::mem::take(&mut failed_proposers),
+                timestamp,
+            ));
+            if history.len() > window_size {
+                history.remove(0);
+            }
+            // The next block can only be proposed once this block is certified
+            timestamp += validator.round_latency;
+        }
+    }
+    selected
+}
+
+#[test]
+fn test_stake_and_latency_simulation() {
+    let num_rounds = 2000;
+    let normal = |voting_power| SimulatedValidator {
+        voting_power,
+        fails_to_propose: false,
+        round_latency: 1000,
+    };
+    let validators = vec![
+        // High stake validator
+        normal(4),
+        normal(1),
+        normal(1),
+        normal(1),
+        // Validator that never manages to propose
+        SimulatedValidator {
+            voting_power: 1,
+            fails_to_propose: true,
+            round_latency: 1000,
+        },
+        // Validator with 4 times higher round latency
+        SimulatedValidator {
+            voting_power: 1,
+            fails_to_propose: false,
+            round_latency: 4000,
+        },
+    ];
+
+    let selected = simulate_stake_and_latency_election(&validators, num_rounds);
+    assert_eq!(selected.iter().sum::<u64>(), num_rounds);
+
+    // Failing validator is only selected again after its failures leave the window
+    assert!(selected[4] * 40 < num_rounds, "{:?}", selected);
+    for i in 1..4 {
+        // Stake is taken into account, on top of the reputation
+        assert!(selected[0] > 2 * selected[i], "{:?}", selected);
+        // Slow validator is selected less often than a validator with equal stake
+        assert!(selected[5] * 2 < selected[i], "{:?}", selected);
+        assert!(selected[4] < selected[i], "{:?}", selected);
+    }
+}
+
+#[test]
+fn test_stake_and_latency_simulation_equal_performance() {
+    let num_rounds = 2000;
+    let validators: Vec<_> = (0..5)
+        .map(|_| SimulatedValidator {
+            voting_power: 1,
+            fails_to_propose: false,
+            round_latency: 1000,
+        })
+        .collect();
+
+    let selected = simulate_stake_and_latency_election(&validators, num_rounds);
+
+    // With equal stake and performance, selection is uniform
+    let p = 1.0 / validators.len() as f32;
+    let expected = (num_rounds as f32 * p) as u64;
+    let std_dev = (num_rounds as f32 * p * (1.0 - p)).pow(0.5);
+    for (i, count) in selected.iter().enumerate() {
+        // Same as in test_api, we check difference from average is below 3 standard deviations
+        assert!(
+            expected.abs_diff(*count) as f32 <= 3.0 * std_dev,
+            "{}: expected={} selected={}, std_dev: {}",
+            i,
+            expected,
+            count,
+            std_dev
+        );
+    }
+}
+
 /// #### LeaderReputation test ####
 
 #[test]
@@ -36,7 +36,7 @@ pub(crate) fn choose_index(mut weights: Vec<u64>, state: Vec<u8>) -> usize {
     // Create cumulative weights vector
     // Since we own the vector, we can safely modify it in place
     for w in &mut weights {
-        total_weight += *w;
+        total_weight = w.saturating_add(total_weight);
         *w = total_weight;
     }
     let chosen_weight = next_in_range(state, total_weight);
@@ -3,7 +3,10 @@
 
 //! Interface between Consensus and Network layers.
 
-use crate::counters;
+use crate::{
+    counters,
+    quorum_store::types::{Batch, BatchRequest},
+};
 use anyhow::anyhow;
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_logger::prelude::*;
@@ -14,6 +17,7 @@ use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse},
     epoch_retrieval::EpochRetrievalRequest,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    proof_of_store::{ProofOfStore, SignedDigest},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
     vote_msg::VoteMsg,
@@ -62,6 +66,17 @@ pub enum ConsensusMsg {
     /// than 2f + 1 signatures on the commit proposal. This part is not on the critical path, but
     /// it can save slow machines to quickly confirm the execution result.
     CommitDecisionMsg(Box<CommitDecision>),
+    /// A batch of transactions broadcast by its author for the quorum store. Proposals refer to
+    /// it by digest once a quorum of validators has persisted it.
+    BatchMsg(Box<Batch>),
+    /// The signature of a validator on the digest of a batch it persisted, sent back to the
+    /// author of the batch.
+    SignedDigestMsg(Box<SignedDigest>),
+    /// The aggregated signatures of a quorum of validators on the digest of a batch, broadcast
+    /// by the author of the batch so that any leader can propose it.
+    ProofOfStoreMsg(Box<ProofOfStore>),
+    /// RPC to fetch a batch missing locally from one of the signers of its proof of store.
+    BatchRequestMsg(Box<BatchRequest>),
 }
 
 /// The interface from Network to Consensus layer.
@@ -480,14 +480,25 @@ impl DropConfigRound {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::network::NetworkTask;
+    use crate::{
+        network::NetworkTask,
+        quorum_store::{
+            batch_store::BatchStore,
+            types::{Batch, BatchRequest},
+        },
+        test_utils::MockQuorumStoreDB,
+    };
     use aptos_config::network_id::NetworkId;
     use aptos_crypto::HashValue;
-    use aptos_types::validator_verifier::random_validator_verifier;
+    use aptos_types::{
+        aggregate_signature::PartialSignatures, validator_verifier::random_validator_verifier,
+    };
     use bytes::Bytes;
     use consensus_types::{
+        block::block_test_utils::random_transactions,
         block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse, BlockRetrievalStatus},
         common::Payload,
+        proof_of_store::ProofOfStore,
     };
     use futures::{channel::oneshot, future};
     use network::{
@@ -756,6 +767,106 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_batch_rpc() {
+        let mut runtime = consensus_runtime();
+        let num_nodes = 2;
+        let mut receivers: Vec<NetworkReceivers> = Vec::new();
+        let mut playground = NetworkPlayground::new(runtime.handle().clone());
+        let mut nodes = Vec::new();
+        let (signers, validator_verifier) = random_validator_verifier(num_nodes, None, false);
+        let peers: Vec<_> = signers.iter().map(|signer| signer.author()).collect();
+        let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Validator]);
+
+        for (peer_id, peer) in peers.iter().enumerate() {
+            let (network_reqs_tx, network_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+            let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+            let (consensus_tx, consensus_rx) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+            let (_conn_mgr_reqs_tx, conn_mgr_reqs_rx) = channel::new_test(8);
+            let (_, conn_status_rx) = conn_notifs_channel::new();
+            let mut network_sender = ConsensusNetworkSender::new(
+                PeerManagerRequestSender::new(network_reqs_tx),
+                ConnectionRequestSender::new(connection_reqs_tx),
+            );
+
+            add_peer_to_storage(
+                &peer_metadata_storage,
+                peer,
+                &[
+                    ProtocolId::ConsensusDirectSendJson,
+                    ProtocolId::ConsensusDirectSendBcs,
+                    ProtocolId::ConsensusRpcJson,
+                ],
+            );
+            network_sender.initialize(peer_metadata_storage.clone());
+            let network_events = ConsensusNetworkEvents::new(consensus_rx, conn_status_rx);
+
+            let twin_id = TwinId {
+                id: peer_id,
+                author: *peer,
+            };
+
+            playground.add_node(twin_id, consensus_tx, network_reqs_rx, conn_mgr_reqs_rx);
+
+            let (self_sender, self_receiver) = channel::new_test(8);
+            let node = NetworkSender::new(
+                *peer,
+                network_sender,
+                self_sender,
+                validator_verifier.clone(),
+            );
+            let (task, receiver) = NetworkTask::new(network_events, self_receiver);
+            receivers.push(receiver);
+            runtime.handle().spawn(task.start());
+            nodes.push(node);
+        }
+        let batch_stores: Vec<_> = nodes
+            .iter()
+            .zip(peers.iter())
+            .map(|(node, peer)| {
+                Arc::new(BatchStore::new(
+                    1,
+                    *peer,
+                    Arc::new(MockQuorumStoreDB::new()),
+                    node.clone(),
+                    validator_verifier.clone(),
+                    1_000,
+                ))
+            })
+            .collect();
+
+        // only node 1 has the batch, and it signed its proof
+        let batch = Batch::new(1, peers[1], u64::MAX, random_transactions(3));
+        batch_stores[1].persist(batch.clone()).unwrap();
+        let mut signatures = PartialSignatures::empty();
+        signatures.add_signature(peers[1], signers[1].sign(&batch.info()));
+        let proof = ProofOfStore::new(
+            batch.info(),
+            validator_verifier
+                .aggregate_signatures(&signatures)
+                .unwrap(),
+        );
+
+        let mut batch_retrieval = receivers.remove(1).batch_retrieval;
+        let batch_store_1 = batch_stores[1].clone();
+        runtime.handle().spawn(async move {
+            while let Some(request) = batch_retrieval.next().await {
+                batch_store_1.process_batch_request(request).await.unwrap();
+            }
+        });
+        timed_block_on(&mut runtime, async {
+            let unknown_digest = BatchRequest::new(1, HashValue::random());
+            assert!(nodes[0]
+                .request_batch(unknown_digest, peers[1], Duration::from_secs(5))
+                .await
+                .is_err());
+
+            let txns = batch_stores[0].get_transactions(&[proof]).await.unwrap();
+            assert_eq!(txns, batch.txns());
+            assert_eq!(batch_stores[0].get_batch(&batch.digest()), Some(batch));
+        });
+    }
+
     #[test]
     fn test_bad_message() {
         let (peer_mgr_notifs_tx, peer_mgr_notifs_rx) =
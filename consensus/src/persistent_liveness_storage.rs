@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{consensusdb::ConsensusDB, epoch_manager::LivenessStorageData, error::DbError};
+use crate::{
+    consensusdb::ConsensusDB, epoch_manager::LivenessStorageData, error::DbError,
+    quorum_store::quorum_store_db::QuorumStoreStorage,
+};
 use anyhow::{format_err, Context, Result};
 use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
@@ -50,6 +53,9 @@ pub trait PersistentLivenessStorage: Send + Sync {
 
     /// Returns a handle of the aptosdb.
     fn aptos_db(&self) -> Arc<dyn DbReader>;
+
+    /// Returns a handle of the storage for quorum store batches.
+    fn quorum_store_db(&self) -> Arc<dyn QuorumStoreStorage>;
 }
 
 #[derive(Clone)]
@@ -429,4 +435,8 @@ impl PersistentLivenessStorage for StorageWriteProxy {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         self.aptos_db.clone()
     }
+
+    fn quorum_store_db(&self) -> Arc<dyn QuorumStoreStorage> {
+        self.db.clone()
+    }
 }
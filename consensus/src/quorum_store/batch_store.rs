@@ -0,0 +1,184 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    monitor,
+    network::{IncomingBatchRetrievalRequest, NetworkSender},
+    network_interface::ConsensusMsg,
+    quorum_store::{
+        counters,
+        quorum_store_db::QuorumStoreStorage,
+        types::{Batch, BatchRequest},
+    },
+};
+use anyhow::{bail, Context};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_types::{transaction::SignedTransaction, validator_verifier::ValidatorVerifier};
+use consensus_types::{common::Author, proof_of_store::ProofOfStore};
+use network::protocols::rpc::error::RpcError;
+use rand::seq::SliceRandom;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// Keeps the batches of the current epoch that this validator persisted, either because it
+/// signed them or because it fetched them to execute a block, and serves them to other
+/// validators until they expire.
+pub struct BatchStore {
+    epoch: u64,
+    author: Author,
+    batches: Mutex<HashMap<HashValue, Batch>>,
+    db: Arc<dyn QuorumStoreStorage>,
+    network_sender: NetworkSender,
+    verifier: ValidatorVerifier,
+    batch_request_timeout_ms: u64,
+}
+
+impl BatchStore {
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        db: Arc<dyn QuorumStoreStorage>,
+        network_sender: NetworkSender,
+        verifier: ValidatorVerifier,
+        batch_request_timeout_ms: u64,
+    ) -> Self {
+        let mut batches = db
+            .get_all_batches()
+            .expect("Failed to read quorum store batches from consensusdb");
+        let stale_digests: Vec<_> = batches
+            .iter()
+            .filter(|(_, batch)| batch.epoch() != epoch)
+            .map(|(digest, _)| *digest)
+            .collect();
+        for digest in &stale_digests {
+            batches.remove(digest);
+        }
+        if let Err(e) = db.delete_batches(stale_digests) {
+            error!(error = ?e, "Failed to delete quorum store batches of previous epochs");
+        }
+        info!(
+            epoch = epoch,
+            "Recovered {} quorum store batches",
+            batches.len()
+        );
+        counters::BATCHES_IN_STORE.set(batches.len() as i64);
+
+        Self {
+            epoch,
+            author,
+            batches: Mutex::new(batches),
+            db,
+            network_sender,
+            verifier,
+            batch_request_timeout_ms,
+        }
+    }
+
+    /// Persists the batch so that it can be served until it expires.
+    pub fn persist(&self, batch: Batch) -> anyhow::Result<()> {
+        self.db.save_batch(&batch)?;
+        let mut batches = self.batches.lock();
+        batches.insert(batch.digest(), batch);
+        counters::BATCHES_IN_STORE.set(batches.len() as i64);
+        Ok(())
+    }
+
+    pub fn get_batch(&self, digest: &HashValue) -> Option<Batch> {
+        self.batches.lock().get(digest).cloned()
+    }
+
+    /// Removes the batches that expired at the given (committed) timestamp.
+    pub fn clear_expired(&self, timestamp_usecs: u64) {
+        let expired_digests: Vec<_> = {
+            let mut batches = self.batches.lock();
+            let expired_digests: Vec<_> = batches
+                .iter()
+                .filter(|(_, batch)| batch.expiration_usecs() <= timestamp_usecs)
+                .map(|(digest, _)| *digest)
+                .collect();
+            for digest in &expired_digests {
+                batches.remove(digest);
+            }
+            counters::BATCHES_IN_STORE.set(batches.len() as i64);
+            expired_digests
+        };
+        if let Err(e) = self.db.delete_batches(expired_digests) {
+            error!(error = ?e, "Failed to delete expired quorum store batches");
+        }
+    }
+
+    /// Returns the transactions of the batches the proofs refer to, in order. Batches missing
+    /// locally are fetched from the validators that signed their proof.
+    pub async fn get_transactions(
+        &self,
+        proofs: &[ProofOfStore],
+    ) -> anyhow::Result<Vec<SignedTransaction>> {
+        let mut txns = vec![];
+        for proof in proofs {
+            let batch = match self.get_batch(proof.digest()) {
+                Some(batch) => batch,
+                None => monitor!("fetch_batch", self.fetch_batch(proof).await)?,
+            };
+            txns.extend(batch.into_transactions());
+        }
+        Ok(txns)
+    }
+
+    async fn fetch_batch(&self, proof: &ProofOfStore) -> anyhow::Result<Batch> {
+        let request = BatchRequest::new(self.epoch, *proof.digest());
+        let mut signers = proof.signers(&self.verifier);
+        signers.retain(|signer| *signer != self.author);
+        signers.shuffle(&mut rand::thread_rng());
+        for signer in signers {
+            counters::BATCH_RETRIEVAL_COUNT.inc();
+            match self
+                .network_sender
+                .request_batch(
+                    request.clone(),
+                    signer,
+                    Duration::from_millis(self.batch_request_timeout_ms),
+                )
+                .await
+            {
+                Ok(batch) => {
+                    self.persist(batch.clone())
+                        .context("Failed to persist fetched batch")?;
+                    return Ok(batch);
+                }
+                Err(e) => {
+                    counters::BATCH_RETRIEVAL_FAILURE_COUNT.inc();
+                    warn!(
+                        remote_peer = signer,
+                        error = ?e,
+                        "Failed to fetch batch {}",
+                        proof.digest()
+                    );
+                }
+            }
+        }
+        bail!("Unable to fetch batch for {} from any signer", proof)
+    }
+
+    pub async fn process_batch_request(
+        &self,
+        request: IncomingBatchRetrievalRequest,
+    ) -> anyhow::Result<()> {
+        let response = match self.get_batch(&request.req.digest()) {
+            Some(batch) if request.req.epoch() == self.epoch => {
+                let response_bytes = request
+                    .protocol
+                    .to_bytes(&ConsensusMsg::BatchMsg(Box::new(batch)))?;
+                Ok(response_bytes.into())
+            }
+            _ => Err(RpcError::ApplicationError(anyhow::anyhow!(
+                "Batch not found for {}",
+                request.req
+            ))),
+        };
+        request
+            .response_sender
+            .send(response)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
+}
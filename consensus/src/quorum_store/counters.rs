@@ -1,7 +1,8 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 use aptos_metrics_core::{
-    op_counters::DurationHistogram, register_histogram, register_histogram_vec, HistogramVec,
+    op_counters::DurationHistogram, register_histogram, register_histogram_vec,
+    register_int_counter, register_int_gauge, HistogramVec, IntCounter, IntGauge,
 };
 use once_cell::sync::Lazy;
 use std::time::Duration;
@@ -42,3 +43,66 @@ pub static MAIN_LOOP: Lazy<DurationHistogram> = Lazy::new(|| {
         .unwrap(),
     )
 });
+
+/// Number of batches created by this validator.
+pub static CREATED_BATCH_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_created_batch_count",
+        "Number of batches created by this validator"
+    )
+    .unwrap()
+});
+
+/// Number of proofs of store formed for the batches of this validator.
+pub static FORMED_PROOF_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_formed_proof_count",
+        "Number of proofs of store formed for the batches of this validator"
+    )
+    .unwrap()
+});
+
+/// Number of batches this validator created that expired before forming a proof of store.
+pub static EXPIRED_PENDING_BATCH_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_expired_pending_batch_count",
+        "Number of batches that expired before forming a proof of store"
+    )
+    .unwrap()
+});
+
+/// Number of proofs of store available to be proposed.
+pub static PROOFS_IN_QUEUE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_proofs_in_queue",
+        "Number of proofs of store available to be proposed"
+    )
+    .unwrap()
+});
+
+/// Number of batches persisted by this validator.
+pub static BATCHES_IN_STORE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_batches_in_store",
+        "Number of batches persisted by this validator"
+    )
+    .unwrap()
+});
+
+/// Number of requests sent to fetch a missing batch.
+pub static BATCH_RETRIEVAL_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_batch_retrieval_count",
+        "Number of requests sent to fetch a missing batch"
+    )
+    .unwrap()
+});
+
+/// Number of failed requests to fetch a missing batch.
+pub static BATCH_RETRIEVAL_FAILURE_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_batch_retrieval_failure_count",
+        "Number of failed requests to fetch a missing batch"
+    )
+    .unwrap()
+});
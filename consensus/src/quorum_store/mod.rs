@@ -3,7 +3,12 @@
 
 /// Equivalent to directly fetching blocks from mempool without a quorum store.
 pub mod direct_mempool_quorum_store;
+/// Disseminates batches of transactions ahead of consensus and collects proofs of store for them.
+pub mod networked_quorum_store;
 
+pub mod batch_store;
 mod counters;
+pub mod quorum_store_db;
 #[cfg(test)]
 mod tests;
+pub mod types;
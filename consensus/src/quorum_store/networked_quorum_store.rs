@@ -0,0 +1,426 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    monitor,
+    network::NetworkSender,
+    quorum_store::{batch_store::BatchStore, counters, types::Batch},
+    round_manager::VerifiedEvent,
+};
+use anyhow::{ensure, Result};
+use aptos_config::config::ConsensusConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::duration_since_epoch;
+use aptos_logger::prelude::*;
+use aptos_mempool::{QuorumStoreRequest, QuorumStoreResponse};
+use aptos_types::{
+    account_address::AccountAddress, aggregate_signature::PartialSignatures,
+    transaction::SignedTransaction, validator_signer::ValidatorSigner,
+    validator_verifier::ValidatorVerifier,
+};
+use channel::aptos_channel;
+use consensus_types::{
+    common::{Author, Payload, PayloadFilter, TransactionSummary},
+    proof_of_store::{ProofOfStore, SignedDigest, SignedDigestInfo},
+    request_response::{ConsensusRequest, ConsensusResponse},
+};
+use futures::{
+    channel::{
+        mpsc::{Receiver, Sender},
+        oneshot,
+    },
+    StreamExt,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::time::{interval, timeout, MissedTickBehavior};
+
+/// A batch created by this validator that is waiting for a quorum of signatures.
+struct PendingBatch {
+    info: SignedDigestInfo,
+    signatures: PartialSignatures,
+}
+
+/// Quorum store that disseminates transactions ahead of consensus: every validator periodically
+/// pulls a batch from its mempool and broadcasts it, the other validators persist it and send
+/// back their signature on its digest, and once a quorum signed, the proof of store is broadcast
+/// so that any leader can propose the batch by digest.
+pub struct NetworkedQuorumStore {
+    epoch: u64,
+    author: Author,
+    config: ConsensusConfig,
+    consensus_receiver: Receiver<ConsensusRequest>,
+    mempool_sender: Sender<QuorumStoreRequest>,
+    network_msg_rx: aptos_channel::Receiver<AccountAddress, VerifiedEvent>,
+    network_sender: NetworkSender,
+    batch_store: Arc<BatchStore>,
+    validator_signer: Arc<ValidatorSigner>,
+    verifier: ValidatorVerifier,
+    // Batches created by this validator waiting for signatures.
+    pending_batches: HashMap<HashValue, PendingBatch>,
+    // Transactions batched by this validator that are neither committed nor expired, which
+    // must not be pulled again from mempool, with the expiration of their batch.
+    batched_txns: HashMap<HashValue, (u64, Vec<TransactionSummary>)>,
+    // Proofs of store that can be proposed.
+    proofs: HashMap<HashValue, ProofOfStore>,
+    // Digests of committed batches with their expiration, so that late proofs aren't proposed.
+    committed_digests: HashMap<HashValue, u64>,
+}
+
+impl NetworkedQuorumStore {
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        config: ConsensusConfig,
+        consensus_receiver: Receiver<ConsensusRequest>,
+        mempool_sender: Sender<QuorumStoreRequest>,
+        network_msg_rx: aptos_channel::Receiver<AccountAddress, VerifiedEvent>,
+        network_sender: NetworkSender,
+        batch_store: Arc<BatchStore>,
+        validator_signer: Arc<ValidatorSigner>,
+        verifier: ValidatorVerifier,
+    ) -> Self {
+        Self {
+            epoch,
+            author,
+            config,
+            consensus_receiver,
+            mempool_sender,
+            network_msg_rx,
+            network_sender,
+            batch_store,
+            validator_signer,
+            verifier,
+            pending_batches: HashMap::new(),
+            batched_txns: HashMap::new(),
+            proofs: HashMap::new(),
+            committed_digests: HashMap::new(),
+        }
+    }
+
+    fn batch_expiry_usecs(&self) -> u64 {
+        self.config.quorum_store_batch_expiry_ms * 1000
+    }
+
+    async fn pull_txns(&self) -> Result<Vec<SignedTransaction>> {
+        let exclude_txns = self
+            .batched_txns
+            .values()
+            .flat_map(|(_, txns)| txns.iter().cloned())
+            .collect();
+        let (callback, callback_rcv) = oneshot::channel();
+        let msg = QuorumStoreRequest::GetBatchRequest(
+            self.config.quorum_store_max_batch_txns,
+            self.config.quorum_store_max_batch_bytes,
+            exclude_txns,
+            callback,
+        );
+        self.mempool_sender
+            .clone()
+            .try_send(msg)
+            .map_err(anyhow::Error::from)?;
+        // wait for response
+        match monitor!(
+            "pull_txn",
+            timeout(
+                Duration::from_millis(self.config.mempool_txn_pull_timeout_ms),
+                callback_rcv
+            )
+            .await
+        ) {
+            Err(_) => Err(anyhow::anyhow!(
+                "[quorum_store] did not receive GetBatchResponse on time"
+            )),
+            Ok(resp) => match resp.map_err(anyhow::Error::from)?? {
+                QuorumStoreResponse::GetBatchResponse(txns) => Ok(txns),
+                _ => Err(anyhow::anyhow!(
+                    "[quorum_store] did not receive expected GetBatchResponse"
+                )),
+            },
+        }
+    }
+
+    async fn create_batch(&mut self) {
+        let get_batch_start_time = Instant::now();
+        let txns = match self.pull_txns().await {
+            Ok(txns) => {
+                counters::quorum_store_service_latency(
+                    counters::GET_BATCH_LABEL,
+                    counters::REQUEST_SUCCESS_LABEL,
+                    get_batch_start_time.elapsed(),
+                );
+                txns
+            }
+            Err(e) => {
+                error!(error = ?e, "GetBatch failed");
+                counters::quorum_store_service_latency(
+                    counters::GET_BATCH_LABEL,
+                    counters::REQUEST_FAIL_LABEL,
+                    get_batch_start_time.elapsed(),
+                );
+                return;
+            }
+        };
+        if txns.is_empty() {
+            return;
+        }
+
+        let expiration_usecs =
+            duration_since_epoch().as_micros() as u64 + self.batch_expiry_usecs();
+        let summaries = txns
+            .iter()
+            .map(|txn| TransactionSummary {
+                sender: txn.sender(),
+                sequence_number: txn.sequence_number(),
+            })
+            .collect();
+        let batch = Batch::new(self.epoch, self.author, expiration_usecs, txns);
+        let info = batch.info();
+        debug!("Created {}", info);
+        counters::CREATED_BATCH_COUNT.inc();
+        self.batched_txns
+            .insert(info.digest, (expiration_usecs, summaries));
+        self.pending_batches.insert(
+            info.digest,
+            PendingBatch {
+                info,
+                signatures: PartialSignatures::empty(),
+            },
+        );
+        // The batch is delivered to ourselves as well, and persisted and signed like any other.
+        self.network_sender.broadcast_batch(batch).await;
+    }
+
+    async fn process_batch(&mut self, peer_id: Author, batch: Batch) -> Result<()> {
+        ensure!(
+            peer_id == batch.author(),
+            "Received {} from {} which is not its author",
+            batch,
+            peer_id
+        );
+        ensure!(
+            batch.txns().len() as u64 <= self.config.quorum_store_max_batch_txns
+                && batch.num_bytes() <= self.config.quorum_store_max_batch_bytes,
+            "{} exceeds the batch limits",
+            batch
+        );
+        // Allow for some clock skew, but don't store batches for longer than we would ourselves.
+        let now = duration_since_epoch().as_micros() as u64;
+        ensure!(
+            batch.expiration_usecs() > now
+                && batch.expiration_usecs() <= now + 2 * self.batch_expiry_usecs(),
+            "{} has an invalid expiration",
+            batch
+        );
+
+        let info = batch.info();
+        self.batch_store.persist(batch)?;
+        let signed_digest = SignedDigest::new(info, &self.validator_signer);
+        self.network_sender
+            .send_signed_digest(signed_digest, peer_id)
+            .await;
+        Ok(())
+    }
+
+    async fn process_signed_digest(
+        &mut self,
+        peer_id: Author,
+        signed_digest: SignedDigest,
+    ) -> Result<()> {
+        ensure!(
+            peer_id == signed_digest.signer(),
+            "Received signed digest of {} from {} on behalf of {}",
+            signed_digest.info(),
+            peer_id,
+            signed_digest.signer()
+        );
+        let digest = signed_digest.digest();
+        let pending_batch = match self.pending_batches.get_mut(&digest) {
+            Some(pending_batch) => pending_batch,
+            // Late signatures for batches that already have a proof or expired.
+            None => return Ok(()),
+        };
+        ensure!(
+            &pending_batch.info == signed_digest.info(),
+            "Signed digest info {} does not match the batch {}",
+            signed_digest.info(),
+            pending_batch.info
+        );
+        pending_batch
+            .signatures
+            .add_signature(signed_digest.signer(), signed_digest.signature().clone());
+        if self
+            .verifier
+            .check_voting_power(pending_batch.signatures.signatures().keys())
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        let pending_batch = self
+            .pending_batches
+            .remove(&digest)
+            .expect("Pending batch must exist");
+        let multi_signature = self
+            .verifier
+            .aggregate_signatures(&pending_batch.signatures)?;
+        let proof = ProofOfStore::new(pending_batch.info, multi_signature);
+        debug!("Formed {}", proof);
+        counters::FORMED_PROOF_COUNT.inc();
+        self.network_sender.broadcast_proof_of_store(proof).await;
+        Ok(())
+    }
+
+    fn process_proof_of_store(&mut self, proof: ProofOfStore) {
+        let now = duration_since_epoch().as_micros() as u64;
+        if proof.expiration_usecs() <= now || self.committed_digests.contains_key(proof.digest()) {
+            return;
+        }
+        self.proofs.insert(*proof.digest(), proof);
+        counters::PROOFS_IN_QUEUE.set(self.proofs.len() as i64);
+    }
+
+    fn handle_block_request(
+        &self,
+        max_txns: u64,
+        max_bytes: u64,
+        payload_filter: PayloadFilter,
+        callback: oneshot::Sender<Result<ConsensusResponse>>,
+    ) {
+        let exclude_digests = match payload_filter {
+            PayloadFilter::InQuorumStore(exclude_digests) => exclude_digests,
+            // Only the empty payloads of reconfiguration suffixes come from mempool directly.
+            PayloadFilter::DirectMempool(_) => HashSet::new(),
+        };
+        let now = duration_since_epoch().as_micros() as u64;
+        let mut candidates: Vec<_> = self
+            .proofs
+            .values()
+            .filter(|proof| {
+                proof.expiration_usecs() > now && !exclude_digests.contains(proof.digest())
+            })
+            .collect();
+        // Propose the batches closest to expiring first.
+        candidates.sort_by_key(|proof| proof.expiration_usecs());
+
+        let mut proofs = vec![];
+        let mut total_txns = 0;
+        let mut total_bytes = 0;
+        for proof in candidates {
+            if total_txns + proof.num_txns() > max_txns
+                || total_bytes + proof.num_bytes() > max_bytes
+            {
+                continue;
+            }
+            total_txns += proof.num_txns();
+            total_bytes += proof.num_bytes();
+            proofs.push(proof.clone());
+        }
+
+        let get_block_response_start_time = Instant::now();
+        let payload = Payload::InQuorumStore(proofs);
+        let result = match callback.send(Ok(ConsensusResponse::GetBlockResponse(payload))) {
+            Err(_) => {
+                error!("Callback failed");
+                counters::CALLBACK_FAIL_LABEL
+            }
+            Ok(_) => counters::CALLBACK_SUCCESS_LABEL,
+        };
+        counters::quorum_store_service_latency(
+            counters::GET_BLOCK_RESPONSE_LABEL,
+            result,
+            get_block_response_start_time.elapsed(),
+        );
+    }
+
+    fn handle_clean_request(
+        &mut self,
+        epoch: u64,
+        timestamp_usecs: u64,
+        committed_digests: Vec<HashValue>,
+        callback: oneshot::Sender<Result<ConsensusResponse>>,
+    ) {
+        if epoch == self.epoch {
+            for digest in committed_digests {
+                let expiration_usecs = match self.proofs.remove(&digest) {
+                    Some(proof) => proof.expiration_usecs(),
+                    // The proof may still be on its way: remember the digest for as long as the
+                    // batch can be valid.
+                    None => timestamp_usecs + self.batch_expiry_usecs(),
+                };
+                self.batched_txns.remove(&digest);
+                self.committed_digests.insert(digest, expiration_usecs);
+            }
+
+            let num_pending_batches = self.pending_batches.len();
+            self.pending_batches
+                .retain(|_, pending_batch| pending_batch.info.expiration_usecs > timestamp_usecs);
+            counters::EXPIRED_PENDING_BATCH_COUNT
+                .inc_by((num_pending_batches - self.pending_batches.len()) as u64);
+            self.proofs
+                .retain(|_, proof| proof.expiration_usecs() > timestamp_usecs);
+            self.batched_txns
+                .retain(|_, (expiration_usecs, _)| *expiration_usecs > timestamp_usecs);
+            self.committed_digests
+                .retain(|_, expiration_usecs| *expiration_usecs > timestamp_usecs);
+            self.batch_store.clear_expired(timestamp_usecs);
+            counters::PROOFS_IN_QUEUE.set(self.proofs.len() as i64);
+        }
+
+        if callback
+            .send(Ok(ConsensusResponse::CleanResponse()))
+            .is_err()
+        {
+            error!("Callback failed");
+        }
+    }
+
+    async fn handle_network_event(&mut self, peer_id: Author, event: VerifiedEvent) -> Result<()> {
+        match event {
+            VerifiedEvent::Batch(batch) => self.process_batch(peer_id, *batch).await,
+            VerifiedEvent::SignedDigest(signed_digest) => {
+                self.process_signed_digest(peer_id, *signed_digest).await
+            }
+            VerifiedEvent::ProofOfStore(proof) => {
+                self.process_proof_of_store(*proof);
+                Ok(())
+            }
+            unexpected_event => unreachable!("Unexpected event: {:?}", unexpected_event),
+        }
+    }
+
+    pub async fn start(mut self) {
+        info!(epoch = self.epoch, "QuorumStore started");
+        let mut batch_interval = interval(Duration::from_millis(
+            self.config.quorum_store_batch_interval_ms,
+        ));
+        batch_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            let _timer = counters::MAIN_LOOP.start_timer();
+            tokio::select! {
+                msg = self.consensus_receiver.next() => match msg {
+                    Some(ConsensusRequest::GetBlockRequest(max_txns, max_bytes, payload_filter, callback)) => {
+                        self.handle_block_request(max_txns, max_bytes, payload_filter, callback);
+                    }
+                    Some(ConsensusRequest::CleanRequest(epoch, _, timestamp_usecs, digests, callback)) => {
+                        self.handle_clean_request(epoch, timestamp_usecs, digests, callback);
+                    }
+                    // All the senders are dropped once the epoch ends.
+                    None => break,
+                },
+                Some((peer_id, event)) = self.network_msg_rx.next() => {
+                    if let Err(e) = self.handle_network_event(peer_id, event).await {
+                        warn!(remote_peer = peer_id, error = ?e, "Failed to process quorum store message");
+                    }
+                }
+                _ = batch_interval.tick() => {
+                    monitor!("create_batch", self.create_batch().await);
+                }
+            }
+        }
+        info!(epoch = self.epoch, "QuorumStore stopped");
+    }
+}
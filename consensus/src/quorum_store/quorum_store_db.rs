@@ -0,0 +1,33 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{consensusdb::ConsensusDB, error::DbError, quorum_store::types::Batch};
+use aptos_crypto::HashValue;
+use std::collections::HashMap;
+
+/// Persistent storage for the batches this validator signed, so that they can still be served
+/// after a restart until they expire.
+pub trait QuorumStoreStorage: Send + Sync {
+    fn save_batch(&self, batch: &Batch) -> Result<(), DbError>;
+
+    fn delete_batches(&self, digests: Vec<HashValue>) -> Result<(), DbError>;
+
+    fn get_all_batches(&self) -> Result<HashMap<HashValue, Batch>, DbError>;
+}
+
+impl QuorumStoreStorage for ConsensusDB {
+    fn save_batch(&self, batch: &Batch) -> Result<(), DbError> {
+        ConsensusDB::save_batch(self, batch)
+    }
+
+    fn delete_batches(&self, digests: Vec<HashValue>) -> Result<(), DbError> {
+        if digests.is_empty() {
+            return Ok(());
+        }
+        ConsensusDB::delete_batches(self, digests)
+    }
+
+    fn get_all_batches(&self) -> Result<HashMap<HashValue, Batch>, DbError> {
+        ConsensusDB::get_all_batches(self)
+    }
+}
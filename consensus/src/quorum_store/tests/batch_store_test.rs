@@ -0,0 +1,82 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network::NetworkSender,
+    network_interface::ConsensusNetworkSender,
+    quorum_store::{batch_store::BatchStore, quorum_store_db::QuorumStoreStorage, types::Batch},
+    test_utils::MockQuorumStoreDB,
+};
+use aptos_types::validator_verifier::random_validator_verifier;
+use channel::{aptos_channel, message_queues::QueueStyle};
+use consensus_types::{block::block_test_utils::random_transactions, common::Author};
+use network::{
+    peer_manager::{ConnectionRequestSender, PeerManagerRequestSender},
+    protocols::network::NewNetworkSender,
+};
+use std::sync::Arc;
+
+fn create_batch_store(epoch: u64, db: Arc<MockQuorumStoreDB>) -> (Author, BatchStore) {
+    let (signers, validator_verifier) = random_validator_verifier(1, None, false);
+    let author = signers[0].author();
+    let (network_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+    let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+    let network_sender = ConsensusNetworkSender::new(
+        PeerManagerRequestSender::new(network_reqs_tx),
+        ConnectionRequestSender::new(connection_reqs_tx),
+    );
+    let (self_sender, _) = channel::new_test(8);
+    let network_sender = NetworkSender::new(
+        author,
+        network_sender,
+        self_sender,
+        validator_verifier.clone(),
+    );
+    let batch_store = BatchStore::new(epoch, author, db, network_sender, validator_verifier, 1_000);
+    (author, batch_store)
+}
+
+#[test]
+fn test_recover_batches_of_current_epoch() {
+    let db = Arc::new(MockQuorumStoreDB::new());
+    let author = Author::random();
+    let old_batch = Batch::new(1, author, 100, random_transactions(2));
+    let current_batch = Batch::new(2, author, 100, random_transactions(2));
+    db.save_batch(&old_batch).unwrap();
+    db.save_batch(&current_batch).unwrap();
+
+    let (_, batch_store) = create_batch_store(2, db.clone());
+    assert_eq!(batch_store.get_batch(&old_batch.digest()), None);
+    assert_eq!(
+        batch_store.get_batch(&current_batch.digest()),
+        Some(current_batch.clone())
+    );
+
+    let persisted = db.get_all_batches().unwrap();
+    assert_eq!(persisted.len(), 1);
+    assert!(persisted.contains_key(&current_batch.digest()));
+}
+
+#[test]
+fn test_clear_expired_batches() {
+    let db = Arc::new(MockQuorumStoreDB::new());
+    let (author, batch_store) = create_batch_store(1, db.clone());
+    let batches: Vec<_> = (1..=3)
+        .map(|i| Batch::new(1, author, i * 100, random_transactions(1)))
+        .collect();
+    for batch in &batches {
+        batch_store.persist(batch.clone()).unwrap();
+    }
+    assert_eq!(db.get_all_batches().unwrap().len(), 3);
+
+    batch_store.clear_expired(200);
+    assert_eq!(batch_store.get_batch(&batches[0].digest()), None);
+    assert_eq!(batch_store.get_batch(&batches[1].digest()), None);
+    assert_eq!(
+        batch_store.get_batch(&batches[2].digest()),
+        Some(batches[2].clone())
+    );
+    let persisted = db.get_all_batches().unwrap();
+    assert_eq!(persisted.len(), 1);
+    assert!(persisted.contains_key(&batches[2].digest()));
+}
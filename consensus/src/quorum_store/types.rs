@@ -0,0 +1,135 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::ensure;
+use aptos_crypto::HashValue;
+use aptos_types::{transaction::SignedTransaction, validator_verifier::ValidatorVerifier, PeerId};
+use consensus_types::proof_of_store::SignedDigestInfo;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// A batch of transactions pulled from the author's mempool and broadcast to all validators
+/// ahead of being proposed. Proposals only refer to it by digest.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Batch {
+    epoch: u64,
+    author: PeerId,
+    // Timestamp in microseconds after which the batch may be garbage collected.
+    expiration_usecs: u64,
+    txns: Vec<SignedTransaction>,
+}
+
+impl Batch {
+    pub fn new(
+        epoch: u64,
+        author: PeerId,
+        expiration_usecs: u64,
+        txns: Vec<SignedTransaction>,
+    ) -> Self {
+        Self {
+            epoch,
+            author,
+            expiration_usecs,
+            txns,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn author(&self) -> PeerId {
+        self.author
+    }
+
+    pub fn expiration_usecs(&self) -> u64 {
+        self.expiration_usecs
+    }
+
+    pub fn txns(&self) -> &[SignedTransaction] {
+        &self.txns
+    }
+
+    pub fn into_transactions(self) -> Vec<SignedTransaction> {
+        self.txns
+    }
+
+    pub fn num_bytes(&self) -> u64 {
+        self.txns
+            .iter()
+            .map(|txn| txn.raw_txn_bytes_len() as u64)
+            .sum()
+    }
+
+    /// The digest of the batch is the hash of its transactions.
+    pub fn digest(&self) -> HashValue {
+        HashValue::sha3_256_of(
+            &bcs::to_bytes(&self.txns).expect("Unable to serialize batch transactions"),
+        )
+    }
+
+    /// The information validators sign to attest that they persisted the batch.
+    pub fn info(&self) -> SignedDigestInfo {
+        SignedDigestInfo::new(
+            self.epoch,
+            self.digest(),
+            self.expiration_usecs,
+            self.txns.len() as u64,
+            self.num_bytes(),
+        )
+    }
+
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            validator.get_voting_power(&self.author).is_some(),
+            "Batch author {} is not a validator",
+            self.author
+        );
+        ensure!(!self.txns.is_empty(), "Batch from {} is empty", self.author);
+        Ok(())
+    }
+}
+
+impl Display for Batch {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[batch from {} in epoch {}, expiration: {}, txns: {}]",
+            self.author,
+            self.epoch,
+            self.expiration_usecs,
+            self.txns.len()
+        )
+    }
+}
+
+/// RPC to fetch a batch that is referenced by a proof of store but missing locally.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BatchRequest {
+    epoch: u64,
+    digest: HashValue,
+}
+
+impl BatchRequest {
+    pub fn new(epoch: u64, digest: HashValue) -> Self {
+        Self { epoch, digest }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.digest
+    }
+}
+
+impl Display for BatchRequest {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[BatchRequest epoch: {}, digest: {}]",
+            self.epoch, self.digest
+        )
+    }
+}
@@ -6,14 +6,15 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_types::{
-    transaction::{ExecutionStatus, TransactionStatus},
+    transaction::{ExecutionStatus, SignedTransaction, TransactionStatus},
     vm_status::StatusCode,
 };
 use consensus_types::{
-    block::block_test_utils::random_payload,
+    block::{block_test_utils::random_payload, Block},
     common::{Payload, PayloadFilter},
     request_response::ConsensusRequest,
 };
+use executor_types::Error as ExecutionError;
 use futures::{channel::mpsc, future::BoxFuture};
 use rand::Rng;
 
@@ -25,7 +26,7 @@ pub struct MockPayloadManager {
 impl MockPayloadManager {
     pub fn new(consensus_to_quorum_store_sender: Option<mpsc::Sender<ConsensusRequest>>) -> Self {
         let quorum_store_client =
-            consensus_to_quorum_store_sender.map(|s| QuorumStoreClient::new(s, 1, 1));
+            consensus_to_quorum_store_sender.map(|s| QuorumStoreClient::new(s, 1, 1, None));
         Self {
             _quorum_store_client: quorum_store_client,
         }
@@ -60,4 +61,13 @@ impl PayloadManager for MockPayloadManager {
         // generate 1k txn is too slow with coverage instrumentation
         Ok(random_payload(10))
     }
+    async fn get_transactions(
+        &self,
+        block: &Block,
+    ) -> Result<Vec<SignedTransaction>, ExecutionError> {
+        match block.payload() {
+            Some(Payload::DirectMempool(txns)) => Ok(txns.clone()),
+            _ => Ok(vec![]),
+        }
+    }
 }
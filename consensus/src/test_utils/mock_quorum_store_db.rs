@@ -0,0 +1,41 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::DbError,
+    quorum_store::{quorum_store_db::QuorumStoreStorage, types::Batch},
+};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use std::collections::HashMap;
+
+/// An in-memory quorum store storage, shared across restarts of a node in tests.
+#[derive(Default)]
+pub struct MockQuorumStoreDB {
+    batches: Mutex<HashMap<HashValue, Batch>>,
+}
+
+impl MockQuorumStoreDB {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl QuorumStoreStorage for MockQuorumStoreDB {
+    fn save_batch(&self, batch: &Batch) -> Result<(), DbError> {
+        self.batches.lock().insert(batch.digest(), batch.clone());
+        Ok(())
+    }
+
+    fn delete_batches(&self, digests: Vec<HashValue>) -> Result<(), DbError> {
+        let mut batches = self.batches.lock();
+        for digest in digests {
+            batches.remove(&digest);
+        }
+        Ok(())
+    }
+
+    fn get_all_batches(&self) -> Result<HashMap<HashValue, Batch>, DbError> {
+        Ok(self.batches.lock().clone())
+    }
+}
@@ -6,6 +6,8 @@ use crate::{
     persistent_liveness_storage::{
         LedgerRecoveryData, PersistentLivenessStorage, RecoveryData, RootMetadata,
     },
+    quorum_store::quorum_store_db::QuorumStoreStorage,
+    test_utils::MockQuorumStoreDB,
 };
 use anyhow::Result;
 use aptos_crypto::HashValue;
@@ -32,6 +34,9 @@ pub struct MockSharedStorage {
     // Liveness state
     pub highest_2chain_timeout_certificate: Mutex<Option<TwoChainTimeoutCertificate>>,
     pub validator_set: ValidatorSet,
+
+    // Quorum store batches
+    pub quorum_store_db: Arc<MockQuorumStoreDB>,
 }
 
 impl MockSharedStorage {
@@ -43,6 +48,7 @@ impl MockSharedStorage {
             last_vote: Mutex::new(None),
             highest_2chain_timeout_certificate: Mutex::new(None),
             validator_set,
+            quorum_store_db: Arc::new(MockQuorumStoreDB::new()),
         }
     }
 }
@@ -227,6 +233,10 @@ impl PersistentLivenessStorage for MockStorage {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         unimplemented!()
     }
+
+    fn quorum_store_db(&self) -> Arc<dyn QuorumStoreStorage> {
+        self.shared_storage.quorum_store_db.clone()
+    }
 }
 
 /// A storage that ignores any requests, used in the tests that don't care about the storage.
@@ -294,4 +304,8 @@ impl PersistentLivenessStorage for EmptyStorage {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         unimplemented!()
     }
+
+    fn quorum_store_db(&self) -> Arc<dyn QuorumStoreStorage> {
+        Arc::new(MockQuorumStoreDB::new())
+    }
 }
@@ -17,6 +17,7 @@ use tokio::{runtime, time::timeout};
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod mock_payload_manager;
+mod mock_quorum_store_db;
 mod mock_state_computer;
 mod mock_storage;
 
@@ -24,6 +25,7 @@ use crate::util::mock_time_service::SimulatedTimeService;
 use aptos_types::block_info::BlockInfo;
 use consensus_types::{block::block_test_utils::gen_test_certificate, common::Payload};
 pub use mock_payload_manager::MockPayloadManager;
+pub use mock_quorum_store_db::MockQuorumStoreDB;
 pub use mock_state_computer::{
     EmptyStateComputer, MockStateComputer, RandomComputeResultStateComputer,
 };
@@ -10,7 +10,10 @@ use crate::{
 use aptos_types::on_chain_config::ProposerElectionType::{
     FixedProposer, RotatingProposer, RoundProposer,
 };
-use consensus_types::{block::Block, common::Round};
+use consensus_types::{
+    block::{block_test_utils::random_transactions, Block},
+    common::Round,
+};
 use futures::StreamExt;
 use std::collections::HashMap;
 
@@ -312,3 +315,48 @@ fn twins_commit_test() {
         }
     });
 }
+
+#[test]
+/// This test checks that transactions disseminated through the quorum store
+/// are proposed by digest and committed.
+///
+/// Setup:
+///
+/// 4 honest nodes (n0, n1, n2, n3) with the quorum store enabled, and 0 twins.
+/// Only n0 has transactions in its mempool.
+///
+/// Test:
+///
+/// n0 batches the transactions, a quorum signs the batch and the proof of
+/// store gets proposed. Check that n1 commits all the transactions, which it
+/// executes from the batch it stored.
+///
+/// Run the test:
+/// cargo xtest -p consensus quorum_store_commit_test -- --nocapture
+fn quorum_store_commit_test() {
+    let mut runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let num_nodes = 4;
+    let num_twins = 0;
+    let mut nodes = SMRNode::start_num_nodes_with_twins_and_config(
+        num_nodes,
+        num_twins,
+        &mut playground,
+        RotatingProposer(2),
+        None,
+        |config| config.consensus.use_quorum_store = true,
+    );
+    let txns = random_transactions(5);
+    nodes[0].shared_mempool.add_txns(txns.clone()).unwrap();
+    runtime.spawn(playground.start());
+
+    timed_block_on(&mut runtime, async {
+        let mut committed_txns = vec![];
+        while committed_txns.len() < txns.len() {
+            let mut block_txns = nodes[1].state_sync.next().await.unwrap();
+            committed_txns.append(&mut block_txns);
+        }
+        // The mock mempool isn't notified of commits, so the transactions may be batched again.
+        assert_eq!(committed_txns[..txns.len()], txns[..]);
+    });
+}
@@ -49,9 +49,9 @@ pub struct SMRNode {
     pub id: TwinId,
     pub storage: Arc<MockStorage>,
     pub commit_cb_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
+    pub shared_mempool: MockSharedMempool,
     _runtime: Runtime,
-    _shared_mempool: MockSharedMempool,
-    _state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
+    pub state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
 }
 
 fn author_from_config(config: &NodeConfig) -> Author {
@@ -83,7 +83,13 @@ impl SMRNode {
         let (state_sync_client, state_sync) = mpsc::unbounded();
         let (commit_cb_sender, commit_cb_receiver) = mpsc::unbounded::<LedgerInfoWithSignatures>();
         let shared_mempool = MockSharedMempool::new();
-        let (quorum_store_to_mempool_sender, _) = mpsc::channel(1_024);
+        // The quorum store pulls batches from the mock mempool, while direct mempool proposals
+        // are left empty.
+        let quorum_store_to_mempool_sender = if config.consensus.use_quorum_store {
+            shared_mempool.consensus_to_mempool_sender.clone()
+        } else {
+            mpsc::channel(1_024).0
+        };
         let state_computer = Arc::new(MockStateComputer::new(
             state_sync_client,
             commit_cb_sender,
@@ -143,6 +149,7 @@ impl SMRNode {
             storage.clone(),
             reconfig_listener,
             commit_notifier,
+            None,
         );
         let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
 
@@ -153,8 +160,8 @@ impl SMRNode {
             _runtime: runtime,
             commit_cb_receiver,
             storage,
-            _shared_mempool: shared_mempool,
-            _state_sync: state_sync,
+            shared_mempool,
+            state_sync,
         }
     }
 
@@ -165,11 +172,31 @@ impl SMRNode {
         playground: &mut NetworkPlayground,
         proposer_type: ProposerElectionType,
         round_proposers_idx: Option<HashMap<Round, usize>>,
+    ) -> Vec<Self> {
+        Self::start_num_nodes_with_twins_and_config(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            |_| (),
+        )
+    }
+
+    /// Starts a given number of nodes and their twins, with their configs updated by `update_config`
+    pub fn start_num_nodes_with_twins_and_config(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        update_config: fn(&mut NodeConfig),
     ) -> Vec<Self> {
         assert!(num_nodes >= num_twins);
         let ValidatorSwarm {
             nodes: mut node_configs,
         } = generator::validator_swarm_for_testing(num_nodes);
+        node_configs.iter_mut().for_each(update_config);
         let peer_metadata_storage = playground.peer_protocols();
         node_configs.iter().for_each(|config| {
             let mut conn_meta = ConnectionMetadata::mock(author_from_config(config));
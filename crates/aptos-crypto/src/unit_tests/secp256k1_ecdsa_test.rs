@@ -0,0 +1,103 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate as aptos_crypto;
+use crate::{
+    secp256k1_ecdsa::{
+        Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature,
+        PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
+    },
+    test_utils::{random_serializable_struct, uniform_keypair_strategy},
+    traits::*,
+};
+
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(CryptoHasher, BCSCryptoHash, Serialize, Deserialize)]
+struct CryptoHashable(pub usize);
+
+proptest! {
+    #[test]
+    fn test_sign_and_verify(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message);
+        prop_assert!(signature.verify(&message, &keypair.public_key).is_ok());
+        prop_assert!(signature.check_s_malleability().is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_message(
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(0));
+        prop_assert!(signature.verify(&CryptoHashable(1), &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_keys_and_signature_roundtrip(
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let private_key_bytes = keypair.private_key.to_bytes();
+        prop_assert_eq!(private_key_bytes.len(), PRIVATE_KEY_LENGTH);
+        let private_key = Secp256k1EcdsaPrivateKey::try_from(&private_key_bytes[..]).unwrap();
+        prop_assert_eq!(&private_key, &keypair.private_key);
+
+        let public_key_bytes = keypair.public_key.to_bytes();
+        prop_assert_eq!(public_key_bytes.len(), PUBLIC_KEY_LENGTH);
+        let public_key = Secp256k1EcdsaPublicKey::try_from(&public_key_bytes[..]).unwrap();
+        prop_assert_eq!(&public_key, &keypair.public_key);
+
+        let signature = keypair.private_key.sign(&CryptoHashable(0));
+        let signature_bytes = signature.to_bytes();
+        prop_assert_eq!(signature_bytes.len(), SIGNATURE_LENGTH);
+        let deserialized = Secp256k1EcdsaSignature::try_from(&signature_bytes[..]).unwrap();
+        prop_assert_eq!(deserialized, signature);
+    }
+
+    #[test]
+    fn test_high_s_signature_is_rejected(
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let message = CryptoHashable(0);
+        let signature = keypair.private_key.sign(&message);
+
+        // Negating `s` yields a signature that ECDSA itself accepts, but which is not canonical.
+        let mut mauled = signature.0;
+        mauled.s = -mauled.s;
+        prop_assert!(libsecp256k1::verify(
+            &libsecp256k1::Message::parse(
+                crate::HashValue::sha3_256_of(&signing_message(&message)).as_ref()
+            ),
+            &mauled,
+            &keypair.public_key.0,
+        ));
+
+        let mauled = Secp256k1EcdsaSignature(mauled);
+        prop_assert_eq!(
+            Secp256k1EcdsaSignature::try_from(&mauled.to_bytes()[..]),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+        prop_assert!(mauled.verify(&message, &keypair.public_key).is_err());
+    }
+}
+
+#[test]
+fn test_invalid_lengths_are_rejected() {
+    assert_eq!(
+        Secp256k1EcdsaPrivateKey::try_from(&[1u8; PRIVATE_KEY_LENGTH - 1][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+    assert_eq!(
+        Secp256k1EcdsaPublicKey::try_from(&[4u8; PUBLIC_KEY_LENGTH - 1][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+    assert_eq!(
+        Secp256k1EcdsaSignature::try_from(&[1u8; SIGNATURE_LENGTH + 1][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Mutex as StdMutex;
+use std::sync::{Mutex as StdMutex, TryLockError};
 
 pub use std::sync::MutexGuard;
 
@@ -22,6 +22,15 @@ impl<T> Mutex<T> {
             .lock()
             .expect("Cannot currently handle a poisoned lock")
     }
+
+    /// try to lock the mutex, returns None if it is currently locked
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        match self.0.try_lock() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::WouldBlock) => None,
+            Err(TryLockError::Poisoned(_)) => panic!("Cannot currently handle a poisoned lock"),
+        }
+    }
 }
 
 #[cfg(test)]
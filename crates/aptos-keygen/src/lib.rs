@@ -4,6 +4,7 @@
 use aptos_crypto::{
     bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    secp256k1_ecdsa::Secp256k1EcdsaPrivateKey,
     x25519, CryptoMaterialError, PrivateKey, Uniform,
 };
 use aptos_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
@@ -35,6 +36,11 @@ impl KeyGen {
         Ed25519PrivateKey::generate(&mut self.0)
     }
 
+    /// Generate a secp256k1 ECDSA private key.
+    pub fn generate_secp256k1_ecdsa_private_key(&mut self) -> Secp256k1EcdsaPrivateKey {
+        Secp256k1EcdsaPrivateKey::generate(&mut self.0)
+    }
+
     /// Generate a bls12381 private key.
     pub fn generate_bls12381_private_key(&mut self) -> bls12381::PrivateKey {
         bls12381::PrivateKey::generate(&mut self.0)
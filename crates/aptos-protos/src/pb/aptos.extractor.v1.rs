@@ -590,7 +590,7 @@ pub struct MoveStructTag {
 pub struct Signature {
     #[prost(enumeration = "signature::Type", tag = "1")]
     pub r#type: i32,
-    #[prost(oneof = "signature::Signature", tags = "2, 3, 4")]
+    #[prost(oneof = "signature::Signature", tags = "2, 3, 4, 5")]
     pub signature: ::core::option::Option<signature::Signature>,
 }
 /// Nested message and enum types in `Signature`.
@@ -601,6 +601,7 @@ pub mod signature {
         Ed25519 = 0,
         MultiEd25519 = 1,
         MultiAgent = 2,
+        FeePayer = 3,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -612,6 +613,7 @@ pub mod signature {
                 Type::Ed25519 => "ED25519",
                 Type::MultiEd25519 => "MULTI_ED25519",
                 Type::MultiAgent => "MULTI_AGENT",
+                Type::FeePayer => "FEE_PAYER",
             }
         }
     }
@@ -623,6 +625,8 @@ pub mod signature {
         MultiEd25519(super::MultiEd25519Signature),
         #[prost(message, tag = "4")]
         MultiAgent(super::MultiAgentSignature),
+        #[prost(message, tag = "5")]
+        FeePayer(super::FeePayerSignature),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -653,6 +657,19 @@ pub struct MultiAgentSignature {
     pub secondary_signers: ::prost::alloc::vec::Vec<AccountSignature>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeePayerSignature {
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<AccountSignature>,
+    #[prost(string, repeated, tag = "2")]
+    pub secondary_signer_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "3")]
+    pub secondary_signers: ::prost::alloc::vec::Vec<AccountSignature>,
+    #[prost(string, tag = "4")]
+    pub fee_payer_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "5")]
+    pub fee_payer_signer: ::core::option::Option<AccountSignature>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AccountSignature {
     #[prost(enumeration = "account_signature::Type", tag = "1")]
     pub r#type: i32,
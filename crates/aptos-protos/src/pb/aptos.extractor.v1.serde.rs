@@ -1828,6 +1828,178 @@ impl<'de> serde::Deserialize<'de> for EventKey {
         deserializer.deserialize_struct("aptos.extractor.v1.EventKey", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for FeePayerSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sender.is_some() {
+            len += 1;
+        }
+        if !self.secondary_signer_addresses.is_empty() {
+            len += 1;
+        }
+        if !self.secondary_signers.is_empty() {
+            len += 1;
+        }
+        if !self.fee_payer_address.is_empty() {
+            len += 1;
+        }
+        if self.fee_payer_signer.is_some() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.FeePayerSignature", len)?;
+        if let Some(v) = self.sender.as_ref() {
+            struct_ser.serialize_field("sender", v)?;
+        }
+        if !self.secondary_signer_addresses.is_empty() {
+            struct_ser
+                .serialize_field("secondarySignerAddresses", &self.secondary_signer_addresses)?;
+        }
+        if !self.secondary_signers.is_empty() {
+            struct_ser.serialize_field("secondarySigners", &self.secondary_signers)?;
+        }
+        if !self.fee_payer_address.is_empty() {
+            struct_ser.serialize_field("feePayerAddress", &self.fee_payer_address)?;
+        }
+        if let Some(v) = self.fee_payer_signer.as_ref() {
+            struct_ser.serialize_field("feePayerSigner", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for FeePayerSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sender",
+            "secondarySignerAddresses",
+            "secondarySigners",
+            "feePayerAddress",
+            "feePayerSigner",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sender,
+            SecondarySignerAddresses,
+            SecondarySigners,
+            FeePayerAddress,
+            FeePayerSigner,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sender" => Ok(GeneratedField::Sender),
+                            "secondarySignerAddresses" => {
+                                Ok(GeneratedField::SecondarySignerAddresses)
+                            }
+                            "secondarySigners" => Ok(GeneratedField::SecondarySigners),
+                            "feePayerAddress" => Ok(GeneratedField::FeePayerAddress),
+                            "feePayerSigner" => Ok(GeneratedField::FeePayerSigner),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = FeePayerSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.FeePayerSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<FeePayerSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut sender__ = None;
+                let mut secondary_signer_addresses__ = None;
+                let mut secondary_signers__ = None;
+                let mut fee_payer_address__ = None;
+                let mut fee_payer_signer__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Sender => {
+                            if sender__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sender"));
+                            }
+                            sender__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SecondarySignerAddresses => {
+                            if secondary_signer_addresses__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "secondarySignerAddresses",
+                                ));
+                            }
+                            secondary_signer_addresses__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SecondarySigners => {
+                            if secondary_signers__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secondarySigners"));
+                            }
+                            secondary_signers__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FeePayerAddress => {
+                            if fee_payer_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayerAddress"));
+                            }
+                            fee_payer_address__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FeePayerSigner => {
+                            if fee_payer_signer__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayerSigner"));
+                            }
+                            fee_payer_signer__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(FeePayerSignature {
+                    sender: sender__,
+                    secondary_signer_addresses: secondary_signer_addresses__.unwrap_or_default(),
+                    secondary_signers: secondary_signers__.unwrap_or_default(),
+                    fee_payer_address: fee_payer_address__.unwrap_or_default(),
+                    fee_payer_signer: fee_payer_signer__,
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.FeePayerSignature",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
 impl serde::Serialize for GenesisTransaction {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4630,6 +4802,9 @@ impl serde::Serialize for Signature {
                 signature::Signature::MultiAgent(v) => {
                     struct_ser.serialize_field("multiAgent", v)?;
                 }
+                signature::Signature::FeePayer(v) => {
+                    struct_ser.serialize_field("feePayer", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -4641,7 +4816,7 @@ impl<'de> serde::Deserialize<'de> for Signature {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["type", "ed25519", "multiEd25519", "multiAgent"];
+        const FIELDS: &[&str] = &["type", "ed25519", "multiEd25519", "multiAgent", "feePayer"];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
@@ -4649,6 +4824,7 @@ impl<'de> serde::Deserialize<'de> for Signature {
             Ed25519,
             MultiEd25519,
             MultiAgent,
+            FeePayer,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4677,6 +4853,7 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" => Ok(GeneratedField::MultiEd25519),
                             "multiAgent" => Ok(GeneratedField::MultiAgent),
+                            "feePayer" => Ok(GeneratedField::FeePayer),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4725,6 +4902,12 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             }
                             signature__ = Some(signature::Signature::MultiAgent(map.next_value()?));
                         }
+                        GeneratedField::FeePayer => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayer"));
+                            }
+                            signature__ = Some(signature::Signature::FeePayer(map.next_value()?));
+                        }
                     }
                 }
                 Ok(Signature {
@@ -4746,6 +4929,7 @@ impl serde::Serialize for signature::Type {
             Self::Ed25519 => "ED25519",
             Self::MultiEd25519 => "MULTI_ED25519",
             Self::MultiAgent => "MULTI_AGENT",
+            Self::FeePayer => "FEE_PAYER",
         };
         serializer.serialize_str(variant)
     }
@@ -4756,7 +4940,7 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519", "MULTI_AGENT"];
+        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519", "MULTI_AGENT", "FEE_PAYER"];
 
         struct GeneratedVisitor;
 
@@ -4801,6 +4985,7 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
                     "ED25519" => Ok(signature::Type::Ed25519),
                     "MULTI_ED25519" => Ok(signature::Type::MultiEd25519),
                     "MULTI_AGENT" => Ok(signature::Type::MultiAgent),
+                    "FEE_PAYER" => Ok(signature::Type::FeePayer),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
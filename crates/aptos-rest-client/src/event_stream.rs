@@ -0,0 +1,105 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::RestError, Client};
+use aptos_api_types::VersionedEvent;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use std::time::Duration;
+use url::Url;
+
+/// Delay before reconnecting once the server closed an event stream.
+const RECONNECT_DELAY: Duration = Duration::from_millis(100);
+
+pub(crate) type ByteStream = BoxStream<'static, reqwest::Result<Bytes>>;
+
+/// The state of a Server-Sent Events stream of events, which is resumed from
+/// the event following the last received one whenever the connection ends,
+/// e.g. because the request timed out.
+pub(crate) struct EventStreamState {
+    client: Client,
+    url: Url,
+    next_sequence_number: u64,
+    body: Option<ByteStream>,
+    buffer: Vec<u8>,
+    failed: bool,
+}
+
+impl EventStreamState {
+    pub(crate) fn new(client: Client, url: Url, start: u64, body: ByteStream) -> Self {
+        Self {
+            client,
+            url,
+            next_sequence_number: start,
+            body: Some(body),
+            buffer: vec![],
+            failed: false,
+        }
+    }
+
+    pub(crate) fn into_stream(self) -> impl Stream<Item = Result<VersionedEvent, RestError>> {
+        stream::unfold(self, |mut state| async move {
+            let result = state.next_event().await?;
+            if result.is_err() {
+                state.failed = true;
+            }
+            Some((result, state))
+        })
+    }
+
+    async fn next_event(&mut self) -> Option<Result<VersionedEvent, RestError>> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            if let Some(data) = self.next_message_data() {
+                return Some(self.parse_event(&data));
+            }
+            match &mut self.body {
+                Some(body) => match body.next().await {
+                    Some(Ok(bytes)) => self.buffer.extend_from_slice(&bytes),
+                    // The connection ended: drop what is left of the last message, it will be
+                    // sent again once resumed.
+                    Some(Err(_)) | None => {
+                        self.body = None;
+                        self.buffer.clear();
+                    }
+                },
+                None => {
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                    match self
+                        .client
+                        .connect_event_stream(self.url.clone(), self.next_sequence_number)
+                        .await
+                    {
+                        Ok(body) => self.body = Some(body),
+                        Err(err) => return Some(Err(err)),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pops the complete messages off the buffer until one carries data, skipping comments
+    /// such as keep-alives.
+    fn next_message_data(&mut self) -> Option<String> {
+        while let Some(end) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+            let message: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let data: Vec<_> = String::from_utf8_lossy(&message)
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|data| data.strip_prefix(' ').unwrap_or(data).to_string())
+                .collect();
+            if !data.is_empty() {
+                return Some(data.join("\n"));
+            }
+        }
+        None
+    }
+
+    fn parse_event(&mut self, data: &str) -> Result<VersionedEvent, RestError> {
+        let event: VersionedEvent = serde_json::from_str(data)?;
+        self.next_sequence_number = event.sequence_number.0 + 1;
+        Ok(event)
+    }
+}
@@ -3,6 +3,7 @@
 
 pub mod aptos;
 pub mod error;
+mod event_stream;
 pub mod faucet;
 
 pub use faucet::FaucetClient;
@@ -19,22 +20,26 @@ pub use types::{Account, Resource};
 
 use crate::aptos::{AptosVersion, Balance};
 use crate::error::RestError;
+use crate::event_stream::{ByteStream, EventStreamState};
 use anyhow::{anyhow, Result};
 use aptos_api_types::mime_types::BCS;
 use aptos_api_types::{
-    mime_types::BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, AptosError, BcsBlock, Block,
-    HexEncodedBytes, MoveModuleId, TransactionData, TransactionOnChainData, UserTransaction,
-    VersionedEvent,
+    mime_types::BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE,
+    mime_types::BCS_VIEW_FUNCTION as BCS_VIEW_CONTENT_TYPE, AptosError, BcsBlock, Block,
+    GasEstimation, HexEncodedBytes, MoveModuleId, TransactionData, TransactionOnChainData,
+    TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent, ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_types::account_config::AccountResource;
 use aptos_types::contract_event::EventWithVersion;
+use aptos_types::event::EventKey;
 use aptos_types::transaction::ExecutionStatus;
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{NewBlockEvent, CORE_CODE_ADDRESS},
-    transaction::SignedTransaction,
+    transaction::{EntryFunction, SignedTransaction},
 };
+use futures::{Stream, StreamExt};
 use move_deps::move_core_types::language_storage::StructTag;
 use reqwest::header::ACCEPT;
 use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient, StatusCode};
@@ -215,6 +220,10 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn estimate_gas_price(&self) -> AptosResult<Response<GasEstimation>> {
+        self.get(self.build_path("estimate_gas_price")?).await
+    }
+
     pub async fn simulate(
         &self,
         txn: &SignedTransaction,
@@ -288,6 +297,44 @@ impl Client {
         Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
     }
 
+    pub async fn submit_batch(
+        &self,
+        txns: &[SignedTransaction],
+    ) -> AptosResult<Response<TransactionsBatchSubmissionResult>> {
+        let txn_payload = bcs::to_bytes(txns)?;
+        let url = self.build_path("transactions/batch")?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
+    pub async fn submit_batch_bcs(
+        &self,
+        txns: &[SignedTransaction],
+    ) -> AptosResult<Response<TransactionsBatchSubmissionResult>> {
+        let txn_payload = bcs::to_bytes(txns)?;
+        let url = self.build_path("transactions/batch")?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .header(ACCEPT, BCS)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
+    }
+
     pub async fn submit_and_wait(
         &self,
         txn: &SignedTransaction,
@@ -474,6 +521,31 @@ impl Client {
         Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
     }
 
+    pub async fn get_pending_transactions(
+        &self,
+        limit: Option<u16>,
+    ) -> AptosResult<Response<Vec<PendingTransaction>>> {
+        let url = self.build_path("transactions/pending")?;
+
+        let mut request = self.inner.get(url);
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+
+        self.json(response).await
+    }
+
+    pub async fn get_pending_transactions_bcs(
+        &self,
+        limit: Option<u16>,
+    ) -> AptosResult<Response<Vec<SignedTransaction>>> {
+        let url = self.build_path("transactions/pending")?;
+        let response = self.get_bcs_with_page(url, None, limit).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_transaction_by_hash(
         &self,
         hash: HashValue,
@@ -663,6 +735,33 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_account_resources_at_version_bcs(
+        &self,
+        address: AccountAddress,
+        version: u64,
+    ) -> AptosResult<Response<BTreeMap<StructTag, Vec<u8>>>> {
+        let url = self.build_path(&format!(
+            "accounts/{}/resources?ledger_version={}",
+            address, version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    pub async fn get_account_resource_at_version_bytes(
+        &self,
+        address: AccountAddress,
+        resource_type: &str,
+        version: u64,
+    ) -> AptosResult<Response<Vec<u8>>> {
+        let url = self.build_path(&format!(
+            "accounts/{}/resource/{}?ledger_version={}",
+            address, resource_type, version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.map(|inner| inner.to_vec()))
+    }
+
     pub async fn get_account_modules(
         &self,
         address: AccountAddress,
@@ -700,6 +799,46 @@ impl Client {
         self.get_bcs(url).await
     }
 
+    pub async fn get_account_modules_at_version_bcs(
+        &self,
+        address: AccountAddress,
+        version: u64,
+    ) -> AptosResult<Response<BTreeMap<MoveModuleId, Vec<u8>>>> {
+        let url = self.build_path(&format!(
+            "accounts/{}/modules?ledger_version={}",
+            address, version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    pub async fn get_account_module_at_version_bcs(
+        &self,
+        address: AccountAddress,
+        module_name: &str,
+        version: u64,
+    ) -> AptosResult<Response<bytes::Bytes>> {
+        let url = self.build_path(&format!(
+            "accounts/{}/module/{}?ledger_version={}",
+            address, module_name, version
+        ))?;
+        self.get_bcs(url).await
+    }
+
+    pub async fn get_events_bcs_by_event_key(
+        &self,
+        event_key: EventKey,
+        start: Option<u64>,
+        limit: Option<u16>,
+    ) -> AptosResult<Response<Vec<EventWithVersion>>> {
+        let url = self.build_path(&format!(
+            "events/{}",
+            aptos_api_types::EventKey::from(event_key)
+        ))?;
+        let response = self.get_bcs_with_page(url, start, limit).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_account_events(
         &self,
         address: AccountAddress,
@@ -746,6 +885,83 @@ impl Client {
         Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
     }
 
+    /// Streams the events of the given event key as they are committed, starting from the
+    /// sequence number `start`, or from the next event to be committed if it is `None`.
+    ///
+    /// The stream reconnects, resuming after the last received event, whenever the connection
+    /// is closed, and ends after the first error.
+    pub async fn stream_events_by_event_key(
+        &self,
+        event_key: EventKey,
+        start: Option<u64>,
+    ) -> AptosResult<impl Stream<Item = AptosResult<VersionedEvent>>> {
+        let start = match start {
+            Some(start) => start,
+            None => next_sequence_number(
+                self.get_events_bcs_by_event_key(event_key, None, Some(1))
+                    .await?
+                    .into_inner(),
+            ),
+        };
+        let url = self.build_path(&format!(
+            "events/{}/stream",
+            aptos_api_types::EventKey::from(event_key)
+        ))?;
+        self.stream_events(url, start).await
+    }
+
+    /// Streams the events of the given event handle as they are committed, like
+    /// `stream_events_by_event_key`.
+    pub async fn stream_account_events(
+        &self,
+        address: AccountAddress,
+        struct_tag: &str,
+        field_name: &str,
+        start: Option<u64>,
+    ) -> AptosResult<impl Stream<Item = AptosResult<VersionedEvent>>> {
+        let start = match start {
+            Some(start) => start,
+            None => next_sequence_number(
+                self.get_account_events_bcs(address, struct_tag, field_name, None, Some(1))
+                    .await?
+                    .into_inner(),
+            ),
+        };
+        let url = self.build_path(&format!(
+            "accounts/{}/events/{}/{}/stream",
+            address.to_hex_literal(),
+            struct_tag,
+            field_name
+        ))?;
+        self.stream_events(url, start).await
+    }
+
+    async fn stream_events(
+        &self,
+        url: Url,
+        start: u64,
+    ) -> AptosResult<impl Stream<Item = AptosResult<VersionedEvent>>> {
+        let body = self.connect_event_stream(url.clone(), start).await?;
+        Ok(EventStreamState::new(self.clone(), url, start, body).into_stream())
+    }
+
+    // The start is always explicit, so that the stream can be resumed without missing events
+    // when reconnecting.
+    pub(crate) async fn connect_event_stream(
+        &self,
+        url: Url,
+        start: u64,
+    ) -> AptosResult<ByteStream> {
+        let response = self
+            .inner
+            .get(url)
+            .query(&[("start", start)])
+            .send()
+            .await?;
+        let (response, _) = self.check_response(response).await?;
+        Ok(response.bytes_stream().boxed())
+    }
+
     pub async fn get_new_block_events(
         &self,
         start: Option<u64>,
@@ -831,6 +1047,64 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_raw_table_item(
+        &self,
+        table_handle: AccountAddress,
+        key: &[u8],
+        version: u64,
+    ) -> AptosResult<Response<Vec<u8>>> {
+        let url = self.build_path(&format!("tables/{}/raw_item", table_handle))?;
+        let data = json!({
+            "key": HexEncodedBytes::from(key.to_vec()),
+        });
+
+        let response = self
+            .inner
+            .post(url)
+            .header(ACCEPT, BCS)
+            .query(&[("ledger_version", version)])
+            .json(&data)
+            .send()
+            .await?;
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.map(|inner| inner.to_vec()))
+    }
+
+    pub async fn view(
+        &self,
+        request: &ViewRequest,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Value>>> {
+        let mut request_builder = self.inner.post(self.build_path("view")?).json(request);
+        if let Some(version) = version {
+            request_builder = request_builder.query(&[("ledger_version", version)]);
+        }
+
+        let response = request_builder.send().await?;
+        self.json(response).await
+    }
+
+    pub async fn view_bcs(
+        &self,
+        request: &EntryFunction,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Vec<u8>>>> {
+        let txn_payload = bcs::to_bytes(request)?;
+        let mut request_builder = self
+            .inner
+            .post(self.build_path("view")?)
+            .header(CONTENT_TYPE, BCS_VIEW_CONTENT_TYPE)
+            .header(ACCEPT, BCS)
+            .body(txn_payload);
+        if let Some(version) = version {
+            request_builder = request_builder.query(&[("ledger_version", version)]);
+        }
+
+        let response = request_builder.send().await?;
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address))?;
         let response = self.inner.get(url).send().await?;
@@ -970,6 +1244,13 @@ fn parse_state_optional(response: &reqwest::Response) -> Option<State> {
         .unwrap_or(None)
 }
 
+/// The sequence number following the latest event, given the latest page of events.
+fn next_sequence_number(latest_events: Vec<EventWithVersion>) -> u64 {
+    latest_events
+        .last()
+        .map_or(0, |event| event.event.sequence_number() + 1)
+}
+
 async fn parse_error(response: reqwest::Response) -> RestError {
     let status_code = response.status();
     let maybe_state = parse_state_optional(&response);
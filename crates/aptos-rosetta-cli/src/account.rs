@@ -4,7 +4,7 @@
 use crate::common::{format_output, BlockArgs, NetworkArgs, UrlArgs};
 use aptos_rosetta::{
     common::native_coin,
-    types::{AccountBalanceRequest, AccountBalanceResponse},
+    types::{AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier},
 };
 use aptos_types::account_address::AccountAddress;
 use clap::{Parser, Subcommand};
@@ -44,6 +44,9 @@ pub struct AccountBalanceCommand {
     /// Account to list the balance
     #[clap(long, parse(try_from_str=aptos::common::types::load_account_arg))]
     account: AccountAddress,
+    /// Whether to list the total stake of the account's stake pool instead
+    #[clap(long)]
+    stake: bool,
 }
 
 impl AccountBalanceCommand {
@@ -52,7 +55,11 @@ impl AccountBalanceCommand {
         client
             .account_balance(&AccountBalanceRequest {
                 network_identifier: self.network_args.network_identifier(),
-                account_identifier: self.account.into(),
+                account_identifier: if self.stake {
+                    AccountIdentifier::stake(self.account)
+                } else {
+                    self.account.into()
+                },
                 block_identifier: self.block_args.into(),
                 currencies: if self.filter_currency {
                     Some(vec![native_coin()])
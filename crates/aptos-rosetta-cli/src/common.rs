@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account, block, construction, network};
+use crate::{account, block, construction, mempool, network, search};
 use aptos_rosetta::{
     client::RosettaClient,
     types::{NetworkIdentifier, NetworkRequest, PartialBlockIdentifier},
@@ -23,7 +23,11 @@ pub enum RosettaCliArgs {
     #[clap(subcommand)]
     Construction(construction::ConstructionCommand),
     #[clap(subcommand)]
+    Mempool(mempool::MempoolCommand),
+    #[clap(subcommand)]
     Network(network::NetworkCommand),
+    #[clap(subcommand)]
+    Search(search::SearchCommand),
 }
 
 impl RosettaCliArgs {
@@ -33,7 +37,9 @@ impl RosettaCliArgs {
             Account(inner) => inner.execute().await,
             Block(inner) => inner.execute().await,
             Construction(inner) => inner.execute().await,
+            Mempool(inner) => inner.execute().await,
             Network(inner) => inner.execute().await,
+            Search(inner) => inner.execute().await,
         }
     }
 }
@@ -3,8 +3,12 @@
 
 use crate::common::{format_output, NetworkArgs, UrlArgs};
 use aptos::common::types::{EncodingOptions, PrivateKeyInputOptions, ProfileOptions};
+use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_logger::info;
-use aptos_rosetta::types::TransactionIdentifier;
+use aptos_rosetta::{
+    client::RosettaClient,
+    types::{NetworkIdentifier, TransactionIdentifier},
+};
 use aptos_types::account_address::AccountAddress;
 use clap::{Parser, Subcommand};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -17,6 +21,12 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 pub enum ConstructionCommand {
     CreateAccount(CreateAccountCommand),
     Transfer(TransferCommand),
+    SetOperator(SetOperatorCommand),
+    SetVoter(SetVoterCommand),
+    AddStake(AddStakeCommand),
+    UnlockStake(UnlockStakeCommand),
+    WithdrawStake(WithdrawStakeCommand),
+    ResetLockup(ResetLockupCommand),
 }
 
 impl ConstructionCommand {
@@ -25,6 +35,12 @@ impl ConstructionCommand {
         match self {
             CreateAccount(inner) => format_output(inner.execute().await),
             Transfer(inner) => format_output(inner.execute().await),
+            SetOperator(inner) => format_output(inner.execute().await),
+            SetVoter(inner) => format_output(inner.execute().await),
+            AddStake(inner) => format_output(inner.execute().await),
+            UnlockStake(inner) => format_output(inner.execute().await),
+            WithdrawStake(inner) => format_output(inner.execute().await),
+            ResetLockup(inner) => format_output(inner.execute().await),
         }
     }
 }
@@ -125,6 +141,182 @@ impl TransferCommand {
     }
 }
 
+/// Arguments shared by all stake pool commands, the stake pool is owned by the signer
+#[derive(Debug, Parser)]
+pub struct StakeArgs {
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    #[clap(flatten)]
+    private_key_options: PrivateKeyInputOptions,
+}
+
+impl StakeArgs {
+    fn extract(self) -> anyhow::Result<(RosettaClient, NetworkIdentifier, Ed25519PrivateKey)> {
+        let private_key = self.private_key_options.extract_private_key(
+            self.encoding_options.encoding,
+            &self.profile_options.profile,
+        )?;
+        Ok((
+            self.url_args.client(),
+            self.network_args.network_identifier(),
+            private_key,
+        ))
+    }
+}
+
+/// Sets the operator of the signer's stake pool via Rosetta
+#[derive(Debug, Parser)]
+pub struct SetOperatorCommand {
+    #[clap(flatten)]
+    stake_args: StakeArgs,
+    /// The new operator of the stake pool
+    #[clap(long, parse(try_from_str=aptos::common::types::load_account_arg))]
+    operator: AccountAddress,
+}
+
+impl SetOperatorCommand {
+    pub async fn execute(self) -> anyhow::Result<TransactionIdentifier> {
+        info!("Set operator {:?}", self);
+        let (client, network_identifier, private_key) = self.stake_args.extract()?;
+        client
+            .set_operator(
+                &network_identifier,
+                &private_key,
+                self.operator,
+                expiry_time()?,
+                None,
+            )
+            .await
+    }
+}
+
+/// Sets the delegated voter of the signer's stake pool via Rosetta
+#[derive(Debug, Parser)]
+pub struct SetVoterCommand {
+    #[clap(flatten)]
+    stake_args: StakeArgs,
+    /// The new delegated voter of the stake pool
+    #[clap(long, parse(try_from_str=aptos::common::types::load_account_arg))]
+    voter: AccountAddress,
+}
+
+impl SetVoterCommand {
+    pub async fn execute(self) -> anyhow::Result<TransactionIdentifier> {
+        info!("Set voter {:?}", self);
+        let (client, network_identifier, private_key) = self.stake_args.extract()?;
+        client
+            .set_voter(
+                &network_identifier,
+                &private_key,
+                self.voter,
+                expiry_time()?,
+                None,
+            )
+            .await
+    }
+}
+
+/// Adds coins from the signer's account to its stake pool via Rosetta
+#[derive(Debug, Parser)]
+pub struct AddStakeCommand {
+    #[clap(flatten)]
+    stake_args: StakeArgs,
+    /// The amount of coins to stake
+    #[clap(long)]
+    amount: u64,
+}
+
+impl AddStakeCommand {
+    pub async fn execute(self) -> anyhow::Result<TransactionIdentifier> {
+        info!("Add stake {:?}", self);
+        let (client, network_identifier, private_key) = self.stake_args.extract()?;
+        client
+            .add_stake(
+                &network_identifier,
+                &private_key,
+                self.amount,
+                expiry_time()?,
+                None,
+            )
+            .await
+    }
+}
+
+/// Unlocks active stake of the signer's stake pool via Rosetta
+#[derive(Debug, Parser)]
+pub struct UnlockStakeCommand {
+    #[clap(flatten)]
+    stake_args: StakeArgs,
+    /// The amount of stake to unlock
+    #[clap(long)]
+    amount: u64,
+}
+
+impl UnlockStakeCommand {
+    pub async fn execute(self) -> anyhow::Result<TransactionIdentifier> {
+        info!("Unlock stake {:?}", self);
+        let (client, network_identifier, private_key) = self.stake_args.extract()?;
+        client
+            .unlock_stake(
+                &network_identifier,
+                &private_key,
+                self.amount,
+                expiry_time()?,
+                None,
+            )
+            .await
+    }
+}
+
+/// Withdraws inactive stake of the signer's stake pool to its account via Rosetta
+#[derive(Debug, Parser)]
+pub struct WithdrawStakeCommand {
+    #[clap(flatten)]
+    stake_args: StakeArgs,
+    /// The amount of stake to withdraw
+    #[clap(long)]
+    amount: u64,
+}
+
+impl WithdrawStakeCommand {
+    pub async fn execute(self) -> anyhow::Result<TransactionIdentifier> {
+        info!("Withdraw stake {:?}", self);
+        let (client, network_identifier, private_key) = self.stake_args.extract()?;
+        client
+            .withdraw_stake(
+                &network_identifier,
+                &private_key,
+                self.amount,
+                expiry_time()?,
+                None,
+            )
+            .await
+    }
+}
+
+/// Resets the lockup of the signer's stake pool via Rosetta
+#[derive(Debug, Parser)]
+pub struct ResetLockupCommand {
+    #[clap(flatten)]
+    stake_args: StakeArgs,
+}
+
+impl ResetLockupCommand {
+    pub async fn execute(self) -> anyhow::Result<TransactionIdentifier> {
+        info!("Reset lockup {:?}", self);
+        let (client, network_identifier, private_key) = self.stake_args.extract()?;
+        client
+            .reset_lockup(&network_identifier, &private_key, expiry_time()?, None)
+            .await
+    }
+}
+
 fn expiry_time() -> anyhow::Result<u64> {
     Ok((SystemTime::now().duration_since(UNIX_EPOCH)? + Duration::from_secs(60)).as_secs())
 }
@@ -21,7 +21,9 @@ mod account;
 mod block;
 mod common;
 mod construction;
+mod mempool;
 mod network;
+mod search;
 
 use crate::common::{ErrorWrapper, RosettaCliArgs};
 use aptos_logger::Level;
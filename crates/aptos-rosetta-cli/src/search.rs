@@ -0,0 +1,86 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{format_output, NetworkArgs, UrlArgs};
+use aptos_rosetta::types::{
+    AccountIdentifier, SearchTransactionsRequest, SearchTransactionsResponse, TransactionIdentifier,
+};
+use aptos_types::account_address::AccountAddress;
+use clap::{Parser, Subcommand};
+
+/// Search APIs
+///
+/// Used for finding committed transactions without knowing their blocks
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html)
+#[derive(Debug, Subcommand)]
+pub enum SearchCommand {
+    Transactions(SearchTransactionsCommand),
+}
+
+impl SearchCommand {
+    pub async fn execute(self) -> anyhow::Result<String> {
+        match self {
+            SearchCommand::Transactions(inner) => format_output(inner.execute().await),
+        }
+    }
+}
+
+/// Search transactions by hash, account, or status
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+#[derive(Debug, Parser)]
+pub struct SearchTransactionsCommand {
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+    /// Hash of the transaction
+    #[clap(long)]
+    txn_hash: Option<String>,
+    /// Account that an operation of the transaction must be on
+    #[clap(long, parse(try_from_str=aptos::common::types::load_account_arg))]
+    account: Option<AccountAddress>,
+    /// Whether the operation must be on the stake of the account's stake pool instead
+    #[clap(long, requires = "account")]
+    stake: bool,
+    /// Status an operation of the transaction must have e.g. success
+    #[clap(long)]
+    status: Option<String>,
+    /// Whether the transaction must have succeeded or failed
+    #[clap(long)]
+    success: Option<bool>,
+    /// Highest block index a transaction may be in
+    #[clap(long)]
+    max_block: Option<u64>,
+    /// Number of matching transactions to skip
+    #[clap(long)]
+    offset: Option<u64>,
+    /// Maximum number of matching transactions to return
+    #[clap(long)]
+    limit: Option<u64>,
+}
+
+impl SearchTransactionsCommand {
+    pub async fn execute(self) -> anyhow::Result<SearchTransactionsResponse> {
+        let stake = self.stake;
+        let request = SearchTransactionsRequest {
+            network_identifier: self.network_args.network_identifier(),
+            operator: None,
+            max_block: self.max_block,
+            offset: self.offset,
+            limit: self.limit,
+            transaction_identifier: self.txn_hash.map(|hash| TransactionIdentifier { hash }),
+            account_identifier: self.account.map(|account| {
+                if stake {
+                    AccountIdentifier::stake(account)
+                } else {
+                    account.into()
+                }
+            }),
+            status: self.status,
+            success: self.success,
+        };
+        self.url_args.client().search_transactions(&request).await
+    }
+}
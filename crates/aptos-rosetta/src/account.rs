@@ -8,7 +8,7 @@
 
 use crate::types::{
     account_module_identifier, account_resource_identifier, coin_module_identifier,
-    AccountBalanceMetadata,
+    stake_module_identifier, stake_pool_resource_identifier, AccountBalanceMetadata,
 };
 use crate::{
     common::{
@@ -27,6 +27,7 @@ use aptos_rest_client::aptos_api_types::AccountData;
 use aptos_rest_client::{
     aptos::{AptosCoin, Balance},
     aptos_api_types::U64,
+    Resource,
 };
 use aptos_sdk::move_types::language_storage::TypeTag;
 use aptos_types::account_address::AccountAddress;
@@ -81,10 +82,23 @@ async fn account_balance(
         .await?;
     let balance_version = block_info.last_version;
 
+    // The only supported sub account is the stake of a stake pool
+    let is_stake = match request.account_identifier.sub_account {
+        None => false,
+        Some(ref sub_account) if sub_account.is_stake() => true,
+        Some(ref sub_account) => {
+            return Err(ApiError::InvalidInput(Some(format!(
+                "Unsupported sub account {}",
+                sub_account.address
+            ))))
+        }
+    };
+
     let (sequence_number, balances) = get_balances(
         &rest_client,
         request.account_identifier.account_address()?,
         balance_version,
+        is_stake,
     )
     .await?;
 
@@ -151,11 +165,12 @@ async fn convert_balances_to_amounts(
     Ok(amounts)
 }
 
-/// Retrieve the balances for an account
+/// Retrieve the balances for an account, or for the stake sub account if `is_stake`
 async fn get_balances(
     rest_client: &aptos_rest_client::Client,
     address: AccountAddress,
     version: u64,
+    is_stake: bool,
 ) -> ApiResult<(u64, HashMap<TypeTag, Balance>)> {
     if let Ok(response) = rest_client
         .get_account_resources_at_version(address, version)
@@ -188,6 +203,10 @@ async fn get_balances(
             )));
         };
 
+        if is_stake {
+            return Ok((sequence_number, get_stake_balances(&response)));
+        }
+
         let balances = response
             .iter()
             .filter(|resource| {
@@ -223,6 +242,50 @@ async fn get_balances(
     }
 }
 
+/// Retrieve the total stake of the stake pool in the resources, regardless of lockup state
+fn get_stake_balances(resources: &[Resource]) -> HashMap<TypeTag, Balance> {
+    /// Type for deserializing the stake of a stake pool
+    #[derive(Debug, Deserialize)]
+    struct StakePool {
+        active: AptosCoin,
+        inactive: AptosCoin,
+        pending_active: AptosCoin,
+        pending_inactive: AptosCoin,
+    }
+
+    let total_stake = resources
+        .iter()
+        .find(|resource| {
+            resource.resource_type.address == AccountAddress::ONE
+                && resource.resource_type.module == stake_module_identifier()
+                && resource.resource_type.name == stake_pool_resource_identifier()
+        })
+        .and_then(|resource| serde_json::from_value::<StakePool>(resource.data.clone()).ok())
+        .map(|pool| {
+            [
+                pool.active,
+                pool.inactive,
+                pool.pending_active,
+                pool.pending_inactive,
+            ]
+            .iter()
+            .map(|coin| coin.value.0)
+            .sum()
+        })
+        .unwrap_or(0);
+
+    let mut balances = HashMap::new();
+    balances.insert(
+        native_coin_tag(),
+        Balance {
+            coin: AptosCoin {
+                value: U64(total_stake),
+            },
+        },
+    );
+    balances
+}
+
 /// A cache for currencies, so we don't have to keep looking up the status of it
 #[derive(Debug)]
 pub struct CoinCache {
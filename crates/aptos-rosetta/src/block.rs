@@ -194,6 +194,45 @@ impl BlockCache {
         Ok(block)
     }
 
+    /// Retrieve the block info for the block containing the version
+    pub async fn get_block_info_by_version(&self, version: u64) -> ApiResult<BlockInfo> {
+        // A cached block contains the version if the cached block before it ends before it
+        if let Some(info) = self.find_cached_block_by_version(version) {
+            return Ok(info);
+        }
+
+        let block = self
+            .rest_client
+            .get_block_by_version(version, false)
+            .await?
+            .into_inner();
+        let block_info = BlockInfo::from_block(&block);
+        self.blocks
+            .write()
+            .unwrap()
+            .insert(block.block_height.0, block_info.clone());
+        self.hashes
+            .write()
+            .unwrap()
+            .insert(block.block_hash, block.block_height.0);
+
+        Ok(block_info)
+    }
+
+    fn find_cached_block_by_version(&self, version: u64) -> Option<BlockInfo> {
+        let blocks = self.blocks.read().unwrap();
+        let (height, info) = blocks
+            .iter()
+            .find(|(_, info)| info.last_version >= version)?;
+        if *height == 0 {
+            return Some(info.clone());
+        }
+        match blocks.get(&(height - 1)) {
+            Some(prev_info) if prev_info.last_version < version => Some(info.clone()),
+            _ => None,
+        }
+    }
+
     /// Retrieve the block info for the hash
     ///
     /// This is particularly bad, since there's no index on this value.  It can only be derived
@@ -9,10 +9,11 @@ use crate::types::{
     ConstructionMetadata, ConstructionMetadataRequest, ConstructionMetadataResponse,
     ConstructionParseRequest, ConstructionParseResponse, ConstructionPayloadsRequest,
     ConstructionPayloadsResponse, ConstructionPreprocessRequest, ConstructionPreprocessResponse,
-    ConstructionSubmitRequest, ConstructionSubmitResponse, Error, MetadataRequest,
-    NetworkIdentifier, NetworkListResponse, NetworkOptionsResponse, NetworkRequest,
-    NetworkStatusResponse, Operation, PreprocessMetadata, PublicKey, Signature, SignatureType,
-    TransactionIdentifier, TransactionIdentifierResponse,
+    ConstructionSubmitRequest, ConstructionSubmitResponse, Error, MempoolRequest, MempoolResponse,
+    MempoolTransactionRequest, MempoolTransactionResponse, MetadataRequest, NetworkIdentifier,
+    NetworkListResponse, NetworkOptionsResponse, NetworkRequest, NetworkStatusResponse, Operation,
+    PreprocessMetadata, PublicKey, SearchTransactionsRequest, SearchTransactionsResponse,
+    Signature, SignatureType, TransactionIdentifier, TransactionIdentifierResponse,
 };
 use anyhow::anyhow;
 use aptos_crypto::ed25519::Ed25519PrivateKey;
@@ -110,6 +111,17 @@ impl RosettaClient {
         self.make_call("construction/submit", request).await
     }
 
+    pub async fn mempool(&self, request: &MempoolRequest) -> anyhow::Result<MempoolResponse> {
+        self.make_call("mempool", request).await
+    }
+
+    pub async fn mempool_transaction(
+        &self,
+        request: &MempoolTransactionRequest,
+    ) -> anyhow::Result<MempoolTransactionResponse> {
+        self.make_call("mempool/transaction", request).await
+    }
+
     pub async fn network_list(&self) -> anyhow::Result<NetworkListResponse> {
         self.make_call("network/list", &MetadataRequest {}).await
     }
@@ -128,6 +140,13 @@ impl RosettaClient {
         self.make_call("network/status", request).await
     }
 
+    pub async fn search_transactions(
+        &self,
+        request: &SearchTransactionsRequest,
+    ) -> anyhow::Result<SearchTransactionsResponse> {
+        self.make_call("search/transactions", request).await
+    }
+
     async fn make_call<'a, I: Serialize + Debug, O: DeserializeOwned>(
         &'a self,
         path: &'static str,
@@ -207,6 +226,138 @@ impl RosettaClient {
         .await
     }
 
+    pub async fn set_operator(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        operator: AccountAddress,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::set_operator(0, None, owner, operator),
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
+    pub async fn set_voter(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        voter: AccountAddress,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::set_voter(0, None, owner, voter),
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
+    pub async fn add_stake(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        amount: u64,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::add_stake(0, None, owner, amount),
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
+    pub async fn unlock_stake(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        amount: u64,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::unlock_stake(0, None, owner, amount),
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
+    pub async fn withdraw_stake(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        amount: u64,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::withdraw_stake(0, None, owner, amount),
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
+    pub async fn reset_lockup(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::reset_lockup(0, None, owner),
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
+    /// Submits a single operation on the stake pool owned by the account of the private key
+    async fn submit_stake_operation<F: FnOnce(AccountAddress) -> Operation>(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        build_operation: F,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        let owner = self
+            .get_account_address(network_identifier.clone(), private_key)
+            .await?;
+        let mut keys = HashMap::new();
+        keys.insert(owner, private_key);
+
+        self.submit_operations(
+            network_identifier.clone(),
+            &keys,
+            vec![build_operation(owner)],
+            expiry_time_secs,
+            sequence_number,
+        )
+        .await
+    }
+
     /// Retrieves the account address from the derivation path if there isn't an overriding account specified
     async fn get_account_address(
         &self,
@@ -320,6 +320,31 @@ async fn construction_parse(
                 && set_operator_function_identifier() == function_name
             {
                 parse_set_operator_operation(sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && set_delegated_voter_function_identifier() == function_name
+            {
+                parse_set_voter_operation(sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && add_stake_function_identifier() == function_name
+            {
+                parse_stake_amount_operation(sender, &type_args, &args, Operation::add_stake)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && unlock_function_identifier() == function_name
+            {
+                parse_stake_amount_operation(sender, &type_args, &args, Operation::unlock_stake)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && withdraw_function_identifier() == function_name
+            {
+                parse_stake_amount_operation(sender, &type_args, &args, Operation::withdraw_stake)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && increase_lockup_function_identifier() == function_name
+            {
+                parse_reset_lockup_operation(sender, &type_args, &args)?
             } else {
                 return Err(ApiError::TransactionParseError(Some(format!(
                     "Unsupported entry function type {:x}::{}::{}",
@@ -484,6 +509,72 @@ fn parse_set_operator_operation(
     }
 }
 
+fn parse_set_voter_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    // There are no typeargs for set voter
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Set voter should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    // Set voter
+    if let Some(encoded_voter) = args.first() {
+        let voter: AccountAddress = bcs::from_bytes(encoded_voter)?;
+
+        Ok(vec![Operation::set_voter(0, None, sender, voter)])
+    } else {
+        Err(ApiError::InvalidOperations)
+    }
+}
+
+/// Parses the stake functions that only take the amount of stake, which is built into an
+/// operation with `build_operation`
+fn parse_stake_amount_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+    build_operation: fn(u64, Option<OperationStatusType>, AccountAddress, u64) -> Operation,
+) -> ApiResult<Vec<Operation>> {
+    // There are no typeargs for stake functions
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Stake functions should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    let amount: u64 = if let Some(amount) = args.first() {
+        bcs::from_bytes(amount)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No amount in stake function".to_string(),
+        )));
+    };
+
+    Ok(vec![build_operation(0, None, sender, amount)])
+}
+
+fn parse_reset_lockup_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    // There are no typeargs or args for increase lockup
+    if !type_args.is_empty() || !args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Increase lockup should not have arguments: {:?} {:?}",
+            type_args, args
+        ))));
+    }
+
+    Ok(vec![Operation::reset_lockup(0, None, sender)])
+}
+
 /// Construction payloads command (OFFLINE)
 ///
 /// Constructs payloads for given known operations
@@ -521,6 +612,25 @@ async fn construction_payloads(
             aptos_stdlib::stake_set_operator(set_operator.operator),
             set_operator.owner,
         ),
+        InternalOperation::SetVoter(set_voter) => (
+            aptos_stdlib::stake_set_delegated_voter(set_voter.voter),
+            set_voter.owner,
+        ),
+        InternalOperation::AddStake(add_stake) => (
+            aptos_stdlib::stake_add_stake(add_stake.amount),
+            add_stake.owner,
+        ),
+        InternalOperation::UnlockStake(unlock_stake) => (
+            aptos_stdlib::stake_unlock(unlock_stake.amount),
+            unlock_stake.owner,
+        ),
+        InternalOperation::WithdrawStake(withdraw_stake) => (
+            aptos_stdlib::stake_withdraw(withdraw_stake.amount),
+            withdraw_stake.owner,
+        ),
+        InternalOperation::ResetLockup(reset_lockup) => {
+            (aptos_stdlib::stake_increase_lockup(), reset_lockup.owner)
+        }
     };
 
     // Build the transaction and make it ready for signing
@@ -0,0 +1,104 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+        Transaction,
+    },
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::HashValue;
+use std::str::FromStr;
+use warp::Filter;
+
+/// Maximum number of transactions listed from mempool, the most the node API returns at once
+const MAX_MEMPOOL_TRANSACTIONS: u16 = 1000;
+
+pub fn mempool_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("mempool")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool))
+}
+
+pub fn mempool_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("mempool" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool_transaction))
+}
+
+/// Lists the hashes of all transactions in the connected node's mempool
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+async fn mempool(
+    request: MempoolRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolResponse> {
+    debug!("/mempool");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+    let transaction_identifiers = rest_client
+        .get_pending_transactions(Some(MAX_MEMPOOL_TRANSACTIONS))
+        .await?
+        .into_inner()
+        .iter()
+        .map(|txn| txn.into())
+        .collect();
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Retrieves a transaction in mempool by its hash
+///
+/// The operations are an estimate from the payload, as the transaction hasn't executed yet.
+/// Transactions that have already been committed are not in mempool, and aren't found.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+async fn mempool_transaction(
+    request: MempoolTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolTransactionResponse> {
+    debug!("/mempool/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+    let hash = HashValue::from_str(&request.transaction_identifier.hash)?;
+    let txn = rest_client
+        .get_transaction_by_hash(hash.into())
+        .await?
+        .into_inner();
+
+    match txn {
+        aptos_rest_client::Transaction::PendingTransaction(txn) => Ok(MempoolTransactionResponse {
+            transaction: Transaction::from_pending_transaction(txn),
+        }),
+        _ => Err(ApiError::TransactionNotFound(Some(format!(
+            "Transaction {} is not in mempool",
+            request.transaction_identifier.hash
+        )))),
+    }
+}
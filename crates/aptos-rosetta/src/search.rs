@@ -0,0 +1,213 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    block::BlockCache,
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        BlockTransaction, Operation, Operator, SearchTransactionsRequest,
+        SearchTransactionsResponse, SubAccountIdentifier, Transaction,
+    },
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::HashValue;
+use aptos_types::account_address::AccountAddress;
+use std::str::FromStr;
+use warp::Filter;
+
+/// Number of transactions returned if no limit is given
+const DEFAULT_SEARCH_LIMIT: u64 = 25;
+
+/// Maximum number of transactions returned at once, and the page size used to scan an account's
+/// transactions, the most the node API returns at once
+const MAX_SEARCH_LIMIT: u64 = 1000;
+
+pub fn search_transactions_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("search" / "transactions")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(search_transactions))
+}
+
+/// Searches committed transactions by hash, account, and status
+///
+/// A search by hash looks up the single transaction.  Otherwise, the account's transactions are
+/// scanned until `offset + limit` matches are found, and up to `limit` of the matches are returned
+/// starting at `offset`.  As the scan stops early, `total_count` is only a lower bound on the
+/// number of matches, and `next_offset` is set if there may be more of them.  The node only
+/// indexes transactions by sender, so a search by account only finds transactions the account
+/// sent, and a search needs either a hash or an account.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+async fn search_transactions(
+    request: SearchTransactionsRequest,
+    server_context: RosettaContext,
+) -> ApiResult<SearchTransactionsResponse> {
+    debug!("/search/transactions");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/search/transactions",
+    );
+
+    check_network(request.network_identifier.clone(), &server_context)?;
+    if request.operator == Some(Operator::Or) {
+        return Err(ApiError::InvalidInput(Some(
+            "Only the `and` operator is supported when searching transactions".to_string(),
+        )));
+    }
+    let limit = request.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if limit == 0 || limit > MAX_SEARCH_LIMIT {
+        return Err(ApiError::InvalidInput(Some(format!(
+            "Limit must be between 1 and {}",
+            MAX_SEARCH_LIMIT
+        ))));
+    }
+    let offset = request.offset.unwrap_or(0);
+    let account_filter = match request.account_identifier {
+        Some(ref account) => Some((account.account_address()?, account.sub_account.clone())),
+        None => None,
+    };
+
+    let rest_client = server_context.rest_client()?;
+    let block_cache = server_context.block_cache()?;
+    let mut total_count = 0;
+    let mut has_more = false;
+    let mut transactions = vec![];
+    if let Some(ref transaction_identifier) = request.transaction_identifier {
+        let hash = HashValue::from_str(&transaction_identifier.hash)?;
+        let txn = match rest_client
+            .get_transaction_by_hash(hash.into())
+            .await
+            .map_err(ApiError::from)
+        {
+            Ok(response) => Some(response.into_inner()),
+            Err(ApiError::TransactionNotFound(_)) => None,
+            Err(err) => return Err(err),
+        };
+        if let Some(txn) = txn {
+            if let Some(found) =
+                matching_transaction(&request, &account_filter, &block_cache, txn).await?
+            {
+                total_count = 1;
+                if offset == 0 {
+                    transactions.push(found);
+                }
+            }
+        }
+    } else if let Some((address, _)) = account_filter {
+        let max_count = offset.saturating_add(limit);
+        let mut start = 0;
+        'pages: loop {
+            let txns = rest_client
+                .get_account_transactions(address, Some(start), Some(MAX_SEARCH_LIMIT))
+                .await?
+                .into_inner();
+            // A partial page is the end of the account's transactions
+            let is_last_page = (txns.len() as u64) < MAX_SEARCH_LIMIT;
+            let mut txns = txns.into_iter().peekable();
+            while let Some(txn) = txns.next() {
+                if let Some(found) =
+                    matching_transaction(&request, &account_filter, &block_cache, txn).await?
+                {
+                    if total_count >= offset {
+                        transactions.push(found);
+                    }
+                    total_count += 1;
+                    // Don't scan any further than needed for this page of results
+                    if total_count >= max_count {
+                        has_more = txns.peek().is_some() || !is_last_page;
+                        break 'pages;
+                    }
+                }
+            }
+            if is_last_page {
+                break;
+            }
+            start += MAX_SEARCH_LIMIT;
+        }
+    } else {
+        return Err(ApiError::InvalidInput(Some(
+            "Searching transactions requires a transaction hash or an account".to_string(),
+        )));
+    }
+
+    let next_offset = if has_more { Some(total_count) } else { None };
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count,
+        next_offset,
+    })
+}
+
+/// Converts the transaction along with its block, if it matches the conditions of the search
+async fn matching_transaction(
+    request: &SearchTransactionsRequest,
+    account_filter: &Option<(AccountAddress, Option<SubAccountIdentifier>)>,
+    block_cache: &BlockCache,
+    txn: aptos_rest_client::Transaction,
+) -> ApiResult<Option<BlockTransaction>> {
+    // Pending transactions aren't in a block yet
+    let version = match txn.version() {
+        Some(version) => version,
+        None => return Ok(None),
+    };
+    if request
+        .success
+        .map_or(false, |success| success != txn.success())
+    {
+        return Ok(None);
+    }
+
+    let transaction = Transaction::from_transaction(txn).await?;
+    if let Some((address, ref sub_account)) = *account_filter {
+        if !transaction
+            .operations
+            .iter()
+            .any(|operation| is_on_account(operation, address, sub_account))
+        {
+            return Ok(None);
+        }
+    }
+    if let Some(ref status) = request.status {
+        if !transaction
+            .operations
+            .iter()
+            .any(|operation| operation.status.as_ref() == Some(status))
+        {
+            return Ok(None);
+        }
+    }
+
+    let block_identifier = block_cache
+        .get_block_info_by_version(version)
+        .await?
+        .block_id;
+    if request
+        .max_block
+        .map_or(false, |max_block| block_identifier.index > max_block)
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(BlockTransaction {
+        block_identifier,
+        transaction,
+    }))
+}
+
+/// Whether the operation is on the account, or the given sub account of it
+fn is_on_account(
+    operation: &Operation,
+    address: AccountAddress,
+    sub_account: &Option<SubAccountIdentifier>,
+) -> bool {
+    operation.account.as_ref().map_or(false, |account| {
+        account.account_address().ok() == Some(address) && account.sub_account == *sub_account
+    })
+}
@@ -9,7 +9,7 @@ use crate::{
     common::{to_hex_lower, BLOCKCHAIN},
     error::{ApiError, ApiResult},
 };
-use aptos_rest_client::aptos_api_types::TransactionInfo;
+use aptos_rest_client::aptos_api_types::{PendingTransaction, TransactionInfo};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -33,6 +33,22 @@ impl AccountIdentifier {
     pub fn account_address(&self) -> ApiResult<AccountAddress> {
         self.try_into()
     }
+
+    /// The stake sub account of the stake pool at `address`
+    pub fn stake(address: AccountAddress) -> AccountIdentifier {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier::stake()),
+        }
+    }
+
+    /// Whether this is the stake sub account of a stake pool
+    pub fn is_stake(&self) -> bool {
+        self.sub_account
+            .as_ref()
+            .map(SubAccountIdentifier::is_stake)
+            .unwrap_or(false)
+    }
 }
 
 impl TryFrom<&AccountIdentifier> for AccountAddress {
@@ -177,6 +193,21 @@ pub struct SubAccountIdentifier {
     pub address: String,
 }
 
+impl SubAccountIdentifier {
+    /// Sub account holding all the stake of a stake pool, regardless of its lockup state
+    const STAKE: &'static str = "stake";
+
+    pub fn stake() -> SubAccountIdentifier {
+        SubAccountIdentifier {
+            address: Self::STAKE.to_string(),
+        }
+    }
+
+    pub fn is_stake(&self) -> bool {
+        self.address == Self::STAKE
+    }
+}
+
 /// Sub network identifier if there are sub networks
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/SubNetworkIdentifier.html)
@@ -201,3 +232,11 @@ impl From<&TransactionInfo> for TransactionIdentifier {
         }
     }
 }
+
+impl From<&PendingTransaction> for TransactionIdentifier {
+    fn from(txn: &PendingTransaction) -> Self {
+        TransactionIdentifier {
+            hash: to_hex_lower(&txn.hash),
+        }
+    }
+}
@@ -57,6 +57,26 @@ pub fn set_operator_function_identifier() -> Identifier {
     ident_str!("set_operator").into()
 }
 
+pub fn set_delegated_voter_function_identifier() -> Identifier {
+    ident_str!("set_delegated_voter").into()
+}
+
+pub fn add_stake_function_identifier() -> Identifier {
+    ident_str!("add_stake").into()
+}
+
+pub fn unlock_function_identifier() -> Identifier {
+    ident_str!("unlock").into()
+}
+
+pub fn withdraw_function_identifier() -> Identifier {
+    ident_str!("withdraw").into()
+}
+
+pub fn increase_lockup_function_identifier() -> Identifier {
+    ident_str!("increase_lockup").into()
+}
+
 // Field identifiers
 pub fn decimals_field_identifier() -> Identifier {
     ident_str!("decimals").into()
@@ -74,6 +94,30 @@ pub fn set_operator_events_field_identifier() -> Identifier {
     ident_str!("set_operator_events").into()
 }
 
+pub fn add_stake_events_field_identifier() -> Identifier {
+    ident_str!("add_stake_events").into()
+}
+
+pub fn unlock_stake_events_field_identifier() -> Identifier {
+    ident_str!("unlock_stake_events").into()
+}
+
+pub fn withdraw_stake_events_field_identifier() -> Identifier {
+    ident_str!("withdraw_stake_events").into()
+}
+
+pub fn increase_lockup_events_field_identifier() -> Identifier {
+    ident_str!("increase_lockup_events").into()
+}
+
+pub fn distribute_rewards_events_field_identifier() -> Identifier {
+    ident_str!("distribute_rewards_events").into()
+}
+
+pub fn delegated_voter_field_identifier() -> Identifier {
+    ident_str!("delegated_voter").into()
+}
+
 pub fn sequence_number_field_identifier() -> Identifier {
     ident_str!("sequence_number").into()
 }
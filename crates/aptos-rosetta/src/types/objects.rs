@@ -7,10 +7,15 @@
 
 use crate::common::native_coin_tag;
 use crate::types::{
-    account_module_identifier, aptos_coin_module_identifier, aptos_coin_resource_identifier,
-    coin_module_identifier, create_account_function_identifier,
+    account_module_identifier, add_stake_events_field_identifier, add_stake_function_identifier,
+    aptos_coin_module_identifier, aptos_coin_resource_identifier, coin_module_identifier,
+    create_account_function_identifier, delegated_voter_field_identifier,
+    distribute_rewards_events_field_identifier, increase_lockup_events_field_identifier,
+    increase_lockup_function_identifier, set_delegated_voter_function_identifier,
     set_operator_events_field_identifier, set_operator_function_identifier,
     stake_module_identifier, stake_pool_resource_identifier, transfer_function_identifier,
+    unlock_function_identifier, unlock_stake_events_field_identifier, withdraw_function_identifier,
+    withdraw_stake_events_field_identifier,
 };
 use crate::{
     common::{is_native_coin, native_coin},
@@ -27,15 +32,18 @@ use crate::{
 use anyhow::anyhow;
 use aptos_crypto::{ed25519::Ed25519PublicKey, ValidCryptoMaterialStringExt};
 use aptos_rest_client::aptos_api_types::{
-    Address, Event, MoveStructTag, MoveType, TransactionPayload, UserTransactionRequest,
-    WriteResource,
+    Address, Event, MoveStructTag, MoveType, PendingTransaction, TransactionPayload,
+    UserTransactionRequest, WriteResource,
 };
 use aptos_rest_client::{
     aptos::Balance,
     aptos_api_types::{WriteSetChange, U64},
 };
 use aptos_types::{account_address::AccountAddress, event::EventKey};
-use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize};
+use serde::{
+    de::{DeserializeOwned, Error as SerdeError, IgnoredAny},
+    Deserialize, Deserializer, Serialize,
+};
 use std::cmp::Ordering;
 use std::{
     collections::HashMap,
@@ -165,9 +173,9 @@ pub enum BlockEventType {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BlockTransaction {
     /// Block associated with transaction
-    block_identifier: BlockIdentifier,
+    pub block_identifier: BlockIdentifier,
     /// Transaction associated with block
-    transaction: Transaction,
+    pub transaction: Transaction,
 }
 
 /// Tells what cases are supported in hashes. Having no value is case insensitive.
@@ -275,7 +283,7 @@ impl Operation {
         operation_type: OperationType,
         operation_index: u64,
         status: Option<OperationStatusType>,
-        address: AccountAddress,
+        account: AccountIdentifier,
         amount: Option<Amount>,
         metadata: Option<OperationMetadata>,
     ) -> Operation {
@@ -287,7 +295,7 @@ impl Operation {
             related_operations: None,
             operation_type: operation_type.to_string(),
             status: status.map(|inner| inner.to_string()),
-            account: Some(address.into()),
+            account: Some(account),
             amount,
             metadata,
         }
@@ -303,7 +311,7 @@ impl Operation {
             OperationType::CreateAccount,
             operation_index,
             status,
-            address,
+            address.into(),
             None,
             Some(OperationMetadata::create_account(sender)),
         )
@@ -320,7 +328,7 @@ impl Operation {
             OperationType::Deposit,
             operation_index,
             status,
-            address,
+            address.into(),
             Some(Amount {
                 value: amount.to_string(),
                 currency,
@@ -340,7 +348,7 @@ impl Operation {
             OperationType::Withdraw,
             operation_index,
             status,
-            address,
+            address.into(),
             Some(Amount {
                 value: format!("-{}", amount),
                 currency,
@@ -351,6 +359,7 @@ impl Operation {
 
     pub fn gas_fee(
         operation_index: u64,
+        status: Option<OperationStatusType>,
         address: AccountAddress,
         gas_used: u64,
         gas_price_per_unit: u64,
@@ -358,8 +367,8 @@ impl Operation {
         Operation::new(
             OperationType::Fee,
             operation_index,
-            Some(OperationStatusType::Success),
-            address,
+            status,
+            address.into(),
             Some(Amount {
                 value: format!("-{}", gas_used.saturating_mul(gas_price_per_unit)),
                 currency: native_coin(),
@@ -375,14 +384,120 @@ impl Operation {
         operator: AccountAddress,
     ) -> Operation {
         Operation::new(
-            OperationType::Withdraw,
+            OperationType::SetOperator,
             operation_index,
             status,
-            address,
+            address.into(),
             None,
             Some(OperationMetadata::set_operator(operator)),
         )
     }
+
+    pub fn set_voter(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        voter: AccountAddress,
+    ) -> Operation {
+        Operation::new(
+            OperationType::SetVoter,
+            operation_index,
+            status,
+            address.into(),
+            None,
+            Some(OperationMetadata::set_voter(voter)),
+        )
+    }
+
+    pub fn add_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::new(
+            OperationType::AddStake,
+            operation_index,
+            status,
+            AccountIdentifier::stake(address),
+            Some(Amount {
+                value: amount.to_string(),
+                currency: native_coin(),
+            }),
+            None,
+        )
+    }
+
+    /// Unlocking moves stake within the stake pool, so the amount is kept out of the balance
+    /// changes and in the metadata instead
+    pub fn unlock_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::new(
+            OperationType::UnlockStake,
+            operation_index,
+            status,
+            AccountIdentifier::stake(address),
+            None,
+            Some(OperationMetadata::unlock_stake(amount)),
+        )
+    }
+
+    pub fn withdraw_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::new(
+            OperationType::WithdrawStake,
+            operation_index,
+            status,
+            AccountIdentifier::stake(address),
+            Some(Amount {
+                value: format!("-{}", amount),
+                currency: native_coin(),
+            }),
+            None,
+        )
+    }
+
+    pub fn reset_lockup(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+    ) -> Operation {
+        Operation::new(
+            OperationType::ResetLockup,
+            operation_index,
+            status,
+            address.into(),
+            None,
+            None,
+        )
+    }
+
+    pub fn staking_reward(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::new(
+            OperationType::StakingReward,
+            operation_index,
+            status,
+            AccountIdentifier::stake(address),
+            Some(Amount {
+                value: amount.to_string(),
+                currency: native_coin(),
+            }),
+            None,
+        )
+    }
 }
 
 impl std::cmp::PartialOrd for Operation {
@@ -415,6 +530,10 @@ pub struct OperationMetadata {
     sender: Option<AccountIdentifier>,
     #[serde(skip_serializing_if = "Option::is_none")]
     operator: Option<AccountIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    voter: Option<AccountIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<U64>,
 }
 
 impl OperationMetadata {
@@ -431,6 +550,20 @@ impl OperationMetadata {
             ..Default::default()
         }
     }
+
+    pub fn set_voter(voter: AccountAddress) -> Self {
+        OperationMetadata {
+            voter: Some(voter.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn unlock_stake(amount: u64) -> Self {
+        OperationMetadata {
+            amount: Some(U64(amount)),
+            ..Default::default()
+        }
+    }
 }
 
 /// Used for query operations to apply conditions.  Defaults to [`Operator::And`] if no value is
@@ -634,6 +767,7 @@ impl Transaction {
             if let Some(ref request) = maybe_user_transaction_request {
                 let mut ops = parse_operations_from_txn_payload(
                     operation_index,
+                    Some(OperationStatusType::Failure),
                     *request.sender.inner(),
                     &request.payload,
                 );
@@ -653,6 +787,7 @@ impl Transaction {
         if let Some(ref request) = maybe_user_transaction_request {
             operations.push(Operation::gas_fee(
                 operation_index,
+                Some(OperationStatusType::Success),
                 *request.sender.inner(),
                 txn_info.gas_used.0,
                 request.gas_unit_price.0,
@@ -669,14 +804,44 @@ impl Transaction {
             }),
         })
     }
+
+    /// Builds a transaction from one still sitting in mempool
+    ///
+    /// Since it hasn't been executed, operations are estimated from the payload, have no
+    /// status, and the fee is the maximum the sender could be charged.
+    pub fn from_pending_transaction(txn: PendingTransaction) -> Transaction {
+        let transaction_identifier = (&txn).into();
+        let request = txn.request;
+        let sender = *request.sender.inner();
+        let mut operations = parse_operations_from_txn_payload(0, None, sender, &request.payload);
+        operations.sort();
+        for (i, operation) in operations.iter_mut().enumerate() {
+            operation.operation_identifier.index = i as u64;
+        }
+        operations.push(Operation::gas_fee(
+            operations.len() as u64,
+            None,
+            sender,
+            request.max_gas_amount.0,
+            request.gas_unit_price.0,
+        ));
+
+        Transaction {
+            transaction_identifier,
+            operations,
+            related_transactions: None,
+            metadata: None,
+        }
+    }
 }
 
 /// Parses operations from the transaction payload
 ///
-/// This case only occurs if the transaction failed, and that's because it's less accurate
-/// than just following the state changes
+/// This case only occurs if the transaction failed or hasn't been executed yet, and that's
+/// because it's less accurate than just following the state changes
 fn parse_operations_from_txn_payload(
     operation_index: u64,
+    status: Option<OperationStatusType>,
     sender: AccountAddress,
     payload: &TransactionPayload,
 ) -> Vec<Operation> {
@@ -706,14 +871,14 @@ fn parse_operations_from_txn_payload(
                             .0;
                     operations.push(Operation::withdraw(
                         operation_index,
-                        Some(OperationStatusType::Failure),
+                        status,
                         sender,
                         native_coin(),
                         amount,
                     ));
                     operations.push(Operation::deposit(
                         operation_index + 1,
-                        Some(OperationStatusType::Failure),
+                        status,
                         receiver.into(),
                         native_coin(),
                         amount,
@@ -729,23 +894,66 @@ fn parse_operations_from_txn_payload(
                     .unwrap();
             operations.push(Operation::create_account(
                 operation_index,
-                Some(OperationStatusType::Failure),
+                status,
                 address.into(),
                 sender,
             ));
         } else if AccountAddress::ONE == *inner.function.module.address.inner()
             && stake_module_identifier() == inner.function.module.name.0
-            && set_operator_function_identifier() == inner.function.name.0
         {
-            let operator =
-                serde_json::from_value::<Address>(inner.arguments.get(0).cloned().unwrap())
-                    .unwrap();
-            operations.push(Operation::set_operator(
-                operation_index,
-                Some(OperationStatusType::Failure),
-                operator.into(),
-                sender,
-            ));
+            let function_name = &inner.function.name.0;
+            if set_operator_function_identifier() == *function_name {
+                let operator =
+                    serde_json::from_value::<Address>(inner.arguments.get(0).cloned().unwrap())
+                        .unwrap();
+                operations.push(Operation::set_operator(
+                    operation_index,
+                    status,
+                    sender,
+                    operator.into(),
+                ));
+            } else if set_delegated_voter_function_identifier() == *function_name {
+                let voter =
+                    serde_json::from_value::<Address>(inner.arguments.get(0).cloned().unwrap())
+                        .unwrap();
+                operations.push(Operation::set_voter(
+                    operation_index,
+                    status,
+                    sender,
+                    voter.into(),
+                ));
+            } else if increase_lockup_function_identifier() == *function_name {
+                operations.push(Operation::reset_lockup(operation_index, status, sender));
+            } else {
+                // The remaining stake functions all take only an amount
+                let amount = || {
+                    serde_json::from_value::<U64>(inner.arguments.get(0).cloned().unwrap())
+                        .unwrap()
+                        .0
+                };
+                if add_stake_function_identifier() == *function_name {
+                    operations.push(Operation::add_stake(
+                        operation_index,
+                        status,
+                        sender,
+                        amount(),
+                    ));
+                } else if unlock_function_identifier() == *function_name {
+                    operations.push(Operation::unlock_stake(
+                        operation_index,
+                        status,
+                        sender,
+                        amount(),
+                    ));
+                } else if withdraw_function_identifier() == *function_name {
+                    operations.push(Operation::withdraw_stake(
+                        operation_index,
+                        status,
+                        sender,
+                        amount(),
+                    ));
+                }
+            }
         }
     }
     operations
@@ -805,26 +1013,70 @@ fn parse_operations_from_write_set(
                 }
             }
         } else if data.typ == stake_pool_tag {
-            // Account sequence number increase (possibly creation)
-            // Find out if it's the 0th sequence number (creation)
+            // Stake pool changes, found from the events emitted to the stake pool's handles.
+            // Movements of stake are reported on the stake sub account of the pool.
+            let status = Some(OperationStatusType::Success);
             for (id, value) in data.data.0.iter() {
-                if id.0 == set_operator_events_field_identifier() {
-                    serde_json::from_value::<EventId>(value.clone()).unwrap();
-                    if let Ok(event) = serde_json::from_value::<EventId>(value.clone()) {
-                        let set_operator_event =
-                            EventKey::new(event.guid.id.creation_num.0, event.guid.id.addr);
-                        if let Some(operator) =
-                            get_set_operator_from_event(events, set_operator_event)
-                        {
-                            operations.push(Operation::set_operator(
-                                operation_index,
-                                Some(OperationStatusType::Success),
-                                address,
-                                operator,
-                            ));
-                            operation_index += 1;
-                        }
-                    }
+                let maybe_operation = if id.0 == set_operator_events_field_identifier() {
+                    get_event_data::<SetOperatorEvent>(events, value).map(|event| {
+                        Operation::set_operator(
+                            operation_index,
+                            status,
+                            address,
+                            *event.new_operator.inner(),
+                        )
+                    })
+                } else if id.0 == add_stake_events_field_identifier() {
+                    get_event_data::<AddStakeEvent>(events, value).map(|event| {
+                        Operation::add_stake(operation_index, status, address, event.amount_added.0)
+                    })
+                } else if id.0 == unlock_stake_events_field_identifier() {
+                    get_event_data::<UnlockStakeEvent>(events, value).map(|event| {
+                        Operation::unlock_stake(
+                            operation_index,
+                            status,
+                            address,
+                            event.amount_unlocked.0,
+                        )
+                    })
+                } else if id.0 == withdraw_stake_events_field_identifier() {
+                    get_event_data::<WithdrawStakeEvent>(events, value).map(|event| {
+                        Operation::withdraw_stake(
+                            operation_index,
+                            status,
+                            address,
+                            event.amount_withdrawn.0,
+                        )
+                    })
+                } else if id.0 == increase_lockup_events_field_identifier() {
+                    // Only the presence of the event matters, the new lockup isn't reported
+                    get_event_data::<IgnoredAny>(events, value)
+                        .map(|_| Operation::reset_lockup(operation_index, status, address))
+                } else if id.0 == distribute_rewards_events_field_identifier() {
+                    get_event_data::<DistributeRewardsEvent>(events, value).map(|event| {
+                        Operation::staking_reward(
+                            operation_index,
+                            status,
+                            address,
+                            event.rewards_amount.0,
+                        )
+                    })
+                } else if id.0 == delegated_voter_field_identifier()
+                    && is_set_delegated_voter_request(maybe_request)
+                {
+                    // Setting the voter emits no event, so it can only be found from the payload
+                    serde_json::from_value::<Address>(value.clone())
+                        .ok()
+                        .map(|voter| {
+                            Operation::set_voter(operation_index, status, address, voter.into())
+                        })
+                } else {
+                    None
+                };
+
+                if let Some(operation) = maybe_operation {
+                    operations.push(operation);
+                    operation_index += 1;
                 }
             }
         } else if data.typ == coin_store_tag {
@@ -884,19 +1136,29 @@ fn get_amount_from_event(events: &[Event], event_key: EventKey) -> Option<u64> {
     None
 }
 
-fn get_set_operator_from_event(events: &[Event], event_key: EventKey) -> Option<AccountAddress> {
-    if let Some(event) = events
+/// Pulls the data of the event emitted to the event handle in `handle`
+fn get_event_data<T: DeserializeOwned>(events: &[Event], handle: &serde_json::Value) -> Option<T> {
+    let handle = serde_json::from_value::<EventId>(handle.clone()).ok()?;
+    let event_key = EventKey::new(handle.guid.id.creation_num.0, handle.guid.id.addr);
+    events
         .iter()
         .find(|event| EventKey::from(event.key) == event_key)
+        .and_then(|event| serde_json::from_value::<T>(event.data.clone()).ok())
+}
+
+/// Whether the transaction is a call to `0x1::stake::set_delegated_voter`
+fn is_set_delegated_voter_request(maybe_request: &Option<UserTransactionRequest>) -> bool {
+    if let Some(UserTransactionRequest {
+        payload: TransactionPayload::EntryFunctionPayload(inner),
+        ..
+    }) = maybe_request
     {
-        if let Ok(SetOperatorEvent { new_operator, .. }) =
-            serde_json::from_value::<SetOperatorEvent>(event.data.clone())
-        {
-            return Some(*new_operator.inner());
-        }
+        AccountAddress::ONE == *inner.function.module.address.inner()
+            && stake_module_identifier() == inner.function.module.name.0
+            && set_delegated_voter_function_identifier() == inner.function.name.0
+    } else {
+        false
     }
-
-    None
 }
 
 /// An enum for processing which operation is in a transaction
@@ -916,6 +1178,11 @@ pub enum InternalOperation {
     CreateAccount(CreateAccount),
     Transfer(Transfer),
     SetOperator(SetOperator),
+    SetVoter(SetVoter),
+    AddStake(AddStake),
+    UnlockStake(UnlockStake),
+    WithdrawStake(WithdrawStake),
+    ResetLockup(ResetLockup),
 }
 
 impl InternalOperation {
@@ -955,6 +1222,68 @@ impl InternalOperation {
                                 }));
                             }
                         }
+                        Ok(OperationType::SetVoter) => {
+                            if let (
+                                Some(OperationMetadata {
+                                    voter: Some(voter), ..
+                                }),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                return Ok(Self::SetVoter(SetVoter {
+                                    owner: account.account_address()?,
+                                    voter: voter.account_address()?,
+                                }));
+                            }
+                        }
+                        Ok(OperationType::AddStake) => {
+                            if let (Some(amount), Some(account)) =
+                                (&operation.amount, &operation.account)
+                            {
+                                if account.is_stake() {
+                                    return Ok(Self::AddStake(AddStake {
+                                        owner: account.account_address()?,
+                                        amount: parse_stake_amount(amount, false)?,
+                                    }));
+                                }
+                            }
+                        }
+                        Ok(OperationType::UnlockStake) => {
+                            if let (
+                                Some(OperationMetadata {
+                                    amount: Some(amount),
+                                    ..
+                                }),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                if account.is_stake() {
+                                    return Ok(Self::UnlockStake(UnlockStake {
+                                        owner: account.account_address()?,
+                                        amount: amount.0,
+                                    }));
+                                }
+                            }
+                        }
+                        Ok(OperationType::WithdrawStake) => {
+                            if let (Some(amount), Some(account)) =
+                                (&operation.amount, &operation.account)
+                            {
+                                if account.is_stake() {
+                                    return Ok(Self::WithdrawStake(WithdrawStake {
+                                        owner: account.account_address()?,
+                                        amount: parse_stake_amount(amount, true)?,
+                                    }));
+                                }
+                            }
+                        }
+                        Ok(OperationType::ResetLockup) => {
+                            if let Some(account) = &operation.account {
+                                return Ok(Self::ResetLockup(ResetLockup {
+                                    owner: account.account_address()?,
+                                }));
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -973,10 +1302,29 @@ impl InternalOperation {
             Self::CreateAccount(inner) => inner.sender,
             Self::Transfer(inner) => inner.sender,
             Self::SetOperator(inner) => inner.owner,
+            Self::SetVoter(inner) => inner.owner,
+            Self::AddStake(inner) => inner.owner,
+            Self::UnlockStake(inner) => inner.owner,
+            Self::WithdrawStake(inner) => inner.owner,
+            Self::ResetLockup(inner) => inner.owner,
         }
     }
 }
 
+/// Parses the amount of stake moved by an operation, which must be in the native coin.
+/// Stake leaving the stake pool is negative, like any other withdrawal.
+fn parse_stake_amount(amount: &Amount, withdraw: bool) -> ApiResult<u64> {
+    is_native_coin(&amount.currency)?;
+    let value = if withdraw {
+        amount.value.strip_prefix('-')
+    } else {
+        Some(amount.value.as_str())
+    };
+    value
+        .and_then(|value| u64::from_str(value).ok())
+        .ok_or(ApiError::InvalidOperations)
+}
+
 /// Operation to create an account
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct CreateAccount {
@@ -1091,6 +1439,40 @@ pub struct SetOperator {
     pub operator: AccountAddress,
 }
 
+/// Set the delegated voter of a stake pool
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SetVoter {
+    pub owner: AccountAddress,
+    pub voter: AccountAddress,
+}
+
+/// Add coins from the owner's account to its stake pool
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AddStake {
+    pub owner: AccountAddress,
+    pub amount: u64,
+}
+
+/// Unlock active stake, so it can be withdrawn once the lockup expires
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UnlockStake {
+    pub owner: AccountAddress,
+    pub amount: u64,
+}
+
+/// Withdraw inactive stake back to the owner's account
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WithdrawStake {
+    pub owner: AccountAddress,
+    pub amount: u64,
+}
+
+/// Reset the lockup of a stake pool to a full lockup period from now
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ResetLockup {
+    pub owner: AccountAddress,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct CoinEvent {
     amount: U64,
@@ -1098,11 +1480,29 @@ pub struct CoinEvent {
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct SetOperatorEvent {
-    _pool_address: Address,
-    _old_operator: Address,
     new_operator: Address,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct AddStakeEvent {
+    amount_added: U64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UnlockStakeEvent {
+    amount_unlocked: U64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WithdrawStakeEvent {
+    amount_withdrawn: U64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DistributeRewardsEvent {
+    rewards_amount: U64,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct EventId {
     guid: Id,
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::types::{CliCommand, CliTypedResult, TransactionOptions};
+use crate::common::types::{CliCommand, CliTypedResult, TransactionOptions, TransactionOutcome};
 use aptos_rest_client::aptos_api_types::HashValue;
 use aptos_rest_client::{
     aptos_api_types::{WriteResource, WriteSetChange},
@@ -31,16 +31,16 @@ pub struct TransferCoins {
 }
 
 #[async_trait]
-impl CliCommand<TransferSummary> for TransferCoins {
+impl CliCommand<TransactionOutcome<TransferSummary>> for TransferCoins {
     fn command_name(&self) -> &'static str {
         "TransferCoins"
     }
 
-    async fn execute(self) -> CliTypedResult<TransferSummary> {
+    async fn execute(self) -> CliTypedResult<TransactionOutcome<TransferSummary>> {
         self.txn_options
             .submit_transaction(aptos_stdlib::aptos_coin_transfer(self.account, self.amount))
             .await
-            .map(TransferSummary::from)
+            .map(|outcome| outcome.map(TransferSummary::from))
     }
 }
 
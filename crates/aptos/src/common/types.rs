@@ -15,15 +15,26 @@ use crate::{
     genesis::git::from_yaml,
 };
 use aptos_crypto::{
-    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_SIGNATURE_LENGTH},
     x25519, PrivateKey, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
+use aptos_gas::TransactionGasLog;
 use aptos_keygen::KeyGen;
-use aptos_rest_client::aptos_api_types::HashValue;
+use aptos_rest_client::aptos_api_types::{
+    Event, HashValue, UserTransaction, WriteModule, WriteResource, WriteSetChange, WriteTableItem,
+};
 use aptos_rest_client::error::RestError;
 use aptos_rest_client::{Client, Transaction};
-use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
-use aptos_types::transaction::{authenticator::AuthenticationKey, TransactionPayload};
+use aptos_sdk::{
+    transaction_builder::{TransactionBuilder, TransactionFactory},
+    types::LocalAccount,
+};
+use aptos_types::transaction::{
+    authenticator::AuthenticationKey, ExecutionStatus, SignedTransaction, TransactionPayload,
+    TransactionStatus,
+};
+use aptos_validator_interface::{DebuggerStateView, RestDebuggerInterface};
+use aptos_vm::AptosVM;
 use async_trait::async_trait;
 use clap::{ArgEnum, Parser};
 use hex::FromHexError;
@@ -33,6 +44,7 @@ use serde::{Deserialize, Serialize};
 use std::os::unix::fs::OpenOptionsExt;
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fmt::{Debug, Display, Formatter},
     fs::OpenOptions,
     path::{Path, PathBuf},
@@ -70,6 +82,8 @@ pub enum CliError {
     MoveTestError,
     #[error("Move Prover failed: {0}")]
     MoveProverError(String),
+    #[error("Simulation failed with status: {0}")]
+    SimulationError(String),
     #[error("Unable to parse '{0}': error: {1}")]
     UnableToParse(&'static str, String),
     #[error("Unable to read file '{0}', error: {1}")]
@@ -91,6 +105,7 @@ impl CliError {
             CliError::MoveCompilationError(_) => "MoveCompilationError",
             CliError::MoveTestError => "MoveTestError",
             CliError::MoveProverError(_) => "MoveProverError",
+            CliError::SimulationError(_) => "SimulationError",
             CliError::UnableToParse(_, _) => "UnableToParse",
             CliError::UnableToReadFile(_, _) => "UnableToReadFile",
             CliError::UnexpectedError(_) => "UnexpectedError",
@@ -319,6 +334,8 @@ pub enum KeyType {
     Ed25519,
     /// X25519 key used for network handshakes and identity
     X25519,
+    /// Secp256k1 ECDSA key used for signing
+    Secp256k1,
 }
 
 impl Display for KeyType {
@@ -326,6 +343,7 @@ impl Display for KeyType {
         let str = match self {
             KeyType::Ed25519 => "ed25519",
             KeyType::X25519 => "x25519",
+            KeyType::Secp256k1 => "secp256k1",
         };
         write!(f, "{}", str)
     }
@@ -338,7 +356,8 @@ impl FromStr for KeyType {
         match s.to_lowercase().as_str() {
             "ed25519" => Ok(KeyType::Ed25519),
             "x25519" => Ok(KeyType::X25519),
-            _ => Err("Invalid key type: Must be one of [ed25519, x25519]"),
+            "secp256k1" => Ok(KeyType::Secp256k1),
+            _ => Err("Invalid key type: Must be one of [ed25519, x25519, secp256k1]"),
         }
     }
 }
@@ -996,6 +1015,131 @@ pub struct ChangeSummary {
     value: Option<String>,
 }
 
+impl From<&WriteSetChange> for ChangeSummary {
+    fn from(change: &WriteSetChange) -> Self {
+        let event = change.type_str();
+        match change {
+            WriteSetChange::DeleteModule(delete) => ChangeSummary {
+                event,
+                address: Some(*delete.address.inner()),
+                module: Some(delete.module.to_string()),
+                ..Default::default()
+            },
+            WriteSetChange::DeleteResource(delete) => ChangeSummary {
+                event,
+                address: Some(*delete.address.inner()),
+                resource: Some(delete.resource.to_string()),
+                ..Default::default()
+            },
+            WriteSetChange::DeleteTableItem(delete) => ChangeSummary {
+                event,
+                handle: Some(delete.handle.to_string()),
+                key: Some(delete.key.to_string()),
+                ..Default::default()
+            },
+            WriteSetChange::WriteModule(WriteModule { address, data, .. }) => ChangeSummary {
+                event,
+                address: Some(*address.inner()),
+                module: data.abi.as_ref().map(|abi| abi.name.to_string()),
+                ..Default::default()
+            },
+            WriteSetChange::WriteResource(WriteResource { address, data, .. }) => ChangeSummary {
+                event,
+                address: Some(*address.inner()),
+                resource: Some(data.typ.to_string()),
+                data: serde_json::to_value(&data.data).ok(),
+                ..Default::default()
+            },
+            WriteSetChange::WriteTableItem(WriteTableItem {
+                handle,
+                key,
+                value,
+                data,
+                ..
+            }) => ChangeSummary {
+                event,
+                handle: Some(handle.to_string()),
+                key: Some(key.to_string()),
+                value: Some(value.to_string()),
+                data: data
+                    .as_ref()
+                    .and_then(|data| serde_json::to_value(data).ok()),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The events and write set changes of a simulated transaction
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulationSummary {
+    pub transaction: TransactionSummary,
+    pub events: Vec<Event>,
+    pub changes: Vec<ChangeSummary>,
+}
+
+impl From<&UserTransaction> for SimulationSummary {
+    fn from(txn: &UserTransaction) -> Self {
+        SimulationSummary {
+            transaction: TransactionSummary::from(&Transaction::UserTransaction(Box::new(
+                txn.clone(),
+            ))),
+            events: txn.events.clone(),
+            changes: txn.info.changes.iter().map(ChangeSummary::from).collect(),
+        }
+    }
+}
+
+/// Files a gas profile was written to
+#[derive(Clone, Debug, Serialize)]
+pub struct GasProfileSummary {
+    /// Folded stacks, which can be rendered as a flamegraph
+    pub folded_stacks: PathBuf,
+    /// JSON breakdown of the cost per instruction, native function and Move function
+    pub cost_breakdown: PathBuf,
+}
+
+/// A transaction that was only simulated locally to profile its gas usage
+#[derive(Clone, Debug, Serialize)]
+pub struct ProfiledTransactionSummary {
+    #[serde(flatten)]
+    pub transaction: TransactionSummary,
+    pub gas_profile: GasProfileSummary,
+}
+
+/// The result of a transaction command, which is only simulated when `--simulate` is given, or
+/// only profiled locally when `--profile-gas` is given
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum TransactionOutcome<T> {
+    Submitted(T),
+    Simulated(SimulationSummary),
+    Profiled(ProfiledTransactionSummary),
+}
+
+impl<T> TransactionOutcome<T> {
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> TransactionOutcome<U> {
+        match self {
+            TransactionOutcome::Submitted(submitted) => TransactionOutcome::Submitted(f(submitted)),
+            TransactionOutcome::Simulated(summary) => TransactionOutcome::Simulated(summary),
+            TransactionOutcome::Profiled(summary) => TransactionOutcome::Profiled(summary),
+        }
+    }
+
+    /// Returns the submitted result, failing if the transaction was only simulated or profiled
+    pub fn submitted(self) -> CliTypedResult<T> {
+        match self {
+            TransactionOutcome::Submitted(submitted) => Ok(submitted),
+            TransactionOutcome::Simulated(_) => Err(CliError::UnexpectedError(
+                "Transaction was only simulated, not submitted".to_string(),
+            )),
+            TransactionOutcome::Profiled(_) => Err(CliError::UnexpectedError(
+                "Transaction was only profiled locally, not submitted".to_string(),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Default, Parser)]
 pub struct FaucetOptions {
     /// URL for the faucet endpoint e.g. https://faucet.devnet.aptoslabs.com
@@ -1029,8 +1173,17 @@ impl FaucetOptions {
 pub const DEFAULT_MAX_GAS: u64 = 1_000;
 pub const DEFAULT_GAS_UNIT_PRICE: u64 = 1;
 
+/// Factor applied to the gas used by a simulation to get the max gas of a transaction, as the
+/// gas used may change between the simulation and the execution.
+pub const ESTIMATED_GAS_SAFETY_FACTOR: f64 = 1.5;
+/// Upper bound on the max gas used to simulate a transaction, it is further limited by the
+/// balance of the sender.
+pub const MAX_GAS_FOR_SIMULATION: u64 = 1_000_000;
+/// Directory the gas profiles of transactions are written to.
+pub const GAS_PROFILING_DIR: &str = "gas-profiling";
+
 /// Gas price options for manipulating how to prioritize transactions
-#[derive(Debug, Eq, Parser, PartialEq)]
+#[derive(Debug, Default, Eq, Parser, PartialEq)]
 pub struct GasOptions {
     /// Gas multiplier per unit of gas
     ///
@@ -1039,8 +1192,10 @@ pub struct GasOptions {
     /// be used as a multiplier for the amount of coins willing
     /// to be paid for a transaction.  This will prioritize the
     /// transaction with a higher gas unit price.
-    #[clap(long, default_value_t = DEFAULT_GAS_UNIT_PRICE)]
-    pub gas_unit_price: u64,
+    ///
+    /// Defaults to the gas unit price estimated by the node.
+    #[clap(long)]
+    pub gas_unit_price: Option<u64>,
     /// Maximum amount of gas units to be used to send this transaction
     ///
     /// The maximum amount of gas units willing to pay for the transaction.
@@ -1050,17 +1205,11 @@ pub struct GasOptions {
     /// max gas set to 100 if the gas unit price is 1.  If I want it to have a
     /// gas unit price of 2, the max gas would need to be 50 to still only have
     /// a maximum price of 100 coins.
-    #[clap(long, default_value_t = DEFAULT_MAX_GAS)]
-    pub max_gas: u64,
-}
-
-impl Default for GasOptions {
-    fn default() -> Self {
-        GasOptions {
-            gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
-            max_gas: DEFAULT_MAX_GAS,
-        }
-    }
+    ///
+    /// Defaults to the gas used by a simulation of the transaction, with a
+    /// safety margin of 50%.
+    #[clap(long)]
+    pub max_gas: Option<u64>,
 }
 
 /// Common options for interacting with an account for a validator
@@ -1076,6 +1225,9 @@ pub struct TransactionOptions {
     pub(crate) rest_options: RestOptions,
     #[clap(flatten)]
     pub(crate) gas_options: GasOptions,
+    /// Only simulate the transaction and print its events and changes, without submitting it
+    #[clap(long)]
+    pub(crate) simulate: bool,
 }
 
 impl TransactionOptions {
@@ -1098,33 +1250,246 @@ impl TransactionOptions {
     }
 
     /// Submit a transaction
+    ///
+    /// Unless given, the gas unit price is estimated by the node and the max gas is the gas used
+    /// by a simulation of the transaction times [`ESTIMATED_GAS_SAFETY_FACTOR`].  With
+    /// `--simulate`, the simulated transaction is returned instead of being submitted.
     pub async fn submit_transaction(
         &self,
         payload: TransactionPayload,
-    ) -> CliTypedResult<Transaction> {
-        let sender_key = self.private_key()?;
+    ) -> CliTypedResult<TransactionOutcome<Transaction>> {
         let client = self.rest_client()?;
-
-        // Get sender address
-        let sender_address = self.sender_address()?;
-
-        // Get sequence number for account
-        let sequence_number = get_sequence_number(&client, sender_address).await?;
+        let (mut sender_account, transaction_factory, gas_unit_price) =
+            self.transaction_context(&client).await?;
+        let sender_account = &mut sender_account;
+
+        let max_gas = match self.gas_options.max_gas {
+            Some(max_gas) if !self.simulate => max_gas,
+            _ => {
+                let max_gas = self
+                    .simulation_max_gas(&client, sender_account.address(), gas_unit_price)
+                    .await?;
+                let simulated_txn = Self::simulate_transaction(
+                    &client,
+                    sender_account,
+                    transaction_factory
+                        .payload(payload.clone())
+                        .max_gas_amount(max_gas),
+                )
+                .await?;
+                if self.simulate {
+                    return Ok(TransactionOutcome::Simulated(SimulationSummary::from(
+                        &simulated_txn,
+                    )));
+                }
+                if !simulated_txn.info.success {
+                    return Err(CliError::SimulationError(simulated_txn.info.vm_status));
+                }
+                (simulated_txn.info.gas_used.0 as f64 * ESTIMATED_GAS_SAFETY_FACTOR).ceil() as u64
+            }
+        };
 
         // Sign and submit transaction
-        let transaction_factory = TransactionFactory::new(chain_id(&client).await?)
-            .with_gas_unit_price(self.gas_options.gas_unit_price)
-            .with_max_gas_amount(self.gas_options.max_gas);
-        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-        let transaction =
-            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+        let transaction = sender_account.sign_with_transaction_builder(
+            transaction_factory.payload(payload).max_gas_amount(max_gas),
+        );
         let response = client
             .submit_and_wait(&transaction)
             .await
             .map_err(|err| CliError::ApiError(err.to_string()))?;
 
-        Ok(response.into_inner())
+        Ok(TransactionOutcome::Submitted(response.into_inner()))
+    }
+
+    /// Simulates the transaction locally against the latest on-chain state, and profiles the gas
+    /// it uses.  The transaction is never submitted, and `--simulate` can't be combined with it.
+    ///
+    /// The profile is written by [`write_gas_profile`].
+    pub async fn profile_gas(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<ProfiledTransactionSummary> {
+        if self.simulate {
+            return Err(CliError::CommandArgumentError(
+                "--profile-gas already simulates the transaction locally, it can't be combined \
+                with --simulate"
+                    .to_string(),
+            ));
+        }
+        let client = self.rest_client()?;
+        let (sender_account, transaction_factory, gas_unit_price) =
+            self.transaction_context(&client).await?;
+        let sender_address = sender_account.address();
+        let sequence_number = sender_account.sequence_number();
+        let max_gas = self
+            .simulation_max_gas(&client, sender_address, gas_unit_price)
+            .await?;
+        let txn = Self::unsigned_transaction(
+            &sender_account,
+            transaction_factory.payload(payload).max_gas_amount(max_gas),
+        )?;
+        let hash = txn.clone().committed_hash();
+
+        let version = client
+            .get_ledger_information()
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner()
+            .version;
+        // The debugger interface talks to the node synchronously, so the VM must not run
+        // on an async worker thread.
+        let (vm_status, output, gas_log) = tokio::task::spawn_blocking(move || {
+            let debugger = RestDebuggerInterface::new(client)?;
+            let state_view = DebuggerStateView::new(&debugger, Some(version));
+            CliTypedResult::Ok(AptosVM::simulate_signed_transaction_with_gas_profiling(
+                &txn,
+                &state_view,
+            ))
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))??;
+        let gas_log =
+            gas_log.ok_or_else(|| CliError::SimulationError(format!("{:?}", vm_status)))?;
+        let gas_profile = write_gas_profile(&gas_log, &format!("txn-{}", hash.to_hex()))?;
+
+        let output = output.txn_output();
+        let transaction = TransactionSummary {
+            transaction_hash: hash.into(),
+            gas_used: Some(output.gas_used()),
+            gas_unit_price: Some(gas_unit_price),
+            pending: None,
+            sender: Some(sender_address),
+            sequence_number: Some(sequence_number),
+            success: Some(matches!(
+                output.status(),
+                TransactionStatus::Keep(ExecutionStatus::Success)
+            )),
+            timestamp_us: None,
+            version: None,
+            vm_status: Some(format!("{:?}", vm_status)),
+        };
+        Ok(ProfiledTransactionSummary {
+            transaction,
+            gas_profile,
+        })
+    }
+
+    /// Returns the sender account, and a transaction factory using either the given gas unit price
+    /// or the one estimated by the node, along with that price.
+    async fn transaction_context(
+        &self,
+        client: &Client,
+    ) -> CliTypedResult<(LocalAccount, TransactionFactory, u64)> {
+        let sender_key = self.private_key()?;
+        let sender_address = self.sender_address()?;
+        let sequence_number = get_sequence_number(client, sender_address).await?;
+
+        let gas_unit_price = if let Some(gas_unit_price) = self.gas_options.gas_unit_price {
+            gas_unit_price
+        } else {
+            client.estimate_gas_price().await?.into_inner().gas_estimate
+        };
+        let transaction_factory =
+            TransactionFactory::new(chain_id(client).await?).with_gas_unit_price(gas_unit_price);
+        Ok((
+            LocalAccount::new(sender_address, sender_key, sequence_number),
+            transaction_factory,
+            gas_unit_price,
+        ))
+    }
+
+    /// Returns the given max gas, or the one from `max_gas_for_simulation` if none was given.
+    async fn simulation_max_gas(
+        &self,
+        client: &Client,
+        sender_address: AccountAddress,
+        gas_unit_price: u64,
+    ) -> CliTypedResult<u64> {
+        match self.gas_options.max_gas {
+            Some(max_gas) => Ok(max_gas),
+            None => Self::max_gas_for_simulation(client, sender_address, gas_unit_price).await,
+        }
     }
+
+    /// Returns the max gas to simulate a transaction with, bounded by the balance of the sender.
+    async fn max_gas_for_simulation(
+        client: &Client,
+        sender_address: AccountAddress,
+        gas_unit_price: u64,
+    ) -> CliTypedResult<u64> {
+        let balance = client
+            .get_account_balance(sender_address)
+            .await?
+            .into_inner()
+            .get();
+        Ok(balance
+            .checked_div(gas_unit_price)
+            .unwrap_or(MAX_GAS_FOR_SIMULATION)
+            .min(MAX_GAS_FOR_SIMULATION))
+    }
+
+    /// Builds the transaction with an invalid signature, as the simulation rejects validly signed
+    /// transactions
+    fn unsigned_transaction(
+        sender_account: &LocalAccount,
+        builder: TransactionBuilder,
+    ) -> CliTypedResult<SignedTransaction> {
+        let raw_txn = builder
+            .sender(sender_account.address())
+            .sequence_number(sender_account.sequence_number())
+            .build();
+        let signature = Ed25519Signature::try_from(&[0u8; ED25519_SIGNATURE_LENGTH][..])
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        Ok(SignedTransaction::new(
+            raw_txn,
+            sender_account.public_key().clone(),
+            signature,
+        ))
+    }
+
+    /// Simulates the transaction on the node
+    async fn simulate_transaction(
+        client: &Client,
+        sender_account: &LocalAccount,
+        builder: TransactionBuilder,
+    ) -> CliTypedResult<UserTransaction> {
+        let txn = Self::unsigned_transaction(sender_account, builder)?;
+        client
+            .simulate(&txn)
+            .await?
+            .into_inner()
+            .pop()
+            .ok_or_else(|| CliError::ApiError("No simulated transaction returned".to_string()))
+    }
+}
+
+/// Writes the gas profile to the [`GAS_PROFILING_DIR`] directory, both as folded stacks that can
+/// be rendered with flamegraph tools (e.g. `inferno-flamegraph`), and as a JSON breakdown of the
+/// cost per instruction, native function and Move function.  The files are named after `name`.
+pub fn write_gas_profile(
+    gas_log: &TransactionGasLog,
+    name: &str,
+) -> CliTypedResult<GasProfileSummary> {
+    let dir = PathBuf::from(GAS_PROFILING_DIR);
+    create_dir_if_not_exist(&dir)?;
+    let folded_stacks = dir.join(format!("{}.folded", name));
+    write_to_file(
+        &folded_stacks,
+        "Gas profile folded stacks",
+        gas_log.to_folded_stacks().as_bytes(),
+    )?;
+    let cost_breakdown = dir.join(format!("{}.json", name));
+    write_to_file(
+        &cost_breakdown,
+        "Gas profile breakdown",
+        serde_json::to_string_pretty(&gas_log.to_cost_breakdown())
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            .as_bytes(),
+    )?;
+    Ok(GasProfileSummary {
+        folded_stacks,
+        cost_breakdown,
+    })
 }
 
 #[derive(Parser)]
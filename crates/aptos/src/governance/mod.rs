@@ -3,7 +3,7 @@
 
 use crate::common::types::{
     CliError, CliTypedResult, MovePackageDir, PoolAddressArgs, PromptOptions, TransactionOptions,
-    TransactionSummary,
+    TransactionOutcome, TransactionSummary,
 };
 use crate::common::utils::prompt_yes_with_override;
 use crate::move_tool::{init_move_dir, IncludedArtifacts};
@@ -70,12 +70,12 @@ pub struct SubmitProposal {
 }
 
 #[async_trait]
-impl CliCommand<ProposalSubmissionSummary> for SubmitProposal {
+impl CliCommand<TransactionOutcome<ProposalSubmissionSummary>> for SubmitProposal {
     fn command_name(&self) -> &'static str {
         "SubmitProposal"
     }
 
-    async fn execute(mut self) -> CliTypedResult<ProposalSubmissionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<ProposalSubmissionSummary>> {
         let (_bytecode, script_hash) = self.compile_proposal_args.compile()?;
 
         // Validate the proposal metadata
@@ -90,7 +90,7 @@ impl CliCommand<ProposalSubmissionSummary> for SubmitProposal {
             self.compile_proposal_args.prompt_options,
         )?;
 
-        let txn = self
+        let txn = match self
             .txn_options
             .submit_transaction(aptos_stdlib::aptos_governance_create_proposal(
                 self.pool_address_args.pool_address,
@@ -98,7 +98,16 @@ impl CliCommand<ProposalSubmissionSummary> for SubmitProposal {
                 self.metadata_url.to_string().as_bytes().to_vec(),
                 metadata_hash.to_hex().as_bytes().to_vec(),
             ))
-            .await?;
+            .await?
+        {
+            TransactionOutcome::Submitted(txn) => txn,
+            TransactionOutcome::Simulated(summary) => {
+                return Ok(TransactionOutcome::Simulated(summary))
+            }
+            TransactionOutcome::Profiled(summary) => {
+                return Ok(TransactionOutcome::Profiled(summary))
+            }
+        };
 
         if let Transaction::UserTransaction(inner) = txn {
             // Find event with proposal id
@@ -119,7 +128,7 @@ impl CliCommand<ProposalSubmissionSummary> for SubmitProposal {
             let request = inner.request;
             let info = inner.info;
 
-            return Ok(ProposalSubmissionSummary {
+            return Ok(TransactionOutcome::Submitted(ProposalSubmissionSummary {
                 proposal_id,
                 transaction_hash: info.hash.into(),
                 transaction_version: info.version.into(),
@@ -127,7 +136,7 @@ impl CliCommand<ProposalSubmissionSummary> for SubmitProposal {
                 gas_price_per_unit: request.gas_unit_price.0,
                 sequence_number: request.sequence_number.0,
                 vm_status: info.vm_status,
-            });
+            }));
         }
         Err(CliError::UnexpectedError(
             "Unable to find parse proposal transaction output".to_string(),
@@ -244,12 +253,12 @@ pub struct SubmitVote {
 }
 
 #[async_trait]
-impl CliCommand<Transaction> for SubmitVote {
+impl CliCommand<TransactionOutcome<Transaction>> for SubmitVote {
     fn command_name(&self) -> &'static str {
         "SubmitVote"
     }
 
-    async fn execute(mut self) -> CliTypedResult<Transaction> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<Transaction>> {
         let (vote_str, vote) = match (self.yes, self.no) {
             (true, false) => ("Yes", true),
             (false, true) => ("No", false),
@@ -377,12 +386,12 @@ pub struct ExecuteProposal {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for ExecuteProposal {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for ExecuteProposal {
     fn command_name(&self) -> &'static str {
         "ExecuteProposal"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let (bytecode, _script_hash) = self.compile_proposal_args.compile()?;
         // TODO: Check hash so we don't do a failed roundtrip?
 
@@ -392,7 +401,7 @@ impl CliCommand<TransactionSummary> for ExecuteProposal {
         self.txn_options
             .submit_transaction(txn)
             .await
-            .map(TransactionSummary::from)
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -22,7 +22,7 @@ use crate::{
     common::{
         types::{
             load_account_arg, CliError, CliTypedResult, MovePackageDir, PromptOptions,
-            TransactionOptions, TransactionSummary,
+            TransactionOptions, TransactionOutcome, TransactionSummary,
         },
         utils::check_if_file_exists,
     },
@@ -30,7 +30,7 @@ use crate::{
 };
 use aptos_gas::{AbstractValueSizeGasParameters, NativeGasParameters};
 use aptos_module_verifier::module_init::verify_module_init_function;
-use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_rest_client::aptos_api_types::{EntryFunctionId, MoveType, ViewRequest};
 use aptos_transactional_test_harness::run_aptos_test;
 use aptos_types::account_address::AccountAddress;
 use aptos_types::transaction::{EntryFunction, ModuleBundle, TransactionPayload};
@@ -78,6 +78,7 @@ pub enum MoveTool {
     Test(TestPackage),
     Prove(ProvePackage),
     TransactionalTest(TransactionalTestOpts),
+    View(ViewFunction),
 }
 
 impl MoveTool {
@@ -93,6 +94,7 @@ impl MoveTool {
             MoveTool::Test(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
             MoveTool::TransactionalTest(tool) => tool.execute_serialized_success().await,
+            MoveTool::View(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -449,12 +451,12 @@ impl IncludedArtifacts {
 pub const MAX_PUBLISH_PACKAGE_SIZE: usize = 60_000;
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for PublishPackage {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for PublishPackage {
     fn command_name(&self) -> &'static str {
         "PublishPackage"
     }
 
-    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let PublishPackage {
             move_options,
             txn_options,
@@ -473,7 +475,7 @@ impl CliCommand<TransactionSummary> for PublishPackage {
                     compiled_units,
                 )))
                 .await
-                .map(TransactionSummary::from)
+                .map(|outcome| outcome.map(TransactionSummary::from))
         } else {
             // Send the compiled module and metadata using the code::publish_package_txn.
             let metadata = package.extract_metadata()?;
@@ -494,7 +496,7 @@ impl CliCommand<TransactionSummary> for PublishPackage {
             txn_options
                 .submit_transaction(payload)
                 .await
-                .map(TransactionSummary::from)
+                .map(|outcome| outcome.map(TransactionSummary::from))
         }
     }
 }
@@ -684,17 +686,25 @@ pub struct RunFunction {
     #[clap(long, multiple_values = true)]
     pub(crate) type_args: Vec<MoveType>,
 
+    /// Simulate the transaction locally and profile its gas usage instead of submitting it
+    ///
+    /// The gas profile is written to the `gas-profiling` directory, both as folded stacks
+    /// that can be rendered as a flamegraph, and as a JSON cost breakdown.  It can't be
+    /// combined with `--simulate`.
+    #[clap(long, conflicts_with = "simulate")]
+    pub(crate) profile_gas: bool,
+
     #[clap(flatten)]
     pub(crate) txn_options: TransactionOptions,
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for RunFunction {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for RunFunction {
     fn command_name(&self) -> &'static str {
         "RunFunction"
     }
 
-    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let args: Vec<Vec<u8>> = self
             .args
             .into_iter()
@@ -709,15 +719,89 @@ impl CliCommand<TransactionSummary> for RunFunction {
             type_args.push(type_tag)
         }
 
+        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
+            self.function_id.module_id,
+            self.function_id.member_id,
+            type_args,
+            args,
+        ));
+        if self.profile_gas {
+            return self
+                .txn_options
+                .profile_gas(payload)
+                .await
+                .map(TransactionOutcome::Profiled);
+        }
         self.txn_options
-            .submit_transaction(TransactionPayload::EntryFunction(EntryFunction::new(
-                self.function_id.module_id,
-                self.function_id.member_id,
-                type_args,
-                args,
-            )))
+            .submit_transaction(payload)
             .await
-            .map(TransactionSummary::from)
+            .map(|outcome| outcome.map(TransactionSummary::from))
+    }
+}
+
+/// Run a view function
+///
+/// Executes a public Move function on the node without submitting a transaction,
+/// and returns its return values.
+#[derive(Parser)]
+pub struct ViewFunction {
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    ///
+    /// Example: `0x842ed41fad9640a2ad08fdd7d3e4f7f505319aac7d67e1c0dd6a7cce8732c7e3::message::get_message`
+    #[clap(long)]
+    pub(crate) function_id: MemberId,
+
+    /// Arguments combined with their type separated by spaces.
+    ///
+    /// Supported types [u8, u64, u128, bool, hex, string, address]
+    ///
+    /// Example: `address:0x1 bool:true u8:0`
+    #[clap(long, multiple_values = true)]
+    pub(crate) args: Vec<ArgWithType>,
+
+    /// TypeTag arguments separated by spaces.
+    ///
+    /// Example: `u8 u64 u128 bool address vector`
+    #[clap(long, multiple_values = true)]
+    pub(crate) type_args: Vec<MoveType>,
+
+    /// Ledger version to execute the function at
+    ///
+    /// Defaults to the latest ledger version
+    #[clap(long)]
+    pub(crate) ledger_version: Option<u64>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<serde_json::Value>> for ViewFunction {
+    fn command_name(&self) -> &'static str {
+        "ViewFunction"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<serde_json::Value>> {
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let request = ViewRequest {
+            function: EntryFunctionId {
+                module: self.function_id.module_id.into(),
+                name: self.function_id.member_id.into(),
+            },
+            type_arguments: self.type_args,
+            arguments: self
+                .args
+                .iter()
+                .map(ArgWithType::to_json)
+                .collect::<CliTypedResult<_>>()?,
+        };
+
+        Ok(client
+            .view(&request, self.ledger_version)
+            .await?
+            .into_inner())
     }
 }
 
@@ -781,10 +865,35 @@ impl FromStr for FunctionArgType {
 
 /// A parseable arg with a type separated by a colon
 pub struct ArgWithType {
-    pub(crate) _ty: FunctionArgType,
+    pub(crate) ty: FunctionArgType,
     pub(crate) arg: Vec<u8>,
 }
 
+impl ArgWithType {
+    /// Converts the BCS encoded argument into its JSON representation in the REST API
+    pub(crate) fn to_json(&self) -> CliTypedResult<serde_json::Value> {
+        match self.ty {
+            FunctionArgType::Address => {
+                serde_json::to_value(bcs::from_bytes::<AccountAddress>(&self.arg)?.to_hex_literal())
+            }
+            FunctionArgType::Bool => serde_json::to_value(bcs::from_bytes::<bool>(&self.arg)?),
+            FunctionArgType::Hex => serde_json::to_value(format!(
+                "0x{}",
+                hex::encode(bcs::from_bytes::<Vec<u8>>(&self.arg)?)
+            )),
+            FunctionArgType::String => serde_json::to_value(bcs::from_bytes::<String>(&self.arg)?),
+            FunctionArgType::U8 => serde_json::to_value(bcs::from_bytes::<u8>(&self.arg)?),
+            FunctionArgType::U64 => {
+                serde_json::to_value(bcs::from_bytes::<u64>(&self.arg)?.to_string())
+            }
+            FunctionArgType::U128 => {
+                serde_json::to_value(bcs::from_bytes::<u128>(&self.arg)?.to_string())
+            }
+        }
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))
+    }
+}
+
 impl FromStr for ArgWithType {
     type Err = CliError;
 
@@ -800,7 +909,7 @@ impl FromStr for ArgWithType {
         let arg = parts.last().unwrap();
         let arg = ty.parse_arg(arg)?;
 
-        Ok(ArgWithType { _ty: ty, arg })
+        Ok(ArgWithType { ty, arg })
     }
 }
 
@@ -4,7 +4,8 @@
 pub mod analyze;
 
 use crate::common::types::{
-    ConfigSearchMode, OptionalPoolAddressArgs, PromptOptions, TransactionSummary,
+    write_gas_profile, ConfigSearchMode, GasProfileSummary, OptionalPoolAddressArgs, PromptOptions,
+    TransactionOutcome, TransactionSummary,
 };
 use crate::common::utils::prompt_yes_with_override;
 use crate::config::GlobalConfig;
@@ -24,8 +25,13 @@ use aptos_config::config::NodeConfig;
 use aptos_crypto::{bls12381, x25519, ValidCryptoMaterialStringExt};
 use aptos_faucet::FaucetArgs;
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
+use aptos_rest_client::aptos_api_types::TransactionData;
 use aptos_types::chain_id::ChainId;
+use aptos_types::transaction::{Transaction, TransactionInfo, TransactionOutput, Version};
+use aptos_types::write_set::WriteSet;
 use aptos_types::{account_address::AccountAddress, account_config::CORE_CODE_ADDRESS};
+use aptos_validator_interface::{DebuggerStateView, RestDebuggerInterface};
+use aptos_vm::{AptosVM, VMExecutor};
 use async_trait::async_trait;
 use cached_packages::aptos_stdlib;
 use clap::Parser;
@@ -33,7 +39,8 @@ use hex::FromHex;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use reqwest::Url;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::{path::PathBuf, thread, time::Duration};
@@ -55,6 +62,7 @@ pub enum NodeTool {
     UpdateConsensusKey(UpdateConsensusKey),
     UpdateValidatorNetworkAddresses(UpdateValidatorNetworkAddresses),
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
+    ReplayTransaction(ReplayTransaction),
 }
 
 impl NodeTool {
@@ -71,6 +79,7 @@ impl NodeTool {
             UpdateConsensusKey(tool) => tool.execute_serialized().await,
             UpdateValidatorNetworkAddresses(tool) => tool.execute_serialized().await,
             AnalyzeValidatorPerformance(tool) => tool.execute_serialized().await,
+            ReplayTransaction(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -234,12 +243,12 @@ pub struct InitializeValidator {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for InitializeValidator {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for InitializeValidator {
     fn command_name(&self) -> &'static str {
         "InitializeValidator"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let operator_config = self.operator_config_file_args.load()?;
         let consensus_public_key = self
             .validator_consensus_key_args
@@ -278,7 +287,7 @@ impl CliCommand<TransactionSummary> for InitializeValidator {
                 bcs::to_bytes(&full_node_network_addresses)?,
             ))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -323,12 +332,12 @@ pub struct JoinValidatorSet {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for JoinValidatorSet {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for JoinValidatorSet {
     fn command_name(&self) -> &'static str {
         "JoinValidatorSet"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let address = self
             .operator_args
             .address_fallback_to_txn(&self.txn_options)?;
@@ -336,7 +345,7 @@ impl CliCommand<TransactionSummary> for JoinValidatorSet {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_join_validator_set(address))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -350,12 +359,12 @@ pub struct LeaveValidatorSet {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for LeaveValidatorSet {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for LeaveValidatorSet {
     fn command_name(&self) -> &'static str {
         "LeaveValidatorSet"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let address = self
             .operator_args
             .address_fallback_to_txn(&self.txn_options)?;
@@ -363,7 +372,7 @@ impl CliCommand<TransactionSummary> for LeaveValidatorSet {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_leave_validator_set(address))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -626,12 +635,12 @@ pub struct UpdateConsensusKey {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for UpdateConsensusKey {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for UpdateConsensusKey {
     fn command_name(&self) -> &'static str {
         "UpdateConsensusKey"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let address = self
             .operator_args
             .address_fallback_to_txn(&self.txn_options)?;
@@ -650,7 +659,7 @@ impl CliCommand<TransactionSummary> for UpdateConsensusKey {
                 consensus_proof_of_possession.to_bytes().to_vec(),
             ))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -668,12 +677,12 @@ pub struct UpdateValidatorNetworkAddresses {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for UpdateValidatorNetworkAddresses {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for UpdateValidatorNetworkAddresses {
     fn command_name(&self) -> &'static str {
         "UpdateValidatorNetworkAddresses"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let address = self
             .operator_args
             .address_fallback_to_txn(&self.txn_options)?;
@@ -709,7 +718,7 @@ impl CliCommand<TransactionSummary> for UpdateValidatorNetworkAddresses {
                 bcs::to_bytes(&full_node_network_addresses)?,
             ))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -828,3 +837,184 @@ impl CliCommand<()> for AnalyzeValidatorPerformance {
         Ok(())
     }
 }
+
+/// Replay a committed transaction locally
+///
+/// Fetches the transaction at the given version from the REST API, re-executes it with
+/// the local AptosVM against the state right before it, and compares the output with
+/// the one that was committed on chain.  State is fetched on demand from the node.
+#[derive(Parser)]
+pub struct ReplayTransaction {
+    /// Version of the transaction to replay
+    #[clap(long)]
+    pub(crate) version: u64,
+
+    /// Also profile the gas used by the replayed user transaction
+    ///
+    /// The gas profile is written to the `gas-profiling` directory, both as folded stacks
+    /// that can be rendered as a flamegraph, and as a JSON cost breakdown.
+    #[clap(long)]
+    pub(crate) profile_gas: bool,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// Result of replaying a transaction, compared against the on-chain output
+#[derive(Debug, Serialize)]
+pub struct ReplayTransactionSummary {
+    pub version: Version,
+    pub status: String,
+    pub on_chain_status: String,
+    pub gas_used: u64,
+    pub on_chain_gas_used: u64,
+    /// Differences between the local and the on-chain output, empty if they match
+    pub differences: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfileSummary>,
+}
+
+#[async_trait]
+impl CliCommand<ReplayTransactionSummary> for ReplayTransaction {
+    fn command_name(&self) -> &'static str {
+        "ReplayTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<ReplayTransactionSummary> {
+        if self.version == 0 {
+            return Err(CliError::CommandArgumentError(
+                "The genesis transaction can't be replayed".to_string(),
+            ));
+        }
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let on_chain = match client
+            .get_transaction_by_version_bcs(self.version)
+            .await?
+            .into_inner()
+        {
+            TransactionData::OnChain(data) => data,
+            TransactionData::Pending(_) => {
+                return Err(CliError::UnexpectedError(format!(
+                    "Transaction at version {} is not committed yet",
+                    self.version
+                )))
+            }
+        };
+
+        let profiled_txn = match (self.profile_gas, &on_chain.transaction) {
+            (false, _) => None,
+            (true, Transaction::UserTransaction(txn)) => Some(txn.clone()),
+            (true, _) => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Transaction at version {} is not a user transaction, so its gas can't be \
+                    profiled",
+                    self.version
+                )))
+            }
+        };
+
+        // The debugger interface talks to the node synchronously, so the VM must not run
+        // on an async worker thread.
+        let version = self.version;
+        let transaction = on_chain.transaction.clone();
+        let (output, gas_log) = tokio::task::spawn_blocking(move || {
+            let debugger = RestDebuggerInterface::new(client)?;
+            let state_view = DebuggerStateView::new(&debugger, Some(version - 1));
+            let output = AptosVM::execute_block(vec![transaction], &state_view)
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!("Failed to execute transaction: {:?}", err))
+                })?
+                .pop()
+                .ok_or_else(|| CliError::UnexpectedError("No output from the VM".to_string()))?;
+            let gas_log = match profiled_txn {
+                Some(txn) => {
+                    let (vm_status, _output, gas_log) =
+                        AptosVM::replay_signed_transaction_with_gas_profiling(&txn, &state_view);
+                    Some(gas_log.ok_or_else(|| {
+                        CliError::UnexpectedError(format!(
+                            "Transaction was discarded while profiling its gas: {:?}",
+                            vm_status
+                        ))
+                    })?)
+                }
+                None => None,
+            };
+            CliTypedResult::Ok((output, gas_log))
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))??;
+
+        let differences =
+            diff_outputs(&output, &on_chain.info, &on_chain.changes, &on_chain.events);
+        let gas_profile = gas_log
+            .map(|gas_log| {
+                write_gas_profile(
+                    &gas_log,
+                    &format!("txn-{}", on_chain.info.transaction_hash().to_hex()),
+                )
+            })
+            .transpose()?;
+
+        Ok(ReplayTransactionSummary {
+            version,
+            status: format!("{:?}", output.status()),
+            on_chain_status: format!("{:?}", on_chain.info.status()),
+            gas_used: output.gas_used(),
+            on_chain_gas_used: on_chain.info.gas_used(),
+            differences,
+            gas_profile,
+        })
+    }
+}
+
+/// Describes every difference between a locally produced `TransactionOutput` and the
+/// committed one.
+fn diff_outputs(
+    output: &TransactionOutput,
+    info: &TransactionInfo,
+    write_set: &WriteSet,
+    events: &[aptos_types::contract_event::ContractEvent],
+) -> Vec<String> {
+    let mut differences = vec![];
+    match output.status().status() {
+        Ok(status) if &status == info.status() => (),
+        _ => differences.push(format!(
+            "Status differs: {:?} locally, {:?} on chain",
+            output.status(),
+            info.status()
+        )),
+    }
+    if output.gas_used() != info.gas_used() {
+        differences.push(format!(
+            "Gas used differs: {} locally, {} on chain",
+            output.gas_used(),
+            info.gas_used()
+        ));
+    }
+
+    let keys: BTreeSet<_> = output
+        .write_set()
+        .iter()
+        .chain(write_set.iter())
+        .map(|(key, _)| key)
+        .collect();
+    for key in keys {
+        match (output.write_set().get(key), write_set.get(key)) {
+            (Some(local), Some(on_chain)) if local == on_chain => (),
+            (Some(_), Some(_)) => differences.push(format!("Write differs for {:?}", key)),
+            (Some(_), None) => differences.push(format!("Write only made locally: {:?}", key)),
+            (None, _) => differences.push(format!("Write only made on chain: {:?}", key)),
+        }
+    }
+
+    if output.events() != events {
+        differences.push(format!(
+            "Events differ: {} emitted locally, {} on chain",
+            output.events().len(),
+            events.len()
+        ));
+    }
+    differences
+}
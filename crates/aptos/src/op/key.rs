@@ -12,7 +12,7 @@ use crate::{
     CliCommand, CliResult,
 };
 use aptos_config::config::{Peer, PeerRole};
-use aptos_crypto::{ed25519, x25519, PrivateKey, ValidCryptoMaterial};
+use aptos_crypto::{ed25519, secp256k1_ecdsa, x25519, PrivateKey, ValidCryptoMaterial};
 use aptos_types::account_address::{from_identity_public_key, AccountAddress};
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
@@ -94,7 +94,7 @@ impl CliCommand<HashMap<AccountAddress, Peer>> for ExtractPeer {
     }
 }
 
-/// Generates a `x25519` or `ed25519` key.
+/// Generates a `x25519`, `ed25519` or `secp256k1` key.
 ///
 /// This can be used for generating an identity.  Two files will be created
 /// `output_file` and `output_file.pub`.  `output_file` will contain the private
@@ -102,7 +102,7 @@ impl CliCommand<HashMap<AccountAddress, Peer>> for ExtractPeer {
 /// key encoded with the `encoding`.
 #[derive(Debug, Parser)]
 pub struct GenerateKey {
-    /// Key type to generate. Must be one of [x25519, ed25519]
+    /// Key type to generate. Must be one of [x25519, ed25519, secp256k1]
     #[clap(long, default_value_t = KeyType::Ed25519)]
     pub(crate) key_type: KeyType,
 
@@ -136,6 +136,10 @@ impl CliCommand<HashMap<&'static str, PathBuf>> for GenerateKey {
                 let private_key = keygen.generate_ed25519_private_key();
                 self.save_params.save_key(&private_key, "ed25519")
             }
+            KeyType::Secp256k1 => {
+                let private_key = keygen.generate_secp256k1_ecdsa_private_key();
+                self.save_params.save_key(&private_key, "secp256k1")
+            }
         }
     }
 }
@@ -184,6 +188,31 @@ impl GenerateKey {
             )?,
         ))
     }
+
+    /// A test friendly typed key generation for secp256k1 ECDSA keys.
+    pub async fn generate_secp256k1_ecdsa(
+        encoding: EncodingType,
+        key_file: &Path,
+    ) -> CliTypedResult<(
+        secp256k1_ecdsa::Secp256k1EcdsaPrivateKey,
+        secp256k1_ecdsa::Secp256k1EcdsaPublicKey,
+    )> {
+        let args = format!(
+            "generate --key-type {key_type:?} --output-file {key_file} --encoding {encoding:?} --assume-yes",
+            key_type = KeyType::Secp256k1,
+            key_file = key_file.display(),
+            encoding = encoding,
+        );
+        let command = GenerateKey::parse_from(args.split_whitespace());
+        command.execute().await?;
+        Ok((
+            encoding.load_key("private_key", key_file)?,
+            encoding.load_key(
+                "public_key",
+                &append_file_extension(key_file, PUBLIC_KEY_EXTENSION)?,
+            )?,
+        ))
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::common::types::{
-    CliCommand, CliResult, CliTypedResult, TransactionOptions, TransactionSummary,
+    CliCommand, CliResult, CliTypedResult, TransactionOptions, TransactionOutcome,
+    TransactionSummary,
 };
 use aptos_types::account_address::AccountAddress;
 use async_trait::async_trait;
@@ -51,16 +52,16 @@ pub struct AddStake {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for AddStake {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for AddStake {
     fn command_name(&self) -> &'static str {
         "AddStake"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_add_stake(self.amount))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -78,16 +79,16 @@ pub struct UnlockStake {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for UnlockStake {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for UnlockStake {
     fn command_name(&self) -> &'static str {
         "UnlockStake"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_unlock(self.amount))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -106,16 +107,16 @@ pub struct WithdrawStake {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for WithdrawStake {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for WithdrawStake {
     fn command_name(&self) -> &'static str {
         "WithdrawStake"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         self.node_op_options
             .submit_transaction(aptos_stdlib::stake_withdraw(self.amount))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -129,16 +130,16 @@ pub struct IncreaseLockup {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for IncreaseLockup {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for IncreaseLockup {
     fn command_name(&self) -> &'static str {
         "IncreaseLockup"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_increase_lockup())
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -169,12 +170,12 @@ pub struct InitializeStakeOwner {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for InitializeStakeOwner {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for InitializeStakeOwner {
     fn command_name(&self) -> &'static str {
         "InitializeStakeOwner"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         let owner_address = self.txn_options.sender_address()?;
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_initialize_stake_owner(
@@ -183,7 +184,7 @@ impl CliCommand<TransactionSummary> for InitializeStakeOwner {
                 self.voter_address.unwrap_or(owner_address),
             ))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -201,16 +202,16 @@ pub struct SetOperator {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for SetOperator {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for SetOperator {
     fn command_name(&self) -> &'static str {
         "SetOperator"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_set_operator(self.operator_address))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
 
@@ -228,15 +229,15 @@ pub struct SetDelegatedVoter {
 }
 
 #[async_trait]
-impl CliCommand<TransactionSummary> for SetDelegatedVoter {
+impl CliCommand<TransactionOutcome<TransactionSummary>> for SetDelegatedVoter {
     fn command_name(&self) -> &'static str {
         "SetDelegatedVoter"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(mut self) -> CliTypedResult<TransactionOutcome<TransactionSummary>> {
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_set_delegated_voter(self.voter_address))
             .await
-            .map(|inner| inner.into())
+            .map(|outcome| outcome.map(TransactionSummary::from))
     }
 }
@@ -12,12 +12,13 @@ use crate::common::types::{
     account_address_from_public_key, AccountAddressWrapper, CliError, CliTypedResult,
     EncodingOptions, FaucetOptions, GasOptions, KeyType, MoveManifestAccountWrapper,
     MovePackageDir, OptionalPoolAddressArgs, PrivateKeyInputOptions, PromptOptions, RestOptions,
-    RngArgs, SaveFile, TransactionOptions, TransactionSummary,
+    RngArgs, SaveFile, SimulationSummary, TransactionOptions, TransactionOutcome,
+    TransactionSummary,
 };
 use crate::common::utils::write_to_file;
 use crate::move_tool::{
     ArgWithType, CompilePackage, DownloadPackage, IncludedArtifacts, InitPackage, MemberId,
-    PublishPackage, RunFunction, TestPackage,
+    PublishPackage, RunFunction, TestPackage, ViewFunction,
 };
 use crate::node::{
     AnalyzeMode, AnalyzeValidatorPerformance, InitializeValidator, JoinValidatorSet,
@@ -143,7 +144,8 @@ impl CliTestFramework {
             account: self.account_id(index),
         }
         .execute()
-        .await?;
+        .await
+        .and_then(TransactionOutcome::submitted)?;
 
         Ok(index)
     }
@@ -202,6 +204,31 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
+    }
+
+    pub async fn simulate_transfer_coins(
+        &self,
+        sender_index: usize,
+        receiver_index: usize,
+        amount: u64,
+    ) -> CliTypedResult<SimulationSummary> {
+        match (TransferCoins {
+            txn_options: TransactionOptions {
+                simulate: true,
+                ..self.transaction_options(sender_index, None)
+            },
+            account: self.account_id(receiver_index),
+            amount,
+        }
+        .execute()
+        .await?)
+        {
+            TransactionOutcome::Simulated(summary) => Ok(summary),
+            TransactionOutcome::Submitted(_) | TransactionOutcome::Profiled(_) => Err(
+                CliError::UnexpectedError("Transaction was not simulated".to_string()),
+            ),
+        }
     }
 
     pub async fn transfer_invalid_addr(
@@ -217,6 +244,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn show_validator_config(
@@ -279,6 +307,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn add_stake(&self, index: usize, amount: u64) -> CliTypedResult<TransactionSummary> {
@@ -288,6 +317,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn unlock_stake(
@@ -301,6 +331,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn withdraw_stake(
@@ -314,6 +345,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn increase_lockup(&self, index: usize) -> CliTypedResult<TransactionSummary> {
@@ -322,6 +354,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn join_validator_set(
@@ -335,6 +368,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn leave_validator_set(
@@ -348,6 +382,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn update_validator_network_addresses(
@@ -372,6 +407,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn analyze_validator_performance(
@@ -410,6 +446,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn init(&self, private_key: &Ed25519PrivateKey) -> CliTypedResult<()> {
@@ -442,6 +479,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn set_operator(
@@ -455,6 +493,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn set_delegated_voter(
@@ -468,6 +507,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     /// Wait for an account to exist
@@ -681,6 +721,7 @@ impl CliTestFramework {
         }
         .execute()
         .await
+        .and_then(TransactionOutcome::submitted)
     }
 
     pub async fn download_package(
@@ -729,6 +770,42 @@ impl CliTestFramework {
             function_id,
             args: parsed_args,
             type_args: parsed_type_args,
+            profile_gas: false,
+        }
+        .execute()
+        .await
+        .and_then(TransactionOutcome::submitted)
+    }
+
+    pub async fn view_function(
+        &self,
+        function_id: MemberId,
+        args: Vec<&str>,
+        type_args: Vec<&str>,
+    ) -> CliTypedResult<Vec<Value>> {
+        let mut parsed_args = Vec::new();
+        for arg in args {
+            parsed_args.push(
+                ArgWithType::from_str(arg)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+            )
+        }
+
+        let mut parsed_type_args = Vec::new();
+        for arg in type_args {
+            parsed_type_args.push(
+                MoveType::from_str(arg)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+            )
+        }
+
+        ViewFunction {
+            function_id,
+            args: parsed_args,
+            type_args: parsed_type_args,
+            ledger_version: None,
+            rest_options: self.rest_options(),
+            profile_options: Default::default(),
         }
         .execute()
         .await
@@ -8,14 +8,16 @@ use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
+use network::application::{storage::PeerMetadataStorage, types::PeerInfo};
 use prometheus::{
     proto::{MetricFamily, MetricType},
     Encoder, TextEncoder,
 };
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::Infallible,
     net::{SocketAddr, ToSocketAddrs},
+    sync::Arc,
     thread,
 };
 use tokio::runtime;
@@ -82,9 +84,30 @@ pub fn get_all_metrics() -> HashMap<String, String> {
     get_metrics(all_metric_families)
 }
 
+/// Returns the information (e.g., connection and monitoring metadata)
+/// of all known peers, grouped by network and keyed by peer id.
+pub fn get_peer_information(
+    peer_metadata_storage: &PeerMetadataStorage,
+) -> BTreeMap<String, BTreeMap<String, PeerInfo>> {
+    peer_metadata_storage
+        .networks()
+        .map(|network_id| {
+            let peers = peer_metadata_storage
+                .read_all(network_id)
+                .into_iter()
+                .map(|(peer_network_id, peer_info)| {
+                    (peer_network_id.peer_id().to_string(), peer_info)
+                })
+                .collect();
+            (network_id.to_string(), peers)
+        })
+        .collect()
+}
+
 async fn serve_requests(
     req: Request<Body>,
     node_config: NodeConfig,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
 ) -> Result<Response<Body>, hyper::Error> {
     let mut resp = Response::new(Body::empty());
     match (req.method(), req.uri().path()) {
@@ -118,6 +141,16 @@ async fn serve_requests(
             let encoded_metrics = serde_json::to_string(&metrics).unwrap();
             *resp.body_mut() = Body::from(encoded_metrics);
         }
+        // Expose the connection and monitoring metadata of all known peers
+        (&Method::GET, "/peer_information") => {
+            if node_config.inspection_service.expose_peer_information {
+                let peer_information = get_peer_information(&peer_metadata_storage);
+                let encoded_information = serde_json::to_string(&peer_information).unwrap();
+                *resp.body_mut() = Body::from(encoded_information);
+            } else {
+                *resp.body_mut() = Body::from(DISABLED_ENDPOINT_MESSAGE);
+            }
+        }
         // Expose the system and build information
         (&Method::GET, "/system_information") => {
             if node_config.inspection_service.expose_system_information {
@@ -139,7 +172,10 @@ async fn serve_requests(
     Ok(resp)
 }
 
-pub fn start_inspection_service(node_config: NodeConfig) {
+pub fn start_inspection_service(
+    node_config: NodeConfig,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
     let service_address = node_config.inspection_service.address.clone();
@@ -160,9 +196,10 @@ pub fn start_inspection_service(node_config: NodeConfig) {
     thread::spawn(move || {
         let make_service = make_service_fn(move |_conn| {
             let node_config = node_config.clone();
+            let peer_metadata_storage = peer_metadata_storage.clone();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
-                    serve_requests(request, node_config.clone())
+                    serve_requests(request, node_config.clone(), peer_metadata_storage.clone())
                 }))
             }
         });
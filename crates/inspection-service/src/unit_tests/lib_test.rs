@@ -1,8 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::inspection_service::get_all_metrics;
+use crate::inspection_service::{get_all_metrics, get_peer_information};
+use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_types::PeerId;
 use assert_approx_eq::assert_approx_eq;
+use network::{application::storage::PeerMetadataStorage, transport::ConnectionMetadata};
 use once_cell::sync::Lazy;
 use prometheus::{proto::MetricFamily, register_int_counter, Counter, IntCounter, Opts, Registry};
 use rusty_fork::rusty_fork_test;
@@ -70,3 +73,28 @@ fn get_all_metrics_test() {
     }
 }
 }
+
+#[test]
+fn get_peer_information_test() {
+    // Create the peer metadata storage and verify there is no peer information
+    let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Validator]);
+    let peer_information = get_peer_information(&peer_metadata_storage);
+    assert!(peer_information[&NetworkId::Validator.to_string()].is_empty());
+
+    // Add a peer and update its monitoring metadata
+    let peer_id = PeerId::random();
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
+    peer_metadata_storage
+        .insert_connection(NetworkId::Validator, ConnectionMetadata::mock(peer_id));
+    peer_metadata_storage
+        .update_monitoring_metadata(peer_network_id, |monitoring_metadata| {
+            monitoring_metadata.depth_from_validators = Some(1);
+        })
+        .unwrap();
+
+    // Verify the peer information contains the peer and its monitoring metadata
+    let peer_information = get_peer_information(&peer_metadata_storage);
+    let peer_info = &peer_information[&NetworkId::Validator.to_string()][&peer_id.to_string()];
+    assert_eq!(peer_info.monitoring_metadata.depth_from_validators, Some(1));
+    assert!(serde_json::to_string(&peer_information).is_ok());
+}
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS coin_activities;
+DROP TABLE IF EXISTS coin_balances;
+DROP TABLE IF EXISTS coin_infos;
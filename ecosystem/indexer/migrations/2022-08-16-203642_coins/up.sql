@@ -0,0 +1,61 @@
+-- Your SQL goes here
+-- coin types, taken from the `0x1::coin::CoinInfo` resources
+CREATE TABLE coin_infos
+(
+    coin_type VARCHAR NOT NULL,
+    creator_address VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    symbol VARCHAR NOT NULL,
+    decimals INT NOT NULL,
+    transaction_version_created uint_64,
+    created_at TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (coin_type)
+);
+
+-- balance of every `0x1::coin::CoinStore`, at each version it is written
+CREATE TABLE coin_balances
+(
+    transaction_version uint_64,
+    owner_address VARCHAR NOT NULL,
+    coin_type VARCHAR NOT NULL,
+    amount uint_64,
+    transaction_timestamp TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (transaction_version, owner_address, coin_type)
+);
+
+CREATE INDEX cb_owner_coin_type_index ON coin_balances (owner_address, coin_type);
+
+-- deposits, withdrawals and gas fees
+CREATE TABLE coin_activities
+(
+    transaction_version uint_64,
+    owner_address VARCHAR NOT NULL,
+    coin_type VARCHAR NOT NULL,
+    amount uint_64,
+    activity_type VARCHAR NOT NULL,
+    event_creation_number uint_64,
+    event_sequence_number uint_64,
+    transaction_timestamp TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (
+        transaction_version,
+        owner_address,
+        coin_type,
+        activity_type,
+        event_creation_number,
+        event_sequence_number
+    )
+);
+
+CREATE INDEX ca_owner_coin_type_index ON coin_activities (owner_address, coin_type);
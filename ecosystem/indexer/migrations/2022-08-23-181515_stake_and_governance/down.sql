@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS proposal_votes;
+DROP TABLE IF EXISTS proposals;
+DROP TABLE IF EXISTS stake_activities;
+DROP TABLE IF EXISTS stake_pools;
@@ -0,0 +1,76 @@
+-- Your SQL goes here
+-- state of every `0x1::stake::StakePool`, at each version it is written
+CREATE TABLE stake_pools
+(
+    transaction_version uint_64,
+    pool_address VARCHAR NOT NULL,
+    active_stake uint_64,
+    pending_active_stake uint_64,
+    pending_inactive_stake uint_64,
+    inactive_stake uint_64,
+    locked_until_secs uint_64,
+    operator_address VARCHAR NOT NULL,
+    voter_address VARCHAR NOT NULL,
+    transaction_timestamp TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (transaction_version, pool_address)
+);
+
+CREATE INDEX sp_pool_address_index ON stake_pools (pool_address);
+
+-- every event emitted by `0x1::stake`
+CREATE TABLE stake_activities
+(
+    event_key VARCHAR NOT NULL,
+    sequence_number uint_64,
+    transaction_version uint_64,
+    pool_address VARCHAR NOT NULL,
+    activity_type VARCHAR NOT NULL,
+    -- only set for the events moving stake
+    amount NUMERIC,
+    data jsonb NOT NULL,
+    transaction_timestamp TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (event_key, sequence_number)
+);
+
+CREATE INDEX sa_pool_address_index ON stake_activities (pool_address);
+
+CREATE TABLE proposals
+(
+    proposal_id uint_64,
+    proposer VARCHAR NOT NULL,
+    stake_pool VARCHAR NOT NULL,
+    execution_hash VARCHAR NOT NULL,
+    proposal_metadata jsonb NOT NULL,
+    transaction_version uint_64,
+    created_at TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (proposal_id)
+);
+
+-- a stake pool can only vote once per proposal
+CREATE TABLE proposal_votes
+(
+    proposal_id uint_64,
+    stake_pool VARCHAR NOT NULL,
+    voter VARCHAR NOT NULL,
+    num_votes uint_64,
+    should_pass BOOLEAN NOT NULL,
+    transaction_version uint_64,
+    transaction_timestamp TIMESTAMP NOT NULL,
+
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (proposal_id, stake_pool)
+);
@@ -0,0 +1,177 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection},
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::{
+        coin::{get_gas_payer, CoinActivity, CoinBalance, CoinInfo, CoinResource},
+        transactions::TransactionModel,
+    },
+    schema,
+};
+use aptos_rest_client::Transaction;
+use async_trait::async_trait;
+use bigdecimal::Zero;
+use diesel::Connection;
+use field_count::FieldCount;
+use futures::future::Either;
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+pub struct CoinTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl CoinTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for CoinTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "CoinTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_coin_infos(conn: &PgPoolConnection, coin_infos: &[CoinInfo]) {
+    let chunks = get_chunks(coin_infos.len(), CoinInfo::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_infos::table)
+                .values(&coin_infos[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into coin_infos");
+    }
+}
+
+fn insert_coin_balances(conn: &PgPoolConnection, coin_balances: &[CoinBalance]) {
+    let chunks = get_chunks(coin_balances.len(), CoinBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_balances::table)
+                .values(&coin_balances[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into coin_balances");
+    }
+}
+
+fn insert_coin_activities(conn: &PgPoolConnection, coin_activities: &[CoinActivity]) {
+    let chunks = get_chunks(coin_activities.len(), CoinActivity::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_activities::table)
+                .values(&coin_activities[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into coin_activities");
+    }
+}
+
+#[async_trait]
+impl TransactionProcessor for CoinTransactionProcessor {
+    fn name(&self) -> &'static str {
+        "coin_processor"
+    }
+
+    async fn process_transaction(
+        &self,
+        transaction: Arc<Transaction>,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let version = transaction.version().unwrap_or(0);
+
+        let (transaction_model, maybe_details_model, maybe_events, maybe_write_set_changes) =
+            TransactionModel::from_transaction(&transaction);
+        let write_set_changes = maybe_write_set_changes.unwrap_or_default();
+        let events = maybe_events.unwrap_or_default();
+
+        let transaction_timestamp = TransactionModel::get_timestamp(&maybe_details_model);
+
+        let mut coin_infos = vec![];
+        let mut coin_balances = vec![];
+        // Maps the event keys of the coin stores to their (owner address, coin type)
+        let mut coin_stores = HashMap::new();
+        for write_set_change in &write_set_changes {
+            match CoinResource::from_write_set_change(write_set_change) {
+                Some(CoinResource::CoinInfo {
+                    coin_type,
+                    creator_address,
+                    resource,
+                }) => coin_infos.push(CoinInfo::from_resource(
+                    coin_type,
+                    creator_address,
+                    resource,
+                    version,
+                    transaction_timestamp,
+                )),
+                Some(CoinResource::CoinStore {
+                    coin_type,
+                    owner_address,
+                    resource,
+                }) => {
+                    coin_balances.push(CoinBalance::from_resource(
+                        coin_type.clone(),
+                        owner_address.clone(),
+                        &resource,
+                        version,
+                        transaction_timestamp,
+                    ));
+                    let coin_store = (owner_address, coin_type);
+                    coin_stores.insert(resource.deposit_events.key(), coin_store.clone());
+                    coin_stores.insert(resource.withdraw_events.key(), coin_store);
+                }
+                None => {}
+            }
+        }
+
+        let mut coin_activities =
+            CoinActivity::from_events(&events, &coin_stores, version, transaction_timestamp);
+        if let (Some(Either::Left(user_txn)), Transaction::UserTransaction(api_txn)) =
+            (&maybe_details_model, transaction.as_ref())
+        {
+            let gas_fee = &transaction_model.gas_used * &user_txn.gas_unit_price;
+            if !gas_fee.is_zero() {
+                coin_activities.push(CoinActivity::from_gas_fee(
+                    get_gas_payer(api_txn),
+                    gas_fee,
+                    version,
+                    transaction_timestamp,
+                ));
+            }
+        }
+
+        let conn = self.get_conn();
+        let tx_result = conn.transaction::<(), diesel::result::Error, _>(|| {
+            insert_coin_infos(&conn, &coin_infos);
+            insert_coin_balances(&conn, &coin_balances);
+            insert_coin_activities(&conn, &coin_activities);
+            Ok(())
+        });
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(self.name(), version)),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+}
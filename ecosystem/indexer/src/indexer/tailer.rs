@@ -280,10 +280,21 @@ pub async fn await_tasks<T: Debug>(tasks: Vec<JoinHandle<T>>) -> Vec<T> {
 mod test {
     use super::*;
     use crate::{
+        coin_processor::CoinTransactionProcessor,
         database::{new_db_pool, PgPoolConnection},
         default_processor::DefaultTransactionProcessor,
-        models::transactions::TransactionModel,
+        models::{
+            coin::{CoinActivity, CoinBalance, CoinInfo},
+            stake::{Proposal, ProposalVote, StakeActivity, StakePool},
+            transactions::TransactionModel,
+        },
+        schema::{
+            coin_activities, coin_balances, coin_infos, proposal_votes, proposals,
+            stake_activities, stake_pools,
+        },
+        stake_processor::StakeTransactionProcessor,
         token_processor::TokenTransactionProcessor,
+        util::u64_to_bigdecimal,
     };
     use aptos_rest_client::State;
     use diesel::Connection;
@@ -335,6 +346,13 @@ mod test {
     pub fn wipe_database(conn: &PgPoolConnection) {
         for table in [
             "metadatas",
+            "coin_activities",
+            "coin_balances",
+            "coin_infos",
+            "stake_pools",
+            "stake_activities",
+            "proposals",
+            "proposal_votes",
             "token_activities",
             "token_datas",
             "token_propertys",
@@ -369,8 +387,12 @@ mod test {
 
         let pg_transaction_processor = DefaultTransactionProcessor::new(conn_pool.clone());
         let token_transaction_processor = TokenTransactionProcessor::new(conn_pool.clone(), false);
+        let coin_transaction_processor = CoinTransactionProcessor::new(conn_pool.clone());
+        let stake_transaction_processor = StakeTransactionProcessor::new(conn_pool.clone());
         tailer.add_processor(Arc::new(pg_transaction_processor));
         tailer.add_processor(Arc::new(token_transaction_processor));
+        tailer.add_processor(Arc::new(coin_transaction_processor));
+        tailer.add_processor(Arc::new(stake_transaction_processor));
         Ok((conn_pool, tailer))
     }
 
@@ -857,6 +879,347 @@ mod test {
             .await
             .unwrap();
 
+        // A coin transfer, which also pays and burns gas
+        let transfer_txn: Transaction = serde_json::from_value(json!(
+            {
+              "type": "user_transaction",
+              "version": "691600",
+              "hash": "0x3ff9e4d4d8fd0e7ea4e27ae5d3a0a4e4db5b7fa2cc2b4e0e0f5a0ad0e3d7c37a",
+              "state_change_hash": "0xebfe1eb7aa5321e7a7d741d927487163c34c821eaab60646ae0efd02b286c97c",
+              "event_root_hash": "0x414343554d554c41544f525f504c414345484f4c4445525f4841534800000000",
+              "gas_used": "10",
+              "success": true,
+              "vm_status": "Executed successfully",
+              "accumulator_root_hash": "0x97bfd5949d32f6c9a9efad93411924bfda658a8829de384d531ee73c2f740971",
+              "sender": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+              "sequence_number": "21387",
+              "max_gas_amount": "1000",
+              "gas_unit_price": "1",
+              "expiration_timestamp_secs": "1649713172",
+              "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::coin::transfer",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": [
+                  "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                  "1000"
+                ]
+              },
+              "signature": {
+                "type": "ed25519_signature",
+                "public_key": "0x14ff6646855dad4a2dab30db773cdd4b22d6f9e6813f3e50142adf4f3efcf9f8",
+                "signature": "0x70781112e78cc8b54b86805c016cef2478bccdef21b721542af0323276ab906c989172adffed5bf2f475f2ec3a5b284a0ac46a6aef0d79f0dbb6b85bfca0080a"
+              },
+              "events": [
+                {
+                  "key": "0x0300000000000000dfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                  "sequence_number": "5",
+                  "type": "0x1::coin::WithdrawEvent",
+                  "data": {
+                    "amount": "1000"
+                  }
+                },
+                {
+                  "key": "0x020000000000000045b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                  "sequence_number": "1",
+                  "type": "0x1::coin::DepositEvent",
+                  "data": {
+                    "amount": "1000"
+                  }
+                }
+              ],
+              "timestamp": "1649713141723410",
+              "changes": [
+                {
+                  "type": "write_resource",
+                  "address": "0x1",
+                  "state_key_hash": "0x5ddf404c60e96e9485beafcabb95609fed8e38e941a725cae4dcec8296fb32d7",
+                  "data": {
+                    "type": "0x1::coin::CoinInfo<0x1::aptos_coin::AptosCoin>",
+                    "data": {
+                      "decimals": 8,
+                      "name": "Aptos Coin",
+                      "supply": {
+                        "vec": []
+                      },
+                      "symbol": "APT"
+                    }
+                  }
+                },
+                {
+                  "type": "write_resource",
+                  "address": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                  "state_key_hash": "0x8bd1f0f0b5a6d0d17f3ff34f0a2b5c6a5d3e1bd0b5a4f07c2e4d2b0f9c6c1a7e",
+                  "data": {
+                    "type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                    "data": {
+                      "coin": {
+                        "value": "8990"
+                      },
+                      "deposit_events": {
+                        "counter": "1",
+                        "guid": {
+                          "id": {
+                            "addr": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                            "creation_num": "2"
+                          }
+                        }
+                      },
+                      "frozen": false,
+                      "withdraw_events": {
+                        "counter": "6",
+                        "guid": {
+                          "id": {
+                            "addr": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                            "creation_num": "3"
+                          }
+                        }
+                      }
+                    }
+                  }
+                },
+                {
+                  "type": "write_resource",
+                  "address": "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                  "state_key_hash": "0x3d4a1e5c3b4a6f1b2f7f6a9c0d8e3b1a2c4d5e6f708192a3b4c5d6e7f8091a2b",
+                  "data": {
+                    "type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                    "data": {
+                      "coin": {
+                        "value": "21000"
+                      },
+                      "deposit_events": {
+                        "counter": "2",
+                        "guid": {
+                          "id": {
+                            "addr": "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                            "creation_num": "2"
+                          }
+                        }
+                      },
+                      "frozen": false,
+                      "withdraw_events": {
+                        "counter": "0",
+                        "guid": {
+                          "id": {
+                            "addr": "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                            "creation_num": "3"
+                          }
+                        }
+                      }
+                    }
+                  }
+                }
+              ]
+            }
+        )).unwrap();
+
+        // Idempotency again
+        tailer
+            .process_transaction(Arc::new(transfer_txn.clone()))
+            .await
+            .unwrap();
+        tailer
+            .process_transaction(Arc::new(transfer_txn.clone()))
+            .await
+            .unwrap();
+
+        let conn = conn_pool.get().unwrap();
+        let sender = "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494";
+        let receiver = "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142";
+
+        let infos = coin_infos::table.load::<CoinInfo>(&conn).unwrap();
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos[0].coin_type, "0x1::aptos_coin::AptosCoin");
+        assert_eq!(
+            infos[0].creator_address,
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(infos[0].symbol, "APT");
+        assert_eq!(infos[0].decimals, 8);
+
+        let balances = coin_balances::table
+            .order(coin_balances::owner_address.asc())
+            .load::<CoinBalance>(&conn)
+            .unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].owner_address, receiver);
+        assert_eq!(balances[0].amount, u64_to_bigdecimal(21000));
+        assert_eq!(balances[1].owner_address, sender);
+        assert_eq!(balances[1].amount, u64_to_bigdecimal(8990));
+        assert_eq!(balances[1].transaction_version, u64_to_bigdecimal(691600));
+
+        // The earlier user transactions paid gas as well
+        let activities = coin_activities::table
+            .filter(coin_activities::transaction_version.eq(u64_to_bigdecimal(691600)))
+            .order(coin_activities::activity_type.asc())
+            .load::<CoinActivity>(&conn)
+            .unwrap();
+        assert_eq!(activities.len(), 3);
+        assert_eq!(activities[0].activity_type, "0x1::aptos_coin::GasFeeEvent");
+        assert_eq!(activities[0].owner_address, sender);
+        assert_eq!(activities[0].amount, u64_to_bigdecimal(10));
+        assert_eq!(activities[1].activity_type, "0x1::coin::DepositEvent");
+        assert_eq!(activities[1].owner_address, receiver);
+        assert_eq!(activities[1].amount, u64_to_bigdecimal(1000));
+        assert_eq!(activities[1].event_creation_number, u64_to_bigdecimal(2));
+        assert_eq!(activities[2].activity_type, "0x1::coin::WithdrawEvent");
+        assert_eq!(activities[2].owner_address, sender);
+        assert_eq!(activities[2].coin_type, "0x1::aptos_coin::AptosCoin");
+        assert_eq!(activities[2].event_sequence_number, u64_to_bigdecimal(5));
+
+        // A stake pool owner adding stake, then proposing and voting, with fake events
+        let governance_txn: Transaction = serde_json::from_value(json!(
+            {
+              "type": "user_transaction",
+              "version": "691700",
+              "hash": "0x6a0c2e3a7f4b5d1e9c8a7b6f5e4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d",
+              "state_change_hash": "0xebfe1eb7aa5321e7a7d741d927487163c34c821eaab60646ae0efd02b286c97c",
+              "event_root_hash": "0x414343554d554c41544f525f504c414345484f4c4445525f4841534800000000",
+              "gas_used": "0",
+              "success": true,
+              "vm_status": "Executed successfully",
+              "accumulator_root_hash": "0x97bfd5949d32f6c9a9efad93411924bfda658a8829de384d531ee73c2f740971",
+              "sender": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+              "sequence_number": "21388",
+              "max_gas_amount": "1000",
+              "gas_unit_price": "1",
+              "expiration_timestamp_secs": "1649713172",
+              "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::aptos_governance::vote",
+                "type_arguments": [],
+                "arguments": [
+                  "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                  "7",
+                  true
+                ]
+              },
+              "signature": {
+                "type": "ed25519_signature",
+                "public_key": "0x14ff6646855dad4a2dab30db773cdd4b22d6f9e6813f3e50142adf4f3efcf9f8",
+                "signature": "0x70781112e78cc8b54b86805c016cef2478bccdef21b721542af0323276ab906c989172adffed5bf2f475f2ec3a5b284a0ac46a6aef0d79f0dbb6b85bfca0080a"
+              },
+              "events": [
+                {
+                  "key": "0x0500000000000000dfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                  "sequence_number": "0",
+                  "type": "0x1::stake::AddStakeEvent",
+                  "data": {
+                    "amount_added": "5000",
+                    "pool_address": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494"
+                  }
+                },
+                {
+                  "key": "0x0400000000000000dfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                  "sequence_number": "0",
+                  "type": "0x1::stake::SetOperatorEvent",
+                  "data": {
+                    "new_operator": "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                    "old_operator": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                    "pool_address": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494"
+                  }
+                },
+                {
+                  "key": "0x0200000000000000000000000000000000000000000000000000000000000000000000000000000001",
+                  "sequence_number": "6",
+                  "type": "0x1::aptos_governance::CreateProposalEvent",
+                  "data": {
+                    "execution_hash": "0x24f1ae5b8ba7a7e6e6cd2e4c3d0b8e26f0bb1c7e4bd1f52cf2dd1d6ac89ba3a5",
+                    "proposal_id": "7",
+                    "proposal_metadata": {
+                      "data": []
+                    },
+                    "proposer": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                    "stake_pool": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494"
+                  }
+                },
+                {
+                  "key": "0x0400000000000000000000000000000000000000000000000000000000000000000000000000000001",
+                  "sequence_number": "12",
+                  "type": "0x1::aptos_governance::VoteEvent",
+                  "data": {
+                    "num_votes": "15000",
+                    "proposal_id": "7",
+                    "should_pass": true,
+                    "stake_pool": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                    "voter": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494"
+                  }
+                }
+              ],
+              "timestamp": "1649713141723410",
+              "changes": [
+                {
+                  "type": "write_resource",
+                  "address": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494",
+                  "state_key_hash": "0x0c9c6e7e0a3a6e3a1d4b5f7e8c9d0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b",
+                  "data": {
+                    "type": "0x1::stake::StakePool",
+                    "data": {
+                      "active": {
+                        "value": "10000"
+                      },
+                      "inactive": {
+                        "value": "0"
+                      },
+                      "pending_active": {
+                        "value": "5000"
+                      },
+                      "pending_inactive": {
+                        "value": "0"
+                      },
+                      "locked_until_secs": "1650000000",
+                      "operator_address": "0x45b44793724a5ecc6ad85fa60949d0824cfc7f61d6bd74490b13598379313142",
+                      "delegated_voter": "0xdfd557c68c6c12b8c65908b3d3c7b95d34bb12ae6eae5a43ee30aa67a4c12494"
+                    }
+                  }
+                }
+              ]
+            }
+        )).unwrap();
+
+        tailer
+            .process_transaction(Arc::new(governance_txn.clone()))
+            .await
+            .unwrap();
+        tailer
+            .process_transaction(Arc::new(governance_txn.clone()))
+            .await
+            .unwrap();
+
+        let pools = stake_pools::table.load::<StakePool>(&conn).unwrap();
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].pool_address, sender);
+        assert_eq!(pools[0].active_stake, u64_to_bigdecimal(10000));
+        assert_eq!(pools[0].pending_active_stake, u64_to_bigdecimal(5000));
+        assert_eq!(pools[0].operator_address, receiver);
+        assert_eq!(pools[0].voter_address, sender);
+
+        let stake_events = stake_activities::table
+            .order(stake_activities::activity_type.asc())
+            .load::<StakeActivity>(&conn)
+            .unwrap();
+        assert_eq!(stake_events.len(), 2);
+        assert_eq!(stake_events[0].activity_type, "0x1::stake::AddStakeEvent");
+        assert_eq!(stake_events[0].amount, Some(u64_to_bigdecimal(5000)));
+        assert_eq!(
+            stake_events[1].activity_type,
+            "0x1::stake::SetOperatorEvent"
+        );
+        assert_eq!(stake_events[1].pool_address, sender);
+        assert_eq!(stake_events[1].amount, None);
+
+        let all_proposals = proposals::table.load::<Proposal>(&conn).unwrap();
+        assert_eq!(all_proposals.len(), 1);
+        assert_eq!(all_proposals[0].proposal_id, u64_to_bigdecimal(7));
+        assert_eq!(all_proposals[0].proposer, sender);
+
+        let votes = proposal_votes::table.load::<ProposalVote>(&conn).unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].proposal_id, u64_to_bigdecimal(7));
+        assert_eq!(votes[0].num_votes, u64_to_bigdecimal(15000));
+        assert!(votes[0].should_pass);
+
         let (_conn_pool, tailer) = setup_indexer().unwrap();
         tailer.set_fetcher_version(4).await;
         assert!(tailer.check_or_update_chain_id().await.is_ok());
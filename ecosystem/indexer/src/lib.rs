@@ -11,12 +11,14 @@ extern crate diesel_migrations;
 #[macro_use]
 extern crate diesel;
 
+pub mod coin_processor;
 pub mod counters;
 pub mod database;
 pub mod default_processor;
 pub mod indexer;
 pub mod models;
 pub mod schema;
+pub mod stake_processor;
 pub mod token_processor;
 mod util;
 
@@ -12,8 +12,9 @@ use clap::Parser;
 use std::sync::Arc;
 
 use aptos_indexer::{
-    database::new_db_pool, default_processor::DefaultTransactionProcessor, indexer::tailer::Tailer,
-    token_processor::TokenTransactionProcessor,
+    coin_processor::CoinTransactionProcessor, database::new_db_pool,
+    default_processor::DefaultTransactionProcessor, indexer::tailer::Tailer,
+    stake_processor::StakeTransactionProcessor, token_processor::TokenTransactionProcessor,
 };
 
 #[derive(Debug, Parser)]
@@ -62,6 +63,16 @@ struct IndexerArgs {
     /// turn on the token URI fetcher
     #[clap(long)]
     index_token_uri_data: bool,
+
+    /// Turn on the indexer to collect coin infos, balances and activities and store them in the
+    /// postgres DB tables.
+    #[clap(long)]
+    index_coin_data: bool,
+
+    /// Turn on the indexer to collect stake pools, stake activities, governance proposals and
+    /// votes and store them in the postgres DB tables.
+    #[clap(long)]
+    index_stake_data: bool,
 }
 
 #[tokio::main]
@@ -88,6 +99,14 @@ async fn main() -> std::io::Result<()> {
             TokenTransactionProcessor::new(conn_pool.clone(), args.index_token_uri_data);
         tailer.add_processor(Arc::new(token_transaction_processor));
     }
+    if args.index_coin_data {
+        let coin_transaction_processor = CoinTransactionProcessor::new(conn_pool.clone());
+        tailer.add_processor(Arc::new(coin_transaction_processor));
+    }
+    if args.index_stake_data {
+        let stake_transaction_processor = StakeTransactionProcessor::new(conn_pool.clone());
+        tailer.add_processor(Arc::new(stake_transaction_processor));
+    }
 
     let starting_version = match args.start_from_version {
         None => tailer.set_fetcher_to_lowest_processor_version().await,
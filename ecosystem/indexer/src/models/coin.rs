@@ -0,0 +1,340 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::extra_unused_lifetimes)]
+use crate::{
+    models::{events::EventModel, write_set_changes::WriteSetChangeModel},
+    schema::{coin_activities, coin_balances, coin_infos},
+    util::{standardize_address, u64_to_bigdecimal},
+};
+use aptos_rest_client::{
+    aptos_api_types::{TransactionSignature, UserTransaction as APIUserTransaction},
+    types,
+};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const APTOS_COIN_TYPE: &str = "0x1::aptos_coin::AptosCoin";
+/// Gas fees are burnt without emitting an event, so they get their own activity type
+pub const GAS_FEE_ACTIVITY_TYPE: &str = "0x1::aptos_coin::GasFeeEvent";
+const COIN_INFO_TYPE_PREFIX: &str = "0x1::coin::CoinInfo<";
+const COIN_STORE_TYPE_PREFIX: &str = "0x1::coin::CoinStore<";
+const DEPOSIT_EVENT_TYPE: &str = "0x1::coin::DepositEvent";
+const WITHDRAW_EVENT_TYPE: &str = "0x1::coin::WithdrawEvent";
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "coin_infos")]
+#[primary_key(coin_type)]
+pub struct CoinInfo {
+    pub coin_type: String,
+    pub creator_address: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub transaction_version_created: bigdecimal::BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "coin_balances")]
+#[primary_key(transaction_version, owner_address, coin_type)]
+pub struct CoinBalance {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "coin_activities")]
+#[primary_key(
+    transaction_version,
+    owner_address,
+    coin_type,
+    activity_type,
+    event_creation_number,
+    event_sequence_number
+)]
+pub struct CoinActivity {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub activity_type: String,
+    pub event_creation_number: bigdecimal::BigDecimal,
+    pub event_sequence_number: bigdecimal::BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoinInfoResource {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Coin {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub value: bigdecimal::BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventGuidId {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub creation_num: u64,
+    pub addr: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventGuid {
+    pub id: EventGuidId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventHandle {
+    pub guid: EventGuid,
+}
+
+impl EventHandle {
+    /// The key of the events emitted to this handle, formatted as in `EventModel`:
+    /// the creation number as little endian bytes, followed by the address
+    pub fn key(&self) -> String {
+        let creation_num = self
+            .guid
+            .id
+            .creation_num
+            .to_le_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        format!(
+            "0x{}{}",
+            creation_num,
+            standardize_address(&self.guid.id.addr).trim_start_matches("0x")
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoinStoreResource {
+    pub coin: Coin,
+    pub deposit_events: EventHandle,
+    pub withdraw_events: EventHandle,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoinEventType {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub amount: bigdecimal::BigDecimal,
+}
+
+/// A `0x1::coin` resource, parsed from a `write_resource` change
+#[derive(Debug, Clone)]
+pub enum CoinResource {
+    CoinInfo {
+        coin_type: String,
+        creator_address: String,
+        resource: CoinInfoResource,
+    },
+    CoinStore {
+        coin_type: String,
+        owner_address: String,
+        resource: CoinStoreResource,
+    },
+}
+
+impl CoinResource {
+    pub fn from_write_set_change(write_set_change: &WriteSetChangeModel) -> Option<CoinResource> {
+        if write_set_change.type_ != "write_resource" {
+            return None;
+        }
+        let type_ = write_set_change.data["type"].as_str()?;
+        let data = write_set_change.data["data"].clone();
+        let address = standardize_address(&write_set_change.address);
+        if let Some(coin_type) = get_type_argument(type_, COIN_INFO_TYPE_PREFIX) {
+            Some(CoinResource::CoinInfo {
+                coin_type,
+                creator_address: address,
+                resource: serde_json::from_value(data).unwrap(),
+            })
+        } else {
+            get_type_argument(type_, COIN_STORE_TYPE_PREFIX).map(|coin_type| {
+                CoinResource::CoinStore {
+                    coin_type,
+                    owner_address: address,
+                    resource: serde_json::from_value(data).unwrap(),
+                }
+            })
+        }
+    }
+}
+
+/// Extracts `T` from a resource type `<prefix>T>`
+fn get_type_argument(type_: &str, prefix: &str) -> Option<String> {
+    type_
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_suffix('>'))
+        .map(|coin_type| coin_type.to_string())
+}
+
+impl CoinInfo {
+    pub fn from_resource(
+        coin_type: String,
+        creator_address: String,
+        resource: CoinInfoResource,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        CoinInfo {
+            coin_type,
+            creator_address,
+            name: resource.name,
+            symbol: resource.symbol,
+            decimals: resource.decimals,
+            transaction_version_created: u64_to_bigdecimal(transaction_version),
+            created_at: transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl CoinBalance {
+    pub fn from_resource(
+        coin_type: String,
+        owner_address: String,
+        resource: &CoinStoreResource,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        CoinBalance {
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            owner_address,
+            coin_type,
+            amount: resource.coin.value.clone(),
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl CoinActivity {
+    /// Converts the `DepositEvent`s and `WithdrawEvent`s into activities. The owner and coin type
+    /// of an event are those of the `CoinStore` holding its event handle, in `coin_stores`.
+    pub fn from_events(
+        events: &[EventModel],
+        coin_stores: &HashMap<String, (String, String)>,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Vec<Self> {
+        events
+            .iter()
+            .filter(|event| event.type_ == DEPOSIT_EVENT_TYPE || event.type_ == WITHDRAW_EVENT_TYPE)
+            .filter_map(|event| {
+                let (owner_address, coin_type) = match coin_stores.get(&event.key) {
+                    Some(coin_store) => coin_store.clone(),
+                    None => {
+                        aptos_logger::warn!(
+                            "No coin store for event {} at version {}",
+                            event.key,
+                            transaction_version
+                        );
+                        return None;
+                    }
+                };
+                let event_data =
+                    serde_json::from_value::<CoinEventType>(event.data.clone()).unwrap();
+                Some(CoinActivity {
+                    transaction_version: u64_to_bigdecimal(transaction_version),
+                    owner_address,
+                    coin_type,
+                    amount: event_data.amount,
+                    activity_type: event.type_.clone(),
+                    event_creation_number: u64_to_bigdecimal(get_creation_number(&event.key)),
+                    event_sequence_number: event.sequence_number.clone(),
+                    transaction_timestamp,
+                    inserted_at: chrono::Utc::now().naive_utc(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn from_gas_fee(
+        gas_payer: String,
+        gas_fee: bigdecimal::BigDecimal,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        CoinActivity {
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            owner_address: standardize_address(&gas_payer),
+            coin_type: APTOS_COIN_TYPE.to_string(),
+            amount: gas_fee,
+            activity_type: GAS_FEE_ACTIVITY_TYPE.to_string(),
+            event_creation_number: u64_to_bigdecimal(0),
+            event_sequence_number: u64_to_bigdecimal(0),
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// The account that pays the gas fee of a user transaction: the fee payer if the transaction has
+/// one, or else the sender
+pub fn get_gas_payer(txn: &APIUserTransaction) -> String {
+    match &txn.request.signature {
+        Some(TransactionSignature::FeePayerSignature(signature)) => {
+            signature.fee_payer_address.inner().to_hex_literal()
+        }
+        _ => txn.request.sender.inner().to_hex_literal(),
+    }
+}
+
+/// Reads the creation number from the first 8 (little endian) bytes of an event key
+fn get_creation_number(event_key: &str) -> u64 {
+    let key = event_key.trim_start_matches("0x");
+    let mut bytes = [0u8; 8];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&key[2 * i..2 * i + 2], 16).unwrap();
+    }
+    u64::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_handle_key() {
+        let handle: EventHandle = serde_json::from_value(serde_json::json!({
+            "counter": "1",
+            "guid": {"id": {"addr": "0xa550c18", "creation_num": "4"}}
+        }))
+        .unwrap();
+        let key = handle.key();
+        assert_eq!(key, format!("0x0400000000000000{:0>64}", "a550c18"));
+        assert_eq!(get_creation_number(&key), 4);
+    }
+
+    #[test]
+    fn test_get_type_argument() {
+        assert_eq!(
+            get_type_argument(
+                "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                COIN_STORE_TYPE_PREFIX
+            ),
+            Some(APTOS_COIN_TYPE.to_string())
+        );
+        assert_eq!(
+            get_type_argument(
+                "0x1::coin::CoinInfo<0x1::aptos_coin::AptosCoin>",
+                COIN_STORE_TYPE_PREFIX
+            ),
+            None
+        );
+    }
+}
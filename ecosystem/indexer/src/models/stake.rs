@@ -0,0 +1,238 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::extra_unused_lifetimes)]
+use crate::{
+    models::{coin::Coin, events::EventModel, write_set_changes::WriteSetChangeModel},
+    schema::{proposal_votes, proposals, stake_activities, stake_pools},
+    util::{standardize_address, u64_to_bigdecimal},
+};
+use aptos_rest_client::types;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const STAKE_POOL_TYPE: &str = "0x1::stake::StakePool";
+const STAKE_EVENT_TYPE_PREFIX: &str = "0x1::stake::";
+const CREATE_PROPOSAL_EVENT_TYPE: &str = "0x1::aptos_governance::CreateProposalEvent";
+const VOTE_EVENT_TYPE: &str = "0x1::aptos_governance::VoteEvent";
+/// The fields holding the amount of stake moved, for the stake events that move stake
+const STAKE_EVENT_AMOUNT_FIELDS: [&str; 5] = [
+    "amount_added",
+    "amount",
+    "rewards_amount",
+    "amount_unlocked",
+    "amount_withdrawn",
+];
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "stake_pools")]
+#[primary_key(transaction_version, pool_address)]
+pub struct StakePool {
+    pub transaction_version: BigDecimal,
+    pub pool_address: String,
+    pub active_stake: BigDecimal,
+    pub pending_active_stake: BigDecimal,
+    pub pending_inactive_stake: BigDecimal,
+    pub inactive_stake: BigDecimal,
+    pub locked_until_secs: BigDecimal,
+    pub operator_address: String,
+    pub voter_address: String,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "stake_activities")]
+#[primary_key(event_key, sequence_number)]
+pub struct StakeActivity {
+    pub event_key: String,
+    pub sequence_number: BigDecimal,
+    pub transaction_version: BigDecimal,
+    pub pool_address: String,
+    pub activity_type: String,
+    pub amount: Option<BigDecimal>,
+    pub data: serde_json::Value,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "proposals")]
+#[primary_key(proposal_id)]
+pub struct Proposal {
+    pub proposal_id: BigDecimal,
+    pub proposer: String,
+    pub stake_pool: String,
+    pub execution_hash: String,
+    pub proposal_metadata: serde_json::Value,
+    pub transaction_version: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "proposal_votes")]
+#[primary_key(proposal_id, stake_pool)]
+pub struct ProposalVote {
+    pub proposal_id: BigDecimal,
+    pub stake_pool: String,
+    pub voter: String,
+    pub num_votes: BigDecimal,
+    pub should_pass: bool,
+    pub transaction_version: BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StakePoolResource {
+    pub active: Coin,
+    pub inactive: Coin,
+    pub pending_active: Coin,
+    pub pending_inactive: Coin,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub locked_until_secs: BigDecimal,
+    pub operator_address: String,
+    pub delegated_voter: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateProposalEventType {
+    pub proposer: String,
+    pub stake_pool: String,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub proposal_id: BigDecimal,
+    pub execution_hash: String,
+    pub proposal_metadata: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VoteEventType {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub proposal_id: BigDecimal,
+    pub voter: String,
+    pub stake_pool: String,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    pub num_votes: BigDecimal,
+    pub should_pass: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum GovernanceEvent {
+    CreateProposalEvent(CreateProposalEventType),
+    VoteEvent(VoteEventType),
+}
+
+impl GovernanceEvent {
+    pub fn from_event(event: &EventModel) -> Option<GovernanceEvent> {
+        let data = event.data.clone();
+        match event.type_.as_str() {
+            CREATE_PROPOSAL_EVENT_TYPE => {
+                let event = serde_json::from_value::<CreateProposalEventType>(data).unwrap();
+                Some(GovernanceEvent::CreateProposalEvent(event))
+            }
+            VOTE_EVENT_TYPE => {
+                let event = serde_json::from_value::<VoteEventType>(data).unwrap();
+                Some(GovernanceEvent::VoteEvent(event))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl StakePool {
+    pub fn from_write_set_change(
+        write_set_change: &WriteSetChangeModel,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Option<Self> {
+        if write_set_change.type_ != "write_resource"
+            || write_set_change.data["type"].as_str() != Some(STAKE_POOL_TYPE)
+        {
+            return None;
+        }
+        let resource =
+            serde_json::from_value::<StakePoolResource>(write_set_change.data["data"].clone())
+                .unwrap();
+        Some(StakePool {
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            pool_address: standardize_address(&write_set_change.address),
+            active_stake: resource.active.value,
+            pending_active_stake: resource.pending_active.value,
+            pending_inactive_stake: resource.pending_inactive.value,
+            inactive_stake: resource.inactive.value,
+            locked_until_secs: resource.locked_until_secs,
+            operator_address: standardize_address(&resource.operator_address),
+            voter_address: standardize_address(&resource.delegated_voter),
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+}
+
+impl StakeActivity {
+    pub fn from_event(
+        event: &EventModel,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Option<Self> {
+        if !event.type_.starts_with(STAKE_EVENT_TYPE_PREFIX) {
+            return None;
+        }
+        let pool_address = event.data["pool_address"].as_str()?;
+        let amount = STAKE_EVENT_AMOUNT_FIELDS
+            .iter()
+            .find_map(|field| event.data[*field].as_str())
+            .map(|amount| BigDecimal::from_str(amount).unwrap());
+        Some(StakeActivity {
+            event_key: event.key.clone(),
+            sequence_number: event.sequence_number.clone(),
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            pool_address: standardize_address(pool_address),
+            activity_type: event.type_.clone(),
+            amount,
+            data: event.data.clone(),
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+}
+
+impl Proposal {
+    pub fn from_event_data(
+        event_data: CreateProposalEventType,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        Proposal {
+            proposal_id: event_data.proposal_id,
+            proposer: standardize_address(&event_data.proposer),
+            stake_pool: standardize_address(&event_data.stake_pool),
+            execution_hash: event_data.execution_hash,
+            proposal_metadata: event_data.proposal_metadata,
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            created_at: transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl ProposalVote {
+    pub fn from_event_data(
+        event_data: VoteEventType,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        ProposalVote {
+            proposal_id: event_data.proposal_id,
+            stake_pool: standardize_address(&event_data.stake_pool),
+            voter: standardize_address(&event_data.voter),
+            num_votes: event_data.num_votes,
+            should_pass: event_data.should_pass,
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
@@ -258,6 +258,17 @@ impl Transaction {
         }
     }
 
+    /// The timestamp of the block the transaction is in. Genesis has none, so it gets the epoch.
+    pub fn get_timestamp(
+        details: &Option<Either<UserTransaction, BlockMetadataTransaction>>,
+    ) -> chrono::NaiveDateTime {
+        match details {
+            Some(Either::Left(user_txn)) => user_txn.timestamp,
+            Some(Either::Right(block_metadata_txn)) => block_metadata_txn.timestamp,
+            None => chrono::NaiveDateTime::from_timestamp(0, 0),
+        }
+    }
+
     fn from_transaction_info(
         info: &TransactionInfo,
         payload: serde_json::Value,
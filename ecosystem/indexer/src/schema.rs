@@ -16,6 +16,44 @@ table! {
     }
 }
 
+table! {
+    coin_activities (transaction_version, owner_address, coin_type, activity_type, event_creation_number, event_sequence_number) {
+        transaction_version -> Numeric,
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        activity_type -> Varchar,
+        event_creation_number -> Numeric,
+        event_sequence_number -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    coin_balances (transaction_version, owner_address, coin_type) {
+        transaction_version -> Numeric,
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    coin_infos (coin_type) {
+        coin_type -> Varchar,
+        creator_address -> Varchar,
+        name -> Varchar,
+        symbol -> Varchar,
+        decimals -> Int4,
+        transaction_version_created -> Numeric,
+        created_at -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     collections (collection_id) {
         collection_id -> Varchar,
@@ -85,6 +123,62 @@ table! {
     }
 }
 
+table! {
+    proposal_votes (proposal_id, stake_pool) {
+        proposal_id -> Numeric,
+        stake_pool -> Varchar,
+        voter -> Varchar,
+        num_votes -> Numeric,
+        should_pass -> Bool,
+        transaction_version -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    proposals (proposal_id) {
+        proposal_id -> Numeric,
+        proposer -> Varchar,
+        stake_pool -> Varchar,
+        execution_hash -> Varchar,
+        proposal_metadata -> Jsonb,
+        transaction_version -> Numeric,
+        created_at -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    stake_activities (event_key, sequence_number) {
+        event_key -> Varchar,
+        sequence_number -> Numeric,
+        transaction_version -> Numeric,
+        pool_address -> Varchar,
+        activity_type -> Varchar,
+        amount -> Nullable<Numeric>,
+        data -> Jsonb,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    stake_pools (transaction_version, pool_address) {
+        transaction_version -> Numeric,
+        pool_address -> Varchar,
+        active_stake -> Numeric,
+        pending_active_stake -> Numeric,
+        pending_inactive_stake -> Numeric,
+        inactive_stake -> Numeric,
+        locked_until_secs -> Numeric,
+        operator_address -> Varchar,
+        voter_address -> Varchar,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     token_activities (event_key, sequence_number) {
         event_key -> Varchar,
@@ -181,12 +275,19 @@ table! {
 
 allow_tables_to_appear_in_same_query!(
     block_metadata_transactions,
+    coin_activities,
+    coin_balances,
+    coin_infos,
     collections,
     events,
     ledger_infos,
     metadatas,
     ownerships,
     processor_statuses,
+    proposal_votes,
+    proposals,
+    stake_activities,
+    stake_pools,
     token_activities,
     token_datas,
     token_propertys,
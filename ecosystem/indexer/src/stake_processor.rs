@@ -0,0 +1,158 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection},
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::{
+        stake::{GovernanceEvent, Proposal, ProposalVote, StakeActivity, StakePool},
+        transactions::TransactionModel,
+    },
+    schema,
+};
+use aptos_rest_client::Transaction;
+use async_trait::async_trait;
+use diesel::Connection;
+use field_count::FieldCount;
+use std::{fmt::Debug, sync::Arc};
+
+pub struct StakeTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl StakeTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for StakeTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "StakeTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_stake_pools(conn: &PgPoolConnection, stake_pools: &[StakePool]) {
+    let chunks = get_chunks(stake_pools.len(), StakePool::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::stake_pools::table)
+                .values(&stake_pools[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into stake_pools");
+    }
+}
+
+fn insert_stake_activities(conn: &PgPoolConnection, stake_activities: &[StakeActivity]) {
+    let chunks = get_chunks(stake_activities.len(), StakeActivity::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::stake_activities::table)
+                .values(&stake_activities[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into stake_activities");
+    }
+}
+
+fn insert_proposals(conn: &PgPoolConnection, proposals: &[Proposal]) {
+    let chunks = get_chunks(proposals.len(), Proposal::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::proposals::table)
+                .values(&proposals[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into proposals");
+    }
+}
+
+fn insert_proposal_votes(conn: &PgPoolConnection, proposal_votes: &[ProposalVote]) {
+    let chunks = get_chunks(proposal_votes.len(), ProposalVote::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::proposal_votes::table)
+                .values(&proposal_votes[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into proposal_votes");
+    }
+}
+
+#[async_trait]
+impl TransactionProcessor for StakeTransactionProcessor {
+    fn name(&self) -> &'static str {
+        "stake_processor"
+    }
+
+    async fn process_transaction(
+        &self,
+        transaction: Arc<Transaction>,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let version = transaction.version().unwrap_or(0);
+
+        let (_, maybe_details_model, maybe_events, maybe_write_set_changes) =
+            TransactionModel::from_transaction(&transaction);
+        let write_set_changes = maybe_write_set_changes.unwrap_or_default();
+        let events = maybe_events.unwrap_or_default();
+        let transaction_timestamp = TransactionModel::get_timestamp(&maybe_details_model);
+
+        let stake_pools = write_set_changes
+            .iter()
+            .filter_map(|write_set_change| {
+                StakePool::from_write_set_change(write_set_change, version, transaction_timestamp)
+            })
+            .collect::<Vec<_>>();
+        let stake_activities = events
+            .iter()
+            .filter_map(|event| StakeActivity::from_event(event, version, transaction_timestamp))
+            .collect::<Vec<_>>();
+        let mut proposals = vec![];
+        let mut proposal_votes = vec![];
+        for event in events.iter().filter_map(GovernanceEvent::from_event) {
+            match event {
+                GovernanceEvent::CreateProposalEvent(event_data) => proposals.push(
+                    Proposal::from_event_data(event_data, version, transaction_timestamp),
+                ),
+                GovernanceEvent::VoteEvent(event_data) => proposal_votes.push(
+                    ProposalVote::from_event_data(event_data, version, transaction_timestamp),
+                ),
+            }
+        }
+
+        let conn = self.get_conn();
+        let tx_result = conn.transaction::<(), diesel::result::Error, _>(|| {
+            insert_stake_pools(&conn, &stake_pools);
+            insert_stake_activities(&conn, &stake_activities);
+            insert_proposals(&conn, &proposals);
+            insert_proposal_votes(&conn, &proposal_votes);
+            Ok(())
+        });
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(self.name(), version)),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+}
@@ -17,3 +17,8 @@ pub fn ensure_not_negative(val: bigdecimal::BigDecimal) -> bigdecimal::BigDecima
     }
     val
 }
+
+/// Pads an address to 32 bytes, so that addresses formatted in different ways can be compared
+pub fn standardize_address(address: &str) -> String {
+    format!("0x{:0>64}", address.trim_start_matches("0x"))
+}
@@ -6,8 +6,8 @@ use aptos_api_types::{
     GenesisPayload, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
     MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveResource,
     MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
-    MultiEd25519Signature, ScriptPayload, Transaction, TransactionInfo, TransactionPayload,
-    TransactionSignature, WriteSet, WriteSetChange,
+    MultiEd25519Signature, ScriptPayload, Secp256k1EcdsaSignature, Transaction, TransactionInfo,
+    TransactionPayload, TransactionSignature, WriteSet, WriteSetChange,
 };
 use aptos_bitvec::BitVec;
 use aptos_logger::warn;
@@ -490,6 +490,17 @@ pub fn convert_ed25519_signature(sig: &Ed25519Signature) -> extractor::Ed25519Si
     }
 }
 
+// TODO: the extractor proto has no secp256k1 signature yet, so the key and signature bytes
+// are exported through the ed25519 message for now.
+pub fn convert_secp256k1_ecdsa_signature(
+    sig: &Secp256k1EcdsaSignature,
+) -> extractor::Ed25519Signature {
+    extractor::Ed25519Signature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+    }
+}
+
 pub fn convert_multi_ed25519_signature(
     sig: &MultiEd25519Signature,
 ) -> extractor::MultiEd25519Signature {
@@ -509,7 +520,9 @@ pub fn convert_account_signature(
     account_signature: &AccountSignature,
 ) -> extractor::AccountSignature {
     let r#type = match account_signature {
-        AccountSignature::Ed25519Signature(_) => extractor::account_signature::Type::Ed25519,
+        AccountSignature::Ed25519Signature(_) | AccountSignature::Secp256k1EcdsaSignature(_) => {
+            extractor::account_signature::Type::Ed25519
+        }
         AccountSignature::MultiEd25519Signature(_) => {
             extractor::account_signature::Type::MultiEd25519
         }
@@ -523,6 +536,9 @@ pub fn convert_account_signature(
                 s,
             ))
         }
+        AccountSignature::Secp256k1EcdsaSignature(s) => {
+            extractor::account_signature::Signature::Ed25519(convert_secp256k1_ecdsa_signature(s))
+        }
     };
     extractor::AccountSignature {
         r#type: r#type as i32,
@@ -538,9 +554,11 @@ pub fn convert_transaction_signature(
         Some(s) => s,
     };
     let r#type = match signature {
-        TransactionSignature::Ed25519Signature(_) => extractor::signature::Type::Ed25519,
+        TransactionSignature::Ed25519Signature(_)
+        | TransactionSignature::Secp256k1EcdsaSignature(_) => extractor::signature::Type::Ed25519,
         TransactionSignature::MultiEd25519Signature(_) => extractor::signature::Type::MultiEd25519,
         TransactionSignature::MultiAgentSignature(_) => extractor::signature::Type::MultiAgent,
+        TransactionSignature::FeePayerSignature(_) => extractor::signature::Type::FeePayer,
     };
 
     let signature = match signature {
@@ -550,6 +568,9 @@ pub fn convert_transaction_signature(
         TransactionSignature::MultiEd25519Signature(s) => {
             extractor::signature::Signature::MultiEd25519(convert_multi_ed25519_signature(s))
         }
+        TransactionSignature::Secp256k1EcdsaSignature(s) => {
+            extractor::signature::Signature::Ed25519(convert_secp256k1_ecdsa_signature(s))
+        }
         TransactionSignature::MultiAgentSignature(s) => {
             extractor::signature::Signature::MultiAgent(extractor::MultiAgentSignature {
                 sender: Some(convert_account_signature(&s.sender)),
@@ -565,6 +586,23 @@ pub fn convert_transaction_signature(
                     .collect(),
             })
         }
+        TransactionSignature::FeePayerSignature(s) => {
+            extractor::signature::Signature::FeePayer(extractor::FeePayerSignature {
+                sender: Some(convert_account_signature(&s.sender)),
+                secondary_signer_addresses: s
+                    .secondary_signer_addresses
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                secondary_signers: s
+                    .secondary_signers
+                    .iter()
+                    .map(convert_account_signature)
+                    .collect(),
+                fee_payer_address: s.fee_payer_address.to_string(),
+                fee_payer_signer: Some(convert_account_signature(&s.fee_payer_signer)),
+            })
+        }
     };
 
     Some(extractor::Signature {
@@ -0,0 +1,153 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{metrics, runtime::FirehoseStreamer};
+use aptos_api::context::Context;
+use aptos_logger::{debug, error, info};
+use aptos_protos::transaction_stream::v1::{
+    transaction_stream_server::{TransactionStream, TransactionStreamServer},
+    StreamTransactionsRequest, StreamTransactionsResponse,
+};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{sync::mpsc, time::sleep};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+/// The number of responses buffered for each client. Once the buffer is full, no more
+/// transactions are read and converted for the client until it consumes some of them.
+const RESPONSE_CHANNEL_SIZE: usize = 16;
+
+/// Serves the `TransactionStream` gRPC service on `address`, until the server fails.
+pub async fn serve(context: Arc<Context>, address: SocketAddr) -> anyhow::Result<()> {
+    info!("[fh-stream] serving transaction stream on {}", address);
+    tonic::transport::Server::builder()
+        .add_service(TransactionStreamServer::new(TransactionStreamService::new(
+            context,
+        )))
+        .serve(address)
+        .await?;
+    Ok(())
+}
+
+/// Streams the converted transactions to gRPC clients, so indexers can connect to the node
+/// directly.
+pub struct TransactionStreamService {
+    context: Arc<Context>,
+}
+
+impl TransactionStreamService {
+    pub fn new(context: Arc<Context>) -> Self {
+        Self { context }
+    }
+}
+
+#[tonic::async_trait]
+impl TransactionStream for TransactionStreamService {
+    type StreamTransactionsStream = ReceiverStream<Result<StreamTransactionsResponse, Status>>;
+
+    async fn stream_transactions(
+        &self,
+        request: Request<StreamTransactionsRequest>,
+    ) -> Result<Response<Self::StreamTransactionsStream>, Status> {
+        let StreamTransactionsRequest {
+            starting_version,
+            transactions_count,
+        } = request.into_inner();
+
+        let first_viable_version = self
+            .context
+            .db
+            .get_first_viable_txn_version()
+            .map_err(|err| Status::internal(err.to_string()))?;
+        if starting_version < first_viable_version {
+            return Err(Status::out_of_range(format!(
+                "Transactions before version {} have been pruned",
+                first_viable_version
+            )));
+        }
+
+        let (sender, receiver) = mpsc::channel(RESPONSE_CHANNEL_SIZE);
+        tokio::spawn(stream_transactions(
+            self.context.clone(),
+            starting_version,
+            transactions_count,
+            sender,
+        ));
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/// Converts the transactions from `starting_version` block by block, and sends them to the
+/// client until `transactions_count` transactions are sent (or forever if it is 0), or until the
+/// client disconnects, or until the conversion fails, in which case the error is sent to the
+/// client. Sending waits for the client to consume the previous responses, so the
+/// conversion never runs ahead of the client by more than `RESPONSE_CHANNEL_SIZE` blocks.
+async fn stream_transactions(
+    context: Arc<Context>,
+    starting_version: u64,
+    transactions_count: u64,
+    sender: mpsc::Sender<Result<StreamTransactionsResponse, Status>>,
+) {
+    let chain_id = u32::from(context.chain_id().id());
+
+    // The starting version may not be committed yet.
+    let mut streamer = loop {
+        match FirehoseStreamer::try_new(context.clone(), starting_version, None) {
+            Ok(streamer) => break streamer,
+            Err(_) if sender.is_closed() => return,
+            Err(err) => {
+                debug!(
+                    "[fh-stream] waiting for starting version {}: {}",
+                    starting_version, err
+                );
+                sleep(Duration::from_millis(300)).await;
+            }
+        }
+    };
+
+    let end_version = match transactions_count {
+        0 => u64::MAX,
+        count => starting_version.saturating_add(count),
+    };
+    let mut next_version = starting_version;
+    while next_version < end_version {
+        let transactions = match streamer.convert_next_block().await {
+            Ok(transactions) => transactions,
+            Err(err) => {
+                error!(
+                    "[fh-stream] failed to convert transactions at version {}: {}",
+                    next_version, err
+                );
+                let _ = sender.send(Err(Status::internal(err.to_string()))).await;
+                return;
+            }
+        };
+        // The first block may start before the starting version, and the last one may end after
+        // the end version.
+        let transactions: Vec<_> = transactions
+            .into_iter()
+            .filter(|txn| txn.version >= next_version && txn.version < end_version)
+            .collect();
+        let num_transactions = match transactions.last() {
+            Some(last) => {
+                next_version = last.version + 1;
+                transactions.len()
+            }
+            None if sender.is_closed() => return,
+            None => continue,
+        };
+
+        let response = StreamTransactionsResponse {
+            chain_id,
+            transactions,
+        };
+        if sender.send(Ok(response)).await.is_err() {
+            debug!(
+                "[fh-stream] client disconnected at version {}",
+                next_version
+            );
+            return;
+        }
+        metrics::TRANSACTIONS_STREAMED.inc_by(num_transactions as u64);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod convert;
+pub mod grpc;
 pub mod metrics;
 pub mod runtime;
 
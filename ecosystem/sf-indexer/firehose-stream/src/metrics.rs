@@ -19,3 +19,11 @@ pub static BLOCKS_SENT: Lazy<IntCounter> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static TRANSACTIONS_STREAMED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_fh_stream_transactions_streamed_count",
+        "Transactions converted and sent to the clients of the gRPC transaction stream",
+    )
+    .unwrap()
+});
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics;
+use crate::{grpc, metrics};
 use aptos_protos::extractor::v1 as extractor;
 
 use crate::convert::convert_transaction;
@@ -50,6 +50,14 @@ pub fn bootstrap(
             .map(|v| v.parse::<u64>().unwrap_or(config_starting_version))
             .unwrap_or(config_starting_version);
 
+        // Serve the transactions over gRPC if configured, rather than printing them to stdout
+        if let Some(grpc_address) = node_config.firehose_stream.grpc_address {
+            if let Err(err) = grpc::serve(context_arc, grpc_address).await {
+                error!("[fh-stream] transaction stream server failed: {}", err);
+            }
+            return;
+        }
+
         let mut streamer = FirehoseStreamer::new(context_arc, starting_version, Some(mp_sender));
         streamer.start().await;
     });
@@ -71,16 +79,24 @@ impl FirehoseStreamer {
         starting_version: u64,
         mp_client_sender: Option<MempoolClientSender>,
     ) -> Self {
-        let resolver = Arc::new(context.move_resolver().unwrap());
-        let (_block_start_version, _block_last_versionn, block_event) = context
-            .db
-            .get_block_info_by_version(starting_version)
-            .unwrap_or_else(|_| {
-                panic!(
-                    "Could not get block_info for starting version {}",
-                    starting_version,
-                )
-            });
+        Self::try_new(context, starting_version, mp_client_sender).unwrap_or_else(|_| {
+            panic!(
+                "Could not get block_info for starting version {}",
+                starting_version,
+            )
+        })
+    }
+
+    /// Same as `new`, but fails instead of panicking when the block of `starting_version` can't
+    /// be found, e.g. because `starting_version` is not committed yet.
+    pub fn try_new(
+        context: Arc<Context>,
+        starting_version: u64,
+        mp_client_sender: Option<MempoolClientSender>,
+    ) -> anyhow::Result<Self> {
+        let resolver = Arc::new(context.move_resolver()?);
+        let (_block_start_version, _block_last_versionn, block_event) =
+            context.db.get_block_info_by_version(starting_version)?;
 
         // fake mempool client/sender, if we need to, so we can use the same code for both api and fh-streamer
         let mp_client_sender = mp_client_sender.unwrap_or_else(|| {
@@ -88,13 +104,13 @@ impl FirehoseStreamer {
             mp_client_sender
         });
 
-        Self {
+        Ok(Self {
             context,
             resolver,
             current_block_height: block_event.height(),
             current_epoch: block_event.epoch(),
             mp_sender: mp_client_sender,
-        }
+        })
     }
 
     pub async fn start(&mut self) {
@@ -105,11 +121,35 @@ impl FirehoseStreamer {
             self.context.chain_id().id(),
         );
         loop {
-            self.convert_next_block().await;
+            let block_height = self.current_block_height;
+            let transactions = match self.convert_next_block().await {
+                Ok(transactions) => transactions,
+                Err(err) => {
+                    error!(
+                        "[fh-stream] failed to convert block {}: {}",
+                        block_height, err
+                    );
+                    sleep(Duration::from_millis(300)).await;
+                    continue;
+                }
+            };
+            if transactions.is_empty() {
+                continue;
+            }
+            println!("\nFIRE BLOCK_START {}", block_height);
+            for transaction in &transactions {
+                self.print_transaction(transaction);
+            }
+            println!("\nFIRE BLOCK_END {}", block_height);
+            metrics::BLOCKS_SENT.inc();
         }
     }
 
-    pub async fn convert_next_block(&mut self) -> Vec<TransactionPB> {
+    /// Converts the transactions of the next block, and moves on to the following block.
+    /// Returns no transactions if the block is not available or invalid yet, in which case the
+    /// same block is converted again on the next call. Fails if the latest ledger info cannot be
+    /// read.
+    pub async fn convert_next_block(&mut self) -> anyhow::Result<Vec<TransactionPB>> {
         let mut result: Vec<TransactionPB> = vec![];
 
         let (block_start_version, block_last_version, _) = match self
@@ -125,11 +165,11 @@ impl FirehoseStreamer {
                     self.current_block_height, err
                 );
                 sleep(Duration::from_millis(300)).await;
-                return vec![];
+                return Ok(vec![]);
             }
         };
 
-        let ledger_info = self.context.get_latest_ledger_info_wrapped().unwrap();
+        let ledger_info = self.context.get_latest_ledger_info_wrapped()?;
         let block_timestamp = self
             .context
             .db
@@ -143,7 +183,6 @@ impl FirehoseStreamer {
         // We are validating the block as we convert and print each transactions. The rules are as follows:
         // 1. first (and only first) transaction is a block metadata or genesis 2. versions are monotonically increasing 3. start and end versions match block boundaries
         // Retry if the block is not valid. Panic if there's anything wrong with encoding a transaction.
-        println!("\nFIRE BLOCK_START {}", self.current_block_height);
 
         let transactions = match self.context.get_transactions(
             block_start_version,
@@ -156,14 +195,14 @@ impl FirehoseStreamer {
             Err(err) => {
                 error!("[fh-stream] failed to get transactions: {}", err);
                 sleep(Duration::from_millis(100)).await;
-                return vec![];
+                return Ok(vec![]);
             }
         };
 
         if transactions.is_empty() {
             debug!("[fh-stream] no transactions to send");
             sleep(Duration::from_millis(100)).await;
-            return vec![];
+            return Ok(vec![]);
         }
         debug!(
             "[fh-stream] got {} transactions from {} to {} [version on last actual transaction {}]",
@@ -192,7 +231,7 @@ impl FirehoseStreamer {
                             self.current_block_height
                         );
                 sleep(Duration::from_millis(500)).await;
-                return vec![];
+                return Ok(vec![]);
             }
             if curr_version != txn_version {
                 error!(
@@ -200,11 +239,10 @@ impl FirehoseStreamer {
                     self.current_block_height, curr_version,
                 );
                 sleep(Duration::from_millis(500)).await;
-                return vec![];
+                return Ok(vec![]);
             }
             let txn_proto =
                 convert_transaction(&txn, self.current_block_height, self.current_epoch);
-            self.print_transaction(&txn_proto);
             result.push(txn_proto);
             curr_version += 1;
         }
@@ -217,13 +255,11 @@ impl FirehoseStreamer {
                 curr_version - 1,
             );
             sleep(Duration::from_millis(500)).await;
-            return vec![];
+            return Ok(vec![]);
         }
 
-        println!("\nFIRE BLOCK_END {}", self.current_block_height);
-        metrics::BLOCKS_SENT.inc();
         self.current_block_height += 1;
-        result
+        Ok(result)
     }
 
     /// First, and only first, transaction in a block has to be bmt or genesis
@@ -0,0 +1,113 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{grpc, tests::new_test_context};
+
+use aptos_api::context::Context;
+use aptos_api_test_context::current_function_name;
+use aptos_config::utils::get_available_port;
+use aptos_protos::transaction_stream::v1::{
+    transaction_stream_client::TransactionStreamClient, StreamTransactionsRequest,
+    StreamTransactionsResponse,
+};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+use tonic::{transport::Channel, Streaming};
+
+async fn start_server(context: Arc<Context>) -> TransactionStreamClient<Channel> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, get_available_port()));
+    tokio::spawn(grpc::serve(context, address));
+
+    let endpoint = format!("http://{}", address);
+    for _ in 0..50 {
+        if let Ok(client) = TransactionStreamClient::connect(endpoint.clone()).await {
+            return client;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!(
+        "Could not connect to the transaction stream at {}",
+        endpoint
+    );
+}
+
+async fn stream_transactions(
+    client: &mut TransactionStreamClient<Channel>,
+    starting_version: u64,
+    transactions_count: u64,
+) -> Streaming<StreamTransactionsResponse> {
+    client
+        .stream_transactions(StreamTransactionsRequest {
+            starting_version,
+            transactions_count,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+fn versions(response: &StreamTransactionsResponse) -> Vec<u64> {
+    response
+        .transactions
+        .iter()
+        .map(|txn| txn.version)
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_ends_after_transactions_count() {
+    let mut test_context = new_test_context(current_function_name!());
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account);
+    test_context.commit_block(&vec![txn.clone()]).await;
+    test_context.commit_block(&[]).await;
+
+    let chain_id = u32::from(test_context.context.chain_id().id());
+    let mut client = start_server(Arc::new(test_context.context.clone())).await;
+    let mut stream = stream_transactions(&mut client, 0, 5).await;
+
+    let mut streamed_versions = vec![];
+    while let Some(response) = stream.message().await.unwrap() {
+        assert_eq!(response.chain_id, chain_id);
+        streamed_versions.extend(versions(&response));
+    }
+    // Version 5 is part of the last block, but is past the requested count.
+    assert_eq!(streamed_versions, vec![0, 1, 2, 3, 4]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_resumes_in_the_middle_of_a_block() {
+    let mut test_context = new_test_context(current_function_name!());
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account);
+    test_context.commit_block(&vec![txn.clone()]).await;
+
+    let mut client = start_server(Arc::new(test_context.context.clone())).await;
+    let mut stream = stream_transactions(&mut client, 2, 0).await;
+
+    // Version 1 is the block metadata of the same block, and must be skipped.
+    let response = stream.message().await.unwrap().unwrap();
+    assert_eq!(versions(&response), vec![2, 3]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_waits_for_new_transactions() {
+    let mut test_context = new_test_context(current_function_name!());
+
+    let mut client = start_server(Arc::new(test_context.context.clone())).await;
+    let mut stream = stream_transactions(&mut client, 1, 0).await;
+
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account);
+    test_context.commit_block(&vec![txn.clone()]).await;
+
+    let response = tokio::time::timeout(Duration::from_secs(10), stream.message())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(versions(&response), vec![1, 2, 3]);
+}
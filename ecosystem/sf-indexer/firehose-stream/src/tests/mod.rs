@@ -1,6 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod grpc_tests;
 mod proto_converter_tests;
 
 pub use aptos_api_test_context::{new_test_context as super_new_test_context, TestContext};
@@ -28,7 +28,7 @@ async fn test_genesis_works() {
 
     let context = Arc::new(test_context.context);
     let mut streamer = FirehoseStreamer::new(context, 0, None);
-    let converted = streamer.convert_next_block().await;
+    let converted = streamer.convert_next_block().await.unwrap();
 
     // position 0 should be genesis
     let txn = converted.first().unwrap().clone();
@@ -56,13 +56,13 @@ async fn test_block_transactions_work() {
     let mut streamer = FirehoseStreamer::new(context, 0, None);
 
     // emulating real stream, getting first block
-    let block_0 = streamer.convert_next_block().await;
+    let block_0 = streamer.convert_next_block().await.unwrap();
     let txn = block_0.first().unwrap().clone();
     assert_eq!(txn.version, 0);
     assert_eq!(txn.r#type(), TransactionType::Genesis);
 
     // getting second block
-    let block_1 = streamer.convert_next_block().await;
+    let block_1 = streamer.convert_next_block().await.unwrap();
     // block metadata expected
     let txn = block_1[0].clone();
     assert_eq!(txn.version, 1);
@@ -270,9 +270,9 @@ async fn build_test_module(account: AccountAddress) -> Vec<u8> {
 
 async fn fetch_all_stream(mut streamer: FirehoseStreamer) -> Vec<TransactionPB> {
     // Overfetching should work
-    let mut res = streamer.convert_next_block().await;
+    let mut res = streamer.convert_next_block().await.unwrap();
     for _ in 0..20 {
-        res.append(&mut streamer.convert_next_block().await);
+        res.append(&mut streamer.convert_next_block().await.unwrap());
     }
     res
 }
@@ -3,15 +3,9 @@
 
 /// This module provides various indexes used by Mempool.
 use crate::core_mempool::transaction::{MempoolTransaction, SequenceInfo, TimelineState};
-use crate::{
-    counters,
-    logging::{LogEntry, LogSchema},
-};
-use aptos_logger::prelude::*;
 use aptos_types::account_address::AccountAddress;
-use rand::seq::SliceRandom;
 use std::{
-    cmp::Ordering,
+    cmp::{Ordering, Reverse},
     collections::{btree_set::Iter, BTreeMap, BTreeSet, HashMap},
     iter::Rev,
     ops::Bound,
@@ -22,7 +16,9 @@ pub type AccountTransactions = BTreeMap<u64, MempoolTransaction>;
 
 /// PriorityIndex represents the main Priority Queue in Mempool.
 /// It's used to form the transaction block for Consensus.
-/// Transactions are ordered by gas price. Second level ordering is done by expiration time.
+/// Transactions are ordered by ranking score, then by gas price, so that a higher gas price
+/// always buys priority among equally ranked transactions. Next level ordering is done by
+/// expiration time.
 ///
 /// We don't store the full content of transactions in the index.
 /// Instead we use `OrderedQueueKey` - logical reference to the transaction in the main store.
@@ -54,6 +50,7 @@ impl PriorityIndex {
     fn make_key(&self, txn: &MempoolTransaction) -> OrderedQueueKey {
         OrderedQueueKey {
             gas_ranking_score: txn.ranking_score,
+            gas_unit_price: txn.get_gas_price(),
             expiration_time: txn.expiration_time,
             address: txn.get_sender(),
             sequence_number: txn.sequence_info,
@@ -72,6 +69,7 @@ impl PriorityIndex {
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct OrderedQueueKey {
     pub gas_ranking_score: u64,
+    pub gas_unit_price: u64,
     pub expiration_time: Duration,
     pub address: AccountAddress,
     pub sequence_number: SequenceInfo,
@@ -89,6 +87,10 @@ impl Ord for OrderedQueueKey {
             Ordering::Equal => {}
             ordering => return ordering,
         }
+        match self.gas_unit_price.cmp(&other.gas_unit_price) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.expiration_time.cmp(&other.expiration_time).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -148,6 +150,10 @@ impl TTLIndex {
         ttl_transactions
     }
 
+    pub(crate) fn iter(&self) -> Iter<TTLOrderingKey> {
+        self.data.iter()
+    }
+
     fn make_key(&self, txn: &MempoolTransaction) -> TTLOrderingKey {
         TTLOrderingKey {
             expiration_time: (self.get_expiration_time)(txn),
@@ -258,93 +264,79 @@ impl TimelineIndex {
 /// ParkingLotIndex keeps track of "not_ready" transactions, e.g., transactions that
 /// can't be included in the next block because their sequence number is too high.
 /// We keep a separate index to be able to efficiently evict them when Mempool is full.
+/// Transactions are ordered by gas price, so the cheapest ones are evicted first.
 pub struct ParkingLotIndex {
     // DS invariants:
     // 1. for each entry (account, txns) in `data`, `txns` is never empty
-    // 2. for all accounts, data.get(account_indices.get(`account`)) == (account, sequence numbers of account's txns)
-    data: Vec<(AccountAddress, BTreeSet<u64>)>,
-    account_indices: HashMap<AccountAddress, usize>,
-    size: usize,
+    // 2. `ordered_by_gas_price` has exactly one key for each (account, sequence number) in `data`
+    data: HashMap<AccountAddress, BTreeMap<u64, u64>>,
+    ordered_by_gas_price: BTreeSet<ParkingLotKey>,
 }
 
+/// Eviction order of parked transactions: lowest gas price first, then, within an account,
+/// highest sequence number first, as those are the furthest from being ready.
+type ParkingLotKey = (u64, AccountAddress, Reverse<u64>);
+
 impl ParkingLotIndex {
     pub(crate) fn new() -> Self {
         Self {
-            data: vec![],
-            account_indices: HashMap::new(),
-            size: 0,
+            data: HashMap::new(),
+            ordered_by_gas_price: BTreeSet::new(),
         }
     }
 
     pub(crate) fn insert(&mut self, txn: &MempoolTransaction) {
-        let sender = &txn.txn.sender();
-        let sequence_number = txn.txn.sequence_number();
-        let is_new_entry = match self.account_indices.get(sender) {
-            Some(index) => {
-                if let Some((_account, seq_nums)) = self.data.get_mut(*index) {
-                    seq_nums.insert(sequence_number)
-                } else {
-                    counters::CORE_MEMPOOL_INVARIANT_VIOLATION_COUNT.inc();
-                    error!(
-                        LogSchema::new(LogEntry::InvariantViolated),
-                        "Parking lot invariant violated: for account {}, account index exists but missing entry in data",
-                        sender
-                    );
-                    return;
-                }
-            }
-            None => {
-                let seq_nums = [sequence_number].iter().cloned().collect::<BTreeSet<_>>();
-                self.data.push((*sender, seq_nums));
-                self.account_indices.insert(*sender, self.data.len() - 1);
-                true
-            }
-        };
-        if is_new_entry {
-            self.size += 1;
+        let sender = txn.get_sender();
+        let sequence_number = txn.sequence_info.transaction_sequence_number;
+        let gas_price = txn.get_gas_price();
+        let previous_gas_price = self
+            .data
+            .entry(sender)
+            .or_insert_with(BTreeMap::new)
+            .insert(sequence_number, gas_price);
+        if let Some(previous_gas_price) = previous_gas_price {
+            self.ordered_by_gas_price.remove(&(
+                previous_gas_price,
+                sender,
+                Reverse(sequence_number),
+            ));
         }
+        self.ordered_by_gas_price
+            .insert((gas_price, sender, Reverse(sequence_number)));
     }
 
     pub(crate) fn remove(&mut self, txn: &MempoolTransaction) {
-        let sender = &txn.txn.sender();
-        if let Some(index) = self.account_indices.get(sender).cloned() {
-            if let Some((_account, txns)) = self.data.get_mut(index) {
-                if txns.remove(&txn.txn.sequence_number()) {
-                    self.size -= 1;
-                }
-
-                // maintain DS invariant
-                if txns.is_empty() {
-                    // remove account with no more txns
-                    self.data.swap_remove(index);
-                    self.account_indices.remove(sender);
-
-                    // update DS for account that was swapped in `swap_remove`
-                    if let Some((swapped_account, _)) = self.data.get(index) {
-                        self.account_indices.insert(*swapped_account, index);
-                    }
-                }
+        let sender = txn.get_sender();
+        let sequence_number = txn.sequence_info.transaction_sequence_number;
+        if let Some(txns) = self.data.get_mut(&sender) {
+            if let Some(gas_price) = txns.remove(&sequence_number) {
+                self.ordered_by_gas_price
+                    .remove(&(gas_price, sender, Reverse(sequence_number)));
+            }
+
+            // maintain DS invariant
+            if txns.is_empty() {
+                self.data.remove(&sender);
             }
         }
     }
 
     pub(crate) fn contains(&self, account: &AccountAddress, seq_num: &u64) -> bool {
-        self.account_indices
+        self.data
             .get(account)
-            .and_then(|idx| self.data.get(*idx))
-            .map_or(false, |(_account, txns)| txns.contains(seq_num))
+            .map_or(false, |txns| txns.contains_key(seq_num))
     }
 
-    /// Returns a random "non-ready" transaction (with highest sequence number for that account).
-    pub(crate) fn get_poppable(&self) -> Option<TxnPointer> {
-        let mut rng = rand::thread_rng();
-        self.data
-            .choose(&mut rng)
-            .and_then(|(sender, txns)| txns.iter().rev().next().map(|seq_num| (*sender, *seq_num)))
+    /// Returns the cheapest "non-ready" transaction, along with its gas price.
+    pub(crate) fn get_poppable(&self) -> Option<(TxnPointer, u64)> {
+        self.ordered_by_gas_price
+            .iter()
+            .next()
+            .map(|(gas_price, sender, Reverse(seq_num))| ((*sender, *seq_num), *gas_price))
     }
 
     pub(crate) fn size(&self) -> usize {
-        self.size
+        self.ordered_by_gas_price.len()
     }
 }
 
@@ -123,6 +123,11 @@ impl Mempool {
         self.transactions.get_by_hash(hash)
     }
 
+    /// Returns up to `limit` transactions currently in Mempool, both ready and parked.
+    pub(crate) fn get_pending_transactions(&self, limit: usize) -> Vec<SignedTransaction> {
+        self.transactions.get_transactions(limit)
+    }
+
     /// Used to add a transaction to the Mempool.
     /// Performs basic validation: checks account's sequence number.
     pub(crate) fn add_txn(
@@ -23,6 +23,7 @@ use aptos_types::{
     transaction::SignedTransaction,
 };
 use std::{
+    cmp::max,
     collections::HashMap,
     ops::Bound,
     time::{Duration, SystemTime},
@@ -56,6 +57,7 @@ pub struct TransactionStore {
     capacity: usize,
     capacity_per_user: usize,
     max_batch_bytes: u64,
+    replacement_gas_price_bump_percentage: u64,
 }
 
 impl TransactionStore {
@@ -78,6 +80,7 @@ impl TransactionStore {
             capacity: config.capacity,
             capacity_per_user: config.capacity_per_user,
             max_batch_bytes: config.shared_mempool_max_batch_bytes,
+            replacement_gas_price_bump_percentage: config.replacement_gas_price_bump_percentage,
         }
     }
 
@@ -104,6 +107,18 @@ impl TransactionStore {
         }
     }
 
+    /// Fetch up to `limit` transactions currently in the store, in the order they entered it.
+    /// Includes transactions sitting in the parking lot.
+    pub(crate) fn get_transactions(&self, limit: usize) -> Vec<SignedTransaction> {
+        // The system TTL index holds every transaction, ordered by insertion time, so only the
+        // returned transactions are visited.
+        self.system_ttl_index
+            .iter()
+            .take(limit)
+            .filter_map(|key| self.get(&key.address, key.sequence_number))
+            .collect()
+    }
+
     /// Fetch mempool transaction by account address + sequence_number.
     pub(crate) fn get_mempool_txn(
         &self,
@@ -123,6 +138,8 @@ impl TransactionStore {
 
         // If the transaction is already in Mempool, we only allow the user to
         // increase the gas unit price to speed up a transaction, but not the max gas.
+        // The increase has to be at least `replacement_gas_price_bump_percentage`, so
+        // that replacements can't be used to cheaply churn mempool.
         //
         // Transactions with all the same inputs (but possibly signed differently) are idempotent
         // since the raw transaction is the same
@@ -146,8 +163,26 @@ impl TransactionStore {
                         "Transaction already in mempool with a different max gas amount"
                             .to_string(),
                     );
-                } else if current_version.txn.gas_unit_price() < txn.get_gas_price() {
-                    // Update txn if gas unit price is a larger value than before
+                } else if current_version.get_gas_price() < txn.get_gas_price() {
+                    let min_gas_price = min_replacement_gas_price(
+                        current_version.get_gas_price(),
+                        self.replacement_gas_price_bump_percentage,
+                    );
+                    if txn.get_gas_price() < min_gas_price {
+                        counters::CORE_MEMPOOL_TXN_REPLACEMENTS
+                            .with_label_values(&[counters::REPLACEMENT_LOW_GAS_PRICE_BUMP_LABEL])
+                            .inc();
+                        return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
+                            format!(
+                                "Transaction already in mempool, replacing it requires a gas price of at least {}",
+                                min_gas_price
+                            ),
+                        );
+                    }
+                    // Update txn if gas unit price is sufficiently larger than before
+                    counters::CORE_MEMPOOL_TXN_REPLACEMENTS
+                        .with_label_values(&[counters::REPLACEMENT_ACCEPTED_LABEL])
+                        .inc();
                     if let Some(txn) = txns.remove(&sequence_number.transaction_sequence_number) {
                         self.index_remove(&txn);
                     };
@@ -241,30 +276,42 @@ impl TransactionStore {
     }
 
     /// Checks if Mempool is full.
-    /// If it's full, tries to free some space by evicting transactions from the ParkingLot.
-    /// We only evict on attempt to insert a transaction that would be ready for broadcast upon insertion.
+    /// If it's full, tries to free some space by evicting the cheapest transactions from the ParkingLot.
+    /// A parked transaction is only evicted to insert a transaction that would be ready for broadcast
+    /// upon insertion, or one that pays a strictly higher gas price than the evicted transaction.
     fn check_is_full_after_eviction(
         &mut self,
         txn: &MempoolTransaction,
         curr_sequence_number: u64,
     ) -> bool {
-        if self.system_ttl_index.size() >= self.capacity
-            && self.check_txn_ready(txn, curr_sequence_number)
-        {
-            // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
-            if let Some((address, sequence_number)) = self.parking_lot_index.get_poppable() {
-                if let Some(txn) = self
+        if self.system_ttl_index.size() >= self.capacity {
+            let is_ready = self.check_txn_ready(txn, curr_sequence_number);
+            while self.system_ttl_index.size() >= self.capacity {
+                // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
+                let (address, sequence_number) = match self.parking_lot_index.get_poppable() {
+                    Some((txn_pointer, gas_price))
+                        if is_ready || gas_price < txn.get_gas_price() =>
+                    {
+                        txn_pointer
+                    }
+                    _ => break,
+                };
+                match self
                     .transactions
                     .get_mut(&address)
                     .and_then(|txns| txns.remove(&sequence_number))
                 {
-                    debug!(
-                        LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
-                            txn.get_sender(),
-                            txn.sequence_info.transaction_sequence_number
-                        ))
-                    );
-                    self.index_remove(&txn);
+                    Some(txn) => {
+                        debug!(LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(
+                            TxnsLog::new_txn(
+                                txn.get_sender(),
+                                txn.sequence_info.transaction_sequence_number
+                            )
+                        ));
+                        counters::CORE_MEMPOOL_EVICTED_TXNS.inc();
+                        self.index_remove(&txn);
+                    }
+                    None => break,
                 }
             }
         }
@@ -599,3 +646,10 @@ impl TransactionStore {
         self.parking_lot_index.size()
     }
 }
+
+/// Minimum gas price a transaction needs to replace one paying `gas_price`: at least one more,
+/// and at least `bump_percentage` percent more, rounded up.
+fn min_replacement_gas_price(gas_price: u64, bump_percentage: u64) -> u64 {
+    let bump = gas_price.saturating_mul(bump_percentage).saturating_add(99) / 100;
+    gas_price.saturating_add(max(bump, 1))
+}
@@ -28,6 +28,10 @@ pub const COMMIT_REJECTED_LABEL: &str = "commit_rejected";
 pub const GC_SYSTEM_TTL_LABEL: &str = "system_ttl";
 pub const GC_CLIENT_EXP_LABEL: &str = "client_expiration";
 
+// Core mempool txn replacement result labels
+pub const REPLACEMENT_ACCEPTED_LABEL: &str = "accepted";
+pub const REPLACEMENT_LOW_GAS_PRICE_BUMP_LABEL: &str = "low_gas_price_bump";
+
 // Core mempool GC txn status label
 pub const GC_ACTIVE_TXN_LABEL: &str = "active";
 pub const GC_PARKED_TXN_LABEL: &str = "parked";
@@ -52,6 +56,7 @@ pub const SUCCESS_LABEL: &str = "success";
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
 pub const CLIENT_EVENT_GET_TXN_LABEL: &str = "client_event_get_txn";
+pub const CLIENT_EVENT_GET_PENDING_TXNS_LABEL: &str = "client_event_get_pending_txns";
 pub const RECONFIG_EVENT_LABEL: &str = "reconfig";
 pub const PEER_BROADCAST_EVENT_LABEL: &str = "peer_broadcast";
 
@@ -109,6 +114,25 @@ pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of parked txns evicted to make room for better paying or ready txns
+pub static CORE_MEMPOOL_EVICTED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_core_mempool_evicted_txns_count",
+        "Number of parked txns evicted from a full core mempool"
+    )
+    .unwrap()
+});
+
+/// Counter tracking attempts to replace a txn in core mempool with a higher gas price
+pub static CORE_MEMPOOL_TXN_REPLACEMENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_txn_replacement_count",
+        "Number of attempts to replace a txn in core mempool with a higher gas price",
+        &["result"]
+    )
+    .unwrap()
+});
+
 /// Counter tracking latency of txns reaching various stages in committing
 /// (e.g. time from txn entering core mempool to being pulled in consensus block)
 pub static CORE_MEMPOOL_TXN_COMMIT_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
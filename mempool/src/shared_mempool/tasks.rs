@@ -151,6 +151,28 @@ pub(crate) async fn process_client_get_transaction<V>(
     }
 }
 
+/// Processes get pending transactions request by client.
+pub(crate) async fn process_client_get_pending_transactions<V>(
+    smp: SharedMempool<V>,
+    limit: usize,
+    callback: oneshot::Sender<Vec<SignedTransaction>>,
+    timer: HistogramTimer,
+) where
+    V: TransactionValidation,
+{
+    timer.stop_and_record();
+    let _timer = counters::process_get_txn_latency_timer_client();
+    let txns = smp.mempool.lock().get_pending_transactions(limit);
+
+    if callback.send(txns).is_err() {
+        error!(LogSchema::event_log(
+            LogEntry::GetPendingTransactions,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<V>(
     smp: SharedMempool<V>,
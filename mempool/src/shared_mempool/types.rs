@@ -207,6 +207,8 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    /// Fetch up to the given number of transactions currently in Mempool
+    GetPendingTransactions(usize, oneshot::Sender<Vec<SignedTransaction>>),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -5,7 +5,7 @@ use crate::{
     core_mempool::{CoreMempool, TimelineState, TtlCache},
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, exist_in_metrics_cache, setup_mempool,
-        TestTransaction,
+        ConsensusMock, TestTransaction,
     },
 };
 use aptos_config::config::NodeConfig;
@@ -569,6 +569,71 @@ fn test_parking_lot_evict_only_for_ready_txn_insertion() {
     }
 }
 
+#[test]
+fn test_parking_lot_evict_lowest_gas_price_first() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 3;
+    let mut pool = CoreMempool::new(&config);
+    // Fill Mempool with two non-ready txns and one ready txn.
+    let cheap_parked_txn = TestTransaction::new(1, 5, 1).make_signed_transaction();
+    let parked_txn = TestTransaction::new(2, 5, 3).make_signed_transaction();
+    add_signed_txn(&mut pool, cheap_parked_txn.clone()).unwrap();
+    add_signed_txn(&mut pool, parked_txn.clone()).unwrap();
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    assert_eq!(pool.get_parking_lot_size(), 2);
+
+    // A non-ready txn can't evict a parked txn with the same gas price.
+    assert!(add_txn(&mut pool, TestTransaction::new(3, 5, 1)).is_err());
+
+    // But it evicts the cheapest parked txn if it pays more.
+    add_txn(&mut pool, TestTransaction::new(3, 5, 2)).unwrap();
+    assert!(pool
+        .get_by_hash(cheap_parked_txn.committed_hash())
+        .is_none());
+    assert!(pool.get_by_hash(parked_txn.committed_hash()).is_some());
+    assert_eq!(pool.get_parking_lot_size(), 2);
+    assert!(add_txn(&mut pool, TestTransaction::new(3, 6, 2)).is_err());
+
+    // A ready txn evicts the cheapest parked txn regardless of its own gas price.
+    add_txn(&mut pool, TestTransaction::new(0, 1, 1)).unwrap();
+    assert_eq!(pool.get_parking_lot_size(), 1);
+    assert!(pool.get_by_hash(parked_txn.committed_hash()).is_some());
+}
+
+#[test]
+fn test_replacement_requires_gas_price_bump() {
+    let mut config = NodeConfig::random();
+    config.mempool.replacement_gas_price_bump_percentage = 10;
+    let mut pool = CoreMempool::new(&config);
+    let mut consensus = ConsensusMock::new();
+    add_txn(&mut pool, TestTransaction::new(0, 0, 100)).unwrap();
+
+    // A bump below 10% is rejected, and the original txn is kept.
+    assert!(add_txn(&mut pool, TestTransaction::new(0, 0, 109)).is_err());
+
+    // A bump of at least 10% replaces the txn.
+    let replacement = TestTransaction::new(0, 0, 110).make_signed_transaction();
+    add_signed_txn(&mut pool, replacement.clone()).unwrap();
+    assert_eq!(consensus.get_block(&mut pool, 10, 10240), vec![replacement]);
+}
+
+#[test]
+fn test_gas_price_breaks_ranking_score_ties() {
+    let (mut pool, mut consensus) = setup_mempool();
+    let cheap_txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let expensive_txn = TestTransaction::new(1, 0, 2).make_signed_transaction();
+    for txn in [&cheap_txn, &expensive_txn] {
+        pool.add_txn(
+            txn.clone(),
+            0,
+            AccountSequenceInfo::Sequential(0),
+            TimelineState::NotReady,
+        );
+    }
+    assert_eq!(consensus.get_block(&mut pool, 1, 1024), vec![expensive_txn]);
+    assert_eq!(consensus.get_block(&mut pool, 1, 1024), vec![cheap_txn]);
+}
+
 #[test]
 fn test_gc_ready_transaction() {
     let mut pool = setup_mempool().0;
@@ -697,6 +762,27 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
     assert_eq!(txn_by_new_hash, Some(new_txn));
 }
 
+#[test]
+fn test_get_pending_transactions() {
+    let mut pool = setup_mempool().0;
+    // the last transaction has a sequence number gap and ends up in the parking lot
+    let mut expected = vec![];
+    for txn in [
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 1),
+        TestTransaction::new(1, 2, 1),
+    ] {
+        expected.extend(add_txns_to_mempool(&mut pool, vec![txn]));
+        // transactions are listed in insertion order, which is tracked with the system clock
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(pool.get_pending_transactions(10), expected);
+    assert_eq!(pool.get_pending_transactions(2), expected[..2].to_vec());
+    assert!(pool.get_pending_transactions(0).is_empty());
+}
+
 #[test]
 fn test_bytes_limit() {
     let mut config = NodeConfig::random();
@@ -399,6 +399,13 @@ impl NetworkBuilder {
                 *interval_duration,
                 self.time_service.clone(),
             ),
+            DiscoveryMethod::Rest(url, interval_duration) => DiscoveryChangeListener::rest(
+                self.network_context,
+                conn_mgr_reqs_tx,
+                url.clone(),
+                *interval_duration,
+                self.time_service.clone(),
+            ),
             DiscoveryMethod::None => return,
         };
 
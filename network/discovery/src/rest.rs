@@ -0,0 +1,342 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiscoveryError;
+use aptos_config::config::PeerSet;
+use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
+use futures::{Future, Stream};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use url::Url;
+
+/// The maximum time to wait for the REST endpoint to respond
+const REST_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The only URL scheme peer sets may be fetched over. Fetched peers are dialed
+/// as seeds, so the endpoint must be authenticated to prevent peer injection.
+const REQUIRED_URL_SCHEME: &str = "https";
+
+type PeerSetFuture = Pin<Box<dyn Future<Output = Result<PeerSet, DiscoveryError>> + Send>>;
+
+/// A stream of `PeerSet`s periodically fetched from a REST endpoint. This
+/// allows operators to rotate seed lists without updating every node config.
+pub struct RestStream {
+    client: reqwest::Client,
+    url: Url,
+    require_https: bool,
+    interval: Pin<Box<Interval>>,
+    pending_request: Option<PeerSetFuture>,
+}
+
+impl RestStream {
+    pub(crate) fn new(url: Url, interval_duration: Duration, time_service: TimeService) -> Self {
+        Self::new_with_scheme_check(url, interval_duration, time_service, true)
+    }
+
+    /// Creates a stream that also accepts plain `http` URLs, e.g., for local test servers
+    #[cfg(test)]
+    pub(crate) fn new_insecure(
+        url: Url,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        Self::new_with_scheme_check(url, interval_duration, time_service, false)
+    }
+
+    fn new_with_scheme_check(
+        url: Url,
+        interval_duration: Duration,
+        time_service: TimeService,
+        require_https: bool,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REST_REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to build the REST discovery client!");
+        RestStream {
+            client,
+            url,
+            require_https,
+            interval: Box::pin(time_service.interval(interval_duration)),
+            pending_request: None,
+        }
+    }
+}
+
+impl Stream for RestStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Wait for delay before sending the next request (if one isn't already in flight)
+        if this.pending_request.is_none() {
+            futures::ready!(this.interval.as_mut().poll_next(cx));
+            if this.require_https {
+                if let Err(error) = verify_url(&this.url) {
+                    return Poll::Ready(Some(Err(error)));
+                }
+            }
+            this.pending_request = Some(Box::pin(fetch_peer_set(
+                this.client.clone(),
+                this.url.clone(),
+            )));
+        }
+
+        let pending_request = this
+            .pending_request
+            .as_mut()
+            .expect("The pending request should exist!");
+        let result = futures::ready!(pending_request.as_mut().poll(cx));
+        this.pending_request = None;
+
+        Poll::Ready(Some(result))
+    }
+}
+
+/// Verifies that the URL is fetched over an authenticated (i.e., TLS) connection
+fn verify_url(url: &Url) -> Result<(), DiscoveryError> {
+    if url.scheme() == REQUIRED_URL_SCHEME {
+        Ok(())
+    } else {
+        Err(DiscoveryError::Verification(format!(
+            "REST discovery URL {} must use {}",
+            url, REQUIRED_URL_SCHEME
+        )))
+    }
+}
+
+/// Fetches a YAML (or JSON) encoded `PeerSet` from the given URL and verifies it
+async fn fetch_peer_set(client: reqwest::Client, url: Url) -> Result<PeerSet, DiscoveryError> {
+    let contents = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(DiscoveryError::Rest)?
+        .text()
+        .await
+        .map_err(DiscoveryError::Rest)?;
+    let peer_set: PeerSet =
+        serde_yaml::from_str(&contents).map_err(|err| DiscoveryError::Parsing(err.to_string()))?;
+
+    verify_peer_set(&peer_set)?;
+    Ok(peer_set)
+}
+
+/// Verifies that every peer in the set can be dialed and authenticated, i.e.,
+/// that each peer has at least one key and every address ends in a noise-ik
+/// protocol with a key known for that peer. Peer sets failing verification are
+/// rejected as a whole so that a bad upload can't partially replace good peers.
+fn verify_peer_set(peer_set: &PeerSet) -> Result<(), DiscoveryError> {
+    for (peer_id, peer) in peer_set {
+        if peer.keys.is_empty() {
+            return Err(DiscoveryError::Verification(format!(
+                "Peer {} has no public keys",
+                peer_id
+            )));
+        }
+        for address in &peer.addresses {
+            match address.find_noise_proto() {
+                Some(key) if peer.keys.contains(&key) => {}
+                Some(key) => {
+                    return Err(DiscoveryError::Verification(format!(
+                        "Peer {} has address {} with unknown key {}",
+                        peer_id, address, key
+                    )))
+                }
+                None => {
+                    return Err(DiscoveryError::Verification(format!(
+                        "Peer {} has address {} without a noise-ik key",
+                        peer_id, address
+                    )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiscoveryChangeListener, DiscoveryChangeStream};
+    use aptos_config::{
+        config::{Peer, PeerRole},
+        network_id::NetworkContext,
+    };
+    use aptos_types::{network_address::NetworkAddress, PeerId};
+    use channel::Receiver;
+    use futures::StreamExt;
+    use network::connectivity_manager::{ConnectivityRequest, DiscoverySource};
+    use std::{
+        collections::HashSet,
+        str::FromStr,
+        sync::{Arc, RwLock},
+    };
+    use warp::Filter;
+
+    const NOISE_ADDRESS: &str = "/ip4/1.2.3.4/tcp/6180/noise-ik/080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120/handshake/0";
+
+    /// Starts a local HTTP stand-in that serves the current peer set contents
+    fn start_http_server(contents: Arc<RwLock<String>>) -> Url {
+        let route = warp::path!("peers").map(move || contents.read().unwrap().clone());
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(server);
+        Url::parse(&format!("http://{}/peers", address)).unwrap()
+    }
+
+    fn create_listener(url: Url) -> Receiver<ConnectivityRequest> {
+        let check_interval = Duration::from_millis(5);
+        let time_service = TimeService::real();
+        let (conn_mgr_reqs_tx, conn_mgr_reqs_rx) =
+            channel::new(1, &network::counters::PENDING_CONNECTIVITY_MANAGER_REQUESTS);
+        // The local test server doesn't serve TLS, so build the listener around an insecure stream
+        let listener = DiscoveryChangeListener {
+            discovery_source: DiscoverySource::Rest,
+            network_context: NetworkContext::mock(),
+            update_channel: conn_mgr_reqs_tx,
+            source_stream: DiscoveryChangeStream::Rest(RestStream::new_insecure(
+                url,
+                check_interval,
+                time_service,
+            )),
+        };
+
+        tokio::task::spawn(Box::pin(listener).run());
+        conn_mgr_reqs_rx
+    }
+
+    fn create_peer_set(address: &str) -> PeerSet {
+        let addr = NetworkAddress::from_str(address).unwrap();
+        let mut peers = PeerSet::new();
+        peers.insert(
+            PeerId::random(),
+            Peer::new(vec![addr], HashSet::new(), PeerRole::Upstream),
+        );
+        peers
+    }
+
+    fn write_peer_set(peers: &PeerSet, contents: &RwLock<String>) {
+        *contents.write().unwrap() = serde_yaml::to_string(peers).unwrap();
+    }
+
+    /// Waits until the listener sends the expected peer set
+    async fn wait_for_peer_set(
+        conn_mgr_reqs_rx: &mut Receiver<ConnectivityRequest>,
+        peers: &PeerSet,
+    ) {
+        loop {
+            match conn_mgr_reqs_rx.next().await {
+                Some(ConnectivityRequest::UpdateDiscoveredPeers(
+                    DiscoverySource::Rest,
+                    actual_peers,
+                )) => {
+                    if actual_peers == *peers {
+                        return;
+                    }
+                }
+                _ => panic!("No message sent by discovery"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rest_listener() {
+        let contents = Arc::new(RwLock::new(String::new()));
+        let peers = PeerSet::new();
+        write_peer_set(&peers, &contents);
+
+        let url = start_http_server(contents.clone());
+        let mut conn_mgr_reqs_rx = create_listener(url);
+
+        // Try empty
+        if let Some(ConnectivityRequest::UpdateDiscoveredPeers(
+            DiscoverySource::Rest,
+            actual_peers,
+        )) = conn_mgr_reqs_rx.next().await
+        {
+            assert_eq!(peers, actual_peers)
+        } else {
+            panic!("No message sent by discovery")
+        }
+
+        // Rotate the peer set served by the endpoint and verify the update is picked up
+        let peers = create_peer_set(NOISE_ADDRESS);
+        write_peer_set(&peers, &contents);
+        wait_for_peer_set(&mut conn_mgr_reqs_rx, &peers).await;
+    }
+
+    #[tokio::test]
+    async fn test_rest_invalid_peer_set() {
+        // Serve a peer set with an address that can't be authenticated
+        let contents = Arc::new(RwLock::new(String::new()));
+        let invalid_peers = create_peer_set("/ip4/1.2.3.4/tcp/6180");
+        assert!(verify_peer_set(&invalid_peers).is_err());
+        write_peer_set(&invalid_peers, &contents);
+        let url = start_http_server(contents.clone());
+
+        // Verify the stream rejects the invalid peer set
+        let mut stream =
+            RestStream::new_insecure(url.clone(), Duration::from_millis(5), TimeService::real());
+        match stream.next().await {
+            Some(Err(DiscoveryError::Verification(_))) => {}
+            result => panic!("Expected a verification error, but got: {:?}", result),
+        }
+        let mut conn_mgr_reqs_rx = create_listener(url);
+
+        // Serve a valid peer set and verify it's accepted by the stream, and that it's the
+        // first update sent by discovery
+        let peers = create_peer_set(NOISE_ADDRESS);
+        assert!(verify_peer_set(&peers).is_ok());
+        write_peer_set(&peers, &contents);
+        match stream.next().await {
+            Some(Ok(actual_peers)) => assert_eq!(peers, actual_peers),
+            result => panic!("Expected the valid peer set, but got: {:?}", result),
+        }
+        if let Some(ConnectivityRequest::UpdateDiscoveredPeers(
+            DiscoverySource::Rest,
+            actual_peers,
+        )) = conn_mgr_reqs_rx.next().await
+        {
+            assert_eq!(peers, actual_peers)
+        } else {
+            panic!("No message sent by discovery")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rest_unreachable_endpoint() {
+        // Point the listener at an endpoint that doesn't serve peers
+        let contents = Arc::new(RwLock::new(String::new()));
+        let url = start_http_server(contents).join("missing").unwrap();
+        let mut stream =
+            RestStream::new_insecure(url, Duration::from_millis(5), TimeService::real());
+
+        match stream.next().await {
+            Some(Err(DiscoveryError::Rest(_))) => {}
+            result => panic!("Expected a REST error, but got: {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rest_rejects_insecure_url() {
+        // Serve a valid peer set over plain HTTP
+        let contents = Arc::new(RwLock::new(String::new()));
+        write_peer_set(&create_peer_set(NOISE_ADDRESS), &contents);
+        let url = start_http_server(contents);
+
+        // Verify the peer set is never fetched, as it can't be authenticated
+        let mut stream = RestStream::new(url, Duration::from_millis(5), TimeService::real());
+        for _ in 0..3 {
+            match stream.next().await {
+                Some(Err(DiscoveryError::Verification(_))) => {}
+                result => panic!("Expected a verification error, but got: {:?}", result),
+            }
+        }
+        assert!(verify_url(&Url::parse("https://seeds.example.com/peers").unwrap()).is_ok());
+    }
+}
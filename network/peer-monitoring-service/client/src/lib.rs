@@ -19,11 +19,19 @@ use network::{
 };
 use peer_monitoring_service_types::{
     PeerMonitoringServiceError, PeerMonitoringServiceMessage, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse,
+    PeerMonitoringServiceResponse, UnexpectedResponseError,
 };
 use std::{sync::Arc, time::Duration};
 use thiserror::Error;
 
+mod logging;
+mod metrics;
+mod peer_monitor;
+#[cfg(test)]
+mod tests;
+
+pub use peer_monitor::start_peer_monitor;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Aptos network rpc error: {0}")]
@@ -31,6 +39,9 @@ pub enum Error {
 
     #[error("Error from remote monitoring service: {0}")]
     PeerMonitoringServiceError(#[from] PeerMonitoringServiceError),
+
+    #[error("Unexpected response: {0}")]
+    UnexpectedResponseError(#[from] UnexpectedResponseError),
 }
 
 /// The interface for sending peer monitoring service requests and querying
@@ -76,6 +87,21 @@ impl PeerMonitoringServiceClient {
             }
         }
     }
+
+    /// Returns all connected peers that support the peer monitoring service
+    pub fn get_connected_peers(&self) -> Vec<PeerNetworkId> {
+        self.peer_metadata
+            .networks()
+            .flat_map(|network_id| {
+                self.peer_metadata
+                    .read_filtered(network_id, |(_, peer_info)| {
+                        peer_info.is_connected()
+                            && peer_info.supports_protocol(ProtocolId::PeerMonitoringServiceRpc)
+                    })
+                    .into_keys()
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
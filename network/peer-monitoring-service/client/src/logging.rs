@@ -0,0 +1,37 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Error;
+use aptos_config::network_id::PeerNetworkId;
+use aptos_logger::Schema;
+use serde::Serialize;
+
+#[derive(Schema)]
+pub struct LogSchema<'a> {
+    name: LogEntry,
+    #[schema(debug)]
+    error: Option<&'a Error>,
+    message: Option<&'a str>,
+    #[schema(display)]
+    peer: Option<&'a PeerNetworkId>,
+    request_type: Option<&'a str>,
+}
+
+impl<'a> LogSchema<'a> {
+    pub fn new(name: LogEntry) -> Self {
+        Self {
+            name,
+            error: None,
+            message: None,
+            peer: None,
+            request_type: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogEntry {
+    PeerMonitor,
+    PeerMonitoringRequestError,
+}
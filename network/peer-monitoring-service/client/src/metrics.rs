@@ -0,0 +1,57 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::network_id::PeerNetworkId;
+use aptos_metrics_core::{
+    register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec,
+};
+use once_cell::sync::Lazy;
+use std::time::Duration;
+
+/// Counter for the peer monitoring requests sent by the client
+pub static PEER_MONITORING_REQUESTS_SENT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_peer_monitoring_service_client_requests_sent",
+        "Counters related to the peer monitoring client requests sent",
+        &["network", "request_type"]
+    )
+    .unwrap()
+});
+
+/// Counter for the peer monitoring request errors encountered by the client
+pub static PEER_MONITORING_REQUEST_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_peer_monitoring_service_client_request_errors",
+        "Counters related to the peer monitoring client request errors",
+        &["network", "request_type"]
+    )
+    .unwrap()
+});
+
+/// The round trip times of the ping requests sent by the client
+pub static PEER_MONITORING_PING_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_peer_monitoring_service_client_ping_latencies",
+        "The round trip times (in seconds) of the peer monitoring client pings",
+        &["network"]
+    )
+    .unwrap()
+});
+
+/// Increments the given counter with the provided peer and label values.
+pub fn increment_request_counter(
+    counter: &Lazy<IntCounterVec>,
+    peer_network_id: &PeerNetworkId,
+    label: &str,
+) {
+    counter
+        .with_label_values(&[peer_network_id.network_id().as_str(), label])
+        .inc();
+}
+
+/// Observes the given ping latency for the provided peer
+pub fn observe_ping_latency(peer_network_id: &PeerNetworkId, latency: Duration) {
+    PEER_MONITORING_PING_LATENCIES
+        .with_label_values(&[peer_network_id.network_id().as_str()])
+        .observe(latency.as_secs_f64());
+}
@@ -0,0 +1,172 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    logging::{LogEntry, LogSchema},
+    metrics::{
+        increment_request_counter, observe_ping_latency, PEER_MONITORING_REQUESTS_SENT,
+        PEER_MONITORING_REQUEST_ERRORS,
+    },
+    Error, PeerMonitoringServiceClient,
+};
+use aptos_config::{config::PeerMonitoringServiceConfig, network_id::PeerNetworkId};
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::{future::join_all, StreamExt};
+use network::application::interface::NetworkInterface;
+use peer_monitoring_service_types::{
+    DepthFromValidatorsResponse, KnownPeersResponse, PeerMonitoringServiceRequest,
+    PeerMonitoringServiceResponse, PingResponse, ServerProtocolVersionResponse,
+    UnexpectedResponseError,
+};
+use std::{convert::TryFrom, time::Duration};
+
+/// The frequency (in seconds) at which to log request errors.
+const ERROR_LOG_FREQ_SECS: u64 = 5;
+/// The weight given to the latest ping latency in the moving average.
+const PING_LATENCY_SAMPLE_WEIGHT: f64 = 0.2;
+
+/// Runs the peer monitor that periodically sends monitoring requests to all
+/// connected peers and records the results in the peer metadata storage.
+pub async fn start_peer_monitor(
+    config: PeerMonitoringServiceConfig,
+    client: PeerMonitoringServiceClient,
+    time_service: TimeService,
+) {
+    info!(LogSchema::new(LogEntry::PeerMonitor).message("Starting the peer monitor!"));
+
+    let request_timeout = Duration::from_millis(config.peer_monitor_request_timeout_ms);
+    let ticker = time_service.interval(Duration::from_millis(config.peer_monitor_interval_ms));
+    futures::pin_mut!(ticker);
+
+    loop {
+        // Wait for the next round before monitoring the peers
+        ticker.next().await;
+
+        // Monitor all connected peers concurrently and wait for the round to complete
+        let monitoring_tasks = client
+            .get_connected_peers()
+            .into_iter()
+            .map(|peer| monitor_peer(&client, &time_service, peer, request_timeout));
+        join_all(monitoring_tasks).await;
+    }
+}
+
+/// Sends all monitoring requests to the given peer and records the
+/// results in the peer's monitoring metadata.
+pub(crate) async fn monitor_peer(
+    client: &PeerMonitoringServiceClient,
+    time_service: &TimeService,
+    peer: PeerNetworkId,
+    request_timeout: Duration,
+) {
+    let (ping_latency, depth, known_peers, protocol_version) = futures::join!(
+        ping_peer(client, time_service, peer, request_timeout),
+        send_request_to_peer::<DepthFromValidatorsResponse>(
+            client,
+            peer,
+            PeerMonitoringServiceRequest::GetDepthFromValidators,
+            request_timeout,
+        ),
+        send_request_to_peer::<KnownPeersResponse>(
+            client,
+            peer,
+            PeerMonitoringServiceRequest::GetKnownPeers,
+            request_timeout,
+        ),
+        send_request_to_peer::<ServerProtocolVersionResponse>(
+            client,
+            peer,
+            PeerMonitoringServiceRequest::GetServerProtocolVersion,
+            request_timeout,
+        ),
+    );
+
+    // Update the monitoring metadata of the peer. If the peer is no
+    // longer found, it has disconnected and there is nothing to update.
+    let peer_metadata_storage = client.peer_metadata_storage();
+    let _ = peer_metadata_storage.update_monitoring_metadata(peer, |monitoring_metadata| {
+        if let Some(ping_latency) = ping_latency {
+            monitoring_metadata.average_ping_latency = Some(calculate_average_latency(
+                monitoring_metadata.average_ping_latency,
+                ping_latency,
+            ));
+        }
+        if let Some(depth) = depth {
+            monitoring_metadata.depth_from_validators = depth.depth_from_validators;
+        }
+        if let Some(known_peers) = known_peers {
+            monitoring_metadata.num_known_peers = Some(known_peers.known_peers.len() as u64);
+        }
+        if let Some(protocol_version) = protocol_version {
+            monitoring_metadata.server_protocol_version = Some(protocol_version.version);
+        }
+    });
+}
+
+/// Pings the given peer and returns the round trip time (if successful)
+async fn ping_peer(
+    client: &PeerMonitoringServiceClient,
+    time_service: &TimeService,
+    peer: PeerNetworkId,
+    request_timeout: Duration,
+) -> Option<Duration> {
+    let ping_start_time = time_service.now();
+    send_request_to_peer::<PingResponse>(
+        client,
+        peer,
+        PeerMonitoringServiceRequest::Ping,
+        request_timeout,
+    )
+    .await?;
+    let ping_latency = time_service.now().duration_since(ping_start_time);
+
+    observe_ping_latency(&peer, ping_latency);
+    Some(ping_latency)
+}
+
+/// Sends the request to the given peer and returns the expected response
+/// type. If the request fails, the error is logged and `None` is returned.
+async fn send_request_to_peer<T>(
+    client: &PeerMonitoringServiceClient,
+    peer: PeerNetworkId,
+    request: PeerMonitoringServiceRequest,
+    request_timeout: Duration,
+) -> Option<T>
+where
+    T: TryFrom<PeerMonitoringServiceResponse, Error = UnexpectedResponseError>,
+{
+    let request_type = request.get_label();
+    increment_request_counter(&PEER_MONITORING_REQUESTS_SENT, &peer, request_type);
+
+    let result = client
+        .send_request(peer, request, request_timeout)
+        .await
+        .and_then(|response| T::try_from(response).map_err(Error::from));
+    match result {
+        Ok(response) => Some(response),
+        Err(error) => {
+            increment_request_counter(&PEER_MONITORING_REQUEST_ERRORS, &peer, request_type);
+            sample!(
+                SampleRate::Duration(Duration::from_secs(ERROR_LOG_FREQ_SECS)),
+                warn!(LogSchema::new(LogEntry::PeerMonitoringRequestError)
+                    .error(&error)
+                    .peer(&peer)
+                    .request_type(request_type)
+                    .message("Failed to send the peer monitoring request!"))
+            );
+            None
+        }
+    }
+}
+
+/// Calculates the new moving average latency using the latest sample
+fn calculate_average_latency(average_latency: Option<Duration>, latency: Duration) -> Duration {
+    match average_latency {
+        Some(average_latency) => Duration::from_secs_f64(
+            PING_LATENCY_SAMPLE_WEIGHT * latency.as_secs_f64()
+                + (1.0 - PING_LATENCY_SAMPLE_WEIGHT) * average_latency.as_secs_f64(),
+        ),
+        None => latency,
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    peer_monitor::monitor_peer, PeerMonitoringServiceClient, PeerMonitoringServiceNetworkSender,
+};
+use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_time_service::{MockTimeService, TimeService};
+use aptos_types::PeerId;
+use channel::{aptos_channel, message_queues::QueueStyle};
+use futures::StreamExt;
+use maplit::hashmap;
+use network::{
+    application::{
+        interface::{MultiNetworkSender, NetworkInterface},
+        storage::PeerMetadataStorage,
+        types::{PeerMonitoringMetadata, PeerState},
+    },
+    peer_manager::{ConnectionRequestSender, PeerManagerRequest, PeerManagerRequestSender},
+    protocols::{network::NewNetworkSender, wire::handshake::v1::ProtocolId},
+    transport::ConnectionMetadata,
+};
+use peer_monitoring_service_types::{
+    DepthFromValidatorsResponse, KnownPeersResponse, PeerMonitoringServiceError,
+    PeerMonitoringServiceMessage, PeerMonitoringServiceRequest, PeerMonitoringServiceResponse,
+    PingResponse, ServerProtocolVersionResponse,
+};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test]
+async fn test_get_connected_peers() {
+    let (mut mock_network, _, client) = MockNetwork::new();
+
+    // Verify there are no connected peers
+    assert!(client.get_connected_peers().is_empty());
+
+    // Add a peer that doesn't support the peer monitoring service and verify it's ignored
+    let _ = mock_network.add_peer(false);
+    assert!(client.get_connected_peers().is_empty());
+
+    // Add a peer that supports the peer monitoring service and verify it's returned
+    let peer = mock_network.add_peer(true);
+    assert_eq!(client.get_connected_peers(), vec![peer]);
+
+    // Disconnect the peer and verify it's no longer returned
+    mock_network.disconnect_peer(peer);
+    assert!(client.get_connected_peers().is_empty());
+}
+
+#[tokio::test]
+async fn test_monitor_peer() {
+    let (mut mock_network, mock_time, client) = MockNetwork::new();
+    let peer = mock_network.add_peer(true);
+
+    // Monitor the peer and respond to the ping after 100ms
+    let monitor_handle = spawn_monitor_peer(&client, &mock_time, peer);
+    mock_network
+        .handle_monitoring_requests(&mock_time, Some(2), Duration::from_millis(100))
+        .await;
+    monitor_handle.await.unwrap();
+
+    // Verify the monitoring metadata of the peer
+    let expected_metadata = PeerMonitoringMetadata {
+        average_ping_latency: Some(Duration::from_millis(100)),
+        depth_from_validators: Some(2),
+        num_known_peers: Some(1),
+        server_protocol_version: Some(1),
+    };
+    assert_eq!(get_monitoring_metadata(&client, peer), expected_metadata);
+
+    // Monitor the peer again and respond to the ping after 600ms
+    let monitor_handle = spawn_monitor_peer(&client, &mock_time, peer);
+    mock_network
+        .handle_monitoring_requests(&mock_time, Some(1), Duration::from_millis(600))
+        .await;
+    monitor_handle.await.unwrap();
+
+    // Verify the average ping latency and depth were updated
+    let monitoring_metadata = get_monitoring_metadata(&client, peer);
+    let average_ping_latency = monitoring_metadata.average_ping_latency.unwrap();
+    assert!((average_ping_latency.as_secs_f64() - 0.2).abs() < 1e-6);
+    assert_eq!(monitoring_metadata.depth_from_validators, Some(1));
+}
+
+#[tokio::test]
+async fn test_monitor_peer_errors() {
+    let (mut mock_network, mock_time, client) = MockNetwork::new();
+    let peer = mock_network.add_peer(true);
+
+    // Monitor the peer and respond to all requests with errors
+    let monitor_handle = spawn_monitor_peer(&client, &mock_time, peer);
+    for _ in 0..4 {
+        let (_, response_sender) = mock_network.next_request().await;
+        response_sender(Err(PeerMonitoringServiceError::InternalError(
+            "Oops!".into(),
+        )));
+    }
+    monitor_handle.await.unwrap();
+
+    // Verify the monitoring metadata of the peer is still empty
+    assert_eq!(
+        get_monitoring_metadata(&client, peer),
+        PeerMonitoringMetadata::default()
+    );
+}
+
+/// A simple mock network for sending monitoring requests to peers
+struct MockNetwork {
+    peer_mgr_reqs_rx: aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+}
+
+/// A callback used to respond to a monitoring request
+type ResponseCallback =
+    Box<dyn FnOnce(Result<PeerMonitoringServiceResponse, PeerMonitoringServiceError>) + Send>;
+
+impl MockNetwork {
+    fn new() -> (Self, MockTimeService, PeerMonitoringServiceClient) {
+        let queue_config = aptos_channel::Config::new(10).queue_style(QueueStyle::FIFO);
+        let (peer_mgr_reqs_tx, peer_mgr_reqs_rx) = queue_config.build();
+        let (connection_reqs_tx, _connection_reqs_rx) = queue_config.build();
+
+        let network_sender = MultiNetworkSender::new(hashmap! {
+            NetworkId::Validator => PeerMonitoringServiceNetworkSender::new(
+                PeerManagerRequestSender::new(peer_mgr_reqs_tx),
+                ConnectionRequestSender::new(connection_reqs_tx),
+            )
+        });
+        let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Validator]);
+        let client =
+            PeerMonitoringServiceClient::new(network_sender, peer_metadata_storage.clone());
+
+        let mock_network = Self {
+            peer_mgr_reqs_rx,
+            peer_metadata_storage,
+        };
+        (mock_network, TimeService::mock().into_mock(), client)
+    }
+
+    /// Adds a new connected peer to the peer metadata storage
+    fn add_peer(&mut self, supports_peer_monitoring: bool) -> PeerNetworkId {
+        let peer_id = PeerId::random();
+        let mut connection_metadata = ConnectionMetadata::mock(peer_id);
+        if supports_peer_monitoring {
+            connection_metadata
+                .application_protocols
+                .insert(ProtocolId::PeerMonitoringServiceRpc);
+        }
+        self.peer_metadata_storage
+            .insert_connection(NetworkId::Validator, connection_metadata);
+
+        PeerNetworkId::new(NetworkId::Validator, peer_id)
+    }
+
+    /// Marks the given peer as disconnected
+    fn disconnect_peer(&mut self, peer: PeerNetworkId) {
+        let mut peer_info = self.peer_metadata_storage.read(peer).unwrap();
+        peer_info.status = PeerState::Disconnected;
+        self.peer_metadata_storage.insert(peer, peer_info);
+    }
+
+    /// Returns the next monitoring request sent by the client (and a response callback)
+    async fn next_request(&mut self) -> (PeerMonitoringServiceRequest, ResponseCallback) {
+        match self.peer_mgr_reqs_rx.next().await {
+            Some(PeerManagerRequest::SendRpc(_, outbound_rpc_request)) => {
+                let protocol_id = outbound_rpc_request.protocol_id;
+                let request = match protocol_id
+                    .from_bytes::<PeerMonitoringServiceMessage>(&outbound_rpc_request.data)
+                    .unwrap()
+                {
+                    PeerMonitoringServiceMessage::Request(request) => request,
+                    message => panic!("Unexpected message: {:?}", message),
+                };
+                let response_sender = outbound_rpc_request.res_tx;
+                let response_callback: ResponseCallback = Box::new(move |response| {
+                    let message = PeerMonitoringServiceMessage::Response(response);
+                    let response_data = protocol_id.to_bytes(&message).unwrap();
+                    let _ = response_sender.send(Ok(response_data.into()));
+                });
+                (request, response_callback)
+            }
+            request => panic!("Unexpected peer manager request: {:?}", request),
+        }
+    }
+
+    /// Responds to all monitoring requests for a single peer. The ping
+    /// is responded to after the mock time has advanced by `ping_latency`.
+    async fn handle_monitoring_requests(
+        &mut self,
+        mock_time: &MockTimeService,
+        depth_from_validators: Option<u64>,
+        ping_latency: Duration,
+    ) {
+        for _ in 0..4 {
+            let (request, response_callback) = self.next_request().await;
+            let response = match request {
+                PeerMonitoringServiceRequest::Ping => {
+                    mock_time.advance_async(ping_latency).await;
+                    PeerMonitoringServiceResponse::Ping(PingResponse {})
+                }
+                PeerMonitoringServiceRequest::GetDepthFromValidators => {
+                    PeerMonitoringServiceResponse::DepthFromValidators(
+                        DepthFromValidatorsResponse {
+                            depth_from_validators,
+                        },
+                    )
+                }
+                PeerMonitoringServiceRequest::GetKnownPeers => {
+                    let known_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+                    let known_peers: HashSet<_> = vec![known_peer].into_iter().collect();
+                    PeerMonitoringServiceResponse::KnownPeers(KnownPeersResponse { known_peers })
+                }
+                PeerMonitoringServiceRequest::GetServerProtocolVersion => {
+                    PeerMonitoringServiceResponse::ServerProtocolVersion(
+                        ServerProtocolVersionResponse { version: 1 },
+                    )
+                }
+                request => panic!("Unexpected monitoring request: {:?}", request),
+            };
+            response_callback(Ok(response));
+        }
+    }
+}
+
+/// Returns the monitoring metadata of the given peer
+fn get_monitoring_metadata(
+    client: &PeerMonitoringServiceClient,
+    peer: PeerNetworkId,
+) -> PeerMonitoringMetadata {
+    client
+        .peer_metadata_storage()
+        .read(peer)
+        .unwrap()
+        .monitoring_metadata
+}
+
+/// Spawns a task that monitors the given peer
+fn spawn_monitor_peer(
+    client: &PeerMonitoringServiceClient,
+    mock_time: &MockTimeService,
+    peer: PeerNetworkId,
+) -> tokio::task::JoinHandle<()> {
+    let client = client.clone();
+    let time_service = TimeService::from(mock_time.clone());
+    tokio::spawn(async move { monitor_peer(&client, &time_service, peer, REQUEST_TIMEOUT).await })
+}
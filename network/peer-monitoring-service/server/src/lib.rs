@@ -9,16 +9,20 @@ use crate::{
     network::PeerMonitoringServiceNetworkEvents,
 };
 use ::network::{application::storage::PeerMetadataStorage, ProtocolId};
-use aptos_config::config::PeerMonitoringServiceConfig;
+use aptos_config::{config::PeerMonitoringServiceConfig, network_id::NetworkId};
 use aptos_logger::prelude::*;
 use bounded_executor::BoundedExecutor;
 use futures::stream::StreamExt;
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse, Result, ServerProtocolVersionResponse,
+    ConnectedPeersResponse, DepthFromValidatorsResponse, KnownPeersResponse,
+    PeerMonitoringServiceError, PeerMonitoringServiceRequest, PeerMonitoringServiceResponse,
+    PingResponse, Result, ServerProtocolVersionResponse,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use thiserror::Error;
 use tokio::runtime::Handle;
 
@@ -50,9 +54,11 @@ impl Error {
     }
 }
 
-/// The server-side actor for the peer monitoring service
+/// The server-side actor for the peer monitoring service of a single network
 pub struct PeerMonitoringServiceServer {
     bounded_executor: BoundedExecutor,
+    config: PeerMonitoringServiceConfig,
+    network_id: NetworkId,
     network_requests: PeerMonitoringServiceNetworkEvents,
     peer_metadata: Arc<PeerMetadataStorage>,
 }
@@ -61,6 +67,7 @@ impl PeerMonitoringServiceServer {
     pub fn new(
         config: PeerMonitoringServiceConfig,
         executor: Handle,
+        network_id: NetworkId,
         network_requests: PeerMonitoringServiceNetworkEvents,
         peer_metadata: Arc<PeerMetadataStorage>,
     ) -> Self {
@@ -69,6 +76,8 @@ impl PeerMonitoringServiceServer {
 
         Self {
             bounded_executor,
+            config,
+            network_id,
             network_requests,
             peer_metadata,
         }
@@ -89,10 +98,14 @@ impl PeerMonitoringServiceServer {
 
             // All handler methods are currently CPU-bound so we want
             // to spawn on the blocking thread pool.
-            let peer_metadata = self.peer_metadata.clone();
+            let handler = Handler::new(
+                self.config.clone(),
+                self.network_id,
+                self.peer_metadata.clone(),
+            );
             self.bounded_executor
                 .spawn_blocking(move || {
-                    let response = Handler::new(peer_metadata).call(protocol, request);
+                    let response = handler.call(protocol, request);
                     log_monitoring_service_response(&response);
                     response_sender.send(response);
                 })
@@ -106,12 +119,22 @@ impl PeerMonitoringServiceServer {
 /// request. We usually clone/create a new handler for every request.
 #[derive(Clone)]
 pub struct Handler {
+    config: PeerMonitoringServiceConfig,
+    network_id: NetworkId, // The network the requests are received on
     peer_metadata: Arc<PeerMetadataStorage>,
 }
 
 impl Handler {
-    pub fn new(peer_metadata: Arc<PeerMetadataStorage>) -> Self {
-        Self { peer_metadata }
+    pub fn new(
+        config: PeerMonitoringServiceConfig,
+        network_id: NetworkId,
+        peer_metadata: Arc<PeerMetadataStorage>,
+    ) -> Self {
+        Self {
+            config,
+            network_id,
+            peer_metadata,
+        }
     }
 
     pub fn call(
@@ -198,11 +221,46 @@ impl Handler {
     }
 
     fn get_depth_from_validators(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        // Nodes on the validator network are validators (i.e., they have a depth of 0).
+        // Otherwise, our depth is one more than the lowest depth of our connected peers.
+        let depth_from_validators = if self
+            .peer_metadata
+            .networks()
+            .any(|network_id| network_id.is_validator_network())
+        {
+            Some(0)
+        } else {
+            self.peer_metadata
+                .networks()
+                .flat_map(|network| self.peer_metadata.read_all(network).into_values())
+                .filter(|peer_info| peer_info.is_connected())
+                .filter_map(|peer_info| peer_info.monitoring_metadata.depth_from_validators)
+                .min()
+                .map(|min_peer_depth| min_peer_depth + 1)
+        };
+
+        Ok(PeerMonitoringServiceResponse::DepthFromValidators(
+            DepthFromValidatorsResponse {
+                depth_from_validators,
+            },
+        ))
     }
 
     fn get_known_peers(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        // Only share the peers we're connected to on the requester's network (so that
+        // e.g. validator network peers aren't revealed to public peers), up to the max.
+        let known_peers: HashSet<_> = self
+            .peer_metadata
+            .read_all(self.network_id)
+            .into_iter()
+            .filter(|(_, peer_info)| peer_info.is_connected())
+            .map(|(peer_network_id, _)| peer_network_id)
+            .take(self.config.max_num_known_peers as usize)
+            .collect();
+
+        Ok(PeerMonitoringServiceResponse::KnownPeers(
+            KnownPeersResponse { known_peers },
+        ))
     }
 
     fn get_server_protocol_version(&self) -> Result<PeerMonitoringServiceResponse, Error> {
@@ -218,7 +276,7 @@ impl Handler {
     }
 
     fn handle_ping(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Ok(PeerMonitoringServiceResponse::Ping(PingResponse {}))
     }
 }
 
@@ -4,7 +4,8 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer, PEER_MONITORING_SERVER_VERSION,
+    Handler, PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer,
+    PEER_MONITORING_SERVER_VERSION,
 };
 use aptos_config::{
     config::{PeerMonitoringServiceConfig, PeerRole},
@@ -29,11 +30,13 @@ use network::{
     transport::{ConnectionId, ConnectionMetadata},
 };
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
-    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+    ConnectedPeersResponse, DepthFromValidatorsResponse, KnownPeersResponse,
+    PeerMonitoringServiceError, PeerMonitoringServiceMessage, PeerMonitoringServiceRequest,
+    PeerMonitoringServiceResponse, PingResponse, ServerProtocolVersionResponse,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
+    convert::TryFrom,
     str::FromStr,
     sync::Arc,
 };
@@ -41,7 +44,7 @@ use std::{
 #[tokio::test]
 async fn test_get_server_protocol_version() {
     // Create the peer monitoring client and server
-    let (mut mock_client, service, _) = MockClient::new();
+    let (mut mock_client, service, _) = MockClient::new(None);
     tokio::spawn(service.start());
 
     // Process a request to fetch the protocol version
@@ -59,7 +62,7 @@ async fn test_get_server_protocol_version() {
 #[tokio::test]
 async fn test_get_connected_peers() {
     // Create the peer monitoring client and server
-    let (mut mock_client, service, peer_metadata_storage) = MockClient::new();
+    let (mut mock_client, service, peer_metadata_storage) = MockClient::new(None);
     tokio::spawn(service.start());
 
     // Process a request to fetch the connected peers
@@ -123,6 +126,140 @@ async fn test_get_connected_peers() {
     assert_eq!(response, expected_response);
 }
 
+#[tokio::test]
+async fn test_get_depth_from_validators() {
+    // Create the peer monitoring client and server for a validator
+    let (mut mock_client, service, _) = MockClient::new(None);
+    tokio::spawn(service.start());
+
+    // Verify validators have a depth of 0
+    verify_depth_from_validators(&mut mock_client, Some(0)).await;
+
+    // Create the peer monitoring client and server for a fullnode
+    let (mut mock_client, service, peer_metadata_storage) =
+        MockClient::new(Some(vec![NetworkId::Vfn, NetworkId::Public]));
+    tokio::spawn(service.start());
+
+    // Verify the depth is unknown (we have no peers)
+    verify_depth_from_validators(&mut mock_client, None).await;
+
+    // Connect to a peer with an unknown depth and verify the depth is still unknown
+    let peer_network_id_1 = insert_connected_peer(&peer_metadata_storage, NetworkId::Public);
+    verify_depth_from_validators(&mut mock_client, None).await;
+
+    // Update the depth of the peer and verify our depth is one greater
+    update_peer_depth(&peer_metadata_storage, peer_network_id_1, 3);
+    verify_depth_from_validators(&mut mock_client, Some(4)).await;
+
+    // Connect to a peer closer to the validators and verify our depth is updated
+    let peer_network_id_2 = insert_connected_peer(&peer_metadata_storage, NetworkId::Vfn);
+    update_peer_depth(&peer_metadata_storage, peer_network_id_2, 0);
+    verify_depth_from_validators(&mut mock_client, Some(1)).await;
+
+    // Disconnect the closer peer and verify our depth increases again
+    peer_metadata_storage
+        .write(peer_network_id_2, |entry| match entry {
+            Entry::Vacant(..) => Err(PeerError::NotFound),
+            Entry::Occupied(inner) => {
+                inner.get_mut().status = PeerState::Disconnected;
+                Ok(())
+            }
+        })
+        .unwrap();
+    verify_depth_from_validators(&mut mock_client, Some(4)).await;
+}
+
+#[tokio::test]
+async fn test_get_known_peers() {
+    // Create the peer monitoring client and server (for the public network)
+    let (mut mock_client, service, peer_metadata_storage) =
+        MockClient::new(Some(vec![NetworkId::Public, NetworkId::Validator]));
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the known peers
+    let request = PeerMonitoringServiceRequest::GetKnownPeers;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    let expected_response = PeerMonitoringServiceResponse::KnownPeers(KnownPeersResponse {
+        known_peers: HashSet::new(),
+    });
+    assert_eq!(response, expected_response);
+
+    // Add two public peers and disconnect one of them
+    let peer_network_id_1 = insert_connected_peer(&peer_metadata_storage, NetworkId::Public);
+    let peer_network_id_2 = insert_connected_peer(&peer_metadata_storage, NetworkId::Public);
+    peer_metadata_storage
+        .write(peer_network_id_2, |entry| match entry {
+            Entry::Vacant(..) => Err(PeerError::NotFound),
+            Entry::Occupied(inner) => {
+                inner.get_mut().status = PeerState::Disconnected;
+                Ok(())
+            }
+        })
+        .unwrap();
+
+    // Add a validator peer
+    let _ = insert_connected_peer(&peer_metadata_storage, NetworkId::Validator);
+
+    // Process a request to fetch the known peers
+    let request = PeerMonitoringServiceRequest::GetKnownPeers;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify only the connected public peer is known
+    let known_peers = vec![peer_network_id_1].into_iter().collect();
+    let expected_response =
+        PeerMonitoringServiceResponse::KnownPeers(KnownPeersResponse { known_peers });
+    assert_eq!(response, expected_response);
+}
+
+#[tokio::test]
+async fn test_get_known_peers_limit() {
+    // Create a handler that returns at most two known peers
+    let max_num_known_peers = 2;
+    let peer_monitoring_service_config = PeerMonitoringServiceConfig {
+        max_num_known_peers,
+        ..Default::default()
+    };
+    let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Public]);
+    let handler = Handler::new(
+        peer_monitoring_service_config,
+        NetworkId::Public,
+        peer_metadata_storage.clone(),
+    );
+
+    // Add more connected peers than the limit
+    let connected_peers: HashSet<_> = (0..5)
+        .map(|_| insert_connected_peer(&peer_metadata_storage, NetworkId::Public))
+        .collect();
+
+    // Verify the number of known peers is limited
+    let response = handler
+        .call(
+            ProtocolId::PeerMonitoringServiceRpc,
+            PeerMonitoringServiceRequest::GetKnownPeers,
+        )
+        .unwrap();
+    let known_peers = KnownPeersResponse::try_from(response).unwrap().known_peers;
+    assert_eq!(known_peers.len(), max_num_known_peers as usize);
+    assert!(known_peers.is_subset(&connected_peers));
+}
+
+#[tokio::test]
+async fn test_ping() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _) = MockClient::new(None);
+    tokio::spawn(service.start());
+
+    // Process a ping request and verify the response
+    let request = PeerMonitoringServiceRequest::Ping;
+    let response = mock_client.send_request(request).await.unwrap();
+    assert_eq!(
+        response,
+        PeerMonitoringServiceResponse::Ping(PingResponse {})
+    );
+}
+
 /// A wrapper around the inbound network interface/channel for easily sending
 /// mock client requests to a [`PeerMonitoringServiceServer`].
 struct MockClient {
@@ -130,7 +267,9 @@ struct MockClient {
 }
 
 impl MockClient {
-    fn new() -> (Self, PeerMonitoringServiceServer, Arc<PeerMetadataStorage>) {
+    fn new(
+        networks: Option<Vec<NetworkId>>,
+    ) -> (Self, PeerMonitoringServiceServer, Arc<PeerMetadataStorage>) {
         initialize_logger();
 
         // Create the peer monitoring service event stream
@@ -149,11 +288,13 @@ impl MockClient {
         );
 
         // Create the peer monitoring server
-        let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Validator]);
+        let networks = networks.unwrap_or_else(|| vec![NetworkId::Validator]);
+        let peer_metadata_storage = PeerMetadataStorage::new(&networks);
         let executor = tokio::runtime::Handle::current();
         let peer_monitoring_server = PeerMonitoringServiceServer::new(
             peer_monitoring_service_config,
             executor,
+            networks[0],
             network_request_stream,
             peer_metadata_storage.clone(),
         );
@@ -203,6 +344,53 @@ impl MockClient {
     }
 }
 
+/// Inserts a new connected peer into the peer metadata storage
+fn insert_connected_peer(
+    peer_metadata_storage: &Arc<PeerMetadataStorage>,
+    network_id: NetworkId,
+) -> PeerNetworkId {
+    let peer_id = PeerId::random();
+    let peer_network_id = PeerNetworkId::new(network_id, peer_id);
+    let connection_metadata = ConnectionMetadata::new(
+        peer_id,
+        ConnectionId::default(),
+        NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
+        ConnectionOrigin::Outbound,
+        MessagingProtocolVersion::V1,
+        ProtocolIdSet::empty(),
+        PeerRole::Unknown,
+    );
+    peer_metadata_storage.insert(peer_network_id, PeerInfo::new(connection_metadata));
+    peer_network_id
+}
+
+/// Updates the depth from validators of the given peer
+fn update_peer_depth(
+    peer_metadata_storage: &Arc<PeerMetadataStorage>,
+    peer_network_id: PeerNetworkId,
+    depth_from_validators: u64,
+) {
+    peer_metadata_storage
+        .update_monitoring_metadata(peer_network_id, |monitoring_metadata| {
+            monitoring_metadata.depth_from_validators = Some(depth_from_validators);
+        })
+        .unwrap();
+}
+
+/// Sends a depth from validators request and verifies the response
+async fn verify_depth_from_validators(
+    mock_client: &mut MockClient,
+    expected_depth_from_validators: Option<u64>,
+) {
+    let request = PeerMonitoringServiceRequest::GetDepthFromValidators;
+    let response = mock_client.send_request(request).await.unwrap();
+    let expected_response =
+        PeerMonitoringServiceResponse::DepthFromValidators(DepthFromValidatorsResponse {
+            depth_from_validators: expected_depth_from_validators,
+        });
+    assert_eq!(response, expected_response);
+}
+
 /// Initializes the Aptos logger for tests
 pub fn initialize_logger() {
     aptos_logger::Logger::builder()
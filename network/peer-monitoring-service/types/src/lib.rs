@@ -6,7 +6,10 @@
 use aptos_config::network_id::PeerNetworkId;
 use network::application::types::PeerInfo;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
 use thiserror::Error;
 
 pub type Result<T, E = PeerMonitoringServiceError> = ::std::result::Result<T, E>;
@@ -90,20 +93,18 @@ pub struct ConnectedPeersResponse {
 /// A response for the depth from validators request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct DepthFromValidatorsResponse {
-    pub todo: bool,
+    pub depth_from_validators: Option<u64>, // None if the depth is currently unknown
 }
 
 /// A response for the known peers request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct KnownPeersResponse {
-    pub todo: bool,
+    pub known_peers: HashSet<PeerNetworkId>,
 }
 
 /// A response for the ping request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-pub struct PingResponse {
-    pub todo: bool,
-}
+pub struct PingResponse {}
 
 /// A response for the server protocol version request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    application::types::{PeerError, PeerInfo},
+    application::types::{PeerError, PeerInfo, PeerMonitoringMetadata},
     transport::ConnectionMetadata,
 };
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
@@ -103,6 +103,22 @@ impl PeerMetadataStorage {
             .write(peer_network_id.peer_id(), modifier)
     }
 
+    /// Updates the monitoring metadata of the given peer using the modifier.
+    /// Returns an error if the peer is not found.
+    pub fn update_monitoring_metadata<F: FnOnce(&mut PeerMonitoringMetadata)>(
+        &self,
+        peer_network_id: PeerNetworkId,
+        modifier: F,
+    ) -> Result<(), PeerError> {
+        self.write(peer_network_id, |entry| match entry {
+            Entry::Occupied(inner) => {
+                modifier(&mut inner.get_mut().monitoring_metadata);
+                Ok(())
+            }
+            Entry::Vacant(_) => Err(PeerError::NotFound),
+        })
+    }
+
     /// Get the underlying `RwLock` of the map.  Usage is discouraged as it leads to the possiblity of
     /// leaving the lock held for a long period of time.  However, not everything fits into the `write`
     /// model.
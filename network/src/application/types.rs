@@ -3,6 +3,7 @@
 
 use crate::{protocols::wire::handshake::v1::ProtocolId, transport::ConnectionMetadata};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Errors related to the peer layer in the `NetworkInterface`
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,6 +16,7 @@ pub enum PeerError {
 pub struct PeerInfo {
     pub status: PeerState,
     pub active_connection: ConnectionMetadata,
+    pub monitoring_metadata: PeerMonitoringMetadata,
 }
 
 impl PeerInfo {
@@ -22,6 +24,7 @@ impl PeerInfo {
         PeerInfo {
             status: PeerState::Connected,
             active_connection: connection_metadata,
+            monitoring_metadata: PeerMonitoringMetadata::default(),
         }
     }
 
@@ -44,3 +47,14 @@ pub enum PeerState {
     Disconnecting,
     Disconnected,
 }
+
+/// The metadata collected about a `Peer` by the peer monitoring service.
+/// Each field is `None` until the peer has successfully responded to the
+/// corresponding monitoring request.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerMonitoringMetadata {
+    pub average_ping_latency: Option<Duration>, // The moving average of ping round trip times
+    pub depth_from_validators: Option<u64>, // The number of hops between the peer and the validators
+    pub num_known_peers: Option<u64>,       // The number of peers known by the peer
+    pub server_protocol_version: Option<u64>, // The peer monitoring protocol version of the peer
+}
@@ -54,6 +54,16 @@ pub static REQUEST_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauge for tracking the average request latency (in milliseconds) of each peer
+pub static AVERAGE_PEER_LATENCIES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_data_client_average_peer_latencies",
+        "Gauge related to the average request latency (ms) of each peer",
+        &["network", "peer_id"]
+    )
+    .unwrap()
+});
+
 /// Gauge for tracking the number of in-flight polls
 pub static IN_FLIGHT_POLLS: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
@@ -154,6 +164,15 @@ pub fn set_gauge(counter: &Lazy<IntGaugeVec>, label: &str, value: u64) {
     counter.with_label_values(&[label]).set(value as i64);
 }
 
+/// Sets the peer gauge with the provided value
+pub fn set_peer_gauge(gauge: &Lazy<IntGaugeVec>, peer_network_id: PeerNetworkId, value: u64) {
+    let peer = peer_network_id.peer_id().short_str();
+    let network = peer_network_id.network_id();
+    gauge
+        .with_label_values(&[network.as_str(), peer.as_str()])
+        .set(value as i64);
+}
+
 /// Starts the timer for the provided histogram and label values.
 pub fn start_request_timer(
     histogram: &Lazy<HistogramVec>,
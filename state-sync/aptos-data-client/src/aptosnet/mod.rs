@@ -5,8 +5,8 @@ use crate::{
     aptosnet::{
         logging::{LogEntry, LogEvent, LogSchema},
         metrics::{
-            increment_request_counter, set_gauge, start_request_timer, DataType, PRIORITIZED_PEER,
-            REGULAR_PEER,
+            increment_request_counter, set_gauge, set_peer_gauge, start_request_timer, DataType,
+            PRIORITIZED_PEER, REGULAR_PEER,
         },
         state::{ErrorType, PeerStates},
     },
@@ -61,6 +61,7 @@ const IN_FLIGHT_METRICS_SAMPLE_FREQ: u64 = 5;
 const PEER_LOG_FREQ_SECS: u64 = 10;
 const POLLER_LOG_FREQ_SECS: u64 = 2;
 const REGULAR_PEER_SAMPLE_FREQ: u64 = 3;
+const MIN_PEER_LATENCY_SECS: f64 = 0.001; // Lower bound on latencies used for peer weighting
 
 /// An [`AptosDataClient`] that fulfills requests from remote peers' Storage Service
 /// over AptosNet.
@@ -72,7 +73,8 @@ const REGULAR_PEER_SAMPLE_FREQ: u64 = 3;
 /// 3. Routes requests to peers that advertise availability for that data.
 /// 4. Maintains peer scores based on each peer's observed quality of service
 ///    and upper client reports of invalid or malicious data.
-/// 5. Selects high quality peers to send each request to.
+/// 5. Selects high quality peers to send each request to, favoring peers
+///    with lower observed request latencies.
 /// 6. Exposes a condensed data summary of our peers' data advertisements.
 ///
 /// The client currently assumes 1-request => 1-response. Streaming responses
@@ -92,6 +94,8 @@ pub struct AptosNetDataClient {
     global_summary_cache: Arc<RwLock<GlobalDataSummary>>,
     /// Used for generating the next request/response id.
     response_id_generator: Arc<U64IdGenerator>,
+    /// The service used to measure request latencies.
+    time_service: TimeService,
 }
 
 impl AptosNetDataClient {
@@ -113,6 +117,7 @@ impl AptosNetDataClient {
             ))),
             global_summary_cache: Arc::new(RwLock::new(GlobalDataSummary::empty())),
             response_id_generator: Arc::new(U64IdGenerator::new()),
+            time_service: time_service.clone(),
         };
         let poller = DataSummaryPoller::new(
             client.clone(),
@@ -160,15 +165,56 @@ impl AptosNetDataClient {
             self.identify_serviceable(regular_peers, request)
         };
 
-        // Randomly select a peer to handle the request
-        serviceable_peers
-            .choose(&mut rand::thread_rng())
-            .copied()
-            .ok_or_else(|| {
-                Error::DataIsUnavailable(
-                    format!("No connected peers are advertising that they can serve this data! Request: {:?}",request),
-                )
-            })
+        // Select a peer to handle the request
+        let selected_peer = if self.data_client_config.latency_weighted_peer_selection {
+            self.choose_peer_by_latency(serviceable_peers)
+        } else {
+            serviceable_peers.choose(&mut rand::thread_rng()).copied()
+        };
+        selected_peer.ok_or_else(|| {
+            Error::DataIsUnavailable(format!(
+                "No connected peers are advertising that they can serve this data! Request: {:?}",
+                request
+            ))
+        })
+    }
+
+    /// Randomly selects a peer from the given set, weighting each peer by
+    /// the inverse of its average request latency. Because data responses
+    /// include the transfer time, this favors peers that are both close
+    /// and fast. Peers without any latency measurements are weighted as an
+    /// average peer, so that they are still selected (and measured).
+    fn choose_peer_by_latency(&self, peers: Vec<PeerNetworkId>) -> Option<PeerNetworkId> {
+        // Calculate the weights of the peers with latency measurements
+        let peer_weights: Vec<Option<f64>> = {
+            let peer_states = self.peer_states.read();
+            peers
+                .iter()
+                .map(|peer| {
+                    peer_states
+                        .get_average_latency_secs(peer)
+                        .map(|latency_secs| 1.0 / latency_secs.max(MIN_PEER_LATENCY_SECS))
+                })
+                .collect()
+        };
+        let measured_weights: Vec<f64> = peer_weights.iter().flatten().copied().collect();
+
+        // If no peers have been measured, select a peer uniformly at random
+        if measured_weights.is_empty() {
+            return peers.choose(&mut rand::thread_rng()).copied();
+        }
+
+        // Otherwise, select a peer using the weights
+        let default_weight = measured_weights.iter().sum::<f64>() / measured_weights.len() as f64;
+        let weighted_peers: Vec<(PeerNetworkId, f64)> = peers
+            .into_iter()
+            .zip(peer_weights)
+            .map(|(peer, weight)| (peer, weight.unwrap_or(default_weight)))
+            .collect();
+        weighted_peers
+            .choose_weighted(&mut rand::thread_rng(), |(_, weight)| *weight)
+            .ok()
+            .map(|(peer, _)| *peer)
     }
 
     /// Identifies the peers in the given set of prospective peers
@@ -373,6 +419,7 @@ impl AptosNetDataClient {
 
         increment_request_counter(&metrics::SENT_REQUESTS, &request.get_label(), peer);
 
+        let request_start_time = self.time_service.now();
         let result = self
             .network_client
             .send_request(
@@ -402,6 +449,18 @@ impl AptosNetDataClient {
                 // feels simpler for the consumer.
                 self.peer_states.write().update_score_success(peer);
 
+                // Record the request latency so that faster peers are preferred
+                let request_latency = self.time_service.now().duration_since(request_start_time);
+                let average_latency_secs = self
+                    .peer_states
+                    .write()
+                    .update_average_latency(peer, request_latency);
+                set_peer_gauge(
+                    &metrics::AVERAGE_PEER_LATENCIES,
+                    peer,
+                    (average_latency_secs * 1000.0) as u64,
+                );
+
                 // Package up all of the context needed to fully report an error
                 // with this RPC.
                 let response_callback = AptosNetResponseCallback {
@@ -17,6 +17,7 @@ use std::{
     cmp::min,
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::Duration,
 };
 use storage_service_types::requests::StorageServiceRequest;
 use storage_service_types::responses::StorageServerSummary;
@@ -33,6 +34,8 @@ const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
 const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
+/// The weight given to the latest latency sample in the moving average.
+const LATENCY_SAMPLE_WEIGHT: f64 = 0.2;
 
 pub(crate) enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
@@ -61,6 +64,9 @@ struct PeerState {
     storage_summary: Option<StorageServerSummary>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// An exponentially weighted moving average of the observed request
+    /// latencies (in seconds), or `None` if no responses have been received.
+    average_latency_secs: Option<f64>,
 }
 
 impl Default for PeerState {
@@ -68,6 +74,7 @@ impl Default for PeerState {
         Self {
             storage_summary: None,
             score: STARTING_SCORE,
+            average_latency_secs: None,
         }
     }
 }
@@ -100,6 +107,18 @@ impl PeerState {
         };
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
+
+    /// Updates the average request latency of the peer using the new sample
+    fn update_average_latency(&mut self, latency: Duration) {
+        let latency_secs = latency.as_secs_f64();
+        self.average_latency_secs = Some(match self.average_latency_secs {
+            Some(average_latency_secs) => {
+                LATENCY_SAMPLE_WEIGHT * latency_secs
+                    + (1.0 - LATENCY_SAMPLE_WEIGHT) * average_latency_secs
+            }
+            None => latency_secs,
+        });
+    }
 }
 
 /// Contains all of the unbanned peers' most recent [`StorageServerSummary`] data
@@ -190,6 +209,22 @@ impl PeerStates {
         }
     }
 
+    /// Records the latency of a successful request to the peer and
+    /// returns the peer's new average latency (in seconds).
+    pub fn update_average_latency(&mut self, peer: PeerNetworkId, latency: Duration) -> f64 {
+        let peer_state = self.peer_to_state.entry(peer).or_default();
+        peer_state.update_average_latency(latency);
+        peer_state.average_latency_secs.unwrap_or_default()
+    }
+
+    /// Returns the average request latency (in seconds) of the peer,
+    /// or `None` if no latency has been observed for the peer yet.
+    pub fn get_average_latency_secs(&self, peer: &PeerNetworkId) -> Option<f64> {
+        self.peer_to_state
+            .get(peer)
+            .and_then(|peer_state| peer_state.average_latency_secs)
+    }
+
     /// Returns the number of in-flight priority polls
     pub fn num_in_flight_priority_polls(&self) -> u64 {
         self.in_flight_priority_polls.len() as u64
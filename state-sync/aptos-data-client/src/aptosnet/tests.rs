@@ -27,7 +27,11 @@ use network::{
     protocols::{network::NewNetworkSender, wire::handshake::v1::ProtocolId},
     transport::ConnectionMetadata,
 };
-use std::{collections::hash_map::Entry, sync::Arc, time::Duration};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+    time::Duration,
+};
 use storage_service_client::{StorageServiceClient, StorageServiceNetworkSender};
 use storage_service_server::network::{NetworkRequest, ResponseSender};
 use storage_service_types::requests::{
@@ -871,6 +875,97 @@ async fn all_peer_request_selection() {
     assert!(peer_for_request == priority_peer_1 || peer_for_request == priority_peer_2);
 }
 
+#[tokio::test]
+async fn request_latencies_are_recorded() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, mock_time, client, _) = MockNetwork::new(None, None, None);
+
+    // Add a priority peer that advertises the data
+    let peer = mock_network.add_peer(true);
+    client.update_summary(peer, mock_storage_summary(200));
+
+    // Verify no latency has been recorded for the peer yet
+    assert_none!(client.peer_states.read().get_average_latency_secs(&peer));
+
+    // Send requests that take different amounts of time to respond to
+    for (response_delay_ms, expected_latency_secs) in [(100, 0.1), (600, 0.2)] {
+        // Send the request to the peer
+        let request_client = client.clone();
+        let request_handle = tokio::spawn(async move {
+            request_client
+                .get_transactions_with_proof(100, 50, 100, false)
+                .await
+        });
+
+        // Advance time and respond to the request
+        let (_, _, _, response_sender) = mock_network.next_request().await.unwrap();
+        mock_time.advance_ms_async(response_delay_ms).await;
+        let data_response =
+            DataResponse::TransactionsWithProof(TransactionListWithProof::new_empty());
+        response_sender.send(Ok(StorageServiceResponse::new(data_response, true).unwrap()));
+        request_handle.await.unwrap().unwrap();
+
+        // Verify the average latency of the peer was updated
+        let average_latency_secs = client
+            .peer_states
+            .read()
+            .get_average_latency_secs(&peer)
+            .unwrap();
+        assert!((average_latency_secs - expected_latency_secs).abs() < 1e-9);
+    }
+}
+
+#[tokio::test]
+async fn latency_weighted_peer_selection() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+    let storage_request = StorageServiceRequest::new(DataRequest::GetStorageServerSummary, true);
+
+    // Add a fast and a slow priority peer
+    let fast_peer = mock_network.add_peer(true);
+    let slow_peer = mock_network.add_peer(true);
+    update_peer_latency(&client, fast_peer, 10);
+    update_peer_latency(&client, slow_peer, 1_000);
+
+    // Select peers many times and verify the fast peer is strongly preferred
+    let selected_peers = select_peers_for_request(&client, &storage_request, 1_000);
+    assert!(selected_peers.get(&fast_peer).copied().unwrap_or_default() > 900);
+
+    // Add a peer with no latency measurements and verify it is still selected
+    let new_peer = mock_network.add_peer(true);
+    let selected_peers = select_peers_for_request(&client, &storage_request, 1_000);
+    assert!(selected_peers.get(&new_peer).copied().unwrap_or_default() > 100);
+
+    // Verify the new peer is preferred over the slow peer
+    assert!(
+        selected_peers.get(&new_peer).copied().unwrap_or_default()
+            > selected_peers.get(&slow_peer).copied().unwrap_or_default()
+    );
+}
+
+#[tokio::test]
+async fn latency_weighted_peer_selection_disabled() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Disable latency weighted peer selection
+    let data_client_config = AptosDataClientConfig {
+        latency_weighted_peer_selection: false,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+    let storage_request = StorageServiceRequest::new(DataRequest::GetStorageServerSummary, true);
+
+    // Add a fast and a slow priority peer
+    let fast_peer = mock_network.add_peer(true);
+    let slow_peer = mock_network.add_peer(true);
+    update_peer_latency(&client, fast_peer, 10);
+    update_peer_latency(&client, slow_peer, 1_000);
+
+    // Select peers many times and verify the slow peer is selected as often as random
+    let selected_peers = select_peers_for_request(&client, &storage_request, 1_000);
+    assert!(selected_peers.get(&slow_peer).copied().unwrap_or_default() > 300);
+}
+
 #[tokio::test]
 async fn validator_peer_prioritization() {
     ::aptos_logger::Logger::init_for_testing();
@@ -1427,6 +1522,29 @@ fn fetch_peer_to_poll(
     result
 }
 
+/// Selects a peer for the given request the specified number of times
+/// and returns the number of times each peer was selected.
+fn select_peers_for_request(
+    client: &AptosNetDataClient,
+    request: &StorageServiceRequest,
+    num_selections: u64,
+) -> HashMap<PeerNetworkId, u64> {
+    let mut selected_peers = HashMap::new();
+    for _ in 0..num_selections {
+        let peer = client.choose_peer_for_request(request).unwrap();
+        *selected_peers.entry(peer).or_default() += 1;
+    }
+    selected_peers
+}
+
+/// Records a request latency (in milliseconds) for the given peer
+fn update_peer_latency(client: &AptosNetDataClient, peer: PeerNetworkId, latency_ms: u64) {
+    client
+        .peer_states
+        .write()
+        .update_average_latency(peer, Duration::from_millis(latency_ms));
+}
+
 /// Fetches the number of in flight requests for peers depending on priority
 fn get_num_in_flight_polls(client: AptosNetDataClient, is_priority_peer: bool) -> u64 {
     if is_priority_peer {
@@ -15,6 +15,10 @@ use futures::{
 use serde::{Deserialize, Serialize};
 use std::{
     pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
     time::Duration,
 };
@@ -268,6 +272,32 @@ impl ConsensusSyncNotification {
     }
 }
 
+/// The execution status of the consensus observer (on validator fullnodes),
+/// shared by the observer and state sync. The observer is the only writer: it
+/// marks itself active before handing over execution (via a sync request) and
+/// inactive when it stops executing. State sync only syncs while it's inactive,
+/// so the chunk executor and the block executor never write at the same time.
+#[derive(Clone, Debug, Default)]
+pub struct ConsensusObserverStatus {
+    active: Arc<AtomicBool>,
+}
+
+impl ConsensusObserverStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true iff the consensus observer is executing and committing blocks
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Updates the execution status of the consensus observer
+    pub fn set_active(&self, active: bool) {
+        self.active.store(active, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{ConsensusNotification, ConsensusNotificationSender, Error};
@@ -19,13 +19,14 @@ use crate::{
     utils,
     utils::PENDING_DATA_LOG_FREQ_SECS,
 };
-use aptos_config::config::{RoleType, StateSyncDriverConfig};
+use aptos_config::config::{ConsensusObserverConfig, RoleType, StateSyncDriverConfig};
 use aptos_data_client::AptosDataClient;
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::waypoint::Waypoint;
 use consensus_notifications::{
-    ConsensusCommitNotification, ConsensusNotification, ConsensusSyncNotification,
+    ConsensusCommitNotification, ConsensusNotification, ConsensusObserverStatus,
+    ConsensusSyncNotification,
 };
 use data_streaming_service::streaming_client::{
     DataStreamingClient, NotificationAndFeedback, NotificationFeedback,
@@ -48,6 +49,9 @@ pub struct DriverConfiguration {
     // The config file of the driver
     pub config: StateSyncDriverConfig,
 
+    // The config of the consensus observer (used by validator fullnodes)
+    pub consensus_observer_config: ConsensusObserverConfig,
+
     // The role of the node
     pub role: RoleType,
 
@@ -56,9 +60,15 @@ pub struct DriverConfiguration {
 }
 
 impl DriverConfiguration {
-    pub fn new(config: StateSyncDriverConfig, role: RoleType, waypoint: Waypoint) -> Self {
+    pub fn new(
+        config: StateSyncDriverConfig,
+        consensus_observer_config: ConsensusObserverConfig,
+        role: RoleType,
+        waypoint: Waypoint,
+    ) -> Self {
         Self {
             config,
+            consensus_observer_config,
             role,
             waypoint,
         }
@@ -85,6 +95,9 @@ pub struct StateSyncDriver<
     // The handler for notifications from consensus
     consensus_notification_handler: ConsensusNotificationHandler,
 
+    // The execution status of the consensus observer (only active on validator fullnodes)
+    consensus_observer_status: ConsensusObserverStatus,
+
     // The component that manages the continuous syncing of the node
     continuous_syncer: ContinuousSyncer<StorageSyncer, StreamingClient>,
 
@@ -126,6 +139,7 @@ impl<
         client_notification_listener: ClientNotificationListener,
         commit_notification_listener: CommitNotificationListener,
         consensus_notification_handler: ConsensusNotificationHandler,
+        consensus_observer_status: ConsensusObserverStatus,
         driver_configuration: DriverConfiguration,
         error_notification_listener: ErrorNotificationListener,
         event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
@@ -155,6 +169,7 @@ impl<
             client_notification_listener,
             commit_notification_listener,
             consensus_notification_handler,
+            consensus_observer_status,
             continuous_syncer,
             aptos_data_client,
             driver_configuration,
@@ -200,9 +215,15 @@ impl<
 
     /// Handles a notification sent by consensus
     async fn handle_consensus_notification(&mut self, notification: ConsensusNotification) {
-        // Verify the notification: full nodes shouldn't receive notifications
-        // and consensus should only send notifications after bootstrapping!
-        let result = if self.driver_configuration.role == RoleType::FullNode {
+        // Verify the notification: full nodes shouldn't receive notifications (unless
+        // they run the consensus observer) and consensus should only send notifications
+        // after bootstrapping!
+        let result = if self.driver_configuration.role == RoleType::FullNode
+            && !self
+                .driver_configuration
+                .consensus_observer_config
+                .observer_enabled
+        {
             Err(Error::FullNodeConsensusNotification(format!(
                 "Received consensus notification: {:?}",
                 notification
@@ -348,6 +369,15 @@ impl<
             metrics::DRIVER_CONSENSUS_SYNC_NOTIFICATION,
         );
 
+        // On full nodes, the consensus observer takes over execution with a sync
+        // request. Stop the continuous syncer and wait for it to finish writing, as
+        // the observer starts executing as soon as the request is handled.
+        if self.driver_configuration.role == RoleType::FullNode {
+            self.continuous_syncer.reset_active_stream(None).await?;
+            self.wait_for_storage_synchronizer_to_drain().await;
+            self.storage_synchronizer.finish_chunk_executor(); // The observer is now in control
+        }
+
         // Initialize a new sync request
         let latest_synced_ledger_info =
             utils::fetch_latest_synced_ledger_info(self.storage.clone())?;
@@ -462,15 +492,7 @@ impl<
 
         // Wait for the storage synchronizer to drain (if it hasn't already).
         // This prevents notifying consensus prematurely.
-        while self.storage_synchronizer.pending_storage_data() {
-            sample!(
-                SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
-                info!("Waiting for the storage synchronizer to handle pending data!")
-            );
-
-            // Yield to avoid starving the storage synchronizer threads.
-            yield_now().await;
-        }
+        self.wait_for_storage_synchronizer_to_drain().await;
 
         // Refresh the latest synced ledger info and handle the sync request
         let latest_synced_ledger_info =
@@ -488,6 +510,19 @@ impl<
         Ok(())
     }
 
+    /// Waits until the storage synchronizer has handled all pending data
+    async fn wait_for_storage_synchronizer_to_drain(&self) {
+        while self.storage_synchronizer.pending_storage_data() {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
+                info!("Waiting for the storage synchronizer to handle pending data!")
+            );
+
+            // Yield to avoid starving the storage synchronizer threads.
+            yield_now().await;
+        }
+    }
+
     /// Returns true iff there's an active sync request from consensus
     fn active_sync_request(&self) -> bool {
         self.consensus_notification_handler.active_sync_request()
@@ -498,9 +533,18 @@ impl<
         self.driver_configuration.role == RoleType::Validator
     }
 
-    /// Returns true iff consensus is currently executing
+    /// Returns true iff the consensus observer is executing and committing
+    /// blocks (on full nodes). The observer takes over execution through a
+    /// sync request (see `handle_consensus_sync_notification`).
+    fn check_if_consensus_observer_executing(&self) -> bool {
+        self.consensus_observer_status.is_active()
+    }
+
+    /// Returns true iff consensus (or the consensus observer) is currently executing
     fn check_if_consensus_executing(&self) -> bool {
-        self.is_validator() && self.bootstrapper.is_bootstrapped() && !self.active_sync_request()
+        (self.is_validator() || self.check_if_consensus_observer_executing())
+            && self.bootstrapper.is_bootstrapped()
+            && !self.active_sync_request()
     }
 
     /// Checks if the connection deadline has passed. If so, validators with
@@ -16,7 +16,7 @@ use aptos_data_client::aptosnet::AptosNetDataClient;
 use aptos_infallible::Mutex;
 use aptos_types::move_resource::MoveStorage;
 use aptos_types::waypoint::Waypoint;
-use consensus_notifications::ConsensusNotificationListener;
+use consensus_notifications::{ConsensusNotificationListener, ConsensusObserverStatus};
 use data_streaming_service::streaming_client::StreamingServiceClient;
 use event_notifications::{EventNotificationSender, EventSubscriptionService};
 use executor_types::ChunkExecutorTrait;
@@ -48,6 +48,7 @@ impl DriverFactory {
         mempool_notification_sender: MempoolNotifier,
         metadata_storage: MetadataStorage,
         consensus_listener: ConsensusNotificationListener,
+        consensus_observer_status: ConsensusObserverStatus,
         mut event_subscription_service: EventSubscriptionService,
         aptos_data_client: AptosNetDataClient,
         streaming_service_client: StreamingServiceClient,
@@ -109,6 +110,7 @@ impl DriverFactory {
         // Create the driver configuration
         let driver_configuration = DriverConfiguration::new(
             node_config.state_sync.state_sync_driver,
+            node_config.consensus_observer,
             node_config.base.role,
             waypoint,
         );
@@ -118,6 +120,7 @@ impl DriverFactory {
             client_notification_listener,
             commit_notification_listener,
             consensus_notification_handler,
+            consensus_observer_status,
             driver_configuration,
             error_notification_listener,
             event_subscription_service,
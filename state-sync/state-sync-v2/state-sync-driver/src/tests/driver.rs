@@ -22,7 +22,9 @@ use aptos_types::{
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
 use claim::{assert_err, assert_none};
-use consensus_notifications::{ConsensusNotificationSender, ConsensusNotifier};
+use consensus_notifications::{
+    ConsensusNotificationSender, ConsensusNotifier, ConsensusObserverStatus,
+};
 use data_streaming_service::streaming_client::new_streaming_service_client_listener_pair;
 use event_notifications::{
     EventNotificationListener, EventSubscriptionService, ReconfigNotificationListener,
@@ -69,6 +71,31 @@ async fn test_consensus_commit_notification() {
     assert_err!(result);
 }
 
+#[tokio::test]
+async fn test_consensus_observer_notifications() {
+    // Create a driver for a full node running the consensus observer
+    let mut node_config = NodeConfig::default();
+    node_config.base.role = RoleType::FullNode;
+    node_config.consensus_observer.observer_enabled = true;
+    let (_full_node_driver, consensus_notifier, _, _, _) =
+        create_driver_for_tests(node_config, Waypoint::default(), None).await;
+
+    // Verify that commit notifications are no longer rejected because the node
+    // is a full node, but only because the node isn't bootstrapped.
+    let error = consensus_notifier
+        .notify_new_commit(vec![create_transaction()], vec![])
+        .await
+        .unwrap_err();
+    verify_bootstrap_not_complete_error(error);
+
+    // Verify the same for sync requests
+    let error = consensus_notifier
+        .sync_to_target(create_ledger_info_at_version(0))
+        .await
+        .unwrap_err();
+    verify_bootstrap_not_complete_error(error);
+}
+
 #[tokio::test]
 async fn test_mempool_commit_notifications() {
     // Create a driver for a validator with a waypoint at version 0
@@ -181,6 +208,16 @@ async fn test_consensus_sync_request() {
     assert_err!(result);
 }
 
+/// Verifies that the given error was returned because the node isn't bootstrapped
+fn verify_bootstrap_not_complete_error(error: consensus_notifications::Error) {
+    match error {
+        consensus_notifications::Error::UnexpectedErrorEncountered(message) => {
+            assert!(message.contains("BootstrapNotComplete"));
+        }
+        error => panic!("Unexpected consensus notification error: {:?}", error),
+    }
+}
+
 /// Creates a state sync driver for a validator node
 async fn create_validator_driver(
     event_key_subscriptions: Option<Vec<EventKey>>,
@@ -288,6 +325,7 @@ async fn create_driver_for_tests(
         mempool_notifier,
         metadata_storage,
         consensus_listener,
+        ConsensusObserverStatus::new(),
         event_subscription_service,
         aptos_data_client,
         streaming_service_client,
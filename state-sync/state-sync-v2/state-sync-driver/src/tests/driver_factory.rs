@@ -17,7 +17,7 @@ use aptos_time_service::TimeService;
 use aptos_types::on_chain_config::ON_CHAIN_CONFIG_REGISTRY;
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
-use consensus_notifications::new_consensus_notifier_listener_pair;
+use consensus_notifications::{new_consensus_notifier_listener_pair, ConsensusObserverStatus};
 use data_streaming_service::streaming_client::new_streaming_service_client_listener_pair;
 use event_notifications::EventSubscriptionService;
 use executor::chunk_executor::ChunkExecutor;
@@ -91,6 +91,7 @@ fn test_new_initialized_configs() {
         mempool_notifier,
         metadata_storage,
         consensus_listener,
+        ConsensusObserverStatus::new(),
         event_subscription_service,
         aptos_data_client,
         streaming_service_client,
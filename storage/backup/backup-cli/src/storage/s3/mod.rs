@@ -0,0 +1,499 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+mod signer;
+mod writer;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    storage::{
+        s3::{
+            signer::{authorization, sha256_hex, uri_encode, Credentials, AMZ_DATE_FORMAT},
+            writer::UploadingFile,
+        },
+        BackupHandle, BackupHandleRef, BackupStorage, FileHandle, FileHandleRef, ShellSafeName,
+        TextLine,
+    },
+    utils::error_notes::ErrorNotes,
+};
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_logger::prelude::*;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{stream, FutureExt, StreamExt, TryStreamExt};
+use itertools::Itertools;
+use regex::Regex;
+use reqwest::{Method, Response, StatusCode};
+use std::time::Duration;
+use structopt::StructOpt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use url::Url;
+
+#[derive(StructOpt)]
+pub struct S3Opt {
+    #[structopt(
+        long = "endpoint",
+        default_value = "https://s3.amazonaws.com",
+        help = "S3 API endpoint, the bucket is addressed in path style under it. \
+        Use https://storage.googleapis.com (with HMAC keys) for GCS, or the address of any \
+        other S3 compatible server."
+    )]
+    pub endpoint: Url,
+    #[structopt(
+        long = "region",
+        default_value = "us-east-1",
+        help = "Region of the bucket, to sign requests with. Use \"auto\" for GCS."
+    )]
+    pub region: String,
+    #[structopt(long = "bucket", help = "Bucket to hold backups.")]
+    pub bucket: String,
+    #[structopt(
+        long = "prefix",
+        default_value = "",
+        help = "Prefix of the object keys, i.e. the folder in the bucket to hold backups."
+    )]
+    pub prefix: String,
+    #[structopt(
+        long = "access-key-id",
+        env = "AWS_ACCESS_KEY_ID",
+        hide_env_values = true
+    )]
+    pub access_key_id: String,
+    #[structopt(
+        long = "secret-access-key",
+        env = "AWS_SECRET_ACCESS_KEY",
+        hide_env_values = true
+    )]
+    pub secret_access_key: String,
+    #[structopt(
+        long = "part-size",
+        default_value = "16777216",
+        help = "Size in bytes of the parts of multipart uploads, files smaller than that are \
+        uploaded in a single request. S3 requires at least 5MiB."
+    )]
+    pub part_size: usize,
+    #[structopt(
+        long = "concurrent-part-uploads",
+        default_value = "4",
+        help = "Number of parts of a file uploaded concurrently."
+    )]
+    pub concurrent_part_uploads: usize,
+    #[structopt(
+        long = "max-retries",
+        default_value = "5",
+        help = "Number of times a request failing with a network error or a server error is \
+        retried, with exponential backoff."
+    )]
+    pub max_retries: usize,
+}
+
+/// A storage backend that talks to S3, or any storage offering an S3 compatible API like GCS or
+/// MinIO, natively.
+#[derive(Clone)]
+pub struct S3 {
+    client: reqwest::Client,
+    endpoint: Url,
+    region: String,
+    bucket: String,
+    /// Either empty or ends with '/'.
+    prefix: String,
+    credentials: Credentials,
+    part_size: usize,
+    concurrent_part_uploads: usize,
+    max_retries: usize,
+}
+
+impl S3 {
+    const METADATA_DIR: &'static str = "metadata";
+    const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+    /// Buffer between the writer of a file and the task uploading it.
+    const PIPE_BUFFER_SIZE: usize = 1 << 20;
+
+    pub fn new_with_opt(opt: S3Opt) -> Result<Self> {
+        ensure!(opt.part_size > 0, "--part-size must be positive.");
+        ensure!(
+            opt.concurrent_part_uploads > 0,
+            "--concurrent-part-uploads must be positive."
+        );
+        let prefix = match opt.prefix.trim_matches('/') {
+            "" => String::new(),
+            prefix => format!("{}/", prefix),
+        };
+
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .build()
+                .expect("Http client should build."),
+            endpoint: opt.endpoint,
+            region: opt.region,
+            bucket: opt.bucket,
+            prefix,
+            credentials: Credentials {
+                access_key_id: opt.access_key_id,
+                secret_access_key: opt.secret_access_key,
+            },
+            part_size: opt.part_size,
+            concurrent_part_uploads: opt.concurrent_part_uploads,
+            max_retries: opt.max_retries,
+        })
+    }
+
+    fn key(&self, file_handle: &FileHandleRef) -> String {
+        format!("{}{}", self.prefix, file_handle)
+    }
+
+    fn url(&self, key: &str, query: &[(&str, &str)]) -> Url {
+        let mut url = self.endpoint.clone();
+        let mut path = format!(
+            "{}/{}",
+            url.path().trim_end_matches('/'),
+            uri_encode(&self.bucket, true)
+        );
+        if !key.is_empty() {
+            path.push('/');
+            path.push_str(&uri_encode(key, false));
+        }
+        url.set_path(&path);
+        let query = query
+            .iter()
+            .map(|(key, value)| format!("{}={}", uri_encode(key, true), uri_encode(value, true)))
+            .sorted()
+            .join("&");
+        url.set_query(if query.is_empty() { None } else { Some(&query) });
+        url
+    }
+
+    /// Sends a signed request, retrying on network errors and server errors.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, &str)],
+        body: Bytes,
+    ) -> Result<Response> {
+        let url = self.url(key, query);
+        let payload_sha256 = sha256_hex(&body);
+        let mut retries = 0;
+        loop {
+            let res = self
+                .send_once(method.clone(), &url, &payload_sha256, body.clone())
+                .await;
+            let err = match res {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+                    let err = format_err!(
+                        "{} {} failed with status {}: {}",
+                        method,
+                        url,
+                        status,
+                        response.text().await.unwrap_or_default(),
+                    );
+                    if !(status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS) {
+                        return Err(err);
+                    }
+                    err
+                }
+                Err(e) => format_err!("{} {} failed: {}", method, url, e),
+            };
+            if retries >= self.max_retries {
+                return Err(err);
+            }
+            let delay = Self::RETRY_BASE_DELAY * 2u32.pow(retries as u32);
+            warn!(error = %err, retries = retries, "S3 request failed, retrying in {:?}.", delay);
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
+    }
+
+    async fn send_once(
+        &self,
+        method: Method,
+        url: &Url,
+        payload_sha256: &str,
+        body: Bytes,
+    ) -> reqwest::Result<Response> {
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let time = Utc::now();
+        let amz_date = time.format(AMZ_DATE_FORMAT).to_string();
+        let authorization = authorization(
+            &self.credentials,
+            &self.region,
+            method.as_str(),
+            url,
+            &[
+                ("host", &host),
+                ("x-amz-content-sha256", payload_sha256),
+                ("x-amz-date", &amz_date),
+            ],
+            payload_sha256,
+            time,
+        );
+        self.client
+            .request(method, url.clone())
+            .header("x-amz-content-sha256", payload_sha256)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await
+    }
+
+    async fn put_object(&self, key: &str, content: Bytes) -> Result<()> {
+        self.send(Method::PUT, key, &[], content).await?;
+        Ok(())
+    }
+
+    /// Uploads what's read from `reader`, as a multipart upload if there's more than one part.
+    async fn upload(self, key: String, mut reader: impl AsyncRead + Send + Unpin) -> Result<()> {
+        let first_part = read_part(&mut reader, self.part_size).await?;
+        if first_part.len() < self.part_size {
+            return self.put_object(&key, first_part).await;
+        }
+
+        let upload_id = self.create_multipart_upload(&key).await?;
+        match self
+            .upload_parts(&key, &upload_id, first_part, reader)
+            .await
+        {
+            Ok(etags) => {
+                self.complete_multipart_upload(&key, &upload_id, etags)
+                    .await
+            }
+            Err(e) => {
+                if let Err(abort_err) = self.abort_multipart_upload(&key, &upload_id).await {
+                    warn!(error = %abort_err, key = key, "Failed to abort multipart upload.");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn create_multipart_upload(&self, key: &str) -> Result<String> {
+        let body = self
+            .send(Method::POST, key, &[("uploads", "")], Bytes::new())
+            .await?
+            .text()
+            .await?;
+        xml_values(&body, "UploadId")
+            .into_iter()
+            .next()
+            .ok_or_else(|| format_err!("No UploadId in response: {}", body))
+    }
+
+    /// Uploads the parts concurrently, returns their ETags in order.
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        first_part: Bytes,
+        reader: impl AsyncRead + Send + Unpin,
+    ) -> Result<Vec<String>> {
+        let part_size = self.part_size;
+        stream::try_unfold(
+            (Some(first_part), reader),
+            move |(first_part, mut reader)| async move {
+                let part = match first_part {
+                    Some(part) => part,
+                    None => read_part(&mut reader, part_size).await?,
+                };
+                Ok::<_, anyhow::Error>(if part.is_empty() {
+                    None
+                } else {
+                    Some((part, (None, reader)))
+                })
+            },
+        )
+        .enumerate()
+        .map(|(i, part)| async move { self.upload_part(key, upload_id, i + 1, part?).await })
+        .buffered(self.concurrent_part_uploads)
+        .try_collect()
+        .await
+    }
+
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: usize,
+        part: Bytes,
+    ) -> Result<String> {
+        let response = self
+            .send(
+                Method::PUT,
+                key,
+                &[
+                    ("partNumber", &part_number.to_string()),
+                    ("uploadId", upload_id),
+                ],
+                part,
+            )
+            .await?;
+        Ok(response
+            .headers()
+            .get("etag")
+            .ok_or_else(|| format_err!("No ETag for part {} of {}", part_number, key))?
+            .to_str()?
+            .to_string())
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        etags: Vec<String>,
+    ) -> Result<()> {
+        let parts: String = etags
+            .iter()
+            .enumerate()
+            .map(|(i, etag)| {
+                format!(
+                    "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                    i + 1,
+                    etag
+                )
+            })
+            .collect();
+        let body = format!(
+            "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
+            parts
+        );
+        let response = self
+            .send(Method::POST, key, &[("uploadId", upload_id)], body.into())
+            .await?
+            .text()
+            .await?;
+        // The request can fail after the response status 200 is sent.
+        ensure!(
+            !response.contains("<Error>"),
+            "Failed to complete multipart upload of {}: {}",
+            key,
+            response
+        );
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()> {
+        self.send(
+            Method::DELETE,
+            key,
+            &[("uploadId", upload_id)],
+            Bytes::new(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BackupStorage for S3 {
+    async fn create_backup(&self, name: &ShellSafeName) -> Result<BackupHandle> {
+        // There are no folders to create, files of a backup simply share the same key prefix.
+        Ok(name.to_string())
+    }
+
+    async fn create_for_write(
+        &self,
+        backup_handle: &BackupHandleRef,
+        name: &ShellSafeName,
+    ) -> Result<(FileHandle, Box<dyn AsyncWrite + Send + Unpin>)> {
+        let file_handle = format!("{}/{}", backup_handle, name.as_ref());
+        let key = self.key(&file_handle);
+        let (writer, reader) = tokio::io::duplex(Self::PIPE_BUFFER_SIZE);
+        let upload = tokio::spawn(
+            self.clone()
+                .upload(key.clone(), reader)
+                .map(move |res| res.err_notes(&key)),
+        );
+        Ok((file_handle, Box::new(UploadingFile::new(writer, upload))))
+    }
+
+    async fn open_for_read(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        let response = self
+            .send(Method::GET, &self.key(file_handle), &[], Bytes::new())
+            .await
+            .err_notes(file_handle)?;
+        Ok(Box::new(
+            response
+                .bytes_stream()
+                .map_err(|e| futures::io::Error::new(futures::io::ErrorKind::Other, e))
+                .into_async_read()
+                .compat(),
+        ))
+    }
+
+    async fn save_metadata_line(&self, name: &ShellSafeName, content: &TextLine) -> Result<()> {
+        let file_handle = format!("{}/{}", Self::METADATA_DIR, name.as_ref());
+        self.put_object(
+            &self.key(&file_handle),
+            Bytes::copy_from_slice(content.as_ref().as_bytes()),
+        )
+        .await
+        .err_notes(name)
+    }
+
+    async fn list_metadata_files(&self) -> Result<Vec<FileHandle>> {
+        let prefix = self.key(&format!("{}/", Self::METADATA_DIR));
+        let mut res = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix.as_str())];
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", token.as_str()));
+            }
+            let body = self
+                .send(Method::GET, "", &query, Bytes::new())
+                .await
+                .err_notes(&prefix)?
+                .text()
+                .await?;
+            for key in xml_values(&body, "Key") {
+                match key.strip_prefix(&self.prefix) {
+                    Some(file_handle) => res.push(file_handle.to_string()),
+                    None => bail!("Listed key {} out of prefix {}", key, self.prefix),
+                }
+            }
+            if xml_values(&body, "IsTruncated").first().map(String::as_str) != Some("true") {
+                break;
+            }
+            continuation_token = Some(
+                xml_values(&body, "NextContinuationToken")
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| format_err!("No NextContinuationToken in {}", body))?,
+            );
+        }
+        Ok(res)
+    }
+}
+
+async fn read_part(reader: &mut (impl AsyncRead + Unpin), part_size: usize) -> Result<Bytes> {
+    let mut buf = Vec::with_capacity(part_size);
+    reader.take(part_size as u64).read_to_end(&mut buf).await?;
+    Ok(buf.into())
+}
+
+/// Returns the unescaped texts of the elements with the tag, in the (flat) XML responses of S3.
+fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    let re = Regex::new(&format!("<{0}>([^<]*)</{0}>", tag)).expect("Tag should be literal.");
+    re.captures_iter(xml)
+        .map(|captures| {
+            captures[1]
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&")
+        })
+        .collect()
+}
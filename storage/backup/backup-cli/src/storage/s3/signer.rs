@@ -0,0 +1,102 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! AWS Signature Version 4, as specified in
+//! https://docs.aws.amazon.com/AmazonS3/latest/API/sig-v4-header-based-auth.html
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac, NewMac};
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const SERVICE: &str = "s3";
+
+pub(super) const AMZ_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Clone)]
+pub(super) struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+pub(super) fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC accepts keys of any size.");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encodes everything but the unreserved characters, and '/' unless `encode_slash`.
+pub(super) fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Returns the value of the `Authorization` header of a request.
+///
+/// `url` must already be in canonical form, i.e. its path and query encoded with `uri_encode`.
+/// `headers` are the lowercase names and the values of the headers to sign, they must include
+/// `host`, `x-amz-content-sha256` and `x-amz-date`.
+pub(super) fn authorization(
+    credentials: &Credentials,
+    region: &str,
+    method: &str,
+    url: &Url,
+    headers: &[(&str, &str)],
+    payload_sha256: &str,
+    time: DateTime<Utc>,
+) -> String {
+    let headers: Vec<_> = headers.iter().sorted_by_key(|(name, _)| *name).collect();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers.iter().map(|(name, _)| *name).join(";");
+    let canonical_query = url.query().unwrap_or("").split('&').sorted().join("&");
+    let canonical_request = [
+        method,
+        url.path(),
+        &canonical_query,
+        &canonical_headers,
+        &signed_headers,
+        payload_sha256,
+    ]
+    .join("\n");
+
+    let date = time.format("%Y%m%d").to_string();
+    let scope = format!("{}/{}/{}/aws4_request", date, region, SERVICE);
+    let string_to_sign = [
+        ALGORITHM,
+        &time.format(AMZ_DATE_FORMAT).to_string(),
+        &scope,
+        &sha256_hex(canonical_request.as_bytes()),
+    ]
+    .join("\n");
+
+    let signing_key = [date.as_str(), region, SERVICE, "aws4_request"]
+        .iter()
+        .fold(
+            format!("AWS4{}", credentials.secret_access_key).into_bytes(),
+            |key, data| hmac_sha256(&key, data),
+        );
+    let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign));
+
+    format!(
+        "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+        ALGORITHM, credentials.access_key_id, scope, signed_headers, signature
+    )
+}
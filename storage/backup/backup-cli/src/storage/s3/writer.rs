@@ -0,0 +1,77 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncWrite, DuplexStream},
+    task::JoinHandle,
+};
+
+/// The bytes written are piped to a task uploading them, which is awaited upon shutdown so that
+/// the file is known to be fully uploaded once shut down.
+pub(super) struct UploadingFile {
+    pipe: DuplexStream,
+    pipe_shut_down: bool,
+    upload: Option<JoinHandle<Result<()>>>,
+}
+
+impl UploadingFile {
+    pub fn new(pipe: DuplexStream, upload: JoinHandle<Result<()>>) -> Self {
+        Self {
+            pipe,
+            pipe_shut_down: false,
+            upload: Some(upload),
+        }
+    }
+}
+
+impl AsyncWrite for UploadingFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, tokio::io::Error>> {
+        Pin::new(&mut self.pipe).poll_write(cx, buf)
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), tokio::io::Error>> {
+        Pin::new(&mut self.pipe).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), tokio::io::Error>> {
+        if !self.pipe_shut_down {
+            let res = Pin::new(&mut self.pipe).poll_shutdown(cx);
+            if let Poll::Ready(Ok(_)) = res {
+                // the upload task sees the end of the file
+                self.pipe_shut_down = true;
+            } else {
+                return res;
+            }
+        }
+
+        let upload = match self.upload.as_mut() {
+            Some(upload) => upload,
+            None => return Poll::Ready(Ok(())),
+        };
+        let res = match Pin::new(upload).poll(cx) {
+            Poll::Ready(res) => res,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.upload = None;
+        Poll::Ready(
+            res.map_err(|e| tokio::io::Error::new(tokio::io::ErrorKind::Other, e))?
+                .map_err(|e| tokio::io::Error::new(tokio::io::ErrorKind::Other, e)),
+        )
+    }
+}
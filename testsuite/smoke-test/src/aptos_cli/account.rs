@@ -25,8 +25,8 @@ async fn test_account_flow() {
             1,
             transfer_amount,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -54,3 +54,40 @@ async fn test_account_flow() {
     cli.assert_account_balance_now(2, DEFAULT_FUNDED_COINS)
         .await;
 }
+
+#[tokio::test]
+async fn test_simulate_and_estimate_gas() {
+    let (_swarm, cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .build_with_cli(2)
+        .await;
+
+    let transfer_amount = 100;
+    let simulated = cli
+        .simulate_transfer_coins(0, 1, transfer_amount)
+        .await
+        .unwrap();
+    assert_eq!(simulated.transaction.success, Some(true));
+    assert!(simulated.transaction.gas_used.unwrap() > 0);
+
+    // Nothing is submitted when simulating
+    cli.assert_account_balance_now(0, DEFAULT_FUNDED_COINS)
+        .await;
+    cli.assert_account_balance_now(1, DEFAULT_FUNDED_COINS)
+        .await;
+
+    // The gas unit price and max gas are estimated when not given
+    let response = cli
+        .transfer_coins(0, 1, transfer_amount, None)
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(Some(response.gas_used), simulated.transaction.gas_used);
+    cli.assert_account_balance_now(
+        0,
+        DEFAULT_FUNDED_COINS - (response.gas_used * response.gas_unit_price) - transfer_amount,
+    )
+    .await;
+    cli.assert_account_balance_now(1, DEFAULT_FUNDED_COINS + transfer_amount)
+        .await;
+}
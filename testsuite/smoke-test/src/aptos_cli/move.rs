@@ -122,6 +122,16 @@ async fn test_move_publish_flow() {
         .await
         .is_ok());
 
+    // The message can be read back with a view function
+    let view_function_id =
+        MemberId::from_str(&format!("{}::message::get_message", account)).unwrap();
+    let account_arg = format!("address:{}", account);
+    let message = cli
+        .view_function(view_function_id, vec![account_arg.as_str()], vec![])
+        .await
+        .expect("Should be able to view the message");
+    assert_eq!(message, vec![serde_json::json!("hello_world")]);
+
     // Now download the package. It will be stored in a directory PACKAGE_NAME inside move_dir.
     let _ = match cli
         .download_package(0, PACKAGE_NAME.to_owned(), cli.move_dir())
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    smoke_test_environment::new_local_swarm_with_aptos,
+    smoke_test_environment::{new_local_swarm_with_aptos, SwarmBuilder},
     test_utils::{assert_balance, create_and_fund_account, transfer_coins},
 };
 use aptos_config::{
@@ -12,13 +12,17 @@ use aptos_config::{
 use aptos_types::network_address::{NetworkAddress, Protocol};
 use forge::{NodeExt, Swarm, SwarmExt};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     net::Ipv4Addr,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 const MAX_WAIT_SECS: u64 = 60;
 
+const OBSERVER_LAST_COMMITTED_ROUND: &str = "aptos_consensus_observer_last_committed_round";
+const OBSERVER_STATE_SYNC_FALLBACKS: &str = "aptos_consensus_observer_state_sync_fallbacks";
+
 #[tokio::test]
 async fn test_full_node_basic_flow() {
     let mut swarm = new_local_swarm_with_aptos(1).await;
@@ -184,6 +188,94 @@ async fn test_vfn_failover() {
     assert_balance(&vfn_client, &account_1, 12).await;
 }
 
+#[tokio::test]
+async fn test_vfn_consensus_observer() {
+    // Create a validator that publishes blocks to its consensus observers
+    let mut swarm = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .with_init_config(Arc::new(|_, config, _| {
+            config.consensus_observer.publisher_enabled = true;
+        }))
+        .build()
+        .await;
+    let transaction_factory = swarm.chain_info().transaction_factory();
+    let version = swarm.versions().max().unwrap();
+
+    // Add a vfn that runs the consensus observer
+    let validator_peer_id = swarm.validators().next().unwrap().peer_id();
+    let mut vfn_config = NodeConfig::default_for_validator_full_node();
+    vfn_config.consensus_observer.observer_enabled = true;
+    let vfn_peer_id = swarm
+        .add_validator_fullnode(&version, vfn_config, validator_peer_id)
+        .unwrap();
+    for fullnode in swarm.full_nodes_mut() {
+        fullnode
+            .wait_until_healthy(Instant::now() + Duration::from_secs(MAX_WAIT_SECS))
+            .await
+            .unwrap();
+        fullnode
+            .wait_for_connectivity(Instant::now() + Duration::from_secs(MAX_WAIT_SECS))
+            .await
+            .unwrap();
+    }
+
+    // Setup accounts
+    let mut account_0 = create_and_fund_account(&mut swarm, 10).await;
+    let account_1 = create_and_fund_account(&mut swarm, 10).await;
+
+    // Wait until the observer has taken over execution from state sync
+    let vfn = swarm.full_node(vfn_peer_id).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(MAX_WAIT_SECS);
+    let start_round = loop {
+        if let Some(round) = vfn.get_metric(OBSERVER_LAST_COMMITTED_ROUND).await.unwrap() {
+            break round;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "The consensus observer didn't commit any blocks!"
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+
+    // Send transactions to the validator and verify the vfn commits them
+    let validator_client = swarm.validator(validator_peer_id).unwrap().rest_client();
+    let vfn_client = swarm.full_node(vfn_peer_id).unwrap().rest_client();
+    for balance in (5..10).rev() {
+        let txn = transfer_coins(
+            &validator_client,
+            &transaction_factory,
+            &mut account_0,
+            &account_1,
+            1,
+        )
+        .await;
+
+        vfn_client.wait_for_signed_transaction(&txn).await.unwrap();
+        assert_balance(&vfn_client, &account_0, balance).await;
+        assert_balance(&vfn_client, &account_1, 20 - balance).await;
+    }
+
+    // Verify the blocks were committed by the observer, without falling back
+    // to state sync (which would also sync the transactions).
+    let vfn = swarm.full_node(vfn_peer_id).unwrap();
+    let end_round = vfn
+        .get_metric(OBSERVER_LAST_COMMITTED_ROUND)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(end_round > start_round);
+    let num_fallbacks = vfn
+        .get_metric_with_fields(OBSERVER_STATE_SYNC_FALLBACKS, HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(num_fallbacks.unwrap_or(0), 0);
+
+    swarm
+        .wait_for_all_nodes_to_catchup(Instant::now() + Duration::from_secs(MAX_WAIT_SECS))
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_private_full_node() {
     let mut swarm = new_local_swarm_with_aptos(4).await;
@@ -13,7 +13,8 @@ use aptos_crypto::HashValue;
 use aptos_rest_client::aptos_api_types::UserTransaction;
 use aptos_rest_client::Transaction;
 use aptos_rosetta::types::{
-    AccountIdentifier, BlockResponse, Operation, OperationStatusType, OperationType,
+    AccountIdentifier, BlockResponse, MempoolRequest, MempoolTransactionRequest, Operation,
+    OperationStatusType, OperationType, SearchTransactionsRequest, TransactionIdentifier,
     TransactionType,
 };
 use aptos_rosetta::{
@@ -172,8 +173,8 @@ async fn test_account_balance() {
             1,
             TRANSFER_AMOUNT,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -193,8 +194,8 @@ async fn test_account_balance() {
             0,
             TRANSFER_AMOUNT,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -278,6 +279,8 @@ async fn test_block() {
 
     // Mapping of account to block and balance mappings
     let mut balances = BTreeMap::<AccountAddress, BTreeMap<u64, i128>>::new();
+    // Mapping of stake pool to block and total stake mappings
+    let mut stake_balances = BTreeMap::<AccountAddress, BTreeMap<u64, i128>>::new();
 
     // Wait until the Rosetta service is ready
     let request = NetworkRequest {
@@ -404,6 +407,59 @@ async fn test_block() {
     .await
     .unwrap();
 
+    // Stake with account 2, the stake pool starts with an expired lockup
+    cli.initialize_stake_owner(2, 1000, None, None)
+        .await
+        .unwrap();
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.add_stake(&network_identifier, private_key_2, 100, expiry_time, None)
+    })
+    .await
+    .unwrap();
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.unlock_stake(&network_identifier, private_key_2, 50, expiry_time, None)
+    })
+    .await
+    .unwrap();
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.withdraw_stake(&network_identifier, private_key_2, 50, expiry_time, None)
+    })
+    .await
+    .unwrap();
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.set_voter(
+            &network_identifier,
+            private_key_2,
+            account_id_0,
+            expiry_time,
+            None,
+        )
+    })
+    .await
+    .unwrap();
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.set_operator(
+            &network_identifier,
+            private_key_2,
+            account_id_1,
+            expiry_time,
+            None,
+        )
+    })
+    .await
+    .unwrap();
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.reset_lockup(&network_identifier, private_key_2, expiry_time, None)
+    })
+    .await
+    .unwrap();
+    // Nothing is unlocked anymore, so there's nothing to withdraw
+    stake_operation_and_wait(&rest_client, Duration::from_secs(5), |expiry_time| {
+        rosetta_client.withdraw_stake(&network_identifier, private_key_2, 50, expiry_time, None)
+    })
+    .await
+    .unwrap_err();
+
     // This one will fail
     let final_txn = transfer_and_wait(
         &rosetta_client,
@@ -504,7 +560,14 @@ async fn test_block() {
             .transactions
             .as_ref()
             .expect("Every actual block should have transactions");
-        parse_block_transactions(&block, &mut balances, actual_txns, &mut current_version).await;
+        parse_block_transactions(
+            &block,
+            &mut balances,
+            &mut stake_balances,
+            actual_txns,
+            &mut current_version,
+        )
+        .await;
 
         // The full block must have been processed
         assert_eq!(current_version - 1, actual_block.last_version.0);
@@ -515,13 +578,15 @@ async fn test_block() {
     }
 
     // Reconcile and ensure all balances are calculated correctly
-    check_balances(&rosetta_client, chain_id, balances).await;
+    check_balances(&rosetta_client, chain_id, balances, false).await;
+    check_balances(&rosetta_client, chain_id, stake_balances, true).await;
 }
 
 /// Parse the transactions in each block
 async fn parse_block_transactions(
     block: &aptos_rosetta::types::Block,
     balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
+    stake_balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
     actual_txns: &[Transaction],
     current_version: &mut u64,
 ) {
@@ -554,7 +619,13 @@ async fn parse_block_transactions(
                 assert_eq!(0, *current_version);
             }
             TransactionType::User => {}
-            TransactionType::BlockMetadata | TransactionType::StateCheckpoint => {
+            TransactionType::BlockMetadata => {
+                // Only epoch changes distribute staking rewards
+                assert!(transaction.operations.iter().all(|operation| {
+                    operation.operation_type == OperationType::StakingReward.to_string()
+                }));
+            }
+            TransactionType::StateCheckpoint => {
                 assert!(transaction.operations.is_empty());
             }
         }
@@ -562,12 +633,13 @@ async fn parse_block_transactions(
         parse_operations(
             block.block_identifier.index,
             balances,
+            stake_balances,
             transaction,
             actual_txn,
         )
         .await;
 
-        for (_, account_balance) in balances.iter() {
+        for (_, account_balance) in balances.iter().chain(stake_balances.iter()) {
             if let Some(amount) = account_balance.get(current_version) {
                 assert!(*amount >= 0, "Amount shouldn't be negative!")
             }
@@ -582,6 +654,7 @@ async fn parse_block_transactions(
 async fn parse_operations(
     block_height: u64,
     balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
+    stake_balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
     transaction: &aptos_rosetta::types::Transaction,
     actual_txn: &Transaction,
 ) {
@@ -713,18 +786,73 @@ async fn parse_operations(
                     );
                 }
             }
-            OperationType::SetOperator => {
+            OperationType::SetOperator
+            | OperationType::SetVoter
+            | OperationType::ResetLockup
+            | OperationType::UnlockStake => {
+                // None of these move any coins in or out of the stake pool
+                assert!(operation.amount.is_none());
                 if actual_txn.success() {
                     assert_eq!(
                         OperationStatusType::Success,
                         status,
-                        "Successful transaction should have successful set operator operation"
+                        "Successful transaction should have successful {} operation",
+                        operation_type
                     );
                 } else {
                     assert_eq!(
                         OperationStatusType::Failure,
                         status,
-                        "Failed transaction should have failed set operator operation"
+                        "Failed transaction should have failed {} operation",
+                        operation_type
+                    );
+                }
+            }
+            OperationType::AddStake
+            | OperationType::WithdrawStake
+            | OperationType::StakingReward => {
+                let account = operation
+                    .account
+                    .as_ref()
+                    .expect("There should be an account in a stake operation");
+                assert!(
+                    account.is_stake(),
+                    "Stake operations should be on the stake sub account"
+                );
+
+                if actual_txn.success() {
+                    assert_eq!(OperationStatusType::Success, status);
+                    let amount = operation
+                        .amount
+                        .as_ref()
+                        .expect("Should have an amount in a stake operation");
+                    assert_eq!(
+                        amount.currency,
+                        native_coin(),
+                        "Stake should be the native coin"
+                    );
+                    let delta = i128::from_str(&amount.value)
+                        .expect("Should be able to parse amount value");
+                    let account_balances = stake_balances
+                        .entry(
+                            account
+                                .account_address()
+                                .expect("Account address should be parsable"),
+                        )
+                        .or_insert_with(|| {
+                            let mut map = BTreeMap::new();
+                            map.insert(block_height, 0);
+                            map
+                        });
+                    let (_, latest_balance) = account_balances.iter().last().unwrap();
+                    let new_balance = *latest_balance + delta;
+                    account_balances.insert(block_height, new_balance);
+                } else {
+                    assert_eq!(
+                        OperationStatusType::Failure,
+                        status,
+                        "Failed transaction should have failed {} operation",
+                        operation_type
                     );
                 }
             }
@@ -784,19 +912,23 @@ async fn parse_operations(
         }
     }
 
+    let transaction_type = transaction.metadata.unwrap().transaction_type;
     assert!(
         has_gas_op
-            || transaction.metadata.unwrap().transaction_type == TransactionType::Genesis
+            || transaction_type == TransactionType::Genesis
+            || transaction_type == TransactionType::BlockMetadata
             || transaction.operations.is_empty(),
-        "Must have a gas operation at least in a transaction except for Genesis",
+        "Must have a gas operation at least in a user transaction",
     );
 }
 
-/// Check that all balances are correct with the account balance command from the blocks
+/// Check that all balances are correct with the account balance command from the blocks, or
+/// all the total stakes of the stake pools if `is_stake`
 async fn check_balances(
     rosetta_client: &RosettaClient,
     chain_id: ChainId,
     balances: BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
+    is_stake: bool,
 ) {
     // TODO: Check some random times that arent on changes?
     for (account, account_balances) in balances {
@@ -805,7 +937,11 @@ async fn check_balances(
             let response = rosetta_client
                 .account_balance(&AccountBalanceRequest {
                     network_identifier: NetworkIdentifier::from(chain_id),
-                    account_identifier: account.into(),
+                    account_identifier: if is_stake {
+                        AccountIdentifier::stake(account)
+                    } else {
+                        account.into()
+                    },
                     block_identifier: Some(PartialBlockIdentifier {
                         index: Some(block_height),
                         hash: None,
@@ -850,8 +986,8 @@ async fn test_invalid_transaction_gas_charged() {
             0,
             TRANSFER_AMOUNT,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -901,6 +1037,152 @@ async fn test_invalid_transaction_gas_charged() {
     );
 }
 
+#[tokio::test]
+async fn test_mempool_and_search_transactions() {
+    let (swarm, cli, _faucet, rosetta_client) = setup_test(1, 2).await;
+    let chain_id = swarm.chain_id();
+    let validator = swarm.validators().next().unwrap();
+    let rest_client = validator.rest_client();
+    let network_identifier: NetworkIdentifier = chain_id.into();
+
+    // Wait until the Rosetta service is ready
+    let request = NetworkRequest {
+        network_identifier: network_identifier.clone(),
+    };
+    try_until_ok_default(|| rosetta_client.network_status(&request))
+        .await
+        .unwrap();
+
+    let sender = cli.account_id(0);
+    let receiver = cli.account_id(1);
+    cli.fund_account(0, Some(10000000)).await.unwrap();
+    let txn = transfer_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        cli.private_key(0),
+        receiver,
+        20,
+        Duration::from_secs(5),
+        None,
+    )
+    .await
+    .unwrap();
+    let txn_identifier = TransactionIdentifier::from(&txn.info);
+
+    // Once committed, the transaction is no longer in mempool
+    let mempool = rosetta_client
+        .mempool(&MempoolRequest {
+            network_identifier: network_identifier.clone(),
+        })
+        .await
+        .unwrap();
+    assert!(!mempool.transaction_identifiers.contains(&txn_identifier));
+    rosetta_client
+        .mempool_transaction(&MempoolTransactionRequest {
+            network_identifier: network_identifier.clone(),
+            transaction_identifier: txn_identifier.clone(),
+        })
+        .await
+        .unwrap_err();
+
+    // Search by hash, along with the block it's in
+    let by_hash = SearchTransactionsRequest {
+        network_identifier: network_identifier.clone(),
+        operator: None,
+        max_block: None,
+        offset: None,
+        limit: None,
+        transaction_identifier: Some(txn_identifier.clone()),
+        account_identifier: None,
+        status: None,
+        success: None,
+    };
+    let response = rosetta_client.search_transactions(&by_hash).await.unwrap();
+    assert_eq!(1, response.total_count);
+    let found = response.transactions.first().unwrap();
+    assert_eq!(txn_identifier, found.transaction.transaction_identifier);
+    let block_info = rest_client
+        .get_block_by_version(txn.info.version.0, false)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(block_info.block_height.0, found.block_identifier.index);
+
+    // The receiver has the deposit, but an unrelated account has no operations
+    let response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            account_identifier: Some(receiver.into()),
+            ..by_hash.clone()
+        })
+        .await
+        .unwrap();
+    assert_eq!(1, response.total_count);
+    let response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            account_identifier: Some(AccountAddress::from_hex_literal("0xdead").unwrap().into()),
+            ..by_hash.clone()
+        })
+        .await
+        .unwrap();
+    assert!(response.transactions.is_empty());
+
+    // Search by the sender's account
+    let by_account = SearchTransactionsRequest {
+        transaction_identifier: None,
+        account_identifier: Some(sender.into()),
+        ..by_hash
+    };
+    let response = rosetta_client
+        .search_transactions(&by_account)
+        .await
+        .unwrap();
+    assert_eq!(1, response.total_count);
+    assert_eq!(None, response.next_offset);
+    assert!(response
+        .transactions
+        .iter()
+        .any(|found| found.transaction.transaction_identifier == txn_identifier));
+
+    // The total count covers the matches skipped by the offset
+    let response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            offset: Some(1),
+            ..by_account.clone()
+        })
+        .await
+        .unwrap();
+    assert_eq!(1, response.total_count);
+    assert!(response.transactions.is_empty());
+
+    // Searching the whole ledger isn't supported
+    rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            account_identifier: None,
+            ..by_account.clone()
+        })
+        .await
+        .unwrap_err();
+
+    // None of the sender's transactions failed
+    let response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            status: Some(OperationStatusType::Failure.to_string()),
+            ..by_account.clone()
+        })
+        .await
+        .unwrap();
+    assert!(response.transactions.is_empty());
+    let response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            success: Some(false),
+            ..by_account
+        })
+        .await
+        .unwrap();
+    assert!(response.transactions.is_empty());
+}
+
 fn assert_transfer_transaction(
     sender: AccountAddress,
     receiver: AccountAddress,
@@ -1114,6 +1396,23 @@ async fn transfer_and_wait(
     wait_for_transaction(rest_client, expiry_time, txn_hash).await
 }
 
+async fn stake_operation_and_wait<F, Fut>(
+    rest_client: &aptos_rest_client::Client,
+    txn_expiry_duration: Duration,
+    submit: F,
+) -> Result<Box<UserTransaction>, Box<UserTransaction>>
+where
+    F: FnOnce(u64) -> Fut,
+    Fut: Future<Output = anyhow::Result<TransactionIdentifier>>,
+{
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = submit(expiry_time.as_secs())
+        .await
+        .expect("Expect stake operation to successfully submit to mempool")
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash).await
+}
+
 async fn wait_for_transaction(
     rest_client: &aptos_rest_client::Client,
     expiry_time: Duration,
@@ -30,5 +30,11 @@ pub const APTOS_VERSION_3: Version = Version { major: 3 };
 //  - Conflict-Resistant Sequence Numbers
 pub const APTOS_VERSION_4: Version = Version { major: 4 };
 
+// NOTE: version number for release 1.5 of Aptos
+// Items gated by this version number include:
+//  - Fee payer transactions
+//  - Secp256k1 ECDSA transaction authenticators
+pub const APTOS_VERSION_5: Version = Version { major: 5 };
+
 // Maximum current known version
-pub const APTOS_MAX_KNOWN_VERSION: Version = APTOS_VERSION_4;
+pub const APTOS_MAX_KNOWN_VERSION: Version = APTOS_VERSION_5;
@@ -0,0 +1,17 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// Defines the optional limit on the total gas used by the transactions of a block. Transactions
+/// following the one at which the limit is reached are not committed, and are marked for retry.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct BlockGasLimit {
+    pub limit: Option<u64>,
+}
+
+impl OnChainConfig for BlockGasLimit {
+    const MODULE_IDENTIFIER: &'static str = "block_gas_limit";
+    const TYPE_IDENTIFIER: &'static str = "BlockGasLimit";
+}
@@ -4,7 +4,7 @@
 use std::collections::HashMap;
 
 use crate::{block_info::Round, on_chain_config::OnChainConfig};
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use move_deps::move_core_types::account_address::AccountAddress;
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +56,18 @@ impl OnChainConsensusConfig {
             OnChainConsensusConfig::V1(config) => &config.proposer_election_type,
         }
     }
+
+    /// Checks that all validators can start an epoch with this config. Invalid
+    /// configs are rejected when deserialized, as every validator would hit
+    /// the same error at the start of the epoch.
+    pub fn validate(&self) -> Result<()> {
+        match self.proposer_election_type() {
+            ProposerElectionType::LeaderReputation(LeaderReputationType::StakeAndLatency(
+                config,
+            )) => config.validate(),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// This is used when on-chain config is not initialized.
@@ -78,8 +90,13 @@ impl OnChainConfig for OnChainConsensusConfig {
     /// so we need two rounds of bcs deserilization to turn it back to OnChainConsensusConfig
     fn deserialize_into_config(bytes: &[u8]) -> Result<Self> {
         let raw_bytes: Vec<u8> = bcs::from_bytes(bytes)?;
-        bcs::from_bytes(&raw_bytes)
-            .map_err(|e| format_err!("[on-chain config] Failed to deserialize into config: {}", e))
+        let config: Self = bcs::from_bytes(&raw_bytes).map_err(|e| {
+            format_err!("[on-chain config] Failed to deserialize into config: {}", e)
+        })?;
+        config
+            .validate()
+            .map_err(|e| format_err!("[on-chain config] Invalid consensus config: {}", e))?;
+        Ok(config)
     }
 }
 
@@ -142,6 +159,9 @@ pub enum LeaderReputationType {
     // Proposer election based on whether nodes succeeded or failed
     // their proposer election rounds, and whether they voted.
     ProposerAndVoter(ProposerAndVoterConfig),
+    // Proposer election based on voting power, combined with a decaying
+    // history of proposal successes and round latencies.
+    StakeAndLatency(StakeAndLatencyConfig),
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -169,6 +189,88 @@ pub struct ProposerAndVoterConfig {
     pub use_history_from_previous_epoch_max_count: u32,
 }
 
+/// Upper bound on the selection weight of a validator with `StakeAndLatencyConfig`, before it is
+/// multiplied by the validator's voting power.
+pub const MAX_STAKE_AND_LATENCY_WEIGHT: u64 = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StakeAndLatencyConfig {
+    // Selection weight for validators whose proposals all succeeded
+    pub max_weight: u64,
+    // Selection weight lower bound, so that no validator is excluded entirely
+    pub min_weight: u64,
+    // Selection weight for validators without proposal history in the window
+    pub no_history_weight: u64,
+    // Decay of the history, in percent per block. E.g., with 5 each block
+    // counts 5% less than the block committed after it.
+    pub decay_percent: u32,
+    // Lower bound of the latency multiplier, in percent. The multiplier is the
+    // ratio between the median round latency and the validator's round latency.
+    pub min_latency_multiplier_percent: u64,
+    // Upper bound of the latency multiplier, in percent
+    pub max_latency_multiplier_percent: u64,
+    // Window into history considered for the statistics, multiplier
+    // on top of number of validators
+    pub window_num_validators_multiplier: usize,
+    // Flag whether to use history from previous epoch (0 if not),
+    // representing a number of historical epochs (beyond the current one)
+    // to consider.
+    pub use_history_from_previous_epoch_max_count: u32,
+}
+
+impl StakeAndLatencyConfig {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.decay_percent < 100,
+            "decay_percent must be below 100, got {}",
+            self.decay_percent
+        );
+        ensure!(
+            self.min_latency_multiplier_percent <= self.max_latency_multiplier_percent,
+            "min_latency_multiplier_percent ({}) must not exceed max_latency_multiplier_percent ({})",
+            self.min_latency_multiplier_percent,
+            self.max_latency_multiplier_percent
+        );
+        ensure!(
+            self.min_weight <= self.max_weight,
+            "min_weight ({}) must not exceed max_weight ({})",
+            self.min_weight,
+            self.max_weight
+        );
+        ensure!(
+            self.no_history_weight <= self.max_weight,
+            "no_history_weight ({}) must not exceed max_weight ({})",
+            self.no_history_weight,
+            self.max_weight
+        );
+        let highest_weight =
+            self.max_weight as u128 * self.max_latency_multiplier_percent as u128 / 100;
+        ensure!(
+            highest_weight <= MAX_STAKE_AND_LATENCY_WEIGHT as u128,
+            "max_weight ({}) scaled by max_latency_multiplier_percent ({}) must not exceed {}",
+            self.max_weight,
+            self.max_latency_multiplier_percent,
+            MAX_STAKE_AND_LATENCY_WEIGHT
+        );
+        Ok(())
+    }
+}
+
+impl Default for StakeAndLatencyConfig {
+    fn default() -> Self {
+        Self {
+            max_weight: 1000,
+            min_weight: 1,
+            no_history_weight: 100,
+            decay_percent: 2,
+            min_latency_multiplier_percent: 10,
+            max_latency_multiplier_percent: 200,
+            window_num_validators_multiplier: 10,
+            use_history_from_previous_epoch_max_count: 5,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
@@ -211,6 +313,92 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_config_serialization_stake_and_latency() {
+        let config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
+            proposer_election_type: ProposerElectionType::LeaderReputation(
+                LeaderReputationType::StakeAndLatency(StakeAndLatencyConfig::default()),
+            ),
+            ..ConsensusConfigV1::default()
+        });
+
+        let s = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<OnChainConsensusConfig>(&s).unwrap(),
+            config
+        );
+        let s = bcs::to_bytes(&config).unwrap();
+        assert_eq!(
+            bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn test_config_stake_and_latency_validation() {
+        let create_config = |config: StakeAndLatencyConfig| {
+            OnChainConsensusConfig::V1(ConsensusConfigV1 {
+                proposer_election_type: ProposerElectionType::LeaderReputation(
+                    LeaderReputationType::StakeAndLatency(config),
+                ),
+                ..ConsensusConfigV1::default()
+            })
+        };
+        create_config(StakeAndLatencyConfig::default())
+            .validate()
+            .unwrap();
+        create_config(StakeAndLatencyConfig {
+            max_weight: MAX_STAKE_AND_LATENCY_WEIGHT / 2,
+            max_latency_multiplier_percent: 200,
+            ..StakeAndLatencyConfig::default()
+        })
+        .validate()
+        .unwrap();
+
+        let invalid_configs = vec![
+            StakeAndLatencyConfig {
+                decay_percent: 100,
+                ..StakeAndLatencyConfig::default()
+            },
+            StakeAndLatencyConfig {
+                min_latency_multiplier_percent: 300,
+                max_latency_multiplier_percent: 200,
+                ..StakeAndLatencyConfig::default()
+            },
+            StakeAndLatencyConfig {
+                min_weight: 2000,
+                ..StakeAndLatencyConfig::default()
+            },
+            StakeAndLatencyConfig {
+                no_history_weight: 2000,
+                ..StakeAndLatencyConfig::default()
+            },
+            StakeAndLatencyConfig {
+                max_weight: MAX_STAKE_AND_LATENCY_WEIGHT,
+                max_latency_multiplier_percent: 200,
+                ..StakeAndLatencyConfig::default()
+            },
+            StakeAndLatencyConfig {
+                max_weight: u64::MAX,
+                max_latency_multiplier_percent: u64::MAX,
+                ..StakeAndLatencyConfig::default()
+            },
+        ];
+        for invalid_config in invalid_configs {
+            let consensus_config = create_config(invalid_config);
+            assert!(consensus_config.validate().is_err());
+
+            // Invalid configs are rejected when read from the on-chain payload
+            let mut configs = HashMap::new();
+            configs.insert(
+                OnChainConsensusConfig::CONFIG_ID,
+                bcs::to_bytes(&bcs::to_bytes(&consensus_config).unwrap()).unwrap(),
+            );
+            let payload = OnChainConfigPayload::new(1, Arc::new(configs));
+            assert!(payload.get::<OnChainConsensusConfig>().is_err());
+        }
+    }
+
     #[test]
     fn test_config_onchain_payload() {
         let consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
@@ -17,6 +17,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{collections::HashMap, fmt, sync::Arc};
 
 mod aptos_version;
+mod block_gas_limit;
 mod consensus_config;
 mod gas_schedule;
 mod validator_set;
@@ -24,7 +25,9 @@ mod validator_set;
 pub use self::{
     aptos_version::{
         Version, APTOS_MAX_KNOWN_VERSION, APTOS_VERSION_2, APTOS_VERSION_3, APTOS_VERSION_4,
+        APTOS_VERSION_5,
     },
+    block_gas_limit::BlockGasLimit,
     consensus_config::{
         ConsensusConfigV1, LeaderReputationType, OnChainConsensusConfig, ProposerElectionType,
     },
@@ -11,7 +11,7 @@ use crate::{
 };
 use aptos_crypto::{
     ed25519::{self, Ed25519PrivateKey, Ed25519Signature},
-    PrivateKey, Uniform,
+    secp256k1_ecdsa, PrivateKey, Uniform,
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
 use proptest::prelude::*;
@@ -43,6 +43,13 @@ proptest! {
         assert!(signed_txn.check_signature().is_ok());
     }
 
+    #[test]
+    fn test_sign_raw_transaction_secp256k1_ecdsa(raw_txn in any::<RawTransaction>(), keypair in secp256k1_ecdsa::keypair_strategy()) {
+        let txn = raw_txn.sign_secp256k1_ecdsa(&keypair.private_key, keypair.public_key).unwrap();
+        let signed_txn = txn.into_inner();
+        assert!(signed_txn.check_signature().is_ok());
+    }
+
     #[test]
     fn transaction_payload_bcs_roundtrip(txn_payload in any::<TransactionPayload>()) {
         assert_canonical_encode_decode(txn_payload);